@@ -0,0 +1,43 @@
+/* C interface to gitprint, built with `cargo build --release --features ffi`.
+ *
+ * Every function taking a config expects a NUL-terminated UTF-8 JSON object in
+ * the schema of gitprint::config_json::ConfigJson. Calls are thread-safe; the
+ * error message returned by gitprint_last_error() is per thread. */
+
+#ifndef GITPRINT_H
+#define GITPRINT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define GITPRINT_OK 0
+#define GITPRINT_INVALID_ARGUMENT 1
+#define GITPRINT_RUN_FAILED 2
+#define GITPRINT_PANIC 3
+
+/* Renders the repository to the config's output_path. */
+int32_t gitprint_run(const char *config_json);
+
+/* Renders the repository and returns the PDF in *out_ptr / *out_len, to be
+ * released with gitprint_free_bytes(). output_path is ignored. */
+int32_t gitprint_render(const char *config_json, uint8_t **out_ptr, size_t *out_len);
+
+/* Releases a buffer returned by gitprint_render(). */
+void gitprint_free_bytes(uint8_t *ptr, size_t len);
+
+/* Message for the last failed call on this thread, or NULL. Valid until the
+ * next gitprint call on the same thread. */
+const char *gitprint_last_error(void);
+
+/* The library version, e.g. "0.4.0". */
+const char *gitprint_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GITPRINT_H */
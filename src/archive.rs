@@ -0,0 +1,337 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+
+/// Archive formats that can be printed directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// `.zip`
+    Zip,
+    /// `.tar.gz` / `.tgz`
+    TarGz,
+    /// Uncompressed `.tar`
+    Tar,
+}
+
+impl ArchiveKind {
+    /// Detects the archive format from a file name or URL path by its extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::archive::ArchiveKind;
+    ///
+    /// assert_eq!(ArchiveKind::from_name("src.tar.gz"), Some(ArchiveKind::TarGz));
+    /// assert_eq!(ArchiveKind::from_name("https://x.org/a.ZIP"), Some(ArchiveKind::Zip));
+    /// assert_eq!(ArchiveKind::from_name("main.rs"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lower = name
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(name)
+            .to_ascii_lowercase();
+        if lower.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if lower.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Like [`from_name`](Self::from_name), but also recognises extension-less GitHub
+    /// `/tarball/` and `/zipball/` API URLs.
+    pub fn from_url(url: &str) -> Option<Self> {
+        Self::from_name(url).or_else(|| {
+            if url.contains("/zipball") {
+                Some(Self::Zip)
+            } else if url.contains("/tarball") {
+                Some(Self::TarGz)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Returns `true` if `path` names an existing local archive file.
+pub fn is_local_archive(path: &str) -> bool {
+    ArchiveKind::from_name(path).is_some() && Path::new(path).is_file()
+}
+
+/// Returns `true` if `url` is an `http(s)` URL pointing at a downloadable archive,
+/// such as `https://github.com/user/repo/archive/refs/heads/main.tar.gz`.
+pub fn is_archive_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://"))
+        && ArchiveKind::from_url(url).is_some()
+}
+
+/// Derives a document name from an archive path or URL: the repository name for
+/// GitHub `/archive/` URLs, otherwise the file name without its archive extension.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::archive::display_name;
+///
+/// assert_eq!(display_name("dist/project-1.2.tar.gz"), "project-1.2");
+/// assert_eq!(
+///     display_name("https://github.com/user/repo/archive/refs/heads/main.zip"),
+///     "repo"
+/// );
+/// ```
+pub fn display_name(source: &str) -> String {
+    let path = source.split(['?', '#']).next().unwrap_or(source);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if let Some(i) = segments
+        .iter()
+        .position(|s| matches!(*s, "archive" | "tarball" | "zipball"))
+        && i > 0
+    {
+        return segments[i - 1].to_string();
+    }
+    let file = segments.last().copied().unwrap_or("archive");
+    let lower = file.to_ascii_lowercase();
+    [".tar.gz", ".tgz", ".tar", ".zip"]
+        .iter()
+        .find(|ext| lower.ends_with(*ext))
+        .map(|ext| file[..file.len() - ext.len()].to_string())
+        .unwrap_or_else(|| file.to_string())
+}
+
+/// Archives larger than this are refused unless `--max-download-size` says otherwise.
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 1 << 30;
+
+/// Downloads `url` to `dest`, writing the body as it arrives. `token` is sent as a
+/// bearer token (for private GitHub repositories).
+///
+/// # Errors
+///
+/// Returns an error if the request fails, the server responds with a non-success
+/// status, or the body is larger than `max_bytes` (checked against `Content-Length`
+/// up front and against the bytes received so far, so a lying or missing header
+/// can't get past it).
+pub async fn download(
+    url: &str,
+    dest: &Path,
+    token: Option<&str>,
+    max_bytes: u64,
+) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let client = crate::github::build_client()?;
+    let mut req = client.get(url);
+    if let Some(t) = token {
+        req = req.header("Authorization", format!("Bearer {t}"));
+    }
+    let mut resp = req.send().await.with_context(|| format!("GET {url}"))?;
+    let status = resp.status();
+    if !status.is_success() {
+        bail!("download failed ({status}): {url}");
+    }
+    let too_large = || {
+        anyhow::anyhow!(
+            "download is larger than {} (raise --max-download-size): {url}",
+            crate::format_size(max_bytes)
+        )
+    };
+    if resp.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(too_large());
+    }
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .with_context(|| format!("writing {}", dest.display()))?;
+    let mut received = 0u64;
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .with_context(|| format!("reading {url}"))?
+    {
+        received += chunk.len() as u64;
+        if received > max_bytes {
+            return Err(too_large());
+        }
+        file.write_all(&chunk)
+            .await
+            .with_context(|| format!("writing {}", dest.display()))?;
+    }
+    file.flush()
+        .await
+        .with_context(|| format!("writing {}", dest.display()))
+}
+
+/// Extracts `archive` into `dest` and returns the directory to print: `dest` itself,
+/// or its only child when the archive wraps everything in one top-level folder (as
+/// GitHub and `git archive --prefix` tarballs do).
+///
+/// Entries that would escape `dest` (absolute paths, `..`) are never written. Blocking;
+/// call from `spawn_blocking` in async code.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or is corrupt.
+pub fn extract(archive: &Path, kind: ArchiveKind, dest: &Path) -> anyhow::Result<PathBuf> {
+    let file = File::open(archive).with_context(|| format!("opening {}", archive.display()))?;
+    let reader = BufReader::new(file);
+    std::fs::create_dir_all(dest)?;
+    match kind {
+        ArchiveKind::Zip => zip::ZipArchive::new(reader)
+            .and_then(|mut z| z.extract(dest))
+            .with_context(|| format!("extracting {}", archive.display()))?,
+        ArchiveKind::TarGz => unpack_tar(flate2::read::GzDecoder::new(reader), dest)
+            .with_context(|| format!("extracting {}", archive.display()))?,
+        ArchiveKind::Tar => {
+            unpack_tar(reader, dest).with_context(|| format!("extracting {}", archive.display()))?
+        }
+    }
+
+    let entries = std::fs::read_dir(dest)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(match entries.as_slice() {
+        [only] if only.file_type()?.is_dir() => only.path(),
+        _ => dest.to_path_buf(),
+    })
+}
+
+/// Unpacks regular files and directories only; `unpack_in` refuses paths that
+/// would land outside `dest`.
+fn unpack_tar(reader: impl std::io::Read, dest: &Path) -> std::io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    archive.entries()?.try_for_each(|entry| {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if kind.is_file() || kind.is_dir() {
+            entry.unpack_in(dest)?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    fn write_tar_gz(path: &Path, files: &[(&str, &str)]) {
+        let gz = flate2::write::GzEncoder::new(
+            File::create(path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut tar = tar::Builder::new(gz);
+        files.iter().for_each(|(name, body)| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, body.as_bytes()).unwrap();
+        });
+        tar.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn tar_gz_with_single_top_dir_is_unwrapped() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("repo-main.tar.gz");
+        write_tar_gz(
+            &archive,
+            &[
+                ("repo-main/src/lib.rs", "pub fn f() {}\n"),
+                ("repo-main/README.md", "# hi\n"),
+            ],
+        );
+        let root = extract(&archive, ArchiveKind::TarGz, &dir.path().join("out")).unwrap();
+        assert_eq!(root, dir.path().join("out/repo-main"));
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "pub fn f() {}\n"
+        );
+    }
+
+    #[test]
+    fn zip_with_several_top_level_entries_keeps_dest() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("a.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        ["a.rs", "b/c.rs"].iter().for_each(|name| {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"fn main() {}\n").unwrap();
+        });
+        zip.finish().unwrap();
+
+        let out = dir.path().join("out");
+        assert_eq!(extract(&archive, ArchiveKind::Zip, &out).unwrap(), out);
+        assert!(out.join("b/c.rs").is_file());
+    }
+
+    #[test]
+    fn corrupt_archive_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("bad.tar.gz");
+        std::fs::write(&archive, "not gzip").unwrap();
+        assert!(extract(&archive, ArchiveKind::TarGz, &dir.path().join("out")).is_err());
+    }
+
+    #[test]
+    fn archive_urls() {
+        assert!(is_archive_url(
+            "https://github.com/u/r/archive/refs/tags/v1.0.tar.gz"
+        ));
+        assert!(is_archive_url(
+            "https://api.github.com/repos/u/r/tarball/main"
+        ));
+        assert!(!is_archive_url("https://github.com/u/r"));
+        assert!(!is_archive_url("git@github.com:u/r.tar.gz"));
+    }
+
+    /// Answers one request with `body`, announcing its length only when
+    /// `declare_length` (otherwise the body ends when the connection closes).
+    fn serve_once(body: Vec<u8>, declare_length: bool) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = std::io::Read::read(&mut stream, &mut [0u8; 4096]);
+            let length = if declare_length {
+                format!("Content-Length: {}\r\n", body.len())
+            } else {
+                String::new()
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\n{length}Connection: close\r\n\r\n"
+            );
+            let _ = stream.write_all(&body);
+        });
+        format!("http://{addr}/src.tar.gz")
+    }
+
+    #[tokio::test]
+    async fn download_streams_the_body_to_dest() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("archive");
+        let body: Vec<u8> = (0..20_000u32).map(|i| i as u8).collect();
+        let url = serve_once(body.clone(), false);
+        download(&url, &dest, None, 1 << 20).await.unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn download_past_the_limit_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("archive");
+        for declare_length in [true, false] {
+            let url = serve_once(vec![b'x'; 20_000], declare_length);
+            let err = download(&url, &dest, None, 4096).await.unwrap_err();
+            assert!(
+                err.to_string().contains("larger than 4.0 KB"),
+                "declare_length={declare_length}: {err}"
+            );
+        }
+    }
+}
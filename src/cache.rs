@@ -0,0 +1,250 @@
+//! On-disk cache of GitHub API responses.
+//!
+//! User reports make dozens of API calls; caching the raw responses for a while
+//! lets repeated runs (tweaking layout flags, say) skip the network and keep the
+//! rate limit for new data. The cache is off unless
+//! [`UserReportConfig::cache`](crate::types::UserReportConfig::cache) enables it.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Default lifetime of a cached response, in seconds (`--cache-ttl`).
+pub const DEFAULT_TTL_SECS: u64 = 3600;
+
+/// Where and for how long API responses are cached.
+#[derive(Debug, Clone)]
+pub struct ApiCache {
+    /// Directory holding one file per request URL.
+    pub dir: PathBuf,
+    /// Entries older than this are fetched again.
+    pub ttl: Duration,
+    /// Ignore existing entries but store fresh responses (`--refresh`).
+    pub refresh: bool,
+}
+
+/// gitprint's directory under the platform cache root: `$GITPRINT_CACHE_DIR` when
+/// set, else `$XDG_CACHE_HOME/gitprint` or `~/.cache/gitprint` on Linux,
+/// `~/Library/Caches/gitprint` on macOS, and `%LOCALAPPDATA%\gitprint` on Windows.
+/// `None` when no home directory can be found.
+pub fn default_dir() -> Option<PathBuf> {
+    let var = |name: &str| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+    };
+    if let Some(dir) = var("GITPRINT_CACHE_DIR") {
+        return Some(dir);
+    }
+    let root = if cfg!(windows) {
+        var("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")))
+    };
+    root.map(|root| root.join("gitprint"))
+}
+
+#[cfg(feature = "native")]
+impl ApiCache {
+    /// The cached body for `url` as fetched with `token`, when there is one
+    /// younger than the TTL.
+    pub(crate) async fn read(&self, url: &str, token: Option<&str>) -> Option<String> {
+        if self.refresh {
+            return None;
+        }
+        let path = self.entry(url, token);
+        let age = tokio::fs::metadata(&path)
+            .await
+            .and_then(|m| m.modified())
+            .ok()?
+            .elapsed()
+            .unwrap_or_default();
+        if age >= self.ttl {
+            return None;
+        }
+        tokio::fs::read_to_string(&path).await.ok()
+    }
+
+    /// Stores `body` as the response for `url` fetched with `token`. Failures are
+    /// ignored: the cache only ever saves requests, so a read-only disk just means
+    /// no caching.
+    ///
+    /// Responses can hold private data, so on Unix directories are created `0700`
+    /// and files `0600`.
+    pub(crate) async fn write(&self, url: &str, token: Option<&str>, body: &str) {
+        use tokio::io::AsyncWriteExt;
+
+        let path = self.entry(url, token);
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        let mut dirs = tokio::fs::DirBuilder::new();
+        dirs.recursive(true);
+        #[cfg(unix)]
+        dirs.mode(0o700);
+        if dirs.create(parent).await.is_err() {
+            return;
+        }
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        if let Ok(mut file) = options.open(&path).await {
+            let _ = file.write_all(body.as_bytes()).await;
+        }
+    }
+
+    /// Cache file for `url`: the URL's path under `public`, or under an
+    /// `auth-<hash>` directory per token so one token's responses are never served
+    /// to another, and each user's responses sit together under `users/<name>/`.
+    /// A hash of the whole URL keeps entries that differ only in the query apart.
+    fn entry(&self, url: &str, token: Option<&str>) -> PathBuf {
+        let path = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split_once('/')
+            .map_or("", |(_, path)| path);
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        let mut entry = self.dir.join(match token {
+            Some(token) => format!("auth-{}", token_hash(token)),
+            None => "public".to_string(),
+        });
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .for_each(|segment| entry.push(sanitize(segment)));
+        let name = entry
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        entry.set_file_name(format!("{name}-{:016x}.json", fnv1a(url)));
+        entry
+    }
+}
+
+/// The first 16 hex digits of the token's SHA-256: enough to keep tokens apart,
+/// without writing the token itself to disk.
+#[cfg(feature = "native")]
+fn token_hash(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(token.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// A URL path segment as a safe file name: anything but ASCII letters, digits,
+/// `-`, `_`, and `.` becomes `_`, and `.` / `..` can't climb out of the cache.
+#[cfg(feature = "native")]
+fn sanitize(segment: &str) -> String {
+    let safe: String = segment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if safe.chars().all(|c| c == '.') {
+        "_".repeat(safe.len())
+    } else {
+        safe
+    }
+}
+
+/// 64-bit FNV-1a: stable across builds, unlike `DefaultHasher`.
+#[cfg(feature = "native")]
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn cache(dir: &Path, ttl: u64) -> ApiCache {
+        ApiCache {
+            dir: dir.to_path_buf(),
+            ttl: Duration::from_secs(ttl),
+            refresh: false,
+        }
+    }
+
+    #[test]
+    fn entry_groups_responses_by_url_path() {
+        let cache = cache(Path::new("/cache"), 60);
+        let events = cache.entry(
+            "https://api.github.com/users/alice/events/public?per_page=100&page=1",
+            None,
+        );
+        assert!(events.starts_with("/cache/public/users/alice/events"));
+        assert!(events.to_string_lossy().ends_with(".json"));
+        let page2 = cache.entry(
+            "https://api.github.com/users/alice/events/public?per_page=100&page=2",
+            None,
+        );
+        assert_ne!(events, page2);
+    }
+
+    #[test]
+    fn entry_keeps_each_token_apart() {
+        let cache = cache(Path::new("/cache"), 60);
+        let url = "https://api.github.com/users/alice";
+        let first = cache.entry(url, Some("ghp_first"));
+        let second = cache.entry(url, Some("ghp_second"));
+        assert!(first.starts_with("/cache"));
+        assert!(!first.starts_with("/cache/public"));
+        assert_ne!(first.parent(), second.parent());
+        assert_eq!(first, cache.entry(url, Some("ghp_first")));
+        assert!(!first.to_string_lossy().contains("ghp_first"));
+    }
+
+    #[test]
+    fn entry_stays_inside_the_cache() {
+        let cache = cache(Path::new("/cache"), 60);
+        let entry = cache.entry("https://api.github.com/repos/../../etc/passwd", None);
+        assert!(entry.starts_with(&cache.dir));
+        assert!(!entry.components().any(|c| c.as_os_str() == ".."));
+    }
+
+    #[tokio::test]
+    async fn read_returns_fresh_entries_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://api.github.com/users/alice";
+        let fresh = cache(dir.path(), 3600);
+        assert_eq!(fresh.read(url, None).await, None);
+        fresh.write(url, None, r#"{"login":"alice"}"#).await;
+        assert_eq!(
+            fresh.read(url, None).await.as_deref(),
+            Some(r#"{"login":"alice"}"#)
+        );
+        assert_eq!(fresh.read(url, Some("ghp_token")).await, None);
+        assert_eq!(cache(dir.path(), 0).read(url, None).await, None);
+        let refresh = ApiCache {
+            refresh: true,
+            ..fresh
+        };
+        assert_eq!(refresh.read(url, None).await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn write_keeps_entries_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://api.github.com/users/alice";
+        let cache = cache(dir.path(), 3600);
+        cache.write(url, Some("ghp_token"), "{}").await;
+        let entry = cache.entry(url, Some("ghp_token"));
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&entry), 0o600);
+        assert_eq!(mode(entry.parent().unwrap()), 0o700);
+    }
+}
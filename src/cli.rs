@@ -1,8 +1,13 @@
 use std::path::PathBuf;
 
 use clap::Parser;
+use clap::builder::BoolishValueParser;
 
-use crate::types::{ActivityFilter, PaperSize};
+use crate::locale::Locale;
+use crate::types::{
+    ActivityFilter, ControlChars, CoverRow, Density, LineMark, MetaRow, NUp, PaperSize,
+    ReportFormat, ReviewLayout,
+};
 
 /// Parsed command-line arguments for the `gitprint` binary.
 #[derive(Parser, Debug)]
@@ -14,11 +19,14 @@ use crate::types::{ActivityFilter, PaperSize};
                   MODES\n\
                   \n  \
                   gitprint <PATH> [OPTIONS]\n    \
-                    Local path, file, or remote URL (https://, git@, ssh://) → PDF\n\
+                    Local path, file, archive, or remote URL (https://, git@, ssh://) → PDF\n\
                   \n  \
                   gitprint --user <USERNAME> [OPTIONS]\n    \
                     GitHub user activity report → PDF\n\
                   \n  \
+                  gitprint --user <USERNAME> --user <USERNAME>... | --team <FILE> [OPTIONS]\n    \
+                    GitHub team report: comparison table, then one chapter per user → PDF\n\
+                  \n  \
                   gitprint <PATH|--user USERNAME> --preview\n    \
                     Preview output in the terminal — no PDF generated",
     version,
@@ -26,54 +34,250 @@ use crate::types::{ActivityFilter, PaperSize};
     after_help = after_help_text(),
 )]
 pub struct Args {
-    /// Local path, file, or remote URL (https://, git@, ssh://)
+    /// Local path, file, .zip/.tar.gz archive, or remote URL (https://, git@, ssh://)
     pub path: Option<String>,
 
     /// Preview output in the terminal instead of generating a PDF
     #[arg(long)]
     pub preview: bool,
 
+    /// Pick the files to print from a checklist in the terminal, with a running
+    /// page estimate, before rendering
+    #[arg(short = 'i', long, conflicts_with_all = ["preview", "theme_gallery"])]
+    pub interactive: bool,
+
     /// Output PDF file path
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
+    /// Overwrite the output PDF if it already exists
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Exit with an error when the run had warnings (unreadable or skipped files,
+    /// failed history lookups, missing glyphs, …); the PDF is still written
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Explain file selection on stderr: -v counts the files the filters dropped,
+    /// -vv names each one and the rule that dropped it
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Project config file [default: .gitprint.json in the repository root]
+    #[arg(long, value_name = "PATH", env = "GITPRINT_CONFIG")]
+    pub config: Option<PathBuf>,
+
     // ── Repository Mode ────────────────────────────────────────────────────────
-    /// Glob patterns for files to include (repeatable)
+    /// Glob patterns for files to include (repeatable) [env: GITPRINT_INCLUDE, comma-separated]
     #[arg(long, action = clap::ArgAction::Append, help_heading = "Repository Mode (Default)")]
     pub include: Vec<String>,
 
-    /// Glob patterns for files to exclude (repeatable)
+    /// Glob patterns for files to exclude, in order; `!PATTERN` re-includes (repeatable) [env: GITPRINT_EXCLUDE, comma-separated]
     #[arg(long, action = clap::ArgAction::Append, help_heading = "Repository Mode (Default)")]
     pub exclude: Vec<String>,
 
+    /// Don't apply the built-in excludes (lock files, build output, images, binaries, …)
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub no_default_excludes: bool,
+
+    /// Regular expressions for files to include, matched against the whole path (repeatable)
+    #[arg(
+        long,
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub include_regex: Vec<String>,
+
+    /// Regular expressions for files to exclude, matched against the whole path (repeatable)
+    #[arg(
+        long,
+        value_name = "REGEX",
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub exclude_regex: Vec<String>,
+
+    /// Print only files in these languages, by name or extension (e.g. rust,toml)
+    #[arg(
+        long,
+        value_name = "LANGS",
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub lang: Vec<String>,
+
+    /// Skip files in these languages, by name or extension (e.g. markdown)
+    #[arg(
+        long,
+        value_name = "LANGS",
+        value_delimiter = ',',
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub exclude_lang: Vec<String>,
+
+    /// Add a curated include/exclude set (repeatable; combines with --include/--exclude)
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = preset_parser(),
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub preset: Vec<String>,
+
     /// Syntax highlighting theme
     #[arg(
         long,
         default_value = "InspiredGitHub",
+        env = "GITPRINT_THEME",
         help_heading = "Repository Mode (Default)"
     )]
     pub theme: String,
 
+    /// Darken theme colors that would print washed out on white paper (for dark
+    /// themes)
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub print_optimize: bool,
+
+    /// Print files longer than N lines without syntax colors, which would take
+    /// longest to highlight (0 highlights every file)
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = crate::highlight::DEFAULT_HIGHLIGHT_MAX_LINES,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub highlight_max_lines: usize,
+
+    /// Show control characters in source files — form feeds, stray carriage
+    /// returns, ANSI escapes — in caret notation (^L, ^M, ^[), or strip them
+    #[arg(
+        long,
+        value_enum,
+        value_name = "POLICY",
+        default_value = "visualize",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub control_chars: ControlChars,
+
+    /// Proportional font for the cover, TOC, headers, and Markdown, as TTF/OTF
+    /// files: regular, then optionally bold and italic (comma-separated). Code stays
+    /// monospace
+    #[arg(
+        long,
+        value_name = "REGULAR[,BOLD[,ITALIC]]",
+        value_delimiter = ',',
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub prose_font: Vec<PathBuf>,
+
+    /// Fallback font for emoji and other characters the code and prose fonts
+    /// lack, as a TTF/OTF file (e.g. a monochrome Noto Emoji). None is bundled
+    #[arg(long, value_name = "PATH", help_heading = "Repository Mode (Default)")]
+    pub emoji_font: Option<PathBuf>,
+
     /// Code font size in points
     #[arg(
         long,
         default_value_t = 8.0,
+        env = "GITPRINT_FONT_SIZE",
         help_heading = "Repository Mode (Default)"
     )]
     pub font_size: f64,
 
+    /// Line spacing as a multiple of the font size, e.g. 1.0 for dense listings or
+    /// 2.0 for room to annotate [default: font size + 2 pt, or as --density sets]
+    #[arg(
+        long,
+        value_name = "FACTOR",
+        value_parser = parse_line_height,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub line_height: Option<f64>,
+
+    /// Spacing preset for lines and the gaps between headings, rows, and files on
+    /// code, TOC, and cover pages
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PRESET",
+        default_value = "normal",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub density: Density,
+
+    /// Extra space between characters of code, in points
+    #[arg(
+        long,
+        value_name = "PT",
+        default_value_t = 0.0,
+        allow_negative_numbers = true,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub letter_spacing: f64,
+
     /// Disable line numbers
-    #[arg(long, help_heading = "Repository Mode (Default)")]
+    #[arg(
+        long,
+        env = "GITPRINT_NO_LINE_NUMBERS",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
     pub no_line_numbers: bool,
 
-    /// Disable table of contents
+    /// Number lines continuously across files, like a legal exhibit; the TOC lists
+    /// each file's first line
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub global_line_numbers: bool,
+
+    /// Shade the line-number gutter by when each line last changed (git blame):
+    /// dark is recent, light is old
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub age_shading: bool,
+
+    /// Put line numbers and age shading on optional content layers that PDF viewers
+    /// can show or hide
     #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub layers: bool,
+
+    /// Disable table of contents
+    #[arg(
+        long,
+        env = "GITPRINT_NO_TOC",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
     pub no_toc: bool,
 
     /// Disable directory tree visualization
-    #[arg(long, help_heading = "Repository Mode (Default)")]
+    #[arg(
+        long,
+        env = "GITPRINT_NO_FILE_TREE",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
     pub no_file_tree: bool,
 
+    /// Print the README, typeset as formatted Markdown, after the cover as an introduction
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub preface: bool,
+
+    /// Leave out the repository statistics page after the cover
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub no_summary: bool,
+
+    /// Leave the commit hash and generation date out of page footers
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub no_commit_footer: bool,
+
+    /// Show the page number alone in headers instead of with the current file's path
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub no_running_header: bool,
+
     /// Use a specific branch
     #[arg(long, help_heading = "Repository Mode (Default)")]
     pub branch: Option<String>,
@@ -82,18 +286,378 @@ pub struct Args {
     #[arg(long, help_heading = "Repository Mode (Default)")]
     pub commit: Option<String>,
 
+    /// Treat PATH as a local path even if it looks like a remote URL
+    #[arg(
+        long,
+        conflicts_with = "remote",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub local: bool,
+
+    /// Treat PATH as a remote to clone even if it doesn't look like a URL
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub remote: bool,
+
+    /// Fetch remote GitHub repositories as a tarball instead of cloning (no git needed)
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub no_git: bool,
+
+    /// Refuse to download a zip or tarball larger than SIZE (e.g. 200MB)
+    #[arg(
+        long,
+        value_name = "SIZE",
+        default_value = "1G",
+        value_parser = parse_size,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub max_download_size: u64,
+
+    /// Access token for cloning private HTTPS repositories
+    #[arg(
+        long,
+        value_name = "TOKEN",
+        env = "GIT_TOKEN",
+        hide_env_values = true,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub clone_token: Option<String>,
+
+    /// Clone only the last N commits of a remote repository [default: 1]
+    ///
+    /// History-based options (--hotspots, --age-shading, --since, --until) and the
+    /// last-modified dates see only these commits.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "full_clone",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub clone_depth: Option<u32>,
+
+    /// Clone a remote repository's whole history instead of its last commit
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub full_clone: bool,
+
+    /// Keep the clone of a remote repository after the run and print where it is
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub keep_clone: bool,
+
+    /// Print a remote repository from a clone kept by an earlier run instead of
+    /// cloning it again
+    ///
+    /// The clone must come from the same URL and be at the commit a fresh clone would
+    /// get. A run that fails keeps its clone for this.
+    #[arg(long, value_name = "DIR", help_heading = "Repository Mode (Default)")]
+    pub reuse_clone: Option<PathBuf>,
+
+    /// Don't look up a GitHub remote's description, topics, stars, and license for
+    /// the cover
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub offline: bool,
+
+    /// Seconds before a git command is killed (0 disables the limit)
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = crate::git::DEFAULT_TIMEOUT_SECS,
+        env = "GITPRINT_GIT_TIMEOUT",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub git_timeout: u64,
+
     /// Paper size
-    #[arg(long, value_enum, default_value_t = PaperSize::A4, help_heading = "Repository Mode (Default)")]
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = PaperSize::A4,
+        env = "GITPRINT_PAPER_SIZE",
+        help_heading = "Repository Mode (Default)"
+    )]
     pub paper_size: PaperSize,
 
     /// Use landscape orientation
-    #[arg(long, help_heading = "Repository Mode (Default)")]
+    #[arg(
+        long,
+        env = "GITPRINT_LANDSCAPE",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
     pub landscape: bool,
 
+    /// Re-indent JSON, XML, and CSV files before highlighting (also prints minified data files)
+    #[arg(
+        long,
+        env = "GITPRINT_PRETTY_DATA",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub pretty_data: bool,
+
+    /// Tint lines in matching files, e.g. `src/auth.rs:40-75,90` (repeatable)
+    #[arg(
+        long,
+        value_name = "GLOB:RANGES",
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub mark: Vec<LineMark>,
+
+    /// Highlight every match of a regex and list the hits on a results page
+    #[arg(long, value_name = "REGEX", help_heading = "Repository Mode (Default)")]
+    pub grep: Option<regex::Regex>,
+
+    /// Print only files whose content matches a regex, e.g. `unsafe` or an API name
+    #[arg(long, value_name = "REGEX", help_heading = "Repository Mode (Default)")]
+    pub only_matching: Option<regex::Regex>,
+
+    /// Append an index of functions, types, and classes with the page each is printed on
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub symbols: bool,
+
+    /// Append the SHA-256 of every printed file, plus the commit and tree hashes, so
+    /// a paper copy can be checked against the repository
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub checksums: bool,
+
+    /// Print the enclosing function or type name in the outer margin when it changes
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub margin_notes: bool,
+
+    /// Open each top-level directory (or DEPTH levels down) with a divider page
+    /// listing its files
+    #[arg(
+        long,
+        value_name = "DEPTH",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub chapters: Option<usize>,
+
+    /// Add a page listing every tag and branch with its commit, date, and distance
+    /// from the default branch
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub refs: bool,
+
+    /// Print a single commit — message, stats, and highlighted per-file patches —
+    /// instead of the repository
+    #[arg(long, value_name = "SHA", help_heading = "Repository Mode (Default)")]
+    pub show: Option<String>,
+
+    /// Add a page ranking the most frequently changed files, with their line counts
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub hotspots: bool,
+
+    /// How far back --hotspots counts commits (any date `git log --since` accepts)
+    #[arg(
+        long,
+        value_name = "DATE",
+        default_value = "1 year ago",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub hotspots_since: String,
+
+    /// Print QR codes linking to the remote: the tree on the cover, each file's blob
+    /// beside its header
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub qr: bool,
+
+    /// Rows of the cover's metadata table to show, in order (e.g. branch,commit,message)
+    #[arg(
+        long,
+        value_name = "ROWS",
+        value_delimiter = ',',
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub cover_rows: Option<Vec<CoverRow>>,
+
+    /// Add a row to the cover's metadata table, e.g. `"Reviewed by=J. Smith"` (repeatable)
+    #[arg(
+        long,
+        value_name = "LABEL=VALUE",
+        action = clap::ArgAction::Append,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub meta: Vec<MetaRow>,
+
+    /// Date order, thousands separator, and cover labels, e.g. `de-DE` or `en_US`
+    /// (en, de, fr, es, it, nl, pt) [default: LC_ALL or LC_TIME, else ISO dates]
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub locale: Option<Locale>,
+
+    /// Print exactly the files listed in FILE (`-` for stdin), one per line, in order
+    ///
+    /// Paths are relative to the repository root. Include/exclude and date filters
+    /// are bypassed.
+    #[arg(long, value_name = "FILE", help_heading = "Repository Mode (Default)")]
+    pub files_from: Option<PathBuf>,
+
+    /// Read --files-from entries as NUL-separated (e.g. from `git diff -z --name-only`)
+    #[arg(
+        short = '0',
+        long = "null",
+        requires = "files_from",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub null: bool,
+
+    /// Descend at most N directory levels below the root (0 = top-level files only)
+    #[arg(long, value_name = "N", help_heading = "Repository Mode (Default)")]
+    pub max_depth: Option<usize>,
+
+    /// Include dotfiles and dot-directories (the default)
+    #[arg(
+        long,
+        overrides_with = "no_hidden",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub hidden: bool,
+
+    /// Skip dotfiles and dot-directories such as .github/ or .env
+    #[arg(
+        long,
+        overrides_with = "hidden",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub no_hidden: bool,
+
+    /// Stop adding files once the PDF reaches N pages; the rest are listed on a
+    /// closing notice page and gitprint exits with status 3. Repositories and
+    /// directories only
+    #[arg(long, value_name = "N", help_heading = "Repository Mode (Default)")]
+    pub max_pages: Option<usize>,
+
+    /// Stop adding files once the PDF would pass about SIZE (e.g. 500K, 20MB);
+    /// truncates like --max-pages
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub max_output_size: Option<u64>,
+
+    /// Also print files not yet committed (untracked but not ignored)
+    #[arg(long, conflicts_with_all = ["branch", "commit"], help_heading = "Repository Mode (Default)")]
+    pub untracked: bool,
+
+    /// Print only files modified relative to REF, or to the index when REF is omitted
+    #[arg(
+        long,
+        value_name = "REF",
+        conflicts_with_all = ["branch", "commit"],
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub changed_only: Option<Option<String>>,
+
+    /// Leave last-modified dates out of the TOC and file headers, skipping the
+    /// history walk that finds them (faster on large repositories)
+    #[arg(
+        long,
+        conflicts_with_all = ["since", "until"],
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub no_dates: bool,
+
+    /// Blank out credentials (API keys, tokens, private keys) and list them on a log page
+    #[arg(
+        long,
+        env = "GITPRINT_REDACT_SECRETS",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub redact_secrets: bool,
+
+    /// Separate files with a rule instead of starting each on a new page
+    #[arg(
+        long,
+        env = "GITPRINT_COMPACT",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub compact: bool,
+
+    /// Add a "Page X of Y" footer to every page
+    #[arg(
+        long,
+        env = "GITPRINT_PAGE_TOTAL",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub page_total: bool,
+
+    /// Impose pages as a foldable booklet: 2-up on landscape sheets in saddle-stitch order
+    ///
+    /// Print duplex, flipping on the short edge, then fold the stack in half.
+    #[arg(long, help_heading = "Repository Mode (Default)")]
+    pub booklet: bool,
+
+    /// Print N logical pages per sheet to save paper
+    #[arg(
+        long,
+        value_enum,
+        value_name = "N",
+        conflicts_with = "booklet",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub nup: Option<NUp>,
+
+    /// Leave room for handwritten review comments: a ruled note column beside a
+    /// narrower code column, or a lined note page after every page of code
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STYLE",
+        num_args = 0..=1,
+        default_missing_value = "margin",
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub review_layout: Option<ReviewLayout>,
+
+    /// Convert all colors to luminance-equivalent grays (for monochrome printers)
+    #[arg(
+        long,
+        conflicts_with = "ink_saver",
+        env = "GITPRINT_GRAYSCALE",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub grayscale: bool,
+
+    /// Lighten saturated syntax and UI colors to use less ink
+    #[arg(
+        long,
+        env = "GITPRINT_INK_SAVER",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub ink_saver: bool,
+
+    /// Emit a tagged PDF (structure tree) for screen readers and reflow
+    ///
+    /// Not with --booklet or --nup: imposed sheets mix pages out of reading order.
+    #[arg(
+        long,
+        env = "GITPRINT_TAGGED",
+        value_parser = BoolishValueParser::new(),
+        conflicts_with_all = ["booklet", "nup"],
+        help_heading = "Repository Mode (Default)"
+    )]
+    pub tagged: bool,
+
     /// List available syntax themes and exit
     #[arg(long, help_heading = "Repository Mode (Default)")]
     pub list_themes: bool,
 
+    /// Write a PDF showing sample code in every theme, one per page, and exit
+    #[arg(long, value_name = "PATH", help_heading = "Repository Mode (Default)")]
+    pub theme_gallery: Option<PathBuf>,
+
     /// List version tags of the repository and exit
     #[arg(long, help_heading = "Repository Mode (Default)")]
     pub list_tags: bool,
@@ -103,9 +667,14 @@ pub struct Args {
     pub nvim: bool,
 
     // ── User Report Mode ───────────────────────────────────────────────────────
-    /// GitHub username — generate a user activity report instead of printing a repo
+    /// GitHub username — generate a user activity report instead of printing a repo.
+    /// Repeat for a team report: one chapter per user plus a comparison table
     #[arg(short = 'u', long = "user", help_heading = "User Report Mode")]
-    pub user: Option<String>,
+    pub user: Vec<String>,
+
+    /// File of GitHub usernames, one per line, for a team report (`#` starts a comment)
+    #[arg(long, value_name = "FILE", help_heading = "User Report Mode")]
+    pub team: Option<PathBuf>,
 
     /// Number of most-recently-pushed repos to include [default: 5]
     #[arg(long, default_value_t = 5, help_heading = "User Report Mode")]
@@ -119,8 +688,33 @@ pub struct Args {
     #[arg(long, help_heading = "User Report Mode")]
     pub no_diffs: bool,
 
+    /// Unchanged lines kept around each change in commit diffs, here and with
+    /// --show; longer runs collapse to a marker [default: 3]
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 3,
+        help_heading = "User Report Mode"
+    )]
+    pub diff_context: usize,
+
+    /// Append every commit from each push in the report window, grouped by repository
+    #[arg(long, help_heading = "User Report Mode")]
+    pub full_diffs: bool,
+
+    /// Most commits fetched for --full-diffs [default: 100]
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 100,
+        help_heading = "User Report Mode"
+    )]
+    pub full_diffs_limit: usize,
+
     /// Show events from this date forward [default: no lower bound; GitHub keeps ≤ 90 days]
     ///
+    /// In repository mode, only files last committed on or after this date are printed.
+    ///
     /// Accepted formats:
     ///   ISO date    2024-01-15  or  2024-01-15T00:00:00Z
     ///   Keywords    today · yesterday
@@ -131,6 +725,7 @@ pub struct Args {
 
     /// Show events up to and including this date [default: no upper bound]
     ///
+    /// In repository mode, only files last committed on or before this date are printed.
     /// Same formats as --since.
     #[arg(long, value_name = "DATE", help_heading = "User Report Mode")]
     pub until: Option<String>,
@@ -142,12 +737,135 @@ pub struct Args {
     #[arg(long, value_enum, default_value_t = ActivityFilter::All, help_heading = "User Report Mode")]
     pub activity: ActivityFilter,
 
-    /// Maximum events shown in the activity feed [default: 30]
-    ///
-    /// Fetches up to 100 events from GitHub and applies --since/--until/--activity
-    /// filters before counting toward this limit.
-    #[arg(long, default_value_t = 30, help_heading = "User Report Mode")]
-    pub events: usize,
+    /// Maximum events shown in the activity feed [default: 30]
+    ///
+    /// Fetches up to 100 events from GitHub and applies --since/--until/--activity
+    /// filters before counting toward this limit.
+    #[arg(long, default_value_t = 30, help_heading = "User Report Mode")]
+    pub events: usize,
+
+    /// What to write [default: pdf]
+    ///
+    /// pdf  — the printable report
+    /// json — the aggregated data (profile, repos, events, commits, statistics)
+    /// both — the PDF, plus the JSON next to it with a .json extension
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReportFormat::Pdf,
+        help_heading = "User Report Mode"
+    )]
+    pub format: ReportFormat,
+
+    /// Seconds a cached GitHub API response stays fresh [default: 3600]
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = crate::cache::DEFAULT_TTL_SECS,
+        help_heading = "User Report Mode"
+    )]
+    pub cache_ttl: u64,
+
+    /// Don't read or write the GitHub API response cache
+    #[arg(long, conflicts_with = "refresh", help_heading = "User Report Mode")]
+    pub no_cache: bool,
+
+    /// Fetch everything from GitHub again, replacing cached responses
+    #[arg(long, help_heading = "User Report Mode")]
+    pub refresh: bool,
+}
+
+impl Args {
+    /// Fills `--include` / `--exclude` from `GITPRINT_INCLUDE` / `GITPRINT_EXCLUDE` when
+    /// the flag was not given on the command line. The variables hold comma-separated
+    /// lists (see [`split_patterns`]).
+    pub fn apply_pattern_env(&mut self) {
+        let from_env = |name: &str| {
+            std::env::var(name)
+                .map(|list| split_patterns(&list))
+                .unwrap_or_default()
+        };
+        if self.include.is_empty() {
+            self.include = from_env("GITPRINT_INCLUDE");
+        }
+        if self.exclude.is_empty() {
+            self.exclude = from_env("GITPRINT_EXCLUDE");
+        }
+    }
+}
+
+/// Splits a comma-separated list of glob patterns. Commas inside `{…}` alternations
+/// belong to the pattern, and blank entries are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::cli::split_patterns;
+///
+/// assert_eq!(
+///     split_patterns("*.lock, docs/**,*.{png,svg}"),
+///     vec!["*.lock", "docs/**", "*.{png,svg}"]
+/// );
+/// ```
+pub fn split_patterns(list: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    list.chars().for_each(|c| match c {
+        ',' if depth == 0 => patterns.push(std::mem::take(&mut current)),
+        _ => {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            current.push(c);
+        }
+    });
+    patterns.push(current);
+    patterns
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Accepts the [`PRESETS`](crate::filter::presets::PRESETS) names, each listed with
+/// its description in `--help`.
+fn preset_parser() -> clap::builder::PossibleValuesParser {
+    clap::builder::PossibleValuesParser::new(
+        crate::filter::presets::PRESETS
+            .iter()
+            .map(|p| clap::builder::PossibleValue::new(p.name).help(p.description)),
+    )
+}
+
+fn parse_line_height(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(factor) if factor > 0.0 && factor.is_finite() => Ok(factor),
+        _ => Err(format!("expected a positive number, got {s:?}")),
+    }
+}
+
+/// Parses a byte count with an optional `K`, `M`, or `G` suffix (binary units; a
+/// trailing `B` or `iB` is allowed), e.g. `1048576`, `500K`, `1.5MB`.
+fn parse_size(s: &str) -> Result<u64, String> {
+    let upper = s.trim().to_ascii_uppercase();
+    let number = upper.trim_end_matches("IB").trim_end_matches('B');
+    let (number, scale) = match number.strip_suffix(['K', 'M', 'G']) {
+        Some(rest) => {
+            let unit = number.chars().last().expect("suffix matched");
+            (
+                rest,
+                1u64 << (10 * (" KMG".find(unit).expect("known unit"))),
+            )
+        }
+        None => (number, 1),
+    };
+    match number.trim().parse::<f64>() {
+        Ok(n) if n > 0.0 && n.is_finite() => Ok((n * scale as f64) as u64),
+        _ => Err(format!("expected a size such as 500K or 20MB, got {s:?}")),
+    }
 }
 
 fn after_help_text() -> &'static str {
@@ -211,35 +929,91 @@ mod tests {
     #[test]
     fn user_flag_short() {
         let args = Args::parse_from(["gitprint", "-u", "izelnakri"]);
-        assert_eq!(args.user, Some("izelnakri".to_string()));
+        assert_eq!(args.user, vec!["izelnakri".to_string()]);
         assert_eq!(args.path, None);
     }
 
     #[test]
     fn user_flag_long() {
         let args = Args::parse_from(["gitprint", "--user", "torvalds"]);
-        assert_eq!(args.user, Some("torvalds".to_string()));
+        assert_eq!(args.user, vec!["torvalds".to_string()]);
     }
 
     #[test]
     fn user_flag_with_output() {
         let args = Args::parse_from(["gitprint", "-u", "alice", "-o", "alice.pdf"]);
-        assert_eq!(args.user, Some("alice".to_string()));
+        assert_eq!(args.user, vec!["alice".to_string()]);
         assert_eq!(args.output, Some(PathBuf::from("alice.pdf")));
     }
 
+    #[test]
+    fn user_flag_repeats_for_a_team() {
+        let args = Args::parse_from(["gitprint", "-u", "alice", "--user", "bob"]);
+        assert_eq!(args.user, vec!["alice".to_string(), "bob".to_string()]);
+        assert_eq!(args.team, None);
+    }
+
+    #[test]
+    fn team_flag() {
+        let args = Args::parse_from(["gitprint", "--team", "users.txt"]);
+        assert_eq!(args.team, Some(PathBuf::from("users.txt")));
+        assert!(args.user.is_empty());
+    }
+
+    #[test]
+    fn output_limit_flags() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--max-pages",
+            "200",
+            "--max-output-size",
+            "1.5MB",
+        ]);
+        assert_eq!(args.max_pages, Some(200));
+        assert_eq!(args.max_output_size, Some(1_572_864));
+        assert_eq!(parse_size("500k"), Ok(512_000));
+        assert!(Args::try_parse_from(["gitprint", ".", "--max-output-size", "lots"]).is_err());
+    }
+
     #[test]
     fn user_report_flags_defaults() {
         let args = Args::parse_from(["gitprint", "-u", "alice"]);
         assert_eq!(args.last_repos, 5);
         assert_eq!(args.last_commits, 5);
         assert!(!args.no_diffs);
+        assert_eq!(args.diff_context, 3);
         assert_eq!(args.events, 30);
         assert!(matches!(args.activity, ActivityFilter::All));
         assert!(args.since.is_none());
         assert!(args.until.is_none());
     }
 
+    #[test]
+    fn since_until_apply_in_repo_mode() {
+        let args = Args::parse_from(["gitprint", ".", "--since", "2024-01-01", "--until", "today"]);
+        assert_eq!(args.path.as_deref(), Some("."));
+        assert_eq!(args.since.as_deref(), Some("2024-01-01"));
+        assert_eq!(args.until.as_deref(), Some("today"));
+    }
+
+    #[test]
+    fn clone_depth_flags() {
+        let args = Args::parse_from(["gitprint", "url", "--clone-depth", "50"]);
+        assert_eq!(args.clone_depth, Some(50));
+        assert!(Args::parse_from(["gitprint", "url", "--full-clone"]).full_clone);
+        assert!(Args::try_parse_from(["gitprint", "url", "--clone-depth", "0"]).is_err());
+        let both = ["gitprint", "url", "--clone-depth", "5", "--full-clone"];
+        assert!(Args::try_parse_from(both).is_err());
+    }
+
+    #[test]
+    fn no_dates_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--no-dates"]).no_dates);
+        assert!(!Args::parse_from(["gitprint", "."]).no_dates);
+        assert!(Args::try_parse_from(["gitprint", ".", "--no-dates", "--since", "today"]).is_err());
+    }
+
     #[test]
     fn since_until_flags() {
         let args = Args::parse_from(["gitprint", "-u", "alice", "--since", "2024-01-01"]);
@@ -280,9 +1054,52 @@ mod tests {
             "--last-commits",
             "3",
             "--no-diffs",
+            "--diff-context",
+            "1",
         ]);
         assert_eq!(args.last_commits, 3);
         assert!(args.no_diffs);
+        assert_eq!(args.diff_context, 1);
+    }
+
+    #[test]
+    fn format_flag() {
+        let args = Args::parse_from(["gitprint", "-u", "alice"]);
+        assert_eq!(args.format, ReportFormat::Pdf);
+        let args = Args::parse_from(["gitprint", "-u", "alice", "--format", "json"]);
+        assert_eq!(args.format, ReportFormat::Json);
+        let args = Args::parse_from(["gitprint", "-u", "alice", "--format", "both"]);
+        assert_eq!(args.format, ReportFormat::Both);
+    }
+
+    #[test]
+    fn cache_flags() {
+        let args = Args::parse_from(["gitprint", "-u", "alice"]);
+        assert_eq!(args.cache_ttl, crate::cache::DEFAULT_TTL_SECS);
+        assert!(!args.no_cache && !args.refresh);
+        let args = Args::parse_from(["gitprint", "-u", "alice", "--cache-ttl", "60", "--refresh"]);
+        assert_eq!(args.cache_ttl, 60);
+        assert!(args.refresh);
+        assert!(
+            Args::try_parse_from(["gitprint", "-u", "alice", "--no-cache", "--refresh"]).is_err()
+        );
+    }
+
+    #[test]
+    fn full_diffs_flags() {
+        let args = Args::parse_from(["gitprint", "-u", "alice"]);
+        assert!(!args.full_diffs);
+        assert_eq!(args.full_diffs_limit, 100);
+        let args = Args::parse_from([
+            "gitprint",
+            "-u",
+            "alice",
+            "--full-diffs",
+            "--full-diffs-limit",
+            "20",
+        ]);
+        assert!(args.full_diffs);
+        assert_eq!(args.full_diffs_limit, 20);
     }
 
     #[test]
@@ -291,6 +1108,19 @@ mod tests {
         assert_eq!(args.output, Some(PathBuf::from("out.pdf")));
     }
 
+    #[test]
+    fn force_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--force"]).force);
+        assert!(Args::parse_from(["gitprint", ".", "-f"]).force);
+        assert!(!Args::parse_from(["gitprint", "."]).force);
+    }
+
+    #[test]
+    fn strict_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--strict"]).strict);
+        assert!(!Args::parse_from(["gitprint", "."]).strict);
+    }
+
     #[test]
     fn output_long_flag() {
         let args = Args::parse_from(["gitprint", ".", "--output", "out.pdf"]);
@@ -331,6 +1161,13 @@ mod tests {
         assert!(args.list_themes);
     }
 
+    #[test]
+    fn theme_gallery_takes_an_output_path() {
+        let args = Args::parse_from(["gitprint", "--theme-gallery", "themes.pdf"]);
+        assert_eq!(args.theme_gallery, Some(PathBuf::from("themes.pdf")));
+        assert!(args.path.is_none());
+    }
+
     #[test]
     fn list_tags_flag() {
         let args = Args::parse_from(["gitprint", ".", "--list-tags"]);
@@ -369,12 +1206,492 @@ mod tests {
         assert_eq!(args.exclude, vec!["*.lock", "*.md"]);
     }
 
+    #[test]
+    fn lang_flags_split_on_commas() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--lang",
+            "rust,toml",
+            "--lang",
+            "python",
+            "--exclude-lang",
+            "markdown",
+        ]);
+        assert_eq!(args.lang, vec!["rust", "toml", "python"]);
+        assert_eq!(args.exclude_lang, vec!["markdown"]);
+    }
+
+    #[test]
+    fn preset_flag_accepts_known_names() {
+        let args = Args::parse_from([
+            "gitprint", ".", "--preset", "src-only", "--preset", "no-tests",
+        ]);
+        assert_eq!(args.preset, vec!["src-only", "no-tests"]);
+        assert!(Args::try_parse_from(["gitprint", ".", "--preset", "everything"]).is_err());
+    }
+
+    #[test]
+    fn regex_filters_are_repeatable() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--include-regex",
+            r"^src/(api|core)/.*\.(rs|proto)$",
+            "--exclude-regex",
+            "_generated",
+            "--exclude-regex",
+            "^vendor/",
+        ]);
+        assert_eq!(args.include_regex, vec![r"^src/(api|core)/.*\.(rs|proto)$"]);
+        assert_eq!(args.exclude_regex, vec!["_generated", "^vendor/"]);
+    }
+
+    #[test]
+    fn global_line_numbers_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--global-line-numbers"]).global_line_numbers);
+        assert!(!Args::parse_from(["gitprint", "."]).global_line_numbers);
+    }
+
+    #[test]
+    fn print_optimize_flag() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--theme",
+            "Solarized (dark)",
+            "--print-optimize",
+        ]);
+        assert!(args.print_optimize);
+        assert!(!Args::parse_from(["gitprint", "."]).print_optimize);
+    }
+
+    #[test]
+    fn prose_font_takes_up_to_three_faces() {
+        let args = Args::parse_from(["gitprint", ".", "--prose-font", "Sans.ttf,Sans-Bold.ttf"]);
+        assert_eq!(
+            args.prose_font,
+            [PathBuf::from("Sans.ttf"), PathBuf::from("Sans-Bold.ttf")]
+        );
+        assert!(Args::parse_from(["gitprint", "."]).prose_font.is_empty());
+    }
+
+    #[test]
+    fn emoji_font_takes_a_path() {
+        let args = Args::parse_from(["gitprint", ".", "--emoji-font", "NotoEmoji-Regular.ttf"]);
+        assert_eq!(
+            args.emoji_font,
+            Some(PathBuf::from("NotoEmoji-Regular.ttf"))
+        );
+        assert_eq!(Args::parse_from(["gitprint", "."]).emoji_font, None);
+    }
+
+    #[test]
+    fn age_shading_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--age-shading"]).age_shading);
+        assert!(!Args::parse_from(["gitprint", "."]).age_shading);
+    }
+
+    #[test]
+    fn layers_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--layers"]).layers);
+        assert!(!Args::parse_from(["gitprint", "."]).layers);
+    }
+
     #[test]
     fn font_size_custom() {
         let args = Args::parse_from(["gitprint", ".", "--font-size", "12.5"]);
         assert_eq!(args.font_size, 12.5);
     }
 
+    #[test]
+    fn pretty_data_flag() {
+        let args = Args::parse_from(["gitprint", ".", "--pretty-data"]);
+        assert!(args.pretty_data);
+        let args = Args::parse_from(["gitprint", "."]);
+        assert!(!args.pretty_data);
+    }
+
+    #[test]
+    fn page_total_flag() {
+        let args = Args::parse_from(["gitprint", ".", "--page-total"]);
+        assert!(args.page_total);
+        assert!(!Args::parse_from(["gitprint", "."]).page_total);
+    }
+
+    #[test]
+    fn booklet_flag() {
+        let args = Args::parse_from(["gitprint", ".", "--booklet"]);
+        assert!(args.booklet);
+        assert!(!Args::parse_from(["gitprint", "."]).booklet);
+    }
+
+    #[test]
+    fn nup_flag() {
+        let args = Args::parse_from(["gitprint", ".", "--nup", "4"]);
+        assert_eq!(args.nup, Some(NUp::Four));
+        let args = Args::parse_from(["gitprint", ".", "--nup", "2"]);
+        assert_eq!(args.nup, Some(NUp::Two));
+        assert!(Args::parse_from(["gitprint", "."]).nup.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "--nup", "3"]).is_err());
+        assert!(Args::try_parse_from(["gitprint", ".", "--nup", "2", "--booklet"]).is_err());
+    }
+
+    #[test]
+    fn highlight_max_lines_flag() {
+        assert_eq!(
+            Args::parse_from(["gitprint", "."]).highlight_max_lines,
+            crate::highlight::DEFAULT_HIGHLIGHT_MAX_LINES
+        );
+        let args = Args::parse_from(["gitprint", ".", "--highlight-max-lines", "0"]);
+        assert_eq!(args.highlight_max_lines, 0);
+    }
+
+    #[test]
+    fn control_chars_policy() {
+        assert_eq!(
+            Args::parse_from(["gitprint", "."]).control_chars,
+            ControlChars::Visualize
+        );
+        let args = Args::parse_from(["gitprint", ".", "--control-chars", "strip"]);
+        assert_eq!(args.control_chars, ControlChars::Strip);
+        assert!(Args::try_parse_from(["gitprint", ".", "--control-chars", "keep"]).is_err());
+    }
+
+    #[test]
+    fn density_presets() {
+        assert_eq!(Args::parse_from(["gitprint", "."]).density, Density::Normal);
+        let args = Args::parse_from(["gitprint", ".", "--density", "compact"]);
+        assert_eq!(args.density, Density::Compact);
+        assert!(Args::try_parse_from(["gitprint", ".", "--density", "tight"]).is_err());
+    }
+
+    #[test]
+    fn review_layout_defaults_to_a_note_margin() {
+        let args = Args::parse_from(["gitprint", ".", "--review-layout"]);
+        assert_eq!(args.review_layout, Some(ReviewLayout::Margin));
+        let args = Args::parse_from(["gitprint", ".", "--review-layout", "pages"]);
+        assert_eq!(args.review_layout, Some(ReviewLayout::Pages));
+        assert!(Args::parse_from(["gitprint", "."]).review_layout.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "--review-layout", "inline"]).is_err());
+    }
+
+    #[test]
+    fn grayscale_and_ink_saver_flags() {
+        let args = Args::parse_from(["gitprint", ".", "--grayscale"]);
+        assert!(args.grayscale);
+        assert!(!args.ink_saver);
+        let args = Args::parse_from(["gitprint", ".", "--ink-saver"]);
+        assert!(args.ink_saver);
+        assert!(Args::try_parse_from(["gitprint", ".", "--grayscale", "--ink-saver"]).is_err());
+    }
+
+    #[test]
+    fn mark_flag_repeatable() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--mark",
+            "src/auth.rs:40-75",
+            "--mark",
+            "*.toml:1",
+        ]);
+        assert_eq!(args.mark.len(), 2);
+        assert_eq!(args.mark[0].ranges, vec![40..=75]);
+        assert!(Args::try_parse_from(["gitprint", ".", "--mark", "src/auth.rs"]).is_err());
+    }
+
+    #[test]
+    fn grep_flag_validates_regex() {
+        let args = Args::parse_from(["gitprint", ".", "--grep", "TODO|FIXME"]);
+        assert!(args.grep.unwrap().is_match("FIXME"));
+        assert!(Args::parse_from(["gitprint", "."]).grep.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "--grep", "("]).is_err());
+    }
+
+    #[test]
+    fn only_matching_flag_validates_regex() {
+        let args = Args::parse_from(["gitprint", ".", "--only-matching", r"\bunsafe\b"]);
+        assert!(args.only_matching.unwrap().is_match("unsafe {"));
+        assert!(Args::try_parse_from(["gitprint", ".", "--only-matching", "("]).is_err());
+    }
+
+    #[test]
+    fn redact_secrets_and_config_flags() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--redact-secrets",
+            "--config",
+            "ci/gitprint.json",
+        ]);
+        assert!(args.redact_secrets);
+        assert_eq!(args.config, Some(PathBuf::from("ci/gitprint.json")));
+        let args = Args::parse_from(["gitprint", "."]);
+        assert!(!args.redact_secrets);
+        assert!(args.config.is_none());
+    }
+
+    #[test]
+    fn changed_only_takes_an_optional_ref() {
+        let args = Args::parse_from(["gitprint", ".", "--changed-only"]);
+        assert_eq!(args.changed_only, Some(None));
+        let args = Args::parse_from(["gitprint", ".", "--changed-only=main", "--untracked"]);
+        assert_eq!(args.changed_only, Some(Some("main".to_string())));
+        assert!(args.untracked);
+        let args = Args::parse_from(["gitprint", "."]);
+        assert_eq!(args.changed_only, None);
+        assert!(!args.untracked);
+    }
+
+    #[test]
+    fn working_tree_flags_conflict_with_revisions() {
+        assert!(Args::try_parse_from(["gitprint", ".", "--untracked", "--commit", "abc"]).is_err());
+        assert!(
+            Args::try_parse_from(["gitprint", ".", "--changed-only", "--branch", "x"]).is_err()
+        );
+    }
+
+    #[test]
+    fn files_from_flags() {
+        let args = Args::parse_from(["gitprint", ".", "--files-from", "-", "-0"]);
+        assert_eq!(args.files_from, Some(PathBuf::from("-")));
+        assert!(args.null);
+        assert!(Args::parse_from(["gitprint", "."]).files_from.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "-0"]).is_err());
+    }
+
+    #[test]
+    fn max_depth_and_hidden_flags() {
+        let args = Args::parse_from(["gitprint", ".", "--max-depth", "2", "--no-hidden"]);
+        assert_eq!(args.max_depth, Some(2));
+        assert!(args.no_hidden);
+        // The last of --hidden / --no-hidden wins.
+        let args = Args::parse_from(["gitprint", ".", "--no-hidden", "--hidden"]);
+        assert!(!args.no_hidden);
+        let args = Args::parse_from(["gitprint", "."]);
+        assert_eq!(args.max_depth, None);
+        assert!(!args.no_hidden);
+    }
+
+    #[test]
+    fn local_and_remote_flags() {
+        assert!(Args::parse_from(["gitprint", "backup@host:notes", "--local"]).local);
+        assert!(Args::parse_from(["gitprint", "mirror:repo", "--remote"]).remote);
+        assert!(Args::try_parse_from(["gitprint", "x", "--local", "--remote"]).is_err());
+    }
+
+    #[test]
+    fn default_excludes_and_verbosity_flags() {
+        let args = Args::parse_from(["gitprint", ".", "--no-default-excludes", "-vv"]);
+        assert!(args.no_default_excludes);
+        assert_eq!(args.verbose, 2);
+        assert_eq!(Args::parse_from(["gitprint", "."]).verbose, 0);
+    }
+
+    #[test]
+    fn no_git_flag() {
+        assert!(Args::parse_from(["gitprint", "https://github.com/u/r", "--no-git"]).no_git);
+        assert!(!Args::parse_from(["gitprint", "."]).no_git);
+        assert_eq!(
+            Args::parse_from(["gitprint", "."]).max_download_size,
+            crate::archive::DEFAULT_MAX_DOWNLOAD_BYTES
+        );
+        assert_eq!(
+            Args::parse_from(["gitprint", ".", "--max-download-size", "200MB"]).max_download_size,
+            200 << 20
+        );
+    }
+
+    #[test]
+    fn clone_token_flag() {
+        let args = Args::parse_from(["gitprint", "https://x.org/r", "--clone-token", "abc"]);
+        assert_eq!(args.clone_token.as_deref(), Some("abc"));
+        assert!(Args::parse_from(["gitprint", "."]).clone_token.is_none());
+    }
+
+    #[test]
+    fn clone_reuse_flags() {
+        let args = Args::parse_from(["gitprint", "url", "--keep-clone", "--reuse-clone", "/tmp/c"]);
+        assert!(args.keep_clone);
+        assert_eq!(args.reuse_clone, Some(PathBuf::from("/tmp/c")));
+        let args = Args::parse_from(["gitprint", "url"]);
+        assert!(!args.keep_clone && args.reuse_clone.is_none());
+    }
+
+    #[test]
+    fn offline_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--offline"]).offline);
+        assert!(!Args::parse_from(["gitprint", "."]).offline);
+    }
+
+    #[test]
+    fn git_timeout_flag() {
+        assert_eq!(
+            Args::parse_from(["gitprint", ".", "--git-timeout", "30"]).git_timeout,
+            30
+        );
+        assert_eq!(
+            Args::parse_from(["gitprint", "."]).git_timeout,
+            crate::git::DEFAULT_TIMEOUT_SECS
+        );
+    }
+
+    #[test]
+    fn split_patterns_keeps_brace_alternations() {
+        assert_eq!(split_patterns(""), Vec::<String>::new());
+        assert_eq!(split_patterns(" a , ,b "), vec!["a", "b"]);
+        assert_eq!(
+            split_patterns("src/{a,b}/*.rs,*.md"),
+            vec!["src/{a,b}/*.rs", "*.md"]
+        );
+    }
+
+    #[test]
+    fn line_height_and_letter_spacing() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--line-height",
+            "1.5",
+            "--letter-spacing",
+            "-0.2",
+        ]);
+        assert_eq!(args.line_height, Some(1.5));
+        assert_eq!(args.letter_spacing, -0.2);
+        let args = Args::parse_from(["gitprint", "."]);
+        assert_eq!((args.line_height, args.letter_spacing), (None, 0.0));
+        assert!(Args::try_parse_from(["gitprint", ".", "--line-height", "0"]).is_err());
+    }
+
+    #[test]
+    fn symbols_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--symbols"]).symbols);
+        assert!(!Args::parse_from(["gitprint", "."]).symbols);
+    }
+
+    #[test]
+    fn checksums_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--checksums"]).checksums);
+        assert!(!Args::parse_from(["gitprint", "."]).checksums);
+    }
+
+    #[test]
+    fn margin_notes_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--margin-notes"]).margin_notes);
+        assert!(!Args::parse_from(["gitprint", "."]).margin_notes);
+    }
+
+    #[test]
+    fn refs_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--refs"]).refs);
+        assert!(!Args::parse_from(["gitprint", "."]).refs);
+    }
+
+    #[test]
+    fn show_takes_a_revision() {
+        let args = Args::parse_from(["gitprint", ".", "--show", "abc1234"]);
+        assert_eq!(args.show.as_deref(), Some("abc1234"));
+        assert!(Args::parse_from(["gitprint", "."]).show.is_none());
+    }
+
+    #[test]
+    fn hotspots_window_defaults_to_a_year() {
+        let args = Args::parse_from(["gitprint", ".", "--hotspots"]);
+        assert!(args.hotspots);
+        assert_eq!(args.hotspots_since, "1 year ago");
+        let args = Args::parse_from(["gitprint", ".", "--hotspots-since", "3 months ago"]);
+        assert_eq!(args.hotspots_since, "3 months ago");
+        assert!(!Args::parse_from(["gitprint", "."]).hotspots);
+    }
+
+    #[test]
+    fn chapters_takes_an_optional_depth() {
+        assert_eq!(
+            Args::parse_from(["gitprint", ".", "--chapters"]).chapters,
+            Some(1)
+        );
+        assert_eq!(
+            Args::parse_from(["gitprint", ".", "--chapters", "2"]).chapters,
+            Some(2)
+        );
+        assert_eq!(Args::parse_from(["gitprint", "."]).chapters, None);
+    }
+
+    #[test]
+    fn qr_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--qr"]).qr);
+        assert!(!Args::parse_from(["gitprint", "."]).qr);
+    }
+
+    #[test]
+    fn cover_rows_and_meta_flags() {
+        let args = Args::parse_from([
+            "gitprint",
+            ".",
+            "--cover-rows",
+            "commit,open-issues",
+            "--meta",
+            "Reviewed by=J. Smith",
+            "--meta",
+            "Ticket=SEC-1421",
+        ]);
+        assert_eq!(
+            args.cover_rows,
+            Some(vec![CoverRow::Commit, CoverRow::OpenIssues])
+        );
+        assert_eq!(args.meta[1].label, "Ticket");
+        assert!(Args::parse_from(["gitprint", "."]).cover_rows.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "--cover-rows", "nope"]).is_err());
+        assert!(Args::try_parse_from(["gitprint", ".", "--meta", "Ticket"]).is_err());
+    }
+
+    #[test]
+    fn locale_flag() {
+        let args = Args::parse_from(["gitprint", ".", "--locale", "de-DE"]);
+        assert_eq!(args.locale, Some(Locale::De));
+        assert!(Args::parse_from(["gitprint", "."]).locale.is_none());
+        assert!(Args::try_parse_from(["gitprint", ".", "--locale", "xx"]).is_err());
+    }
+
+    #[test]
+    fn no_commit_footer_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--no-commit-footer"]).no_commit_footer);
+        assert!(!Args::parse_from(["gitprint", "."]).no_commit_footer);
+    }
+
+    #[test]
+    fn preface_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--preface"]).preface);
+        assert!(!Args::parse_from(["gitprint", "."]).preface);
+    }
+
+    #[test]
+    fn no_summary_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--no-summary"]).no_summary);
+        assert!(!Args::parse_from(["gitprint", "."]).no_summary);
+    }
+
+    #[test]
+    fn no_running_header_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--no-running-header"]).no_running_header);
+        assert!(!Args::parse_from(["gitprint", "."]).no_running_header);
+    }
+
+    #[test]
+    fn compact_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--compact"]).compact);
+        assert!(!Args::parse_from(["gitprint", "."]).compact);
+    }
+
+    #[test]
+    fn tagged_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "--tagged"]).tagged);
+        assert!(!Args::parse_from(["gitprint", "."]).tagged);
+        assert!(Args::try_parse_from(["gitprint", ".", "--tagged", "--booklet"]).is_err());
+        assert!(Args::try_parse_from(["gitprint", ".", "--tagged", "--nup", "2"]).is_err());
+    }
+
     #[test]
     fn preview_flag() {
         let args = Args::parse_from(["gitprint", ".", "--preview"]);
@@ -383,10 +1700,17 @@ mod tests {
         assert!(!args.preview);
     }
 
+    #[test]
+    fn interactive_flag() {
+        assert!(Args::parse_from(["gitprint", ".", "-i"]).interactive);
+        assert!(!Args::parse_from(["gitprint", "."]).interactive);
+        assert!(Args::try_parse_from(["gitprint", ".", "--interactive", "--preview"]).is_err());
+    }
+
     #[test]
     fn preview_with_user() {
         let args = Args::parse_from(["gitprint", "-u", "alice", "--preview"]);
         assert!(args.preview);
-        assert_eq!(args.user, Some("alice".to_string()));
+        assert_eq!(args.user, vec!["alice".to_string()]);
     }
 }
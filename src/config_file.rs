@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::preprocess::{OnFailure, Preprocessor};
+use crate::types::LangStyle;
+
+/// File name looked up in the repository root when `--config` is not given.
+pub const FILE_NAME: &str = ".gitprint.json";
+
+/// Project-level settings read from a `.gitprint.json` file.
+///
+/// Every field is optional; unknown keys are rejected so typos surface as errors
+/// instead of being silently ignored.
+///
+/// ```json
+/// {
+///   "exclude": ["docs/**", "!docs/architecture.md"],
+///   "extra_default_excludes": ["*.generated.ts"],
+///   "redact_patterns": ["internal-id: (\\d+)"],
+///   "preprocess": "./scripts/strip-license {path}",
+///   "preprocess_on_failure": "skip",
+///   "lang": {
+///     "markdown": { "font_size": 9.5, "wrap": true },
+///     "rust": { "theme": "base16-ocean.light" }
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Exclude globs applied before any `--exclude`, in order; `!pattern`
+    /// re-includes what an earlier one excluded.
+    pub exclude: Vec<String>,
+    /// Globs added to the built-in default excludes, such as a project's own
+    /// generated files. `--no-default-excludes` drops the built-in list but keeps
+    /// these.
+    pub extra_default_excludes: Vec<String>,
+    /// Extra regexes for `--redact-secrets`; only the first capture group is blanked
+    /// when the pattern has one.
+    pub redact_patterns: Vec<String>,
+    /// Shell command each file's content is piped through before highlighting;
+    /// `{path}` expands to the file's repository-relative path.
+    pub preprocess: Option<String>,
+    /// Seconds the `preprocess` command may run per file (`0` disables the limit).
+    pub preprocess_timeout: Option<u64>,
+    /// What to do with a file whose `preprocess` command fails.
+    pub preprocess_on_failure: OnFailure,
+    /// Font size, wrapping, and theme overrides per language name or file extension.
+    pub lang: HashMap<String, LangStyle>,
+}
+
+impl ConfigFile {
+    /// Parses the config file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid config JSON.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read config file {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("invalid config file {}", path.display()))
+    }
+
+    /// The `preprocess` hook with its timeout and failure policy, if one is set.
+    pub fn preprocessor(&self) -> Option<Preprocessor> {
+        let command = self.preprocess.as_ref()?;
+        let timeout = self
+            .preprocess_timeout
+            .unwrap_or(crate::preprocess::DEFAULT_TIMEOUT_SECS);
+        Some(
+            Preprocessor::command(command.clone())
+                .with_timeout((timeout > 0).then(|| std::time::Duration::from_secs(timeout)))
+                .with_on_failure(self.preprocess_on_failure),
+        )
+    }
+
+    /// Loads `explicit` if given, otherwise [`FILE_NAME`] from the repository root
+    /// (the parent directory when `repo_path` is a single file). A missing default
+    /// file yields an empty config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chosen file exists but cannot be parsed, or if an
+    /// explicit path does not exist.
+    pub fn discover(explicit: Option<&Path>, repo_path: &Path) -> anyhow::Result<Self> {
+        if let Some(path) = explicit {
+            return Self::load(path);
+        }
+        let dir: PathBuf = if repo_path.is_file() {
+            repo_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        } else {
+            repo_path.to_path_buf()
+        };
+        let path = dir.join(FILE_NAME);
+        if path.is_file() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discover_reads_repo_root_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(FILE_NAME),
+            r#"{"redact_patterns": ["secret=(\\w+)"]}"#,
+        )
+        .unwrap();
+        let config = ConfigFile::discover(None, dir.path()).unwrap();
+        assert_eq!(config.redact_patterns, vec![r"secret=(\w+)".to_string()]);
+    }
+
+    #[test]
+    fn exclude_keeps_negations_in_order() {
+        let config: ConfigFile =
+            serde_json::from_str(r#"{"exclude": ["docs/**", "!docs/index.md"]}"#).unwrap();
+        assert_eq!(config.exclude, vec!["docs/**", "!docs/index.md"]);
+    }
+
+    #[test]
+    fn extra_default_excludes_are_parsed() {
+        let config: ConfigFile =
+            serde_json::from_str(r#"{"extra_default_excludes": ["*.pb.go"]}"#).unwrap();
+        assert_eq!(config.extra_default_excludes, vec!["*.pb.go"]);
+    }
+
+    #[test]
+    fn discover_without_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            ConfigFile::discover(None, dir.path()).unwrap(),
+            ConfigFile::default()
+        );
+    }
+
+    #[test]
+    fn explicit_path_must_exist() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("nope.json");
+        assert!(ConfigFile::discover(Some(&missing), dir.path()).is_err());
+    }
+
+    #[test]
+    fn preprocess_settings() {
+        let config: ConfigFile = serde_json::from_str(
+            r#"{"preprocess": "cat", "preprocess_timeout": 0, "preprocess_on_failure": "abort"}"#,
+        )
+        .unwrap();
+        let hook = config.preprocessor().unwrap();
+        assert_eq!(hook.on_failure(), OnFailure::Abort);
+        assert!(format!("{hook:?}").contains("timeout: None"));
+        assert!(ConfigFile::default().preprocessor().is_none());
+    }
+
+    #[test]
+    fn lang_styles_are_parsed() {
+        let config: ConfigFile = serde_json::from_str(
+            r#"{"lang": {"markdown": {"font_size": 9.5, "wrap": true}, "rust": {"theme": "x"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.lang["markdown"].font_size, Some(9.5));
+        assert_eq!(config.lang["markdown"].wrap, Some(true));
+        assert_eq!(config.lang["rust"].theme.as_deref(), Some("x"));
+        assert!(serde_json::from_str::<ConfigFile>(r#"{"lang": {"rust": {"size": 9}}}"#).is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("c.json");
+        std::fs::write(&path, r#"{"redact_pattern": []}"#).unwrap();
+        let err = ConfigFile::load(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown field"), "got: {err:#}");
+    }
+}
@@ -0,0 +1,513 @@
+//! The JSON form of [`Config`] taken by the C and WebAssembly bindings.
+//!
+//! Keys match the `Config` fields, with JSON-friendly values where a field holds a
+//! compiled type: `grep` and `only_matching` are regex strings, `marks` and `meta` are `--mark`
+//! and `--meta` strings, `cover_rows` are `--cover-rows` names, `locale` is a
+//! `--locale` tag, and `redact_secrets` plus the `.gitprint.json` keys
+//! (`redact_patterns`, `preprocess*`, `lang`) build the redactor and hook. Every key
+//! is optional and defaults to the CLI's default; unknown keys are rejected.
+//!
+//! ```json
+//! {
+//!   "repo_path": "/src/project",
+//!   "output_path": "/tmp/project.pdf",
+//!   "exclude_patterns": ["*.lock"],
+//!   "paper_size": "letter",
+//!   "color_mode": "grayscale",
+//!   "nup": 2,
+//!   "marks": ["src/auth.rs:40-75"],
+//!   "changed_only": "main"
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, bail};
+use serde::Deserialize;
+
+use crate::config_file::ConfigFile;
+use crate::preprocess::OnFailure;
+use crate::types::{
+    ColorMode, Config, ControlChars, CoverRow, Density, LangStyle, LineMark, MetaRow, NUp,
+    PaperSize, ReviewLayout,
+};
+
+/// The `changed_only` value.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ChangedOnly {
+    /// `true` lists files changed against the index; `false` lists every file.
+    Index(bool),
+    /// Files changed against this revision.
+    Rev(String),
+}
+
+/// A [`Config`] as JSON. See the [module docs](self) for the value formats.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigJson {
+    pub repo_path: PathBuf,
+    pub output_path: PathBuf,
+    pub include_patterns: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub include_regexes: Vec<String>,
+    pub exclude_regexes: Vec<String>,
+    /// Language names or extensions, as for `--lang`.
+    pub languages: Vec<String>,
+    pub exclude_languages: Vec<String>,
+    /// Names from [`PRESETS`](crate::filter::presets::PRESETS), as for `--preset`.
+    pub presets: Vec<String>,
+    pub no_default_excludes: bool,
+    pub extra_default_excludes: Vec<String>,
+    pub theme: String,
+    pub font_size: f64,
+    pub line_height: Option<f64>,
+    /// `compact`, `normal`, or `relaxed`, as for `--density`.
+    pub density: Option<String>,
+    pub letter_spacing: f64,
+    pub no_line_numbers: bool,
+    pub global_line_numbers: bool,
+    pub age_shading: bool,
+    pub layers: bool,
+    pub print_optimize: bool,
+    pub highlight_max_lines: usize,
+    /// `visualize` or `strip`, as for `--control-chars`.
+    pub control_chars: Option<String>,
+    pub prose_fonts: Vec<PathBuf>,
+    pub emoji_font: Option<PathBuf>,
+    pub toc: bool,
+    pub file_tree: bool,
+    pub preface: bool,
+    pub summary: bool,
+    pub commit_footer: bool,
+    pub running_header: bool,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    /// `a4`, `letter`, or `legal`.
+    pub paper_size: String,
+    pub landscape: bool,
+    pub remote_url: Option<String>,
+    pub offline: bool,
+    pub github_token: Option<String>,
+    pub pretty_data: bool,
+    pub page_total: bool,
+    pub booklet: bool,
+    /// `2` or `4`.
+    pub nup: Option<u8>,
+    /// `margin` or `pages`, as for `--review-layout`.
+    pub review_layout: Option<String>,
+    /// `color`, `grayscale`, or `ink-saver`.
+    pub color_mode: String,
+    pub tagged: bool,
+    pub compact: bool,
+    /// `<glob>:<ranges>`, as for `--mark`.
+    pub marks: Vec<String>,
+    /// A regular expression.
+    pub grep: Option<String>,
+    /// A regular expression, as for `--only-matching`.
+    pub only_matching: Option<String>,
+    pub symbols: bool,
+    pub checksums: bool,
+    pub margin_notes: bool,
+    /// How far back to count commits for the hotspot page; `None` leaves it out.
+    pub hotspots: Option<String>,
+    pub refs: bool,
+    pub show: Option<String>,
+    pub diff_context: usize,
+    pub chapters: Option<usize>,
+    pub qr: bool,
+    /// Row names, as for `--cover-rows`; `None` shows every row.
+    pub cover_rows: Option<Vec<String>>,
+    /// `<label>=<value>`, as for `--meta`.
+    pub meta: Vec<String>,
+    /// A locale tag, as for `--locale`; `None` keeps ISO dates and plain numbers.
+    pub locale: Option<String>,
+    /// Turns on `--redact-secrets`, with any `redact_patterns` added.
+    pub redact_secrets: bool,
+    pub redact_patterns: Vec<String>,
+    pub preprocess: Option<String>,
+    pub preprocess_timeout: Option<u64>,
+    pub preprocess_on_failure: OnFailure,
+    pub lang: HashMap<String, LangStyle>,
+    pub untracked: bool,
+    pub changed_only: Option<ChangedOnly>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub no_dates: bool,
+    pub files_from: Option<Vec<PathBuf>>,
+    pub max_depth: Option<usize>,
+    pub hidden: bool,
+    pub max_pages: Option<usize>,
+    /// In bytes.
+    pub max_output_size: Option<u64>,
+}
+
+impl Default for ConfigJson {
+    fn default() -> Self {
+        Self {
+            repo_path: PathBuf::from("."),
+            output_path: PathBuf::from("output.pdf"),
+            include_patterns: vec![],
+            exclude_patterns: vec![],
+            include_regexes: vec![],
+            exclude_regexes: vec![],
+            languages: vec![],
+            exclude_languages: vec![],
+            presets: vec![],
+            no_default_excludes: false,
+            extra_default_excludes: vec![],
+            theme: "InspiredGitHub".to_string(),
+            font_size: 8.0,
+            line_height: None,
+            density: None,
+            letter_spacing: 0.0,
+            no_line_numbers: false,
+            global_line_numbers: false,
+            age_shading: false,
+            layers: false,
+            print_optimize: false,
+            highlight_max_lines: crate::highlight::DEFAULT_HIGHLIGHT_MAX_LINES,
+            control_chars: None,
+            prose_fonts: vec![],
+            emoji_font: None,
+            toc: true,
+            file_tree: true,
+            preface: false,
+            summary: true,
+            commit_footer: true,
+            running_header: true,
+            branch: None,
+            commit: None,
+            paper_size: "a4".to_string(),
+            landscape: false,
+            remote_url: None,
+            offline: false,
+            github_token: None,
+            pretty_data: false,
+            page_total: false,
+            booklet: false,
+            nup: None,
+            review_layout: None,
+            color_mode: "color".to_string(),
+            tagged: false,
+            compact: false,
+            marks: vec![],
+            grep: None,
+            only_matching: None,
+            symbols: false,
+            checksums: false,
+            margin_notes: false,
+            hotspots: None,
+            refs: false,
+            show: None,
+            diff_context: 3,
+            chapters: None,
+            qr: false,
+            cover_rows: None,
+            meta: vec![],
+            locale: None,
+            redact_secrets: false,
+            redact_patterns: vec![],
+            preprocess: None,
+            preprocess_timeout: None,
+            preprocess_on_failure: OnFailure::default(),
+            lang: HashMap::new(),
+            untracked: false,
+            changed_only: None,
+            since: None,
+            until: None,
+            no_dates: false,
+            files_from: None,
+            max_depth: None,
+            hidden: true,
+            max_pages: None,
+            max_output_size: None,
+        }
+    }
+}
+
+impl ConfigJson {
+    /// Parses a JSON config object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not valid JSON or has unknown or mistyped keys.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(text).context("invalid config JSON")
+    }
+
+    /// Compiles the patterns and settings into a [`Config`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown paper size, color mode, density, control
+    /// character policy, review layout, cover row, locale, or `nup`, an invalid
+    /// mark, meta row, regex, or redaction pattern, `no_dates` together with `since` or `until`,
+    /// or `tagged` together with `booklet` or `nup`.
+    pub fn into_config(self) -> anyhow::Result<Config> {
+        if self.no_dates && (self.since.is_some() || self.until.is_some()) {
+            bail!("no_dates can't be combined with since or until");
+        }
+        if self.tagged && (self.booklet || self.nup.is_some()) {
+            bail!("tagged can't be combined with booklet or nup");
+        }
+        let paper_size = <PaperSize as clap::ValueEnum>::from_str(&self.paper_size, true)
+            .map_err(|_| anyhow::anyhow!("unknown paper_size: {}", self.paper_size))?;
+        let color_mode = match self.color_mode.to_ascii_lowercase().as_str() {
+            "color" => ColorMode::Color,
+            "grayscale" => ColorMode::Grayscale,
+            "ink-saver" => ColorMode::InkSaver,
+            other => bail!("unknown color_mode: {other}"),
+        };
+        let nup = match self.nup {
+            None => None,
+            Some(2) => Some(NUp::Two),
+            Some(4) => Some(NUp::Four),
+            Some(n) => bail!("nup must be 2 or 4, got {n}"),
+        };
+        let density = self
+            .density
+            .map(|name| {
+                <Density as clap::ValueEnum>::from_str(&name, true)
+                    .map_err(|_| anyhow::anyhow!("unknown density: {name}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let control_chars = self
+            .control_chars
+            .map(|name| {
+                <ControlChars as clap::ValueEnum>::from_str(&name, true)
+                    .map_err(|_| anyhow::anyhow!("unknown control_chars: {name}"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let review_layout = self
+            .review_layout
+            .map(|name| {
+                <ReviewLayout as clap::ValueEnum>::from_str(&name, true)
+                    .map_err(|_| anyhow::anyhow!("unknown review_layout: {name}"))
+            })
+            .transpose()?;
+        let marks = self
+            .marks
+            .iter()
+            .map(|m| m.parse::<LineMark>().map_err(|e| anyhow::anyhow!(e)))
+            .collect::<anyhow::Result<_>>()?;
+        let cover_rows = match &self.cover_rows {
+            None => CoverRow::ALL.to_vec(),
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    <CoverRow as clap::ValueEnum>::from_str(name, true)
+                        .map_err(|_| anyhow::anyhow!("unknown cover row: {name}"))
+                })
+                .collect::<anyhow::Result<_>>()?,
+        };
+        let meta = self
+            .meta
+            .iter()
+            .map(|m| m.parse::<MetaRow>().map_err(|e| anyhow::anyhow!(e)))
+            .collect::<anyhow::Result<_>>()?;
+        let locale = self
+            .locale
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(|e: String| anyhow::anyhow!(e))?
+            .unwrap_or_default();
+        let grep = self
+            .grep
+            .as_deref()
+            .map(regex::Regex::new)
+            .transpose()
+            .context("invalid grep pattern")?;
+        let only_matching = self
+            .only_matching
+            .as_deref()
+            .map(regex::Regex::new)
+            .transpose()
+            .context("invalid only_matching pattern")?;
+        let redact = self
+            .redact_secrets
+            .then(|| crate::redact::Redactor::new(&self.redact_patterns))
+            .transpose()?;
+        let preprocess = ConfigFile {
+            preprocess: self.preprocess,
+            preprocess_timeout: self.preprocess_timeout,
+            preprocess_on_failure: self.preprocess_on_failure,
+            ..ConfigFile::default()
+        }
+        .preprocessor();
+        Ok(Config {
+            repo_path: self.repo_path,
+            output_path: self.output_path,
+            include_patterns: self.include_patterns,
+            exclude_patterns: self.exclude_patterns,
+            include_regexes: self.include_regexes,
+            exclude_regexes: self.exclude_regexes,
+            languages: self.languages,
+            exclude_languages: self.exclude_languages,
+            presets: self.presets,
+            no_default_excludes: self.no_default_excludes,
+            extra_default_excludes: self.extra_default_excludes,
+            theme: self.theme,
+            font_size: self.font_size,
+            line_height: self.line_height,
+            density,
+            letter_spacing: self.letter_spacing,
+            no_line_numbers: self.no_line_numbers,
+            global_line_numbers: self.global_line_numbers,
+            age_shading: self.age_shading,
+            layers: self.layers,
+            print_optimize: self.print_optimize,
+            highlight_max_lines: self.highlight_max_lines,
+            control_chars,
+            prose_fonts: self.prose_fonts,
+            emoji_font: self.emoji_font,
+            toc: self.toc,
+            file_tree: self.file_tree,
+            preface: self.preface,
+            summary: self.summary,
+            commit_footer: self.commit_footer,
+            running_header: self.running_header,
+            branch: self.branch,
+            commit: self.commit,
+            git_timeout: Some(std::time::Duration::from_secs(
+                crate::git::DEFAULT_TIMEOUT_SECS,
+            )),
+            paper_size,
+            landscape: self.landscape,
+            remote_url: self.remote_url,
+            offline: self.offline,
+            github_token: self.github_token,
+            pretty_data: self.pretty_data,
+            page_total: self.page_total,
+            booklet: self.booklet,
+            nup,
+            review_layout,
+            color_mode,
+            tagged: self.tagged,
+            compact: self.compact,
+            marks,
+            grep,
+            only_matching,
+            symbols: self.symbols,
+            checksums: self.checksums,
+            margin_notes: self.margin_notes,
+            hotspots: self.hotspots,
+            refs: self.refs,
+            show: self.show,
+            diff_context: self.diff_context,
+            chapters: self.chapters,
+            qr: self.qr,
+            cover_rows,
+            meta,
+            locale,
+            redact,
+            preprocess,
+            lang_styles: self.lang,
+            untracked: self.untracked,
+            changed_only: self.changed_only.and_then(|c| match c {
+                ChangedOnly::Index(on) => on.then_some(None),
+                ChangedOnly::Rev(rev) => Some(Some(rev)),
+            }),
+            since: self.since,
+            until: self.until,
+            no_dates: self.no_dates,
+            files_from: self.files_from,
+            max_depth: self.max_depth,
+            hidden: self.hidden,
+            max_pages: self.max_pages,
+            max_output_size: self.max_output_size,
+            write_progress: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_object_uses_cli_defaults() {
+        let config = ConfigJson::parse("{}").unwrap().into_config().unwrap();
+        assert_eq!(config.theme, "InspiredGitHub");
+        assert_eq!(config.font_size, 8.0);
+        assert!(config.toc && config.file_tree && config.summary);
+        assert_eq!(config.color_mode, ColorMode::Color);
+        assert!(config.redact.is_none());
+        assert!(config.preprocess.is_none());
+        assert_eq!(config.cover_rows, CoverRow::ALL);
+    }
+
+    #[test]
+    fn converts_json_friendly_values() {
+        let config = ConfigJson::parse(
+            r#"{
+                "paper_size": "Letter",
+                "color_mode": "ink-saver",
+                "nup": 4,
+                "review_layout": "pages",
+                "density": "Relaxed",
+                "marks": ["src/*.rs:3-5"],
+                "cover_rows": ["commit", "Default-Branch"],
+                "meta": ["Ticket=SEC-1421"],
+                "locale": "de_DE.UTF-8",
+                "grep": "TODO|FIXME",
+                "redact_secrets": true,
+                "changed_only": "main",
+                "no_dates": true,
+                "highlight_max_lines": 500,
+                "control_chars": "strip",
+                "no_default_excludes": true,
+                "extra_default_excludes": ["*.gen.rs"]
+            }"#,
+        )
+        .unwrap()
+        .into_config()
+        .unwrap();
+        assert!(matches!(config.paper_size, PaperSize::Letter));
+        assert_eq!(config.color_mode, ColorMode::InkSaver);
+        assert_eq!(config.nup, Some(NUp::Four));
+        assert_eq!(config.review_layout, Some(ReviewLayout::Pages));
+        assert_eq!(config.density, Density::Relaxed);
+        assert_eq!(config.marks[0].ranges, vec![3..=5]);
+        assert_eq!(
+            config.cover_rows,
+            [CoverRow::Commit, CoverRow::DefaultBranch]
+        );
+        assert_eq!(config.meta[0].value, "SEC-1421");
+        assert_eq!(config.locale, crate::locale::Locale::De);
+        assert!(config.grep.unwrap().is_match("FIXME"));
+        assert!(config.redact.is_some());
+        assert_eq!(config.changed_only, Some(Some("main".to_string())));
+        assert!(config.no_dates);
+        assert_eq!(config.highlight_max_lines, 500);
+        assert_eq!(config.control_chars, ControlChars::Strip);
+        assert!(config.no_default_excludes);
+        assert_eq!(config.extra_default_excludes, ["*.gen.rs"]);
+
+        let index = ConfigJson::parse(r#"{"changed_only": true}"#).unwrap();
+        assert_eq!(index.into_config().unwrap().changed_only, Some(None));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_values() {
+        assert!(ConfigJson::parse(r#"{"paper": "a4"}"#).is_err());
+        let bad = |json: &str| ConfigJson::parse(json).unwrap().into_config().is_err();
+        assert!(bad(r#"{"paper_size": "a3"}"#));
+        assert!(bad(r#"{"nup": 3}"#));
+        assert!(bad(r#"{"review_layout": "inline"}"#));
+        assert!(bad(r#"{"density": "tight"}"#));
+        assert!(bad(r#"{"marks": ["src/main.rs"]}"#));
+        assert!(bad(r#"{"cover_rows": ["colour"]}"#));
+        assert!(bad(r#"{"meta": ["Ticket"]}"#));
+        assert!(bad(r#"{"locale": "xx"}"#));
+        assert!(bad(r#"{"grep": "("}"#));
+        assert!(bad(r#"{"no_dates": true, "since": "2024-01-01"}"#));
+        assert!(bad(r#"{"tagged": true, "booklet": true}"#));
+        assert!(bad(r#"{"tagged": true, "nup": 2}"#));
+    }
+}
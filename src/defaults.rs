@@ -68,6 +68,8 @@ pub const DEFAULT_EXCLUDES: &[&str] = &[
     "*.sqlite",
     "*.db",
     "*.pdf",
+    // gitprint's own half-written output (`.<name>.pdf.<pid>-<seq>-<nanos>.tmp`)
+    "**/.*.pdf.*.tmp",
 ];
 
 #[cfg(test)]
@@ -132,4 +134,13 @@ mod tests {
         assert!(DEFAULT_EXCLUDES.contains(&".vscode/**"));
         assert!(DEFAULT_EXCLUDES.contains(&".DS_Store"));
     }
+
+    #[test]
+    fn unfinished_output_is_excluded() {
+        let glob = Glob::new("**/.*.pdf.*.tmp").unwrap().compile_matcher();
+        assert!(DEFAULT_EXCLUDES.contains(&"**/.*.pdf.*.tmp"));
+        assert!(glob.is_match(".repo.pdf.4242-0-0000beef.tmp"));
+        assert!(glob.is_match("out/.repo.pdf.4242-1-0000beef.tmp"));
+        assert!(!glob.is_match("notes.tmp"));
+    }
 }
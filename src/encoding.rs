@@ -0,0 +1,112 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+
+/// Bytes the encoding guess looks at; enough for a confident guess on source
+/// files without scanning a large one end to end.
+const DETECT_BYTES: usize = 64 * 1024;
+
+/// A file's text, decoded to UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    /// The decoded text; bytes with no mapping in the encoding became U+FFFD.
+    pub text: String,
+    /// Name of the encoding the file was in (`Shift_JIS`, `windows-1252`,
+    /// `UTF-16LE`, …); `None` for UTF-8.
+    pub encoding: Option<&'static str>,
+}
+
+/// Decodes a file's bytes as text.
+///
+/// Valid UTF-8 is taken as it is. A UTF-16 byte-order mark decides the encoding;
+/// otherwise it is guessed from the bytes (Latin-1 files come out as its
+/// superset, `windows-1252`). Binary content — a NUL byte early on, without a
+/// byte-order mark — is decoded as UTF-8 without guessing, NULs intact, so
+/// [`is_binary`](crate::filter::is_binary) still turns it away.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::encoding::decode;
+///
+/// assert_eq!(decode(b"fn main() {}".to_vec()).encoding, None);
+///
+/// // "Größe" in Latin-1.
+/// let latin1 = decode(b"let gr\xf6\xdfe = 1; // Gr\xf6\xdfe in Zeilen".to_vec());
+/// assert_eq!(latin1.encoding, Some("windows-1252"));
+/// assert!(latin1.text.contains("größe"));
+/// ```
+pub fn decode(bytes: Vec<u8>) -> Decoded {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(text) => {
+            return Decoded {
+                text,
+                encoding: None,
+            };
+        }
+        Err(e) => e.into_bytes(),
+    };
+    let encoding = match Encoding::for_bom(&bytes) {
+        Some((encoding, _)) => encoding,
+        None if crate::filter::is_binary(&bytes) => {
+            return Decoded {
+                text: String::from_utf8_lossy(&bytes).into_owned(),
+                encoding: None,
+            };
+        }
+        None => {
+            let sample = &bytes[..bytes.len().min(DETECT_BYTES)];
+            let mut detector = EncodingDetector::new();
+            detector.feed(sample, sample.len() == bytes.len());
+            detector.guess(None, true)
+        }
+    };
+    // `decode` drops the byte-order mark, and may switch encodings on one.
+    let (text, encoding, _) = encoding.decode(&bytes);
+    Decoded {
+        text: text.into_owned(),
+        encoding: (encoding != UTF_8).then(|| encoding.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8_is_passed_through() {
+        let decoded = decode("// caf\u{e9} \u{1F600}\n".as_bytes().to_vec());
+        assert_eq!(decoded.text, "// caf\u{e9} \u{1F600}\n");
+        assert_eq!(decoded.encoding, None);
+    }
+
+    #[test]
+    fn shift_jis_is_detected() {
+        // "// こんにちは世界" (hello, world) in Shift_JIS.
+        let bytes = b"// \x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd\x90\xa2\x8a\x45\nint x;\n";
+        let decoded = decode(bytes.to_vec());
+        assert_eq!(decoded.encoding, Some("Shift_JIS"));
+        assert_eq!(
+            decoded.text,
+            "// \u{3053}\u{3093}\u{306B}\u{3061}\u{306F}\u{4E16}\u{754C}\nint x;\n"
+        );
+    }
+
+    #[test]
+    fn utf16_follows_its_byte_order_mark() {
+        let bytes: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("x = 1\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let decoded = decode(bytes);
+        assert_eq!(decoded.text, "x = 1\n");
+        assert_eq!(decoded.encoding, Some("UTF-16LE"));
+        assert!(!crate::filter::is_binary(decoded.text.as_bytes()));
+    }
+
+    #[test]
+    fn binary_is_left_for_the_binary_check() {
+        let decoded = decode(vec![0x89, b'P', b'N', b'G', 0, 0, 0xFF]);
+        assert_eq!(decoded.encoding, None);
+        assert!(crate::filter::is_binary(decoded.text.as_bytes()));
+    }
+}
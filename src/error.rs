@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Result type returned by gitprint's library API.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Everything that can go wrong while printing a repository.
+///
+/// Callers can match on the variant to tell user mistakes (a bad path, pattern, or
+/// theme) apart from environment failures (git, I/O) and cancellation.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(config: gitprint::types::Config) {
+/// match gitprint::run(&config).await {
+///     Ok(_) => {}
+///     Err(gitprint::Error::PathNotFound(path)) => eprintln!("no such path: {}", path.display()),
+///     Err(e) => eprintln!("error: {e}"),
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input path does not exist.
+    PathNotFound(PathBuf),
+    /// The input is not a git repository, directory, or file inside one.
+    UnsupportedPath(PathBuf),
+    /// A git command could not be run or exited with an error; holds git's message.
+    Git(String),
+    /// A git command outlived the `--git-timeout` limit and was killed.
+    GitTimeout(Duration),
+    /// An include or exclude glob pattern is invalid.
+    InvalidPattern {
+        /// The pattern as given.
+        pattern: String,
+        /// Why it was rejected.
+        message: String,
+    },
+    /// An `--include-regex` or `--exclude-regex` expression is invalid.
+    InvalidRegex {
+        /// The expression as given.
+        pattern: String,
+        /// Why it was rejected.
+        message: String,
+    },
+    /// The requested syntax highlighting theme does not exist.
+    UnknownTheme(String),
+    /// A `--lang` / `--exclude-lang` name matches no known language.
+    UnknownLanguage(String),
+    /// A `--preset` name is not one of [`PRESETS`](crate::filter::presets::PRESETS).
+    UnknownPreset(String),
+    /// A single-file input is binary or minified and cannot be printed.
+    Unprintable(PathBuf),
+    /// An option that only applies across files, such as `--max-pages`, was given
+    /// with a single-file input; holds the flag.
+    RepositoryOnly(&'static str),
+    /// The preprocess hook failed on a file under [`OnFailure::Abort`](crate::preprocess::OnFailure::Abort),
+    /// or on a single-file input under `OnFailure::Skip`.
+    Preprocess {
+        /// The file being preprocessed.
+        path: PathBuf,
+        /// What went wrong.
+        message: String,
+    },
+    /// Building or serializing the PDF failed.
+    Pdf(String),
+    /// A filesystem operation failed.
+    Io(std::io::Error),
+    /// The run was stopped through [`run_with_cancel`](crate::run_with_cancel).
+    Cancelled,
+    /// Any other failure.
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PathNotFound(path) => write!(f, "{}: path not found", path.display()),
+            Self::UnsupportedPath(path) => write!(
+                f,
+                "{}: not a git repository, directory, or file",
+                path.display()
+            ),
+            Self::Git(message) => f.write_str(message),
+            Self::GitTimeout(limit) => write!(f, "git timed out after {}s", limit.as_secs()),
+            Self::InvalidPattern { pattern, message } => {
+                write!(f, "invalid glob pattern '{pattern}': {message}")
+            }
+            Self::InvalidRegex { pattern, message } => {
+                write!(f, "invalid regex '{pattern}': {message}")
+            }
+            Self::UnknownTheme(name) => write!(
+                f,
+                "theme not found: {name} (use --list-themes to see available themes)"
+            ),
+            Self::UnknownLanguage(name) => write!(
+                f,
+                "unknown language: {name} (give a name such as rust or an extension such as rs)"
+            ),
+            Self::UnknownPreset(name) => write!(f, "unknown preset: {name}"),
+            Self::Unprintable(path) => write!(f, "{}: binary or minified file", path.display()),
+            Self::RepositoryOnly(flag) => {
+                write!(
+                    f,
+                    "{flag} applies to a repository or directory, not a single file"
+                )
+            }
+            Self::Preprocess { path, message } => {
+                write!(f, "{}: preprocess hook failed: {message}", path.display())
+            }
+            Self::Pdf(message) => write!(f, "PDF generation failed: {message}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Cancelled => f.write_str("cancelled"),
+            Self::Other(e) => write!(f, "{e:#}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<lopdf::Error> for Error {
+    fn from(e: lopdf::Error) -> Self {
+        Self::Pdf(e.to_string())
+    }
+}
+
+/// Unwraps an [`Error`] that was carried through `anyhow` so its variant survives;
+/// anything else becomes [`Error::Other`].
+impl From<anyhow::Error> for Error {
+    fn from(e: anyhow::Error) -> Self {
+        e.downcast::<Self>().unwrap_or_else(Self::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_survives_a_round_trip_through_anyhow() {
+        let wrapped = anyhow::Error::from(Error::UnknownTheme("nope".to_string()));
+        assert!(matches!(Error::from(wrapped), Error::UnknownTheme(name) if name == "nope"));
+    }
+
+    #[test]
+    fn other_errors_keep_their_context() {
+        let e = Error::from(anyhow::anyhow!("inner").context("outer"));
+        assert!(matches!(e, Error::Other(_)));
+        assert_eq!(e.to_string(), "outer: inner");
+    }
+
+    #[test]
+    fn messages_name_the_offending_input() {
+        assert_eq!(
+            Error::PathNotFound(PathBuf::from("/nope")).to_string(),
+            "/nope: path not found"
+        );
+        assert_eq!(
+            Error::GitTimeout(Duration::from_secs(5)).to_string(),
+            "git timed out after 5s"
+        );
+        assert_eq!(
+            Error::RepositoryOnly("--max-pages").to_string(),
+            "--max-pages applies to a repository or directory, not a single file"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! C ABI for embedding gitprint in other languages.
+//!
+//! Build with `--features ffi` to get a `cdylib` exporting the functions below;
+//! `include/gitprint.h` declares them. Each call takes a NUL-terminated UTF-8
+//! [`ConfigJson`] object, runs the whole pipeline on its own tokio runtime, and
+//! returns a status code. On failure, [`gitprint_last_error`] describes what went
+//! wrong.
+//!
+//! ```python
+//! import ctypes, json
+//!
+//! lib = ctypes.CDLL("libgitprint.so")
+//! lib.gitprint_last_error.restype = ctypes.c_char_p
+//! config = json.dumps({"repo_path": ".", "output_path": "out.pdf"}).encode()
+//! if lib.gitprint_run(config) != 0:
+//!     raise RuntimeError(lib.gitprint_last_error().decode())
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config_json::ConfigJson;
+use crate::types::Config;
+
+/// The call succeeded.
+pub const GITPRINT_OK: i32 = 0;
+/// A pointer was null, or the config was not valid UTF-8 or [`ConfigJson`].
+pub const GITPRINT_INVALID_ARGUMENT: i32 = 1;
+/// The run itself failed: a bad path, pattern, or theme, git, or I/O.
+pub const GITPRINT_RUN_FAILED: i32 = 2;
+/// gitprint panicked; the process is still usable.
+pub const GITPRINT_PANIC: i32 = 3;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Distinguishes the scratch files of concurrent [`gitprint_render`] calls.
+static SCRATCH: AtomicU64 = AtomicU64::new(0);
+
+/// Renders the repository described by `config_json` to its `output_path`.
+/// Returns [`GITPRINT_OK`] or an error status.
+///
+/// # Safety
+///
+/// `config_json` must be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gitprint_run(config_json: *const c_char) -> i32 {
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let config = unsafe { parse_config(config_json) }?;
+        run(&config)
+    })
+}
+
+/// Renders the repository described by `config_json` and hands the PDF back in
+/// memory instead of leaving it at `output_path`, which is ignored. On success
+/// `*out_ptr` and `*out_len` describe a buffer the caller must release with
+/// [`gitprint_free_bytes`]; on failure they are set to null and 0.
+///
+/// # Safety
+///
+/// `config_json` must be null or point to a NUL-terminated string; `out_ptr` and
+/// `out_len` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gitprint_render(
+    config_json: *const c_char,
+    out_ptr: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_ptr.is_null() || out_len.is_null() {
+        set_error("out_ptr and out_len must not be null");
+        return GITPRINT_INVALID_ARGUMENT;
+    }
+    // SAFETY: both were checked for null; the caller guarantees they are writable.
+    unsafe {
+        *out_ptr = std::ptr::null_mut();
+        *out_len = 0;
+    }
+    guard(|| {
+        // SAFETY: forwarded from the caller's contract.
+        let mut config = unsafe { parse_config(config_json) }?;
+        config.output_path = std::env::temp_dir().join(format!(
+            "gitprint-ffi-{}-{}.pdf",
+            std::process::id(),
+            SCRATCH.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = run(&config).and_then(|()| {
+            std::fs::read(&config.output_path).map_err(|e| (GITPRINT_RUN_FAILED, e.to_string()))
+        });
+        let _ = std::fs::remove_file(&config.output_path);
+        let bytes = result?.into_boxed_slice();
+        // SAFETY: as above.
+        unsafe {
+            *out_len = bytes.len();
+            *out_ptr = Box::into_raw(bytes).cast::<u8>();
+        }
+        Ok(())
+    })
+}
+
+/// Releases a buffer returned by [`gitprint_render`]. Null is ignored.
+///
+/// # Safety
+///
+/// `ptr` and `len` must come from one successful [`gitprint_render`] call, and the
+/// buffer must not be used or freed again afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gitprint_free_bytes(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        // SAFETY: `ptr`/`len` are a boxed slice leaked by `gitprint_render`.
+        drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+    }
+}
+
+/// The message for the last failed call on this thread, or null if it succeeded.
+/// The string stays valid until the next gitprint call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn gitprint_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|e| e.as_ref().map_or(std::ptr::null(), |e| e.as_ptr()))
+}
+
+/// The gitprint version, e.g. `0.4.0`, as a static NUL-terminated string.
+#[unsafe(no_mangle)]
+pub extern "C" fn gitprint_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Runs `f`, recording its error message and turning panics into [`GITPRINT_PANIC`]
+/// so they never unwind across the C boundary.
+fn guard(f: impl FnOnce() -> Result<(), (i32, String)>) -> i32 {
+    LAST_ERROR.with_borrow_mut(|e| *e = None);
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err((GITPRINT_PANIC, "gitprint panicked".to_string())));
+    match outcome {
+        Ok(()) => GITPRINT_OK,
+        Err((status, message)) => {
+            set_error(&message);
+            status
+        }
+    }
+}
+
+fn set_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with_borrow_mut(|e| *e = Some(message));
+}
+
+/// Reads and compiles the JSON config behind `config_json`.
+///
+/// # Safety
+///
+/// `config_json` must be null or point to a NUL-terminated string.
+unsafe fn parse_config(config_json: *const c_char) -> Result<Config, (i32, String)> {
+    if config_json.is_null() {
+        return Err((GITPRINT_INVALID_ARGUMENT, "config_json is null".to_string()));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract.
+    let text = unsafe { CStr::from_ptr(config_json) }
+        .to_str()
+        .map_err(|e| (GITPRINT_INVALID_ARGUMENT, format!("config_json: {e}")))?;
+    ConfigJson::parse(text)
+        .and_then(ConfigJson::into_config)
+        .map(|mut config| {
+            // Resolved against the current directory, as the CLI does for its input.
+            if let Ok(path) = std::path::absolute(&config.repo_path) {
+                config.repo_path = path;
+            }
+            config
+        })
+        .map_err(|e| (GITPRINT_INVALID_ARGUMENT, format!("{e:#}")))
+}
+
+/// Runs the pipeline to completion on a fresh multi-threaded runtime.
+fn run(config: &Config) -> Result<(), (i32, String)> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| (GITPRINT_RUN_FAILED, format!("cannot start runtime: {e}")))?;
+    runtime
+        .block_on(crate::run(config))
+        .map(|_| ())
+        .map_err(|e| (GITPRINT_RUN_FAILED, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        let ptr = gitprint_last_error();
+        assert!(!ptr.is_null());
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert_eq!(
+            unsafe { gitprint_run(std::ptr::null()) },
+            GITPRINT_INVALID_ARGUMENT
+        );
+        assert!(last_error().contains("null"));
+        let json = CString::new(r#"{"paper_size": "a3"}"#).unwrap();
+        assert_eq!(
+            unsafe { gitprint_run(json.as_ptr()) },
+            GITPRINT_INVALID_ARGUMENT
+        );
+        assert!(last_error().contains("a3"));
+    }
+
+    #[test]
+    fn render_returns_pdf_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        let json = serde_json::json!({ "repo_path": dir.path(), "offline": true }).to_string();
+        let json = CString::new(json).unwrap();
+        let (mut ptr, mut len) = (std::ptr::null_mut(), 0);
+        let status = unsafe { gitprint_render(json.as_ptr(), &mut ptr, &mut len) };
+        assert_eq!(status, GITPRINT_OK, "{}", last_error());
+        assert!(gitprint_last_error().is_null());
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert!(bytes.starts_with(b"%PDF"));
+        unsafe { gitprint_free_bytes(ptr, len) };
+    }
+}
@@ -0,0 +1,773 @@
+/// Named include/exclude sets for `--preset`.
+pub mod presets;
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
+
+use crate::defaults::DEFAULT_EXCLUDES;
+use crate::error::{Error, Result};
+
+/// Filters file paths based on glob (and optionally regex) include/exclude patterns.
+///
+/// Exclude patterns take precedence over include patterns. Like `.gitignore`
+/// lines, they are evaluated in order: the last one matching a path decides, and a
+/// `!`-prefixed pattern re-includes what an earlier one excluded. Default excludes
+/// (lock files, binaries, build artifacts) come first, so `!Cargo.lock` brings one
+/// back.
+pub struct FileFilter {
+    include_set: Option<GlobSet>,
+    /// Default excludes followed by the user's, with any `!` stripped.
+    exclude_set: GlobSet,
+    /// Each `exclude_set` pattern as written, by index, for [`Exclusion`].
+    exclude_patterns: Vec<String>,
+    /// How many of the `exclude_set` patterns are default excludes.
+    default_count: usize,
+    /// Whether each `exclude_set` pattern, by index, was a `!` re-include.
+    exclude_negated: Vec<bool>,
+    /// `--lang` and `--preset` globs; a path must match these as well as `include_set`.
+    selection_set: Option<GlobSet>,
+    /// `--exclude-lang` and `--preset` exclude globs.
+    selection_exclude_set: Option<GlobSet>,
+    /// `--include-regex`; a path may match these instead of `include_set`.
+    include_regex: Option<RegexSet>,
+    /// `--exclude-regex`.
+    exclude_regex: Option<RegexSet>,
+}
+
+impl FileFilter {
+    /// Creates a new `FileFilter` from glob include and exclude patterns.
+    ///
+    /// An empty `include_patterns` slice allows all files (subject to excludes).
+    /// Default excludes (lock files, build artifacts, binaries, etc.) are applied
+    /// unless a later `!pattern` re-includes the path; a literal leading `!` is
+    /// written `\!`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any glob pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// // Include only Rust files, exclude test helpers
+    /// let filter = FileFilter::new(
+    ///     &["*.rs".to_string()],
+    ///     &["test_*.rs".to_string()],
+    /// ).unwrap();
+    ///
+    /// assert!(filter.should_include(Path::new("main.rs")));
+    /// assert!(!filter.should_include(Path::new("test_helper.rs")));
+    /// assert!(!filter.should_include(Path::new("README.md")));
+    ///
+    /// // Exclude docs/ but keep one file from it
+    /// let filter = FileFilter::new(
+    ///     &[],
+    ///     &["docs/**".to_string(), "!docs/architecture.md".to_string()],
+    /// ).unwrap();
+    ///
+    /// assert!(filter.should_include(Path::new("docs/architecture.md")));
+    /// assert!(!filter.should_include(Path::new("docs/setup.md")));
+    /// ```
+    pub fn new(include_patterns: &[String], exclude_patterns: &[String]) -> Result<Self> {
+        let defaults: Vec<String> = DEFAULT_EXCLUDES.iter().map(|p| p.to_string()).collect();
+        Self::with_default_excludes(&defaults, include_patterns, exclude_patterns)
+    }
+
+    /// Like [`new`](Self::new), with `default_excludes` in place of
+    /// [`DEFAULT_EXCLUDES`] — empty for `--no-default-excludes`, longer with a
+    /// project's `extra_default_excludes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any glob pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// let filter = FileFilter::with_default_excludes(&["*.pb.go".to_string()], &[], &[]).unwrap();
+    /// assert!(filter.should_include(Path::new("Cargo.lock")));
+    /// assert!(!filter.should_include(Path::new("api.pb.go")));
+    /// ```
+    pub fn with_default_excludes(
+        default_excludes: &[String],
+        include_patterns: &[String],
+        exclude_patterns: &[String],
+    ) -> Result<Self> {
+        let include_set = optional_glob_set(include_patterns)?;
+
+        let (exclude_negated, exclude_globs): (Vec<bool>, Vec<Glob>) = default_excludes
+            .iter()
+            .map(|p| Ok((false, parse_glob(p)?)))
+            .chain(exclude_patterns.iter().map(|p| match p.strip_prefix('!') {
+                Some(rest) => Ok((true, parse_glob(rest)?)),
+                None => Ok((false, parse_glob(p)?)),
+            }))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let exclude_set = exclude_globs
+            .into_iter()
+            .fold(GlobSetBuilder::new(), |mut b, g| {
+                b.add(g);
+                b
+            })
+            .build()
+            .map_err(glob_set_error)?;
+
+        Ok(Self {
+            include_set,
+            exclude_set,
+            exclude_patterns: default_excludes
+                .iter()
+                .chain(exclude_patterns)
+                .cloned()
+                .collect(),
+            default_count: default_excludes.len(),
+            exclude_negated,
+            selection_set: None,
+            selection_exclude_set: None,
+            include_regex: None,
+            exclude_regex: None,
+        })
+    }
+
+    /// Narrows the filter to the languages and presets selected with `--lang` and
+    /// `--preset`, and drops those given with `--exclude-lang` or excluded by a
+    /// preset, each already expanded to globs by
+    /// [`language_globs`](crate::highlight::language_globs) or
+    /// [`presets::expand`]. Unlike extra `include_patterns`, the selection
+    /// intersects with them: `--include 'src/**' --lang rust` keeps only Rust files
+    /// under `src/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any glob pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// let filter = FileFilter::new(&["src/**".to_string()], &[])
+    ///     .unwrap()
+    ///     .with_selection(&["*.rs".to_string()], &[])
+    ///     .unwrap();
+    /// assert!(filter.should_include(Path::new("src/lib.rs")));
+    /// assert!(!filter.should_include(Path::new("src/README.md")));
+    /// assert!(!filter.should_include(Path::new("build.rs")));
+    /// ```
+    pub fn with_selection(
+        mut self,
+        include_globs: &[String],
+        exclude_globs: &[String],
+    ) -> Result<Self> {
+        self.selection_set = optional_glob_set(include_globs)?;
+        self.selection_exclude_set = optional_glob_set(exclude_globs)?;
+        Ok(self)
+    }
+
+    /// Adds regular-expression includes and excludes, matched against the whole
+    /// `/`-separated path, for selections globs can't express. They follow the glob
+    /// rules: a path is included if it matches any include glob or include regex,
+    /// and an exclude regex drops it outright (a `!` glob can't bring it back).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRegex`] if any expression is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// let filter = FileFilter::new(&[], &[])
+    ///     .unwrap()
+    ///     .with_regexes(&[r"^src/(api|core)/.*\.(rs|proto)$".to_string()], &[])
+    ///     .unwrap();
+    /// assert!(filter.should_include(Path::new("src/api/routes.rs")));
+    /// assert!(filter.should_include(Path::new("src/core/types.proto")));
+    /// assert!(!filter.should_include(Path::new("src/cli/main.rs")));
+    /// ```
+    pub fn with_regexes(
+        mut self,
+        include_regexes: &[String],
+        exclude_regexes: &[String],
+    ) -> Result<Self> {
+        self.include_regex = optional_regex_set(include_regexes)?;
+        self.exclude_regex = optional_regex_set(exclude_regexes)?;
+        Ok(self)
+    }
+
+    /// Returns `true` if `path` should be included given the configured patterns.
+    ///
+    /// Exclude patterns win over include patterns; among the excludes, the last
+    /// matching one decides. Patterns see the path with `/` separators on every
+    /// platform, so the same globs work on Windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// let filter = FileFilter::new(&["*.rs".to_string()], &[]).unwrap();
+    /// assert!(filter.should_include(Path::new("src/lib.rs")));
+    /// assert!(!filter.should_include(Path::new("Cargo.toml")));
+    /// assert!(!filter.should_include(Path::new("Cargo.lock"))); // default exclude
+    /// ```
+    pub fn should_include(&self, path: &Path) -> bool {
+        self.exclusion(path).is_none()
+    }
+
+    /// The rule that keeps `path` out, or `None` when
+    /// [`should_include`](Self::should_include) lets it through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::{Exclusion, FileFilter};
+    /// use std::path::Path;
+    ///
+    /// let filter = FileFilter::new(&[], &["docs/**".to_string()]).unwrap();
+    /// assert_eq!(filter.exclusion(Path::new("Cargo.lock")), Some(Exclusion::Default("Cargo.lock")));
+    /// assert_eq!(filter.exclusion(Path::new("docs/a.md")), Some(Exclusion::Pattern("docs/**")));
+    /// assert_eq!(filter.exclusion(Path::new("src/lib.rs")), None);
+    /// ```
+    pub fn exclusion(&self, path: &Path) -> Option<Exclusion<'_>> {
+        let text = crate::display_path(path);
+        let path = Path::new(&text);
+        if let Some(last) = self.exclude_set.matches(path).into_iter().max()
+            && !self.exclude_negated[last]
+        {
+            let pattern = self.exclude_patterns[last].as_str();
+            return Some(if last < self.default_count {
+                Exclusion::Default(pattern)
+            } else {
+                Exclusion::Pattern(pattern)
+            });
+        }
+        if self
+            .selection_exclude_set
+            .as_ref()
+            .is_some_and(|set| set.is_match(path))
+        {
+            return Some(Exclusion::Deselected);
+        }
+        if let Some(set) = &self.exclude_regex
+            && let Some(i) = set.matches(&text).into_iter().next()
+        {
+            return Some(Exclusion::Regex(set.patterns()[i].as_str()));
+        }
+        let included = match (&self.include_set, &self.include_regex) {
+            (None, None) => true,
+            (globs, regexes) => {
+                globs.as_ref().is_some_and(|set| set.is_match(path))
+                    || regexes.as_ref().is_some_and(|set| set.is_match(&text))
+            }
+        };
+        if !included {
+            return Some(Exclusion::NotIncluded);
+        }
+        self.selection_set
+            .as_ref()
+            .is_some_and(|set| !set.is_match(path))
+            .then_some(Exclusion::NotSelected)
+    }
+
+    /// Filters a list of paths, retaining only those that pass `should_include`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::filter::FileFilter;
+    /// use std::path::PathBuf;
+    ///
+    /// let filter = FileFilter::new(&["*.rs".to_string()], &[]).unwrap();
+    /// let paths = vec![
+    ///     PathBuf::from("main.rs"),
+    ///     PathBuf::from("README.md"),
+    ///     PathBuf::from("lib.rs"),
+    /// ];
+    /// let kept: Vec<_> = filter.filter_paths(paths).collect();
+    /// assert_eq!(kept, vec![PathBuf::from("main.rs"), PathBuf::from("lib.rs")]);
+    /// ```
+    pub fn filter_paths(&self, paths: Vec<PathBuf>) -> impl Iterator<Item = PathBuf> + '_ {
+        paths.into_iter().filter(|p| self.should_include(p))
+    }
+}
+
+/// Why a [`FileFilter`] keeps a path out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exclusion<'a> {
+    /// A default exclude glob, built in or from `extra_default_excludes`.
+    Default(&'a str),
+    /// An `--exclude` glob (or one from `.gitprint.json`'s `exclude`).
+    Pattern(&'a str),
+    /// An `--exclude-regex` expression.
+    Regex(&'a str),
+    /// Dropped by `--exclude-lang` or a preset's excludes.
+    Deselected,
+    /// Matched no `--include` glob or regex.
+    NotIncluded,
+    /// Outside the `--lang` and `--preset` selection.
+    NotSelected,
+}
+
+impl std::fmt::Display for Exclusion<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default(pattern) => write!(f, "default exclude {pattern}"),
+            Self::Pattern(pattern) => write!(f, "exclude {pattern}"),
+            Self::Regex(regex) => write!(f, "exclude regex {regex}"),
+            Self::Deselected => f.write_str("excluded language or preset"),
+            Self::NotIncluded => f.write_str("no include pattern matches"),
+            Self::NotSelected => f.write_str("outside the selected languages or presets"),
+        }
+    }
+}
+
+/// Compiles `patterns` into one set, or `None` when there are none.
+fn optional_glob_set(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let set = patterns
+        .iter()
+        .try_fold(GlobSetBuilder::new(), |mut b, p| {
+            b.add(parse_glob(p)?);
+            Ok::<_, Error>(b)
+        })?
+        .build()
+        .map_err(glob_set_error)?;
+    Ok(Some(set))
+}
+
+/// Compiles `patterns` into one regex set, or `None` when there are none. Each is
+/// checked on its own first so the error names the bad one.
+fn optional_regex_set(patterns: &[String]) -> Result<Option<RegexSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let invalid = |pattern: &String, e: regex::Error| Error::InvalidRegex {
+        pattern: pattern.clone(),
+        message: e.to_string(),
+    };
+    patterns
+        .iter()
+        .try_for_each(|p| regex::Regex::new(p).map(drop).map_err(|e| invalid(p, e)))?;
+    RegexSet::new(patterns)
+        .map(Some)
+        .map_err(|e| invalid(&patterns.join(" | "), e))
+}
+
+fn parse_glob(pattern: &str) -> Result<Glob> {
+    GlobBuilder::new(&slash_pattern(pattern))
+        .backslash_escape(true)
+        .build()
+        .map_err(|e| Error::InvalidPattern {
+            pattern: pattern.to_string(),
+            message: e.kind().to_string(),
+        })
+}
+
+/// On Windows, `pattern` with its `\` separators turned into `/`, so `src\**` and
+/// `src/**` select the same files; a leading `\!` stays an escape. Elsewhere `\`
+/// escapes the next character, as in `.gitignore`, and the pattern is left alone.
+fn slash_pattern(pattern: &str) -> Cow<'_, str> {
+    if !cfg!(windows) || !pattern.contains('\\') {
+        return Cow::Borrowed(pattern);
+    }
+    let (escape, rest) = match pattern.strip_prefix("\\!") {
+        Some(rest) => ("\\!", rest),
+        None => ("", pattern),
+    };
+    Cow::Owned(format!("{escape}{}", rest.replace('\\', "/")))
+}
+
+fn glob_set_error(e: globset::Error) -> Error {
+    Error::InvalidPattern {
+        pattern: e.glob().unwrap_or_default().to_string(),
+        message: e.kind().to_string(),
+    }
+}
+
+/// Returns `true` if the content appears to be a binary file.
+///
+/// Detection is based on the presence of non-text byte sequences (e.g. null bytes).
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::is_binary;
+///
+/// assert!(is_binary(b"hello\x00world")); // null byte → binary
+/// assert!(!is_binary(b"fn main() {}")); // valid UTF-8 → not binary
+/// assert!(!is_binary(b""));
+/// ```
+pub fn is_binary(content: &[u8]) -> bool {
+    content.iter().take(8000).any(|&b| b == 0)
+}
+
+/// Returns `true` if the content appears to be minified.
+///
+/// A file is considered minified when any of its first 5 lines exceeds 500 characters,
+/// which is characteristic of bundled or minified JavaScript/CSS.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::is_minified;
+///
+/// assert!(is_minified(&"x".repeat(501)));   // single very long line
+/// assert!(!is_minified("fn main() {\n    println!(\"hello\");\n}\n"));
+/// assert!(!is_minified(""));
+/// ```
+pub fn is_minified(content: &str) -> bool {
+    content.lines().take(5).any(|line| line.len() > 500)
+}
+
+/// Parses a `--files-from` list: one path per line, or NUL-separated when `nul` is
+/// set. Blank entries and leading `./` are dropped and duplicates keep their first
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::parse_file_list;
+/// use std::path::PathBuf;
+///
+/// let files = parse_file_list("src/b.rs\n./src/a.rs\n\nsrc/b.rs\n", false);
+/// assert_eq!(files, vec![PathBuf::from("src/b.rs"), PathBuf::from("src/a.rs")]);
+/// ```
+pub fn parse_file_list(input: &str, nul: bool) -> Vec<PathBuf> {
+    let mut seen = std::collections::HashSet::new();
+    let entries: Box<dyn Iterator<Item = &str>> = if nul {
+        Box::new(input.split('\0'))
+    } else {
+        Box::new(input.lines().map(str::trim_end))
+    };
+    entries
+        .map(|e| e.strip_prefix("./").unwrap_or(e))
+        .filter(|e| !e.is_empty())
+        .map(PathBuf::from)
+        .filter(|p| seen.insert(p.clone()))
+        .collect()
+}
+
+/// Returns `true` if a file last modified on `date` (`YYYY-MM-DD`) falls within the
+/// inclusive `since`..=`until` window. Files without a known date only pass when
+/// neither bound is set.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::in_date_range;
+///
+/// assert!(in_date_range(Some("2024-03-01"), Some("2024-01-01"), Some("2024-03-31")));
+/// assert!(!in_date_range(Some("2023-12-31"), Some("2024-01-01"), None));
+/// assert!(!in_date_range(None, None, Some("2024-03-31")));
+/// assert!(in_date_range(None, None, None));
+/// ```
+pub fn in_date_range(date: Option<&str>, since: Option<&str>, until: Option<&str>) -> bool {
+    match date {
+        Some(d) => since.is_none_or(|s| d >= s) && until.is_none_or(|u| d <= u),
+        None => since.is_none() && until.is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_file_list_nul_separated() {
+        let files = parse_file_list("a b.rs\0dir/c.rs\0\0", true);
+        assert_eq!(
+            files,
+            vec![PathBuf::from("a b.rs"), PathBuf::from("dir/c.rs")]
+        );
+    }
+
+    #[test]
+    fn date_range_bounds_are_inclusive() {
+        assert!(in_date_range(Some("2024-01-01"), Some("2024-01-01"), None));
+        assert!(in_date_range(Some("2024-03-31"), None, Some("2024-03-31")));
+        assert!(!in_date_range(Some("2024-04-01"), None, Some("2024-03-31")));
+        assert!(!in_date_range(None, Some("2024-01-01"), None));
+    }
+
+    #[test]
+    fn default_excludes_applied() {
+        let filter = FileFilter::new(&[], &[]).unwrap();
+        assert!(!filter.should_include(Path::new("Cargo.lock")));
+        assert!(!filter.should_include(Path::new("node_modules/foo.js")));
+        assert!(!filter.should_include(Path::new("image.png")));
+        assert!(!filter.should_include(Path::new("target/debug/binary")));
+        assert!(!filter.should_include(Path::new(".git/HEAD")));
+        assert!(!filter.should_include(Path::new("bundle.min.js")));
+    }
+
+    #[test]
+    fn custom_exclude() {
+        let filter = FileFilter::new(&[], &["*.md".to_string()]).unwrap();
+        assert!(!filter.should_include(Path::new("README.md")));
+        assert!(!filter.should_include(Path::new("docs/GUIDE.md")));
+        assert!(filter.should_include(Path::new("main.rs")));
+    }
+
+    #[test]
+    fn include_only() {
+        let filter = FileFilter::new(&["*.rs".to_string()], &[]).unwrap();
+        assert!(filter.should_include(Path::new("main.rs")));
+        assert!(filter.should_include(Path::new("src/lib.rs")));
+        assert!(!filter.should_include(Path::new("README.md")));
+        assert!(!filter.should_include(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn include_and_exclude_interaction() {
+        let filter = FileFilter::new(&["*.rs".to_string()], &["test_*.rs".to_string()]).unwrap();
+        assert!(filter.should_include(Path::new("main.rs")));
+        assert!(!filter.should_include(Path::new("test_helper.rs")));
+    }
+
+    #[test]
+    fn empty_filter_includes_normal_files() {
+        let filter = FileFilter::new(&[], &[]).unwrap();
+        assert!(filter.should_include(Path::new("src/main.rs")));
+        assert!(filter.should_include(Path::new("Cargo.toml")));
+        assert!(filter.should_include(Path::new("README.md")));
+    }
+
+    #[test]
+    fn multiple_include_patterns() {
+        let filter = FileFilter::new(&["*.rs".to_string(), "*.toml".to_string()], &[]).unwrap();
+        assert!(filter.should_include(Path::new("main.rs")));
+        assert!(filter.should_include(Path::new("Cargo.toml")));
+        assert!(!filter.should_include(Path::new("README.md")));
+    }
+
+    #[test]
+    fn multiple_exclude_patterns() {
+        let filter = FileFilter::new(&[], &["*.md".to_string(), "*.txt".to_string()]).unwrap();
+        assert!(!filter.should_include(Path::new("README.md")));
+        assert!(!filter.should_include(Path::new("notes.txt")));
+        assert!(filter.should_include(Path::new("main.rs")));
+    }
+
+    #[test]
+    fn selection_intersects_with_include_patterns() {
+        let filter = FileFilter::new(&[], &[])
+            .unwrap()
+            .with_selection(&[], &["*.md".to_string()])
+            .unwrap();
+        assert!(filter.should_include(Path::new("main.rs")));
+        assert!(!filter.should_include(Path::new("docs/GUIDE.md")));
+
+        let filter = FileFilter::new(&["src/**".to_string()], &[])
+            .unwrap()
+            .with_selection(&["*.rs".to_string(), "*.toml".to_string()], &[])
+            .unwrap();
+        assert!(filter.should_include(Path::new("src/main.rs")));
+        assert!(!filter.should_include(Path::new("Cargo.toml")));
+        assert!(!filter.should_include(Path::new("src/notes.txt")));
+    }
+
+    #[test]
+    fn negated_exclude_reincludes_in_order() {
+        let patterns = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let filter =
+            FileFilter::new(&[], &patterns(&["docs/**", "!docs/architecture.md"])).unwrap();
+        assert!(filter.should_include(Path::new("docs/architecture.md")));
+        assert!(!filter.should_include(Path::new("docs/guide.md")));
+
+        // A later exclude wins over an earlier re-include.
+        let filter =
+            FileFilter::new(&[], &patterns(&["docs/**", "!docs/*.md", "docs/draft-*"])).unwrap();
+        assert!(filter.should_include(Path::new("docs/guide.md")));
+        assert!(!filter.should_include(Path::new("docs/draft-api.md")));
+
+        // Default excludes can be re-included.
+        let filter = FileFilter::new(&[], &patterns(&["!Cargo.lock"])).unwrap();
+        assert!(filter.should_include(Path::new("Cargo.lock")));
+        assert!(!filter.should_include(Path::new("yarn.lock")));
+
+        // Re-including doesn't bypass include patterns.
+        let filter = FileFilter::new(&patterns(&["*.rs"]), &patterns(&["!*.md"])).unwrap();
+        assert!(!filter.should_include(Path::new("README.md")));
+    }
+
+    #[test]
+    fn regexes_follow_glob_precedence() {
+        let regexes = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let filter = FileFilter::new(&["*.toml".to_string()], &[])
+            .unwrap()
+            .with_regexes(
+                &regexes(&[r"^src/(api|core)/"]),
+                &regexes(&[r"_generated\."]),
+            )
+            .unwrap();
+        assert!(filter.should_include(Path::new("Cargo.toml")));
+        assert!(filter.should_include(Path::new("src/api/routes.rs")));
+        assert!(!filter.should_include(Path::new("src/cli/main.rs")));
+        assert!(!filter.should_include(Path::new("src/api/schema_generated.rs")));
+        assert!(!filter.should_include(Path::new("src/core/logo.png")));
+
+        let result = FileFilter::new(&[], &[])
+            .unwrap()
+            .with_regexes(&[], &regexes(&["ok", "(unclosed"]));
+        assert!(
+            matches!(result, Err(Error::InvalidRegex { pattern, .. }) if pattern == "(unclosed")
+        );
+    }
+
+    #[test]
+    fn exclude_takes_precedence_over_include() {
+        let filter = FileFilter::new(&["*.rs".to_string()], &["main.rs".to_string()]).unwrap();
+        assert!(!filter.should_include(Path::new("main.rs")));
+        assert!(filter.should_include(Path::new("lib.rs")));
+    }
+
+    #[test]
+    fn filter_paths_works() {
+        let filter = FileFilter::new(&["*.rs".to_string()], &[]).unwrap();
+        let paths = vec![
+            PathBuf::from("main.rs"),
+            PathBuf::from("README.md"),
+            PathBuf::from("lib.rs"),
+        ];
+        let filtered: Vec<_> = filter.filter_paths(paths).collect();
+        assert_eq!(
+            filtered,
+            vec![PathBuf::from("main.rs"), PathBuf::from("lib.rs")]
+        );
+    }
+
+    #[test]
+    fn filter_paths_empty_input() {
+        let filter = FileFilter::new(&[], &[]).unwrap();
+        let filtered: Vec<_> = filter.filter_paths(vec![]).collect();
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn is_binary_with_null_bytes() {
+        let content = b"hello\x00world";
+        assert!(is_binary(content));
+    }
+
+    #[test]
+    fn is_binary_with_text() {
+        let content = b"fn main() { println!(\"hello\"); }";
+        assert!(!is_binary(content));
+    }
+
+    #[test]
+    fn is_binary_with_empty() {
+        assert!(!is_binary(b""));
+    }
+
+    #[test]
+    fn is_binary_with_utf8() {
+        assert!(!is_binary("こんにちは世界".as_bytes()));
+    }
+
+    #[test]
+    fn is_minified_with_long_line() {
+        let long_line = "a".repeat(501);
+        assert!(is_minified(&long_line));
+    }
+
+    #[test]
+    fn is_minified_with_normal_content() {
+        assert!(!is_minified("fn main() {\n    println!(\"hi\");\n}\n"));
+    }
+
+    #[test]
+    fn is_minified_long_line_after_fifth() {
+        let mut content = "short\n".repeat(5);
+        content.push_str(&"a".repeat(501));
+        assert!(!is_minified(&content));
+    }
+
+    #[test]
+    fn is_minified_exactly_500_chars() {
+        let line = "a".repeat(500);
+        assert!(!is_minified(&line));
+    }
+
+    #[test]
+    fn is_minified_empty() {
+        assert!(!is_minified(""));
+    }
+
+    #[test]
+    fn is_minified_long_line_on_line_3() {
+        let content = format!("short\nshort\n{}\nshort\nshort\n", "a".repeat(501));
+        assert!(is_minified(&content));
+    }
+
+    #[test]
+    fn invalid_include_glob_returns_error() {
+        let result = FileFilter::new(&["[invalid".to_string()], &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn exclusion_names_the_deciding_rule() {
+        let filter = FileFilter::new(&["src/**".to_string()], &["src/gen/**".to_string()])
+            .unwrap()
+            .with_selection(&["*.rs".to_string()], &["*_test.rs".to_string()])
+            .unwrap()
+            .with_regexes(&[], &["^src/old/".to_string()])
+            .unwrap();
+        let why = |path: &str| filter.exclusion(Path::new(path));
+        assert_eq!(why("Cargo.lock"), Some(Exclusion::Default("Cargo.lock")));
+        assert_eq!(why("src/gen/a.rs"), Some(Exclusion::Pattern("src/gen/**")));
+        assert_eq!(why("src/old/a.rs"), Some(Exclusion::Regex("^src/old/")));
+        assert_eq!(why("src/a_test.rs"), Some(Exclusion::Deselected));
+        assert_eq!(why("build.rs"), Some(Exclusion::NotIncluded));
+        assert_eq!(why("src/notes.md"), Some(Exclusion::NotSelected));
+        assert_eq!(why("src/lib.rs"), None);
+        assert_eq!(
+            Exclusion::Default("*.svg").to_string(),
+            "default exclude *.svg"
+        );
+    }
+
+    #[test]
+    fn globs_match_either_separator() {
+        let filter =
+            FileFilter::new(&["src/**/*.rs".to_string()], &["src/gen/**".to_string()]).unwrap();
+        assert!(filter.should_include(Path::new("src//pdf/toc.rs")));
+        assert!(!filter.should_include(Path::new("src/gen/out.rs")));
+        assert_eq!(slash_pattern("\\!draft.md"), "\\!draft.md");
+        if cfg!(windows) {
+            assert!(filter.should_include(Path::new("src\\pdf\\toc.rs")));
+            assert_eq!(slash_pattern("src\\gen\\**"), "src/gen/**");
+            assert_eq!(slash_pattern("\\!a\\b"), "\\!a/b");
+        } else {
+            assert_eq!(slash_pattern("src\\*"), "src\\*");
+        }
+    }
+
+    #[test]
+    fn invalid_exclude_glob_returns_error() {
+        let result = FileFilter::new(&[], &["[invalid".to_string()]);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidPattern { pattern, .. }) if pattern == "[invalid"
+        ));
+    }
+}
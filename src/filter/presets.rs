@@ -0,0 +1,224 @@
+use crate::error::{Error, Result};
+
+/// A named, curated set of include and exclude globs for `--preset`.
+#[derive(Debug, Clone, Copy)]
+pub struct Preset {
+    /// The `--preset` value.
+    pub name: &'static str,
+    /// One line for `--help`.
+    pub description: &'static str,
+    /// Files the preset selects; empty selects everything.
+    pub include: &'static [&'static str],
+    /// Files the preset drops.
+    pub exclude: &'static [&'static str],
+}
+
+/// Every preset, in `--help` order.
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "src-only",
+        description: "Source code only: no docs, config, or data files",
+        include: &[
+            "*.rs", "*.c", "*.h", "*.cc", "*.cpp", "*.cxx", "*.hh", "*.hpp", "*.cs", "*.go",
+            "*.java", "*.kt", "*.kts", "*.scala", "*.groovy", "*.swift", "*.m", "*.mm", "*.py",
+            "*.pyi", "*.rb", "*.php", "*.pl", "*.pm", "*.lua", "*.js", "*.jsx", "*.mjs", "*.cjs",
+            "*.ts", "*.tsx", "*.mts", "*.cts", "*.vue", "*.svelte", "*.dart", "*.ex", "*.exs",
+            "*.erl", "*.hrl", "*.hs", "*.ml", "*.mli", "*.clj", "*.cljs", "*.lisp", "*.el",
+            "*.scm", "*.r", "*.R", "*.jl", "*.zig", "*.nim", "*.d", "*.sh", "*.bash", "*.zsh",
+            "*.fish", "*.ps1", "*.sql", "*.proto", "*.html", "*.css", "*.scss", "*.sass", "*.less",
+        ],
+        exclude: &[],
+    },
+    Preset {
+        name: "docs",
+        description: "Documentation: Markdown, reStructuredText, docs/, README, CHANGELOG",
+        include: &[
+            "*.md",
+            "*.markdown",
+            "*.mdx",
+            "*.rst",
+            "*.adoc",
+            "*.asciidoc",
+            "*.org",
+            "*.textile",
+            "*.txt",
+            "**/docs/**",
+            "**/doc/**",
+            "**/README*",
+            "**/CHANGELOG*",
+            "**/CONTRIBUTING*",
+            "**/LICENSE*",
+        ],
+        exclude: &[],
+    },
+    Preset {
+        name: "config",
+        description: "Configuration and build files: TOML, YAML, JSON, Dockerfiles, Makefiles, CI",
+        include: &[
+            "*.toml",
+            "*.yaml",
+            "*.yml",
+            "*.json",
+            "*.jsonc",
+            "*.json5",
+            "*.ini",
+            "*.cfg",
+            "*.conf",
+            "*.properties",
+            "*.xml",
+            "*.gradle",
+            "*.cmake",
+            "*.mk",
+            "*.nix",
+            "*.tf",
+            "*.tfvars",
+            "**/Dockerfile*",
+            "**/Makefile",
+            "**/CMakeLists.txt",
+            "**/.editorconfig",
+            "**/.gitignore",
+            "**/.gitattributes",
+            ".github/**",
+            ".gitlab-ci.yml",
+        ],
+        exclude: &[],
+    },
+    Preset {
+        name: "no-tests",
+        description: "Drop test directories, fixtures, and test files",
+        include: &[],
+        exclude: &[
+            "**/test/**",
+            "**/tests/**",
+            "**/__tests__/**",
+            "**/spec/**",
+            "**/testdata/**",
+            "**/fixtures/**",
+            "**/test_*.py",
+            "*_test.py",
+            "*_test.go",
+            "*_test.rs",
+            "*_spec.rb",
+            "*.test.js",
+            "*.test.jsx",
+            "*.test.ts",
+            "*.test.tsx",
+            "*.spec.js",
+            "*.spec.jsx",
+            "*.spec.ts",
+            "*.spec.tsx",
+            "*Test.java",
+            "*Tests.java",
+            "*Tests.cs",
+        ],
+    },
+];
+
+/// Looks up a preset by name.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::presets;
+///
+/// assert_eq!(presets::find("docs").unwrap().name, "docs");
+/// assert!(presets::find("everything").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.name == name)
+}
+
+/// Merges the named presets into one `(include, exclude)` glob pair: a file is
+/// selected if any preset includes it, and dropped if any preset excludes it.
+///
+/// # Errors
+///
+/// Returns [`Error::UnknownPreset`] for a name not in [`PRESETS`].
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::filter::presets;
+///
+/// let (include, exclude) = presets::expand(&["docs".to_string(), "no-tests".to_string()]).unwrap();
+/// assert!(include.contains(&"*.md".to_string()));
+/// assert!(exclude.contains(&"**/tests/**".to_string()));
+/// ```
+pub fn expand(names: &[String]) -> Result<(Vec<String>, Vec<String>)> {
+    names
+        .iter()
+        .try_fold((vec![], vec![]), |(mut include, mut exclude), name| {
+            let preset = find(name).ok_or_else(|| Error::UnknownPreset(name.clone()))?;
+            include.extend(preset.include.iter().map(|g| g.to_string()));
+            exclude.extend(preset.exclude.iter().map(|g| g.to_string()));
+            Ok((include, exclude))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::filter::FileFilter;
+
+    fn filter(names: &[&str]) -> FileFilter {
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let (include, exclude) = expand(&names).unwrap();
+        FileFilter::new(&[], &[])
+            .unwrap()
+            .with_selection(&include, &exclude)
+            .unwrap()
+    }
+
+    #[test]
+    fn every_preset_compiles() {
+        PRESETS.iter().for_each(|p| {
+            FileFilter::new(&[], &[])
+                .unwrap()
+                .with_selection(
+                    &p.include.iter().map(|g| g.to_string()).collect::<Vec<_>>(),
+                    &p.exclude.iter().map(|g| g.to_string()).collect::<Vec<_>>(),
+                )
+                .unwrap_or_else(|e| panic!("{}: {e}", p.name));
+        });
+    }
+
+    #[test]
+    fn presets_select_their_files() {
+        let src = filter(&["src-only"]);
+        assert!(src.should_include(Path::new("src/main.rs")));
+        assert!(!src.should_include(Path::new("README.md")));
+        assert!(!src.should_include(Path::new("Cargo.toml")));
+
+        let docs = filter(&["docs"]);
+        assert!(docs.should_include(Path::new("docs/guide/setup.html")));
+        assert!(docs.should_include(Path::new("crates/core/README")));
+        assert!(!docs.should_include(Path::new("src/lib.rs")));
+
+        let config = filter(&["config"]);
+        assert!(config.should_include(Path::new(".github/workflows/ci.yml")));
+        assert!(config.should_include(Path::new("docker/Dockerfile.dev")));
+        assert!(!config.should_include(Path::new("src/lib.rs")));
+    }
+
+    #[test]
+    fn presets_combine() {
+        let f = filter(&["src-only", "no-tests"]);
+        assert!(f.should_include(Path::new("pkg/server.go")));
+        assert!(!f.should_include(Path::new("pkg/server_test.go")));
+        assert!(!f.should_include(Path::new("tests/integration.rs")));
+        assert!(!f.should_include(Path::new("app/__tests__/button.test.tsx")));
+        assert!(!f.should_include(Path::new("README.md")));
+
+        let f = filter(&["src-only", "config"]);
+        assert!(f.should_include(Path::new("src/lib.rs")));
+        assert!(f.should_include(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn unknown_preset_is_an_error() {
+        let err = expand(&["everything".to_string()]).unwrap_err();
+        assert!(matches!(err, Error::UnknownPreset(ref name) if name == "everything"));
+    }
+}
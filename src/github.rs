@@ -2,19 +2,25 @@
 //!
 //! All functions operate on public data and work without authentication.
 //! Set `GITHUB_TOKEN` in the environment for higher rate limits (5 000/hr vs 60/hr)
-//! and access to private repositories.
+//! and access to private repositories. GET responses go through the
+//! [`ApiCache`](crate::cache::ApiCache) passed in, when there is one. Without the `native` feature only the
+//! response types and URL helpers are built.
 
+#[cfg(feature = "native")]
+use crate::cache::ApiCache;
+#[cfg(feature = "native")]
 use anyhow::{Context, bail};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 const API_BASE: &str = "https://api.github.com";
+#[cfg(feature = "native")]
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // ── Response types ─────────────────────────────────────────────────────────────
 
 /// GitHub user public profile returned by `GET /users/{username}`.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubUser {
     pub login: String,
     pub name: Option<String>,
@@ -32,7 +38,7 @@ pub struct GitHubUser {
 
 /// A GitHub repository as returned by the repos and search APIs.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GitHubRepo {
     pub name: String,
     pub full_name: String,
@@ -50,11 +56,56 @@ pub struct GitHubRepo {
     pub size: u64, // in KB
     #[serde(default)]
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub license: Option<License>,
+}
+
+/// A repository's license as detected by GitHub.
+#[allow(missing_docs)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct License {
+    pub name: String,
+    /// SPDX identifier (`MIT`, `Apache-2.0`); `NOASSERTION` when GitHub can't tell.
+    pub spdx_id: Option<String>,
+}
+
+/// A public organization membership as returned by `GET /users/{username}/orgs`.
+#[allow(missing_docs)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GitHubOrg {
+    pub login: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The user's role (`admin`, `member`), filled in only when the token can see
+    /// the membership; the orgs listing itself carries no role.
+    #[serde(skip_deserializing)]
+    pub role: Option<String>,
+}
+
+/// One day of a user's contributions calendar.
+#[allow(missing_docs)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionDay {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub contribution_count: u64,
+}
+
+/// Membership details returned by `GET /orgs/{org}/memberships/{username}`.
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct OrgMembership {
+    role: String,
 }
 
 /// A public GitHub event as returned by `GET /users/{username}/events/public`.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubEvent {
     #[serde(rename = "type")]
     pub kind: String,
@@ -65,14 +116,14 @@ pub struct GitHubEvent {
 
 /// The repository reference embedded in a GitHub event.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct EventRepo {
     pub name: String,
 }
 
 /// A single commit with its file patches, as returned by `GET /repos/{owner}/{repo}/commits/{sha}`.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommitDetail {
     pub sha: String,
     pub html_url: String,
@@ -83,7 +134,7 @@ pub struct CommitDetail {
 
 /// Commit metadata (message and author) embedded in a `CommitDetail`.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommitInfo {
     pub message: String,
     pub author: CommitAuthor,
@@ -91,7 +142,7 @@ pub struct CommitInfo {
 
 /// Author name and date embedded in a `CommitInfo`.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommitAuthor {
     pub name: String,
     pub date: String,
@@ -99,7 +150,7 @@ pub struct CommitAuthor {
 
 /// A single changed file within a commit, including optional unified diff patch.
 #[allow(missing_docs)]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct CommitFile {
     pub filename: String,
     pub status: String,
@@ -110,6 +161,7 @@ pub struct CommitFile {
 
 // ── Client helpers ──────────────────────────────────────────────────────────────
 
+#[cfg(feature = "native")]
 pub(crate) fn build_client() -> anyhow::Result<reqwest::Client> {
     reqwest::Client::builder()
         .user_agent(format!("gitprint/{VERSION}"))
@@ -117,15 +169,27 @@ pub(crate) fn build_client() -> anyhow::Result<reqwest::Client> {
         .context("failed to build HTTP client")
 }
 
+#[cfg(feature = "native")]
 fn auth_header(token: Option<&str>) -> Option<String> {
     token.map(|t| format!("Bearer {t}"))
 }
 
+/// GETs `url` and parses the JSON body, answering from `cache` when it holds a
+/// fresh response and storing the new one otherwise.
+#[cfg(feature = "native")]
 pub(crate) async fn get_json<T: for<'de> Deserialize<'de>>(
     client: &reqwest::Client,
     url: &str,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<T> {
+    if let Some(cache) = cache
+        && let Some(body) = cache.read(url, token).await
+        && let Ok(value) = serde_json::from_str::<T>(&body)
+    {
+        return Ok(value);
+    }
+
     let mut req = client
         .get(url)
         .header("Accept", "application/vnd.github+json");
@@ -147,23 +211,94 @@ pub(crate) async fn get_json<T: for<'de> Deserialize<'de>>(
     if !status.is_success() {
         bail!("GitHub API error {status}: {url}");
     }
-    resp.json::<T>()
+    let body = resp
+        .text()
         .await
-        .with_context(|| format!("parsing response from {url}"))
+        .with_context(|| format!("reading response from {url}"))?;
+    let value =
+        serde_json::from_str::<T>(&body).with_context(|| format!("parsing response from {url}"))?;
+    if let Some(cache) = cache {
+        cache.write(url, token, &body).await;
+    }
+    Ok(value)
 }
 
 // ── Public API functions ────────────────────────────────────────────────────────
 
+/// Splits a GitHub repository URL (`https://`, `ssh://` or SCP-style) into owner
+/// and repository name. Returns `None` for non-GitHub URLs.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::github::repo_slug;
+///
+/// assert_eq!(repo_slug("git@github.com:user/repo.git"), Some(("user", "repo")));
+/// assert_eq!(repo_slug("https://gitlab.com/user/repo"), None);
+/// ```
+pub fn repo_slug(repo_url: &str) -> Option<(&str, &str)> {
+    let rest = [
+        "https://github.com/",
+        "http://github.com/",
+        "ssh://git@github.com/",
+        "git@github.com:",
+    ]
+    .iter()
+    .find_map(|prefix| repo_url.strip_prefix(prefix))?;
+    let mut parts = rest.trim_end_matches('/').split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next()?.trim_end_matches(".git");
+    Some((owner, repo))
+}
+
+/// Maps a GitHub repository URL to the REST tarball endpoint for `rev` (the
+/// default branch when `None`). Returns `None` for non-GitHub URLs.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::github::tarball_url;
+///
+/// assert_eq!(
+///     tarball_url("git@github.com:user/repo.git", Some("v1.0")).as_deref(),
+///     Some("https://api.github.com/repos/user/repo/tarball/v1.0")
+/// );
+/// assert_eq!(tarball_url("https://gitlab.com/user/repo", None), None);
+/// ```
+pub fn tarball_url(repo_url: &str, rev: Option<&str>) -> Option<String> {
+    let (owner, repo) = repo_slug(repo_url)?;
+    Some(match rev {
+        Some(r) => format!("{API_BASE}/repos/{owner}/{repo}/tarball/{r}"),
+        None => format!("{API_BASE}/repos/{owner}/{repo}/tarball"),
+    })
+}
+
 /// Fetch a user's public profile.
-pub async fn get_user(username: &str, token: Option<&str>) -> anyhow::Result<GitHubUser> {
+#[cfg(feature = "native")]
+pub async fn get_user(
+    username: &str,
+    token: Option<&str>,
+    cache: Option<&ApiCache>,
+) -> anyhow::Result<GitHubUser> {
     let client = build_client()?;
     let url = format!("{API_BASE}/users/{username}");
-    get_json::<GitHubUser>(&client, &url, token)
+    get_json::<GitHubUser>(&client, &url, token, cache)
         .await
         .with_context(|| format!("fetching user '{username}'"))
 }
 
+/// Fetch a repository's description, topics, counters, and license.
+#[cfg(feature = "native")]
+pub async fn get_repo(owner: &str, repo: &str, token: Option<&str>) -> anyhow::Result<GitHubRepo> {
+    let client = build_client()?;
+    let url = format!("{API_BASE}/repos/{owner}/{repo}");
+    get_json::<GitHubRepo>(&client, &url, token, None)
+        .await
+        .with_context(|| format!("fetching repo '{owner}/{repo}'"))
+}
+
 /// Wrapper for the GitHub search/repositories response.
+#[cfg(feature = "native")]
 #[derive(Debug, Deserialize)]
 struct SearchReposResponse {
     items: Vec<GitHubRepo>,
@@ -172,17 +307,19 @@ struct SearchReposResponse {
 /// Fetch a user's top starred repositories via the Search API.
 ///
 /// Uses `/search/repositories` because `/users/{u}/repos` does not support `sort=stars`.
+#[cfg(feature = "native")]
 pub async fn get_user_starred_repos(
     username: &str,
     limit: usize,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<Vec<GitHubRepo>> {
     let client = build_client()?;
     let per_page = limit.min(100);
     let url = format!(
         "{API_BASE}/search/repositories?q=user:{username}+fork:false&sort=stars&order=desc&per_page={per_page}"
     );
-    get_json::<SearchReposResponse>(&client, &url, token)
+    get_json::<SearchReposResponse>(&client, &url, token, cache)
         .await
         .map(|r| r.items)
         .with_context(|| format!("fetching starred repos for '{username}'"))
@@ -192,42 +329,214 @@ pub async fn get_user_starred_repos(
 ///
 /// `limit` is capped at 100 (GitHub's maximum per-page).
 /// Only returns repos the user owns directly (`type=owner`).
+#[cfg(feature = "native")]
 pub async fn get_user_repos(
     username: &str,
     sort: &str,
     limit: usize,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<Vec<GitHubRepo>> {
     let client = build_client()?;
     let per_page = limit.min(100);
     let url = format!(
         "{API_BASE}/users/{username}/repos?type=owner&sort={sort}&direction=desc&per_page={per_page}"
     );
-    get_json::<Vec<GitHubRepo>>(&client, &url, token)
+    get_json::<Vec<GitHubRepo>>(&client, &url, token, cache)
         .await
         .with_context(|| format!("fetching repos for '{username}' (sort={sort})"))
 }
 
-/// Fetch a user's recent public events (max 100, GitHub returns up to 90 days).
+/// Events per page of the events API (its maximum).
+pub const EVENTS_PER_PAGE: usize = 100;
+
+/// Pages of the events API: it serves at most 300 events, from the last 90 days.
+pub const MAX_EVENT_PAGES: usize = 3;
+
+/// Fetch one page (1-based) of a user's recent public events, newest first.
+#[cfg(feature = "native")]
 pub async fn get_user_events(
     username: &str,
-    limit: usize,
+    page: usize,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<Vec<GitHubEvent>> {
     let client = build_client()?;
-    let per_page = limit.min(100);
-    let url = format!("{API_BASE}/users/{username}/events/public?per_page={per_page}");
-    get_json::<Vec<GitHubEvent>>(&client, &url, token)
+    let url =
+        format!("{API_BASE}/users/{username}/events/public?per_page={EVENTS_PER_PAGE}&page={page}");
+    get_json::<Vec<GitHubEvent>>(&client, &url, token, cache)
+        .await
+        .with_context(|| format!("fetching events for '{username}' (page {page})"))
+}
+
+/// Fetch the organizations a user publicly belongs to.
+#[cfg(feature = "native")]
+pub async fn get_user_orgs(
+    username: &str,
+    token: Option<&str>,
+    cache: Option<&ApiCache>,
+) -> anyhow::Result<Vec<GitHubOrg>> {
+    let client = build_client()?;
+    let url = format!("{API_BASE}/users/{username}/orgs?per_page=100");
+    get_json::<Vec<GitHubOrg>>(&client, &url, token, cache)
+        .await
+        .with_context(|| format!("fetching organizations for '{username}'"))
+}
+
+/// Fetch a user's role in an organization. Needs a token that can see the
+/// membership; fails otherwise.
+#[cfg(feature = "native")]
+pub async fn get_org_role(
+    org: &str,
+    username: &str,
+    token: Option<&str>,
+    cache: Option<&ApiCache>,
+) -> anyhow::Result<String> {
+    let client = build_client()?;
+    let url = format!("{API_BASE}/orgs/{org}/memberships/{username}");
+    get_json::<OrgMembership>(&client, &url, token, cache)
+        .await
+        .map(|m| m.role)
+        .with_context(|| format!("fetching {username}'s membership in '{org}'"))
+}
+
+/// GraphQL response envelope: `data` on success, `errors` otherwise.
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct CalendarData {
+    user: Option<CalendarUser>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CalendarUser {
+    contributions_collection: ContributionsCollection,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionsCollection {
+    contribution_calendar: ContributionCalendar,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct ContributionCalendar {
+    weeks: Vec<ContributionWeek>,
+}
+
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContributionWeek {
+    contribution_days: Vec<ContributionDay>,
+}
+
+#[cfg(feature = "native")]
+const CALENDAR_QUERY: &str = "query($login: String!, $from: DateTime, $to: DateTime) {
+  user(login: $login) {
+    contributionsCollection(from: $from, to: $to) {
+      contributionCalendar { weeks { contributionDays { date contributionCount } } }
+    }
+  }
+}";
+
+/// POSTs a GraphQL query to `url` and returns its `data`. The GraphQL API only
+/// serves authenticated requests, so this fails early without a token.
+#[cfg(feature = "native")]
+async fn post_graphql<T: for<'de> Deserialize<'de>>(
+    client: &reqwest::Client,
+    url: &str,
+    query: &str,
+    variables: serde_json::Value,
+    token: Option<&str>,
+) -> anyhow::Result<T> {
+    let Some(auth) = auth_header(token) else {
+        bail!("the GitHub GraphQL API needs GITHUB_TOKEN");
+    };
+    let resp = client
+        .post(url)
+        .header("Authorization", auth)
+        .json(&serde_json::json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .with_context(|| format!("POST {url}"))?;
+    let status = resp.status();
+    if !status.is_success() {
+        bail!("GitHub API error {status}: {url}");
+    }
+    let body: GraphQlResponse<T> = resp
+        .json()
         .await
-        .with_context(|| format!("fetching events for '{username}'"))
+        .with_context(|| format!("parsing response from {url}"))?;
+    match (body.data, body.errors.first()) {
+        (_, Some(error)) => bail!("GitHub GraphQL error: {}", error.message),
+        (Some(data), None) => Ok(data),
+        (None, None) => bail!("GitHub GraphQL response without data: {url}"),
+    }
+}
+
+/// Fetch a user's daily contribution counts between `since` and `until`
+/// (`YYYY-MM-DD`, at most a year apart), oldest first. Without bounds GitHub
+/// returns the last year. Needs a token.
+#[cfg(feature = "native")]
+pub async fn get_contribution_calendar(
+    username: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    token: Option<&str>,
+) -> anyhow::Result<Vec<ContributionDay>> {
+    let client = build_client()?;
+    let variables = serde_json::json!({
+        "login": username,
+        "from": since.map(|d| format!("{d}T00:00:00Z")),
+        "to": until.map(|d| format!("{d}T23:59:59Z")),
+    });
+    let data: CalendarData = post_graphql(
+        &client,
+        &format!("{API_BASE}/graphql"),
+        CALENDAR_QUERY,
+        variables,
+        token,
+    )
+    .await
+    .with_context(|| format!("fetching the contributions calendar for '{username}'"))?;
+    let user = data
+        .user
+        .with_context(|| format!("no GitHub user '{username}'"))?;
+    Ok(user
+        .contributions_collection
+        .contribution_calendar
+        .weeks
+        .into_iter()
+        .flat_map(|w| w.contribution_days)
+        .collect())
 }
 
 /// Response envelope for the commits search endpoint.
+#[cfg(feature = "native")]
 #[derive(Deserialize)]
 struct CommitSearchResponse {
     items: Vec<CommitSearchItem>,
 }
 
+#[cfg(feature = "native")]
 #[derive(Deserialize)]
 struct CommitSearchItem {
     sha: String,
@@ -235,11 +544,13 @@ struct CommitSearchItem {
     commit: CommitSearchMeta,
 }
 
+#[cfg(feature = "native")]
 #[derive(Deserialize)]
 struct CommitSearchRepo {
     full_name: String,
 }
 
+#[cfg(feature = "native")]
 #[derive(Deserialize)]
 struct CommitSearchMeta {
     message: String,
@@ -250,17 +561,19 @@ struct CommitSearchMeta {
 /// Uses `GET /search/commits?q=author:{username}` (stable since GitHub API v3 2022+).
 /// Returns `(owner/repo, sha, first-line-of-message)` tuples, newest first.
 /// Returns an empty Vec on error so the caller can degrade gracefully.
+#[cfg(feature = "native")]
 pub async fn search_user_commits(
     username: &str,
     limit: usize,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<Vec<(String, String, String)>> {
     let client = build_client()?;
     let per_page = limit.min(100);
     let url = format!(
         "{API_BASE}/search/commits?q=author:{username}&sort=committer-date&order=desc&per_page={per_page}"
     );
-    get_json::<CommitSearchResponse>(&client, &url, token)
+    get_json::<CommitSearchResponse>(&client, &url, token, cache)
         .await
         .map(|r| {
             r.items
@@ -280,20 +593,62 @@ pub async fn search_user_commits(
         .with_context(|| format!("searching commits by '{username}'"))
 }
 
+/// Response envelope for the issues search endpoint; only the total is read.
+#[cfg(feature = "native")]
+#[derive(Deserialize)]
+struct SearchCountResponse {
+    total_count: u64,
+}
+
+/// A search qualifier limiting results to those updated between `since` and
+/// `until` (`YYYY-MM-DD`, either open-ended), or `None` when both are.
+#[cfg(feature = "native")]
+fn updated_range(since: Option<&str>, until: Option<&str>) -> Option<String> {
+    (since.is_some() || until.is_some())
+        .then(|| format!("updated:{}..{}", since.unwrap_or("*"), until.unwrap_or("*")))
+}
+
+/// Count pull requests by others that `username` reviewed, optionally only those
+/// updated between `since` and `until`.
+///
+/// Uses `GET /search/issues?q=type:pr+reviewed-by:{username}`, which reaches further
+/// back than the 90-day event feed.
+#[cfg(feature = "native")]
+pub async fn count_reviewed_prs(
+    username: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    token: Option<&str>,
+    cache: Option<&ApiCache>,
+) -> anyhow::Result<u64> {
+    let client = build_client()?;
+    let mut query = format!("type:pr+reviewed-by:{username}+-author:{username}");
+    if let Some(range) = updated_range(since, until) {
+        query = format!("{query}+{range}");
+    }
+    let url = format!("{API_BASE}/search/issues?q={query}&per_page=1");
+    get_json::<SearchCountResponse>(&client, &url, token, cache)
+        .await
+        .map(|r| r.total_count)
+        .with_context(|| format!("searching PRs reviewed by '{username}'"))
+}
+
 /// Fetch a single commit with its file patches.
+#[cfg(feature = "native")]
 pub async fn get_commit_detail(
     owner_repo: &str,
     sha: &str,
     token: Option<&str>,
+    cache: Option<&ApiCache>,
 ) -> anyhow::Result<CommitDetail> {
     let client = build_client()?;
     let url = format!("{API_BASE}/repos/{owner_repo}/commits/{sha}");
-    get_json::<CommitDetail>(&client, &url, token)
+    get_json::<CommitDetail>(&client, &url, token, cache)
         .await
         .with_context(|| format!("fetching commit {sha} in {owner_repo}"))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
 mod tests {
     use super::*;
     use httpmock::prelude::*;
@@ -308,6 +663,19 @@ mod tests {
         assert_eq!(auth_header(None), None);
     }
 
+    #[test]
+    fn updated_range_is_open_ended() {
+        assert_eq!(updated_range(None, None), None);
+        assert_eq!(
+            updated_range(Some("2024-01-01"), None).as_deref(),
+            Some("updated:2024-01-01..*")
+        );
+        assert_eq!(
+            updated_range(None, Some("2024-12-31")).as_deref(),
+            Some("updated:*..2024-12-31")
+        );
+    }
+
     #[tokio::test]
     async fn parses_user_response() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -322,14 +690,95 @@ mod tests {
         });
 
         let client = build_client()?;
-        let user: GitHubUser =
-            get_json(&client, &format!("{}/users/alice", server.base_url()), None).await?;
+        let user: GitHubUser = get_json(
+            &client,
+            &format!("{}/users/alice", server.base_url()),
+            None,
+            None,
+        )
+        .await?;
         assert_eq!(user.login, "alice");
         assert_eq!(user.public_repos, 10);
         assert_eq!(user.followers, 42);
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parses_orgs_response() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/users/alice/orgs");
+            then.status(200).json_body(serde_json::json!([
+                { "login": "acme", "description": "Widgets", "url": "https://api.github.com/orgs/acme" },
+                { "login": "tools", "description": null }
+            ]));
+        });
+
+        let client = build_client()?;
+        let orgs: Vec<GitHubOrg> = get_json(
+            &client,
+            &format!("{}/users/alice/orgs", server.base_url()),
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(orgs.len(), 2);
+        assert_eq!(orgs[0].login, "acme");
+        assert_eq!(orgs[0].description.as_deref(), Some("Widgets"));
+        assert!(orgs[1].description.is_none() && orgs[1].role.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parses_graphql_calendar_response() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/graphql")
+                .header("Authorization", "Bearer tok");
+            then.status(200)
+                .json_body(serde_json::json!({ "data": { "user": {
+                "contributionsCollection": { "contributionCalendar": { "weeks": [
+                    { "contributionDays": [
+                        { "date": "2024-03-01", "contributionCount": 2 },
+                        { "date": "2024-03-02", "contributionCount": 0 }
+                    ] }
+                ] } }
+            } } }));
+        });
+
+        let client = build_client()?;
+        let url = format!("{}/graphql", server.base_url());
+        let data: CalendarData = post_graphql(
+            &client,
+            &url,
+            CALENDAR_QUERY,
+            serde_json::json!({}),
+            Some("tok"),
+        )
+        .await?;
+        let days = &data
+            .user
+            .unwrap()
+            .contributions_collection
+            .contribution_calendar
+            .weeks[0]
+            .contribution_days;
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].contribution_count, 2);
+
+        let anonymous = post_graphql::<CalendarData>(
+            &client,
+            &url,
+            CALENDAR_QUERY,
+            serde_json::json!({}),
+            None,
+        )
+        .await;
+        assert!(anonymous.is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parses_repo_list_response() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -349,6 +798,7 @@ mod tests {
             &client,
             &format!("{}/users/alice/repos", server.base_url()),
             None,
+            None,
         )
         .await?;
         assert_eq!(repos.len(), 1);
@@ -357,6 +807,35 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parses_repo_response() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/repos/alice/myrepo");
+            then.status(200).json_body(serde_json::json!({
+                "name": "myrepo", "full_name": "alice/myrepo",
+                "html_url": "https://github.com/alice/myrepo", "description": "A repo",
+                "language": "Rust", "stargazers_count": 7, "forks_count": 1,
+                "pushed_at": null, "updated_at": null, "fork": false,
+                "open_issues_count": 3, "topics": ["cli", "pdf"], "default_branch": "main",
+                "license": { "key": "mit", "name": "MIT License", "spdx_id": "MIT" }
+            }));
+        });
+
+        let client = build_client()?;
+        let repo: GitHubRepo = get_json(
+            &client,
+            &format!("{}/repos/alice/myrepo", server.base_url()),
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(repo.topics, ["cli", "pdf"]);
+        assert_eq!(repo.default_branch.as_deref(), Some("main"));
+        assert_eq!(repo.license.unwrap().spdx_id.as_deref(), Some("MIT"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parses_event_list_response() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -375,6 +854,7 @@ mod tests {
             &client,
             &format!("{}/users/alice/events/public", server.base_url()),
             None,
+            None,
         )
         .await?;
         assert_eq!(events.len(), 1);
@@ -409,6 +889,7 @@ mod tests {
             &client,
             &format!("{}/repos/alice/myrepo/commits/{sha}", server.base_url()),
             None,
+            None,
         )
         .await?;
         assert_eq!(detail.sha, sha);
@@ -426,10 +907,14 @@ mod tests {
         });
 
         let client = build_client().unwrap();
-        let err =
-            get_json::<GitHubUser>(&client, &format!("{}/users/alice", server.base_url()), None)
-                .await
-                .unwrap_err();
+        let err = get_json::<GitHubUser>(
+            &client,
+            &format!("{}/users/alice", server.base_url()),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
         assert!(err.to_string().contains("rate limit"), "got: {err}");
     }
 }
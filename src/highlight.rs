@@ -1,22 +1,59 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::iter::Peekable;
 use std::path::Path;
+use std::str::Chars;
+use std::sync::OnceLock;
 
 use syntect::easy::HighlightLines;
-use syntect::highlighting::{FontStyle, ThemeSet};
-use syntect::parsing::SyntaxSet;
+use syntect::highlighting::{Color, FontStyle, Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
 
-use crate::types::{HighlightedLine, HighlightedToken, RgbColor};
+use crate::types::{ControlChars, HighlightedLine, HighlightedToken, RgbColor};
 
 /// Syntax highlighter backed by the bundled syntect theme and syntax sets.
-pub struct Highlighter {
-    syntax_set: SyntaxSet,
-    theme: syntect::highlighting::Theme,
+///
+/// It borrows its syntax definitions, so highlighters for different themes share
+/// one set; [`Highlighter::new`] uses the process-wide [`syntax_set`].
+pub struct Highlighter<'s> {
+    syntax_set: &'s SyntaxSet,
+    /// Each file name and extension the syntaxes claim, lowercased, resolved once.
+    by_extension: HashMap<String, &'s SyntaxReference>,
+    theme: Theme,
+    /// Files longer than this many lines get plain tokens (`--highlight-max-lines`).
+    line_limit: Option<usize>,
+    /// What becomes of control characters in the text (`--control-chars`).
+    control_chars: ControlChars,
+}
+
+/// Default for `--highlight-max-lines`: past this, regex highlighting costs more
+/// than the colors are worth.
+pub const DEFAULT_HIGHLIGHT_MAX_LINES: usize = 20_000;
+
+/// The bundled syntax definitions, deserialized on first use and shared by every
+/// highlighter after that.
+pub fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// The bundled themes, deserialized on first use and shared after that.
+pub fn theme_set() -> &'static ThemeSet {
+    static SET: OnceLock<ThemeSet> = OnceLock::new();
+    SET.get_or_init(ThemeSet::load_defaults)
 }
 
-impl Highlighter {
+/// Contrast ratio against white paper that `--print-optimize` raises every
+/// foreground to (WCAG AA for body text).
+pub const PRINT_CONTRAST: f32 = 4.5;
+
+impl Highlighter<'static> {
     /// Creates a new `Highlighter` using the named syntect theme.
     ///
     /// Theme names are the keys returned by [`list_themes`]. Pass `"InspiredGitHub"` for
-    /// the default light theme.
+    /// the default light theme. The bundled sets are loaded once per process, so
+    /// only the first call pays for deserializing them.
     ///
     /// # Errors
     ///
@@ -32,23 +69,165 @@ impl Highlighter {
     /// let err = Highlighter::new("no-such-theme").err().unwrap();
     /// assert!(err.to_string().contains("no-such-theme"));
     /// ```
-    pub fn new(theme_name: &str) -> anyhow::Result<Self> {
-        let syntax_set = SyntaxSet::load_defaults_newlines();
-        let theme_set = ThemeSet::load_defaults();
+    pub fn new(theme_name: &str) -> crate::error::Result<Self> {
+        Highlighter::with_shared_sets(syntax_set(), theme_set(), theme_name)
+    }
+}
+
+impl<'s> Highlighter<'s> {
+    /// Creates a `Highlighter` that borrows `syntax_set` and takes the named theme
+    /// from `theme_set`, for callers that load or extend the sets themselves and
+    /// keep them around for many highlighters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `theme_name` is not found in `theme_set`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::highlight::{self, Highlighter};
+    /// use std::path::Path;
+    ///
+    /// let syntaxes = highlight::syntax_set();
+    /// let hl = Highlighter::with_shared_sets(syntaxes, highlight::theme_set(), "InspiredGitHub")
+    ///     .unwrap();
+    /// assert_eq!(hl.language(Path::new("lib.rs")), "Rust");
+    /// ```
+    pub fn with_shared_sets(
+        syntax_set: &'s SyntaxSet,
+        theme_set: &ThemeSet,
+        theme_name: &str,
+    ) -> crate::error::Result<Self> {
+        let theme = theme_named(theme_set, theme_name)?;
+        // Later syntaxes win a shared extension, as in syntect's own lookup.
+        let by_extension = syntax_set
+            .syntaxes()
+            .iter()
+            .flat_map(|syntax| {
+                syntax
+                    .file_extensions
+                    .iter()
+                    .map(move |ext| (ext.to_ascii_lowercase(), syntax))
+            })
+            .collect();
+        Ok(Self {
+            syntax_set,
+            by_extension,
+            theme,
+            line_limit: None,
+            control_chars: ControlChars::default(),
+        })
+    }
+
+    /// A highlighter sharing this one's syntax definitions but drawing with another
+    /// theme, for per-language theme overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `theme_name` is not found in the bundled theme set.
+    pub fn with_theme(&self, theme_name: &str) -> crate::error::Result<Self> {
+        let theme = theme_named(theme_set(), theme_name)?;
+        Ok(Self {
+            syntax_set: self.syntax_set,
+            by_extension: self.by_extension.clone(),
+            theme,
+            line_limit: self.line_limit,
+            control_chars: self.control_chars,
+        })
+    }
+
+    /// This highlighter giving files of more than `limit` lines plain tokens in the
+    /// theme's foreground color instead of running the syntax regexes over them.
+    /// `None` highlights every file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::highlight::Highlighter;
+    /// use std::path::Path;
+    ///
+    /// let hl = Highlighter::new("InspiredGitHub").unwrap().with_line_limit(Some(1));
+    /// let lines: Vec<_> = hl.highlight_lines("fn a() {}\nfn b() {}", Path::new("a.rs")).collect();
+    /// assert_eq!(lines[0].tokens.len(), 1);
+    /// ```
+    pub fn with_line_limit(mut self, limit: Option<usize>) -> Self {
+        self.line_limit = limit;
+        self
+    }
+
+    /// This highlighter passing each line through [`sanitize_controls`] with
+    /// `policy` before it is highlighted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::highlight::Highlighter;
+    /// use gitprint::types::ControlChars;
+    /// use std::path::Path;
+    ///
+    /// let hl = Highlighter::new("InspiredGitHub").unwrap().with_control_chars(ControlChars::Strip);
+    /// let lines: Vec<_> = hl.highlight_lines("a\x0cb", Path::new("a.txt")).collect();
+    /// assert_eq!(lines[0].tokens[0].text, "ab");
+    /// ```
+    pub fn with_control_chars(mut self, policy: ControlChars) -> Self {
+        self.control_chars = policy;
+        self
+    }
+
+    /// This highlighter with its theme passed through [`optimize_for_print`]
+    /// (`--print-optimize`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::highlight::Highlighter;
+    ///
+    /// let hl = Highlighter::new("base16-ocean.dark").unwrap().print_optimized();
+    /// ```
+    pub fn print_optimized(mut self) -> Self {
+        optimize_for_print(&mut self.theme);
+        self
+    }
+
+    /// Name of the syntax `path` is highlighted as (`"Rust"`, `"Markdown"`,
+    /// `"Plain Text"`, …), detected from its file name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::highlight::Highlighter;
+    /// use std::path::Path;
+    ///
+    /// let hl = Highlighter::new("InspiredGitHub").unwrap();
+    /// assert_eq!(hl.language(Path::new("src/main.rs")), "Rust");
+    /// assert_eq!(hl.language(Path::new("notes.unknown")), "Plain Text");
+    /// ```
+    pub fn language(&self, path: &Path) -> &str {
+        &self.syntax_for(path).name
+    }
 
-        let theme = theme_set.themes.get(theme_name).cloned().ok_or_else(|| {
-            anyhow::anyhow!(
-                "theme not found: {theme_name} (use --list-themes to see available themes)"
-            )
-        })?;
+    fn syntax_for(&self, path: &Path) -> &'s SyntaxReference {
+        self.known_syntax(path)
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text())
+    }
 
-        Ok(Self { syntax_set, theme })
+    /// The syntax claiming `path`'s whole file name, else its extension.
+    fn known_syntax(&self, path: &Path) -> Option<&'s SyntaxReference> {
+        let lookup = |part: Option<&std::ffi::OsStr>| {
+            let part = part?.to_str()?.to_ascii_lowercase();
+            self.by_extension.get(&part).copied()
+        };
+        lookup(path.file_name()).or_else(|| lookup(path.extension()))
     }
 
     /// Returns a lazy iterator that yields one [`HighlightedLine`] at a time.
     ///
-    /// Syntax is detected from the file extension of `path`; unknown extensions fall
-    /// back to plain text. Line numbers start at 1.
+    /// Syntax is detected from the file extension of `path`, then from a shebang or
+    /// mode line; anything else is plain text. Line numbers start at 1. Past the
+    /// [line limit](Self::with_line_limit), lines come back as one plain token each.
+    /// Control characters are [visualized or stripped](Self::with_control_chars)
+    /// first, so none reach the page raw.
     ///
     /// # Examples
     ///
@@ -69,20 +248,44 @@ impl Highlighter {
         path: &Path,
     ) -> impl Iterator<Item = HighlightedLine> + 'a {
         let syntax = self
-            .syntax_set
-            .find_syntax_for_file(path)
-            .ok()
-            .flatten()
+            .known_syntax(path)
+            .or_else(|| {
+                let first = content.lines().next()?;
+                self.syntax_set.find_syntax_by_first_line(first)
+            })
             .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
-
+        let plain = self
+            .line_limit
+            .is_some_and(|limit| content.lines().nth(limit).is_some());
+        let foreground = self.theme.settings.foreground.unwrap_or(Color::BLACK);
         let mut h = HighlightLines::new(syntax, &self.theme);
         let mut lines = content.lines().enumerate();
 
         std::iter::from_fn(move || {
             let (i, line_text) = lines.next()?;
+            let line_text = sanitize_controls(line_text, self.control_chars);
+            if plain {
+                let tokens = (!line_text.is_empty())
+                    .then(|| HighlightedToken {
+                        text: line_text.to_string(),
+                        color: RgbColor {
+                            r: foreground.r,
+                            g: foreground.g,
+                            b: foreground.b,
+                        },
+                        bold: false,
+                        italic: false,
+                    })
+                    .into_iter()
+                    .collect();
+                return Some(HighlightedLine {
+                    line_number: i + 1,
+                    tokens,
+                });
+            }
 
             let tokens = h
-                .highlight_line(line_text, &self.syntax_set)
+                .highlight_line(&line_text, self.syntax_set)
                 .unwrap_or_default()
                 .into_iter()
                 .map(|(style, text)| HighlightedToken {
@@ -105,6 +308,302 @@ impl Highlighter {
     }
 }
 
+/// Makes `text` safe to draw: control characters — anything in C0, C1, or DEL
+/// other than a tab — are shown in caret notation (`^L`, `^M`, `^[`, `^?`; C1 as
+/// `<U+0085>`) or, under [`ControlChars::Strip`], dropped along with the rest of
+/// any ANSI escape sequence they start. Text without them is returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::highlight::sanitize_controls;
+/// use gitprint::types::ControlChars;
+///
+/// let line = "\x1b[1;31merror\x1b[0m\x0c";
+/// assert_eq!(sanitize_controls(line, ControlChars::Visualize), "^[[1;31merror^[[0m^L");
+/// assert_eq!(sanitize_controls(line, ControlChars::Strip), "error");
+/// assert_eq!(sanitize_controls("\tok", ControlChars::Strip), "\tok");
+/// ```
+pub fn sanitize_controls(text: &str, policy: ControlChars) -> Cow<'_, str> {
+    let is_unsafe = |c: char| c.is_control() && c != '\t';
+    if !text.contains(is_unsafe) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, policy) {
+            (c, _) if !is_unsafe(c) => out.push(c),
+            ('\x1b', ControlChars::Strip) => skip_escape(&mut chars),
+            (_, ControlChars::Strip) => {}
+            ('\x7f', ControlChars::Visualize) => out.push_str("^?"),
+            ('\0'..='\x1f', ControlChars::Visualize) => {
+                out.push('^');
+                out.push(char::from(c as u8 + 0x40));
+            }
+            (c, ControlChars::Visualize) => {
+                let _ = write!(out, "<U+{:04X}>", c as u32);
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Consumes the rest of an escape sequence whose ESC was just read: a CSI
+/// (`ESC [` parameters, final byte), an OSC (`ESC ]` up to BEL or `ESC \`), or
+/// a short `ESC` intermediates-and-final sequence such as `ESC ( B`. Whatever
+/// doesn't fit is left in place.
+fn skip_escape(chars: &mut Peekable<Chars<'_>>) {
+    match chars.peek() {
+        Some('[') => {
+            chars.next();
+            while chars.next_if(|c| ('\x20'..='\x3f').contains(c)).is_some() {}
+            chars.next_if(|c| ('\x40'..='\x7e').contains(c));
+        }
+        Some(']') => {
+            chars.next();
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        }
+        _ => {
+            while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+            chars.next_if(|c| ('\x30'..='\x7e').contains(c));
+        }
+    }
+}
+
+/// A copy of the theme called `name` in `theme_set`.
+fn theme_named(theme_set: &ThemeSet, name: &str) -> crate::error::Result<Theme> {
+    theme_set
+        .themes
+        .get(name)
+        .cloned()
+        .ok_or_else(|| crate::error::Error::UnknownTheme(name.to_string()))
+}
+
+/// Splits `tokens` at the boundaries of every non-empty `re` match in the line they
+/// form, so matches can be styled independently of syntax coloring. Each returned
+/// token is paired with whether it lies inside a match.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::highlight::split_matches;
+/// use gitprint::types::{HighlightedToken, RgbColor};
+///
+/// let token = |text: &str| HighlightedToken {
+///     text: text.into(),
+///     color: RgbColor { r: 0, g: 0, b: 0 },
+///     bold: false,
+///     italic: false,
+/// };
+/// let re = regex::Regex::new("TODO").unwrap();
+/// let parts = split_matches(vec![token("// TO"), token("DO: x")], &re);
+/// let texts: Vec<_> = parts.iter().map(|(t, hit)| (t.text.as_str(), *hit)).collect();
+/// assert_eq!(texts, [("// ", false), ("TO", true), ("DO", true), (": x", false)]);
+/// ```
+pub fn split_matches(
+    tokens: Vec<HighlightedToken>,
+    re: &regex::Regex,
+) -> Vec<(HighlightedToken, bool)> {
+    let line: String = tokens.iter().map(|t| t.text.as_str()).collect();
+    let matches: Vec<(usize, usize)> = re
+        .find_iter(&line)
+        .filter(|m| !m.is_empty())
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    if matches.is_empty() {
+        return tokens.into_iter().map(|t| (t, false)).collect();
+    }
+    let in_match = |at: usize| matches.iter().any(|&(s, e)| s <= at && at < e);
+
+    let mut offset = 0;
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            let start = offset;
+            let end = start + token.text.len();
+            offset = end;
+            let mut cuts: Vec<usize> = matches
+                .iter()
+                .flat_map(|&(s, e)| [s, e])
+                .filter(|&c| c > start && c < end)
+                .collect();
+            cuts.sort_unstable();
+            cuts.dedup();
+            std::iter::once(start)
+                .chain(cuts.clone())
+                .zip(cuts.into_iter().chain(std::iter::once(end)))
+                .map(|(a, b)| {
+                    let piece = HighlightedToken {
+                        text: token.text[a - start..b - start].to_string(),
+                        ..token.clone()
+                    };
+                    (piece, in_match(a))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Common languages the bundled syntax set lacks (their files print as plain
+/// text), so `--lang` can still select them by name.
+const EXTRA_LANGUAGES: &[(&str, &[&str])] = &[
+    ("Dockerfile", &["Dockerfile", "dockerfile"]),
+    ("Kotlin", &["kt", "kts"]),
+    ("Nix", &["nix"]),
+    ("Protobuf", &["proto"]),
+    ("Swift", &["swift"]),
+    ("Terraform", &["tf", "tfvars"]),
+    ("TOML", &["toml"]),
+    ("TSX", &["tsx"]),
+    ("TypeScript", &["ts", "mts", "cts"]),
+    ("Vue", &["vue"]),
+    ("Zig", &["zig"]),
+];
+
+/// Expands `--lang` / `--exclude-lang` names into the globs that select those
+/// languages' files.
+///
+/// A name matches a syntax by its name (`rust`, `c++`) or, failing that, by one of
+/// its extensions (`rs`, `bash`), ignoring case; every extension of the matched
+/// syntax is then selected. As in highlighting, an "extension" may also be a
+/// whole file name such as `Makefile` or `Gemfile`.
+///
+/// # Errors
+///
+/// Returns [`Error::UnknownLanguage`](crate::error::Error::UnknownLanguage) for a
+/// name that matches no syntax.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::highlight::language_globs;
+///
+/// let globs = language_globs(&["rust".to_string(), "md".to_string()]).unwrap();
+/// assert!(globs.contains(&"*.rs".to_string()));
+/// assert!(globs.contains(&"*.markdown".to_string()));
+/// assert!(language_globs(&["klingon".to_string()]).is_err());
+/// ```
+pub fn language_globs(names: &[String]) -> crate::error::Result<Vec<String>> {
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+    let languages: Vec<(&str, Vec<&str>)> = syntax_set()
+        .syntaxes()
+        .iter()
+        .filter(|s| !s.file_extensions.is_empty())
+        .map(|s| {
+            let extensions = s.file_extensions.iter().map(String::as_str).collect();
+            (s.name.as_str(), extensions)
+        })
+        .chain(
+            EXTRA_LANGUAGES
+                .iter()
+                .map(|(name, exts)| (*name, exts.to_vec())),
+        )
+        .collect();
+    names
+        .iter()
+        .map(|name| {
+            let name = name.trim();
+            let (_, extensions) = languages
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .or_else(|| {
+                    languages
+                        .iter()
+                        .find(|(_, exts)| exts.iter().any(|e| e.eq_ignore_ascii_case(name)))
+                })
+                .ok_or_else(|| crate::error::Error::UnknownLanguage(name.to_string()))?;
+            Ok(extensions
+                .iter()
+                .flat_map(|e| [format!("*.{e}"), format!("**/{e}")])
+                .collect::<Vec<_>>())
+        })
+        .collect::<crate::error::Result<Vec<_>>>()
+        .map(|globs| globs.concat())
+}
+
+/// Darkens every foreground in `theme` that would print washed out on white
+/// paper until it reaches [`PRINT_CONTRAST`], keeping its hue. Colors that are
+/// already legible, and backgrounds, are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::highlight::{contrast_on_white, optimize_for_print, theme_set, PRINT_CONTRAST};
+///
+/// let mut theme = theme_set().themes["base16-ocean.dark"].clone();
+/// optimize_for_print(&mut theme);
+/// let fg = theme.settings.foreground.unwrap();
+/// assert!(contrast_on_white(fg) >= PRINT_CONTRAST);
+/// ```
+pub fn optimize_for_print(theme: &mut Theme) {
+    let darken = |color: &mut Option<Color>| {
+        if let Some(c) = color {
+            *c = legible_on_white(*c);
+        }
+    };
+    darken(&mut theme.settings.foreground);
+    darken(&mut theme.settings.caret);
+    theme
+        .scopes
+        .iter_mut()
+        .for_each(|item| darken(&mut item.style.foreground));
+}
+
+/// WCAG contrast ratio of `color` against white, from 1.0 (white) to 21.0 (black).
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::highlight::contrast_on_white;
+/// use syntect::highlighting::Color;
+///
+/// assert!((contrast_on_white(Color::BLACK) - 21.0).abs() < 0.01);
+/// assert!((contrast_on_white(Color::WHITE) - 1.0).abs() < 0.01);
+/// ```
+pub fn contrast_on_white(color: Color) -> f32 {
+    let linear = |c: u8| {
+        let c = f32::from(c) / 255.0;
+        if c <= 0.039_28 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let luminance = 0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b);
+    1.05 / (luminance + 0.05)
+}
+
+/// Scales `color` toward black just far enough to reach [`PRINT_CONTRAST`].
+fn legible_on_white(color: Color) -> Color {
+    if contrast_on_white(color) >= PRINT_CONTRAST {
+        return color;
+    }
+    let scaled = |k: f32| Color {
+        r: (f32::from(color.r) * k) as u8,
+        g: (f32::from(color.g) * k) as u8,
+        b: (f32::from(color.b) * k) as u8,
+        a: color.a,
+    };
+    // Contrast only grows as k shrinks; bisect for the largest k that passes.
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    (0..16).for_each(|_| {
+        let mid = (lo + hi) / 2.0;
+        if contrast_on_white(scaled(mid)) >= PRINT_CONTRAST {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    });
+    scaled(lo)
+}
+
 /// Returns all available theme names in sorted order.
 ///
 /// # Examples
@@ -117,7 +616,7 @@ impl Highlighter {
 /// assert!(themes.windows(2).all(|w| w[0] <= w[1])); // sorted
 /// ```
 pub fn list_themes() -> Vec<String> {
-    let mut themes: Vec<_> = ThemeSet::load_defaults().themes.into_keys().collect();
+    let mut themes: Vec<_> = theme_set().themes.keys().cloned().collect();
     themes.sort();
     themes
 }
@@ -126,11 +625,107 @@ pub fn list_themes() -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn language_globs_match_names_extensions_and_extras() {
+        let globs = |name: &str| language_globs(&[name.to_string()]).unwrap();
+        assert_eq!(globs("Rust"), globs("rs"));
+        assert!(globs("bash").contains(&"*.zsh".to_string()));
+        assert!(globs("makefile").contains(&"**/Makefile".to_string()));
+        assert!(globs("toml").contains(&"*.toml".to_string()));
+        assert!(language_globs(&[]).unwrap().is_empty());
+        let err = language_globs(&["rust".to_string(), "cobol".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("cobol"));
+    }
+
+    fn tok(text: &str) -> HighlightedToken {
+        HighlightedToken {
+            text: text.into(),
+            color: RgbColor { r: 1, g: 2, b: 3 },
+            bold: true,
+            italic: false,
+        }
+    }
+
+    #[test]
+    fn split_matches_no_match_keeps_tokens() {
+        let re = regex::Regex::new("zzz").unwrap();
+        let parts = split_matches(vec![tok("a"), tok("b")], &re);
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|(_, hit)| !hit));
+    }
+
+    #[test]
+    fn split_matches_keeps_style_and_handles_multibyte() {
+        let re = regex::Regex::new("é+").unwrap();
+        let parts = split_matches(vec![tok("caféé!")], &re);
+        let texts: Vec<_> = parts.iter().map(|(t, h)| (t.text.as_str(), *h)).collect();
+        assert_eq!(texts, [("caf", false), ("éé", true), ("!", false)]);
+        assert!(parts.iter().all(|(t, _)| t.bold && t.color.b == 3));
+    }
+
+    #[test]
+    fn split_matches_multiple_matches_in_one_token() {
+        let re = regex::Regex::new("x").unwrap();
+        let parts = split_matches(vec![tok("axbx")], &re);
+        let flags: Vec<_> = parts.iter().map(|(_, h)| *h).collect();
+        assert_eq!(flags, [false, true, false, true]);
+    }
+
+    #[test]
+    fn print_optimize_lifts_every_dark_theme_foreground() {
+        let mut theme = theme_set().themes["Solarized (dark)"].clone();
+        assert!(
+            theme
+                .scopes
+                .iter()
+                .filter_map(|item| item.style.foreground)
+                .any(|fg| contrast_on_white(fg) < PRINT_CONTRAST)
+        );
+        optimize_for_print(&mut theme);
+        theme
+            .scopes
+            .iter()
+            .filter_map(|item| item.style.foreground)
+            .for_each(|fg| assert!(contrast_on_white(fg) >= PRINT_CONTRAST, "{fg:?}"));
+    }
+
+    #[test]
+    fn print_optimize_keeps_legible_colors_and_hue() {
+        let navy = Color {
+            r: 0,
+            g: 0,
+            b: 128,
+            a: 255,
+        };
+        assert_eq!(legible_on_white(navy), navy);
+        let pale = legible_on_white(Color {
+            r: 250,
+            g: 200,
+            b: 100,
+            a: 255,
+        });
+        assert!(contrast_on_white(pale) >= PRINT_CONTRAST);
+        assert!(pale.r > pale.g && pale.g > pale.b);
+    }
+
     #[test]
     fn new_with_valid_theme() {
         assert!(Highlighter::new("InspiredGitHub").is_ok());
     }
 
+    #[test]
+    fn highlighters_share_one_syntax_set() {
+        let light = Highlighter::new("InspiredGitHub").unwrap();
+        let dark = light.with_theme("base16-ocean.dark").unwrap();
+        assert!(std::ptr::eq(light.syntax_set, dark.syntax_set));
+        assert!(std::ptr::eq(light.syntax_set, syntax_set()));
+
+        let own = SyntaxSet::load_defaults_nonewlines();
+        let borrowed = Highlighter::with_shared_sets(&own, theme_set(), "InspiredGitHub").unwrap();
+        assert!(std::ptr::eq(borrowed.syntax_set, &own));
+        assert!(Highlighter::with_shared_sets(&own, theme_set(), "nope").is_err());
+    }
+
     #[test]
     fn new_with_another_valid_theme() {
         assert!(Highlighter::new("base16-ocean.dark").is_ok());
@@ -160,6 +755,112 @@ mod tests {
         assert!(!lines[0].tokens.is_empty());
     }
 
+    #[test]
+    fn syntax_lookup_agrees_with_syntect() {
+        let h = Highlighter::new("InspiredGitHub").unwrap();
+        [
+            "a.rs",
+            "b.RS",
+            "Makefile",
+            "config.ru",
+            ".bashrc",
+            "x.unknown",
+            "Gemfile",
+        ]
+        .iter()
+        .for_each(|name| {
+            let path = Path::new("no/such/dir").join(name);
+            let expected = syntax_set()
+                .find_syntax_for_file(&path)
+                .ok()
+                .flatten()
+                .map_or("Plain Text", |s| s.name.as_str());
+            assert_eq!(h.language(&path), expected, "{name}");
+        });
+    }
+
+    #[test]
+    fn shebang_picks_the_syntax_of_extensionless_scripts() {
+        let h = Highlighter::new("InspiredGitHub").unwrap();
+        let script = "#!/usr/bin/env python3\nprint('hi')\n";
+        let tokens = |path: &str| {
+            let lines: Vec<_> = h.highlight_lines(script, Path::new(path)).collect();
+            format!("{:?}", lines[0].tokens)
+        };
+        assert_eq!(tokens("bin/run"), tokens("bin/run.py"));
+        assert_ne!(tokens("bin/run"), tokens("bin/run.txt"));
+    }
+
+    #[test]
+    fn files_past_the_line_limit_get_plain_tokens() {
+        let h = Highlighter::new("InspiredGitHub")
+            .unwrap()
+            .with_line_limit(Some(2));
+        let short: Vec<_> = h
+            .highlight_lines("fn a() {}\n", Path::new("a.rs"))
+            .collect();
+        assert!(short[0].tokens.len() > 1);
+        let long: Vec<_> = h
+            .highlight_lines("fn a() {}\n\nfn b() {}\n", Path::new("a.rs"))
+            .collect();
+        assert_eq!(long.len(), 3);
+        assert_eq!(long[0].tokens.len(), 1);
+        assert_eq!(long[0].tokens[0].text, "fn a() {}");
+        assert!(long[1].tokens.is_empty());
+        assert_eq!(
+            h.with_theme("base16-ocean.dark").unwrap().line_limit,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn control_characters_are_visualized() {
+        let visualize = |text| sanitize_controls(text, ControlChars::Visualize);
+        assert!(matches!(visualize("plain\ttext"), Cow::Borrowed(_)));
+        assert_eq!(visualize("a\x0cb\rc\0"), "a^Lb^Mc^@");
+        assert_eq!(visualize("del\x7f"), "del^?");
+        assert_eq!(visualize("nel\u{85}"), "nel<U+0085>");
+        assert_eq!(visualize("\x1b[32mok"), "^[[32mok");
+    }
+
+    #[test]
+    fn stripping_drops_whole_escape_sequences() {
+        let strip = |text| sanitize_controls(text, ControlChars::Strip);
+        assert_eq!(strip("\x1b[38;5;196mred\x1b[m"), "red");
+        assert_eq!(
+            strip("\x1b]8;;https://x.test\x1b\\link\x1b]8;;\x07"),
+            "link"
+        );
+        assert_eq!(strip("\x1b(Bascii\x1bc"), "ascii");
+        assert_eq!(strip("page\x0c\r\u{9b}"), "page");
+        // A bare ESC at the end, or before text, takes nothing else with it.
+        assert_eq!(strip("a\x1b"), "a");
+        assert_eq!(strip("\x1b\u{e9}t\u{e9}"), "\u{e9}t\u{e9}");
+    }
+
+    #[test]
+    fn highlighted_lines_carry_no_control_characters() {
+        let h = Highlighter::new("InspiredGitHub").unwrap();
+        let source = "// \x1b[1mbold\x1b[0m\n\x0c\nfn a() {}\n";
+        let lines: Vec<_> = h.highlight_lines(source, Path::new("a.rs")).collect();
+        let text = |line: &HighlightedLine| {
+            line.tokens
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect::<String>()
+        };
+        assert_eq!(text(&lines[0]), "// ^[[1mbold^[[0m");
+        assert_eq!(text(&lines[1]), "^L");
+        let stripped = Highlighter::new("InspiredGitHub")
+            .unwrap()
+            .with_control_chars(ControlChars::Strip);
+        let lines: Vec<_> = stripped
+            .highlight_lines(source, Path::new("a.rs"))
+            .collect();
+        assert_eq!(text(&lines[0]), "// bold");
+        assert_eq!(text(&lines[1]), "");
+    }
+
     #[test]
     fn highlight_lines_multiline() {
         let h = Highlighter::new("InspiredGitHub").unwrap();
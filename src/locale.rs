@@ -0,0 +1,337 @@
+use std::str::FromStr;
+
+use crate::types::CoverRow;
+
+/// Conventions for dates, thousands separators, and cover labels (`--locale`).
+///
+/// The default, [`Locale::Neutral`], prints ISO 8601 dates, numbers without
+/// separators, and English labels. The others follow a language, and for English
+/// a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// ISO dates (`2024-01-15`), plain numbers (`12345`), English labels.
+    #[default]
+    Neutral,
+    /// English outside the United States: `15/01/2024`, `12,345`.
+    En,
+    /// English (United States): `01/15/2024`, `12,345`.
+    EnUs,
+    /// German: `15.01.2024`, `12.345`.
+    De,
+    /// French: `15/01/2024`, `12 345` (a no-break space).
+    Fr,
+    /// Spanish: `15/01/2024`, `12.345`.
+    Es,
+    /// Italian: `15/01/2024`, `12.345`.
+    It,
+    /// Dutch: `15-01-2024`, `12.345`.
+    Nl,
+    /// Portuguese: `15/01/2024`, `12.345`.
+    Pt,
+}
+
+impl Locale {
+    /// The locale named by `LC_ALL` or `LC_TIME`, in that order. Unset, `C`,
+    /// `POSIX`, and unsupported locales give [`Locale::Neutral`]; `LANG` alone
+    /// does not change the output.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_TIME"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Rewrites a leading `YYYY-MM-DD` in `text` in the locale's date order,
+    /// keeping whatever follows (a time, a zone). Other text is returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::locale::Locale;
+    ///
+    /// assert_eq!(Locale::De.date("2024-01-15 10:00:00 UTC"), "15.01.2024 10:00:00 UTC");
+    /// assert_eq!(Locale::EnUs.date("2024-01-15"), "01/15/2024");
+    /// assert_eq!(Locale::Neutral.date("2024-01-15"), "2024-01-15");
+    /// assert_eq!(Locale::De.date("unknown"), "unknown");
+    /// ```
+    pub fn date(self, text: &str) -> String {
+        let bytes = text.as_bytes();
+        let is_iso = bytes.len() >= 10
+            && bytes[..10].iter().enumerate().all(|(i, b)| match i {
+                4 | 7 => *b == b'-',
+                _ => b.is_ascii_digit(),
+            });
+        if !is_iso {
+            return text.to_string();
+        }
+        let (y, m, d, rest) = (&text[..4], &text[5..7], &text[8..10], &text[10..]);
+        match self {
+            Self::Neutral => text.to_string(),
+            Self::EnUs => format!("{m}/{d}/{y}{rest}"),
+            Self::De => format!("{d}.{m}.{y}{rest}"),
+            Self::Nl => format!("{d}-{m}-{y}{rest}"),
+            Self::En | Self::Fr | Self::Es | Self::It | Self::Pt => format!("{d}/{m}/{y}{rest}"),
+        }
+    }
+
+    /// Formats `n` with the locale's thousands separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::locale::Locale;
+    ///
+    /// assert_eq!(Locale::En.number(1234567), "1,234,567");
+    /// assert_eq!(Locale::De.number(12345), "12.345");
+    /// assert_eq!(Locale::Neutral.number(12345), "12345");
+    /// assert_eq!(Locale::De.number(999), "999");
+    /// ```
+    pub fn number(self, n: u64) -> String {
+        let separator = match self {
+            Self::Neutral => return n.to_string(),
+            Self::En | Self::EnUs => ',',
+            Self::Fr => '\u{00A0}',
+            Self::De | Self::Es | Self::It | Self::Nl | Self::Pt => '.',
+        };
+        let digits = n.to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        digits.chars().enumerate().for_each(|(i, c)| {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(separator);
+            }
+            out.push(c);
+        });
+        out
+    }
+
+    /// The cover table's label for `row` in the locale's language.
+    pub fn label(self, row: CoverRow) -> &'static str {
+        let [de, fr, es, it, nl, pt] = translations(row);
+        match self {
+            Self::Neutral | Self::En | Self::EnUs => row.label(),
+            Self::De => de,
+            Self::Fr => fr,
+            Self::Es => es,
+            Self::It => it,
+            Self::Nl => nl,
+            Self::Pt => pt,
+        }
+    }
+}
+
+/// Cover labels in German, French, Spanish, Italian, Dutch, and Portuguese. Each
+/// fits the cover's 16-character label column with a space to spare.
+fn translations(row: CoverRow) -> [&'static str; 6] {
+    match row {
+        CoverRow::Branch => ["Branch", "Branche", "Rama", "Ramo", "Branch", "Ramo"],
+        CoverRow::Commit => ["Commit", "Commit", "Commit", "Commit", "Commit", "Commit"],
+        CoverRow::Author => ["Autor", "Auteur", "Autor", "Autore", "Auteur", "Autor"],
+        CoverRow::Date => ["Datum", "Date", "Fecha", "Data", "Datum", "Data"],
+        CoverRow::Signature => [
+            "Signatur",
+            "Signature",
+            "Firma",
+            "Firma",
+            "Handtekening",
+            "Assinatura",
+        ],
+        CoverRow::Message => [
+            "Nachricht",
+            "Message",
+            "Mensaje",
+            "Messaggio",
+            "Bericht",
+            "Mensagem",
+        ],
+        CoverRow::Description => [
+            "Beschreibung",
+            "Description",
+            "Descripción",
+            "Descrizione",
+            "Beschrijving",
+            "Descrição",
+        ],
+        CoverRow::Topics => [
+            "Themen",
+            "Sujets",
+            "Temas",
+            "Argomenti",
+            "Onderwerpen",
+            "Tópicos",
+        ],
+        CoverRow::Stars => [
+            "Sterne",
+            "Étoiles",
+            "Estrellas",
+            "Stelle",
+            "Sterren",
+            "Estrelas",
+        ],
+        CoverRow::Forks => ["Forks", "Forks", "Forks", "Fork", "Forks", "Forks"],
+        CoverRow::OpenIssues => [
+            "Offene Issues",
+            "Issues ouvertes",
+            "Issues abiertas",
+            "Issue aperte",
+            "Open issues",
+            "Issues abertas",
+        ],
+        CoverRow::DefaultBranch => [
+            "Standard-Branch",
+            "Branche défaut",
+            "Rama principal",
+            "Ramo principale",
+            "Standaardbranch",
+            "Ramo padrão",
+        ],
+        CoverRow::License => [
+            "Lizenz", "Licence", "Licencia", "Licenza", "Licentie", "Licença",
+        ],
+        CoverRow::Files => [
+            "Dateien",
+            "Fichiers",
+            "Archivos",
+            "File",
+            "Bestanden",
+            "Arquivos",
+        ],
+        CoverRow::Lines => ["Zeilen", "Lignes", "Líneas", "Righe", "Regels", "Linhas"],
+        CoverRow::RepoSize => [
+            "Repo-Größe",
+            "Taille du dépôt",
+            "Tamaño repo",
+            "Dim. repo",
+            "Repo-grootte",
+            "Tamanho repo",
+        ],
+        CoverRow::FsSize => [
+            "FS-Größe",
+            "Taille FS",
+            "Tamaño FS",
+            "Dim. FS",
+            "FS-grootte",
+            "Tamanho FS",
+        ],
+        CoverRow::FsOwner => [
+            "FS-Besitzer",
+            "Propriétaire FS",
+            "Propietario FS",
+            "Proprietario FS",
+            "FS-eigenaar",
+            "Dono FS",
+        ],
+        CoverRow::FsGroup => [
+            "FS-Gruppe",
+            "Groupe FS",
+            "Grupo FS",
+            "Gruppo FS",
+            "FS-groep",
+            "Grupo FS",
+        ],
+        CoverRow::Generated => [
+            "Erstellt",
+            "Généré",
+            "Generado",
+            "Generato",
+            "Gegenereerd",
+            "Gerado",
+        ],
+    }
+}
+
+/// Parses a BCP 47 tag (`de-DE`) or a POSIX locale name (`de_DE.UTF-8`). Only the
+/// language matters, except that `en-US` gets US dates.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::locale::Locale;
+///
+/// assert_eq!("de-DE".parse::<Locale>(), Ok(Locale::De));
+/// assert_eq!("en_US.UTF-8".parse::<Locale>(), Ok(Locale::EnUs));
+/// assert_eq!("C".parse::<Locale>(), Ok(Locale::Neutral));
+/// assert!("tlh".parse::<Locale>().is_err());
+/// ```
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = name.split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+        Ok(match (language.as_str(), region.as_str()) {
+            ("c" | "posix", _) => Self::Neutral,
+            ("en", "US") => Self::EnUs,
+            ("en", _) => Self::En,
+            ("de", _) => Self::De,
+            ("fr", _) => Self::Fr,
+            ("es", _) => Self::Es,
+            ("it", _) => Self::It,
+            ("nl", _) => Self::Nl,
+            ("pt", _) => Self::Pt,
+            _ => {
+                return Err(format!(
+                    "unsupported locale: {s} (expected en, en-US, de, fr, es, it, nl, or pt)"
+                ));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tags_and_posix_names() {
+        assert_eq!("fr".parse(), Ok(Locale::Fr));
+        assert_eq!("pt-BR".parse(), Ok(Locale::Pt));
+        assert_eq!("en-GB".parse(), Ok(Locale::En));
+        assert_eq!("en-us".parse(), Ok(Locale::EnUs));
+        assert_eq!("nl_NL.UTF-8@euro".parse(), Ok(Locale::Nl));
+        assert_eq!("POSIX".parse(), Ok(Locale::Neutral));
+        assert!("".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn dates_follow_the_locale_order() {
+        let commit = "2024-01-15 12:00:00 +0100";
+        assert_eq!(Locale::Neutral.date(commit), commit);
+        assert_eq!(Locale::En.date(commit), "15/01/2024 12:00:00 +0100");
+        assert_eq!(Locale::Nl.date("2024-01-15"), "15-01-2024");
+        assert_eq!(Locale::De.date("2024-1-15"), "2024-1-15");
+        assert_eq!(Locale::De.date(""), "");
+    }
+
+    #[test]
+    fn numbers_group_thousands() {
+        assert_eq!(Locale::EnUs.number(0), "0");
+        assert_eq!(Locale::EnUs.number(100_000), "100,000");
+        assert_eq!(Locale::Fr.number(1_234), "1\u{00A0}234");
+        assert_eq!(Locale::It.number(12_345_678), "12.345.678");
+    }
+
+    #[test]
+    fn labels_fit_the_cover_column() {
+        let locales = [
+            Locale::Neutral,
+            Locale::De,
+            Locale::Fr,
+            Locale::Es,
+            Locale::It,
+            Locale::Nl,
+            Locale::Pt,
+        ];
+        CoverRow::ALL.iter().for_each(|&row| {
+            locales.iter().for_each(|locale| {
+                let label = locale.label(row);
+                assert!(label.chars().count() < 16, "{locale:?}: {label}");
+            });
+        });
+        assert_eq!(Locale::De.label(CoverRow::Generated), "Erstellt");
+        assert_eq!(Locale::EnUs.label(CoverRow::Generated), "Generated");
+    }
+}
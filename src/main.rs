@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 
@@ -109,9 +109,232 @@ fn is_leap_year(y: u32) -> bool {
     (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
 }
 
+/// Parses an optional `--since`/`--until` value, exiting with an error naming `flag`
+/// when it is malformed.
+fn parse_date_arg(flag: &str, value: Option<&str>) -> Option<String> {
+    value.map(|v| {
+        parse_date_filter(v).unwrap_or_else(|e| {
+            eprintln!("error: {flag}: {e}");
+            std::process::exit(1);
+        })
+    })
+}
+
+/// Reads the `--files-from` list from `source` (`-` for stdin), exiting on I/O errors.
+fn read_file_list(source: &Path, nul: bool) -> Vec<PathBuf> {
+    let input = if source == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(source)
+    };
+    match input {
+        Ok(text) => gitprint::filter::parse_file_list(&text, nul),
+        Err(e) => {
+            eprintln!("error: --files-from {}: {e}", source.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Downloads (when `url` is set) and extracts an archive into a temp dir, returning
+/// the dir guard and the extracted root to print. `token` is only passed for GitHub
+/// API downloads.
+async fn extract_archive(
+    source: &str,
+    url: Option<&str>,
+    token: Option<&str>,
+    max_bytes: u64,
+) -> anyhow::Result<(gitprint::git::TempCloneDir, PathBuf)> {
+    use gitprint::archive::{self, ArchiveKind};
+
+    let temp =
+        gitprint::git::TempCloneDir::for_url(url.unwrap_or(source), None, None, None).await?;
+    let (file, kind) = match url {
+        Some(url) => {
+            eprintln!("Downloading {url}...");
+            let file = temp.path().join("archive");
+            archive::download(url, &file, token, max_bytes).await?;
+            (
+                file,
+                ArchiveKind::from_url(url).unwrap_or(ArchiveKind::TarGz),
+            )
+        }
+        None => {
+            let kind = ArchiveKind::from_name(source)
+                .ok_or_else(|| anyhow::anyhow!("{source}: not a .zip or .tar.gz archive"))?;
+            (PathBuf::from(source), kind)
+        }
+    };
+    let dest = temp.path().join("contents");
+    let root = tokio::task::spawn_blocking(move || archive::extract(&file, kind, &dest))
+        .await
+        .map_err(|e| anyhow::anyhow!("extraction panicked: {e}"))??;
+    Ok((temp, root))
+}
+
+/// Exits with an error when `path` already exists and `--force` was not given.
+fn refuse_overwrite(path: &Path, force: bool) {
+    if !force && path.exists() {
+        eprintln!(
+            "error: {} already exists (use --force to overwrite)",
+            path.display()
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Prints the terminal preview for each user in turn.
+/// Groups `warnings` into the block printed after a run: a heading per kind with
+/// its count, then the first few warnings of that kind.
+fn warning_summary(warnings: &[gitprint::types::Warning]) -> String {
+    const SHOWN: usize = 5;
+    let mut kinds: Vec<_> = warnings.iter().map(|w| w.kind).collect();
+    kinds.sort();
+    kinds.dedup();
+    kinds
+        .into_iter()
+        .map(|kind| {
+            let group: Vec<_> = warnings.iter().filter(|w| w.kind == kind).collect();
+            let mut block = format!("warning: {} ({})\n", kind.label(), group.len());
+            group
+                .iter()
+                .take(SHOWN)
+                .for_each(|w| block.push_str(&format!("  {w}\n")));
+            if group.len() > SHOWN {
+                block.push_str(&format!("  \u{2026} and {} more\n", group.len() - SHOWN));
+            }
+            block
+        })
+        .collect()
+}
+
+/// Groups the files a run left out into the block printed after it: a heading per
+/// reason with its count, then the first few files — every file when `all`.
+fn skip_summary(skipped: &[gitprint::types::SkippedFile], all: bool) -> String {
+    const SHOWN: usize = 5;
+    let mut reasons: Vec<_> = skipped.iter().map(|s| s.reason).collect();
+    reasons.sort();
+    reasons.dedup();
+    reasons
+        .into_iter()
+        .map(|reason| {
+            let group: Vec<_> = skipped.iter().filter(|s| s.reason == reason).collect();
+            let mut block = format!("skipped: {} ({})\n", reason.label(), group.len());
+            let shown = if all { group.len() } else { SHOWN };
+            group
+                .iter()
+                .take(shown)
+                .for_each(|s| block.push_str(&format!("  {s}\n")));
+            if group.len() > shown {
+                block.push_str(&format!(
+                    "  \u{2026} and {} more (-v lists them all)\n",
+                    group.len() - shown
+                ));
+            }
+            block
+        })
+        .collect()
+}
+
+/// With `--interactive`, `config` narrowed to the files picked in the terminal, or
+/// `None` when the picker was cancelled or nothing was picked; otherwise a copy of
+/// `config`.
+async fn pick_files(
+    config: &gitprint::types::Config,
+    interactive: bool,
+) -> anyhow::Result<Option<gitprint::types::Config>> {
+    if !interactive {
+        return Ok(Some(config.clone()));
+    }
+    match gitprint::picker::pick_files(config).await? {
+        Some(paths) if !paths.is_empty() => Ok(Some(gitprint::types::Config {
+            files_from: Some(paths),
+            ..config.clone()
+        })),
+        Some(_) => {
+            eprintln!("No files picked; nothing printed");
+            Ok(None)
+        }
+        None => {
+            eprintln!("Cancelled; nothing printed");
+            Ok(None)
+        }
+    }
+}
+
+/// The `-v` account of file selection: how many listed files the filters kept,
+/// then with `-vv` each dropped file and the rule that dropped it.
+fn filter_report(report: &gitprint::types::RunReport, verbose: u8) -> String {
+    use gitprint::types::SkipReason;
+
+    if verbose == 0 || report.files_listed == 0 {
+        return String::new();
+    }
+    let excluded: Vec<_> = report
+        .skipped
+        .iter()
+        .filter(|s| matches!(s.reason, SkipReason::DefaultExclude | SkipReason::Filter))
+        .collect();
+    let mut out = String::new();
+    if verbose >= 2 {
+        excluded
+            .iter()
+            .for_each(|s| out.push_str(&format!("excluded {s}\n")));
+    }
+    out.push_str(&format!(
+        "Filters kept {} of {} listed files\n",
+        report.files_listed.saturating_sub(excluded.len()),
+        report.files_listed
+    ));
+    out
+}
+
+/// Writes the PDF for `config`, then prints the `-v` filter report, what was
+/// skipped (every file with `-v`), the warnings — `earlier` ones from before the
+/// run, such as clone retries, first — and the summary line; fails on any warning
+/// when `strict`. Returns how many files `--max-pages` or `--max-output-size` cut.
+async fn print_repo(
+    config: &gitprint::types::Config,
+    earlier: Vec<gitprint::types::Warning>,
+    verbose: u8,
+    strict: bool,
+) -> anyhow::Result<usize> {
+    let mut report = gitprint::run(config).await?;
+    report.warnings.splice(0..0, earlier);
+    // A `--files-from` list bypasses the filters, so there is nothing to explain.
+    if config.files_from.is_none() {
+        eprint!("{}", filter_report(&report, verbose));
+    }
+    eprint!("{}", skip_summary(&report.skipped, verbose > 0));
+    eprint!("{}", warning_summary(&report.warnings));
+    eprintln!("{} — {report}", config.output_path.display());
+    let count = report.warnings.len();
+    anyhow::ensure!(
+        !strict || count == 0,
+        "{count} warning{} with --strict",
+        if count == 1 { "" } else { "s" }
+    );
+    Ok(report.files_truncated)
+}
+
+async fn preview_users(
+    config: &gitprint::types::UserReportConfig,
+    usernames: &[String],
+) -> anyhow::Result<()> {
+    for username in usernames {
+        let config = gitprint::types::UserReportConfig {
+            username: username.clone(),
+            ..config.clone()
+        };
+        gitprint::preview::user(&config).await?;
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() {
-    let args = gitprint::cli::Args::parse();
+    let mut args = gitprint::cli::Args::parse();
+    args.apply_pattern_env();
 
     if args.list_themes {
         gitprint::highlight::list_themes()
@@ -121,25 +344,51 @@ async fn main() {
     }
 
     // ── User report mode ───────────────────────────────────────────────────────
-    if let Some(username) = args.user {
-        let output_path = args
-            .output
-            .unwrap_or_else(|| PathBuf::from(format!("{username}.pdf")));
-
-        let since = match args.since.as_deref().map(parse_date_filter) {
-            Some(Err(e)) => {
-                eprintln!("error: --since: {e}");
-                std::process::exit(1);
-            }
-            other => other.and_then(Result::ok),
+    let mut usernames = std::mem::take(&mut args.user);
+    if let Some(team) = args.team.as_deref() {
+        let text = std::fs::read_to_string(team).unwrap_or_else(|e| {
+            eprintln!("error: --team {}: {e}", team.display());
+            std::process::exit(1);
+        });
+        usernames.extend(gitprint::user_report::parse_team(&text));
+        if usernames.is_empty() {
+            eprintln!("error: --team {}: no usernames found", team.display());
+            std::process::exit(1);
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    usernames.retain(|u| seen.insert(u.to_ascii_lowercase()));
+
+    if let Some(username) = usernames.first().cloned() {
+        let team = usernames.len() > 1;
+        let ext = match args.format {
+            gitprint::types::ReportFormat::Json => "json",
+            _ => "pdf",
         };
-        let until = match args.until.as_deref().map(parse_date_filter) {
-            Some(Err(e)) => {
-                eprintln!("error: --until: {e}");
-                std::process::exit(1);
+        let output_path = args.output.unwrap_or_else(|| {
+            PathBuf::from(if team {
+                format!("team.{ext}")
+            } else {
+                format!("{username}.{ext}")
+            })
+        });
+        if !args.preview {
+            refuse_overwrite(&output_path, args.force);
+            if args.format == gitprint::types::ReportFormat::Both {
+                refuse_overwrite(&output_path.with_extension("json"), args.force);
             }
-            other => other.and_then(Result::ok),
-        };
+        }
+
+        let cache = (!args.no_cache)
+            .then(gitprint::cache::default_dir)
+            .flatten()
+            .map(|dir| gitprint::cache::ApiCache {
+                dir: dir.join("github"),
+                ttl: std::time::Duration::from_secs(args.cache_ttl),
+                refresh: args.refresh,
+            });
+        let since = parse_date_arg("--since", args.since.as_deref());
+        let until = parse_date_arg("--until", args.until.as_deref());
 
         let config = gitprint::types::UserReportConfig {
             output_path,
@@ -148,17 +397,24 @@ async fn main() {
             last_repos: args.last_repos,
             last_commits: args.last_commits,
             no_diffs: args.no_diffs,
+            diff_context: args.diff_context,
+            full_diffs: args.full_diffs,
+            full_diffs_limit: args.full_diffs_limit,
             font_size: args.font_size,
             github_token: std::env::var("GITHUB_TOKEN").ok(),
+            cache,
             since,
             until,
             activity: args.activity,
             events: args.events,
+            format: args.format,
             username,
         };
 
         let result = if args.preview {
-            gitprint::preview::user(&config).await
+            preview_users(&config, &usernames).await
+        } else if team {
+            gitprint::user_report::run_team(&config, &usernames).await
         } else {
             gitprint::user_report::run(&config).await
         };
@@ -170,22 +426,155 @@ async fn main() {
     }
 
     // ── Repository mode ────────────────────────────────────────────────────────
-    let path = match args.path {
+    // --theme-gallery reads no repository; the current directory only supplies the
+    // config file, and the gallery takes the place of the output PDF.
+    if let Some(gallery) = args.theme_gallery.clone() {
+        args.path.get_or_insert_with(|| ".".to_string());
+        args.output = Some(gallery);
+    }
+    let mut path = match args.path.take() {
         Some(p) => p,
         None => {
-            eprintln!("error: a path or -u/--user is required");
+            eprintln!("error: a path, -u/--user, or --team is required");
             std::process::exit(1);
         }
     };
 
-    let is_remote = gitprint::git::is_remote_url(&path);
+    // A GitHub tree/blob URL pasted from the browser: clone the repository itself and
+    // take the ref (unless --branch/--commit override it) and the path from the URL.
+    let git_timeout =
+        (args.git_timeout > 0).then(|| std::time::Duration::from_secs(args.git_timeout));
+    let clone_token = args.clone_token.clone();
+    let github_url = (!args.local)
+        .then(|| gitprint::git::GitHubPathUrl::parse(&path))
+        .flatten();
+    let (url_subpath, is_blob_url) = match github_url {
+        Some(url) => {
+            let refs = if url.needs_ref_lookup() {
+                let auth = clone_token
+                    .as_deref()
+                    .and_then(|t| gitprint::git::GitAuth::for_url(&url.repo_url, t));
+                gitprint::git::remote_refs(&url.repo_url, auth.as_ref(), git_timeout).await
+            } else {
+                vec![]
+            };
+            let (rev, subpath) = url.split(&refs);
+            if args.branch.is_none() && args.commit.is_none() {
+                if gitprint::git::is_commit_hash(&rev) {
+                    args.commit = Some(rev);
+                } else {
+                    args.branch = Some(rev);
+                }
+            }
+            let is_blob = url.is_blob;
+            path = url.repo_url;
+            (subpath, is_blob)
+        }
+        None => (None, false),
+    };
+
+    let is_remote = !args.local && (args.remote || gitprint::git::is_remote_url(&path));
+    let git_auth = clone_token
+        .as_deref()
+        .and_then(|t| gitprint::git::GitAuth::for_url(&path, t));
+    // Archive input: a local .zip/.tar.gz, an archive URL, or — with --no-git — the
+    // GitHub tarball of a repository URL, printed through the plain-directory pipeline.
+    let is_archive_url = !args.local && gitprint::archive::is_archive_url(&path);
+    let github_tarball = (is_remote && !is_archive_url && args.no_git).then(|| {
+        let rev = args.commit.as_deref().or(args.branch.as_deref());
+        gitprint::github::tarball_url(&path, rev).unwrap_or_else(|| {
+            eprintln!("error: --no-git only supports GitHub repository URLs");
+            std::process::exit(1);
+        })
+    });
+    let archive_url = github_tarball
+        .clone()
+        .or_else(|| is_archive_url.then(|| path.clone()));
+    let is_archive = archive_url.is_some() || gitprint::archive::is_local_archive(&path);
+    let since = parse_date_arg("--since", args.since.as_deref());
+    let until = parse_date_arg("--until", args.until.as_deref());
+    let files_from = args
+        .files_from
+        .as_deref()
+        .map(|source| read_file_list(source, args.null));
 
-    // Clone remote URL to a temp dir; hold it alive until after run().
-    let temp_dir = if is_remote {
+    let output_path = args.output.unwrap_or_else(|| {
+        let blob_name = url_subpath
+            .as_ref()
+            .filter(|_| is_blob_url)
+            .and_then(|p| p.file_name());
+        let name = if let Some(file_name) = blob_name {
+            file_name.to_string_lossy().to_string()
+        } else if is_archive && github_tarball.is_none() {
+            gitprint::archive::display_name(&path)
+        } else if is_remote {
+            gitprint::git::repo_name_from_url(&path)
+        } else {
+            PathBuf::from(&path)
+                .canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "output".to_string())
+        };
+        PathBuf::from(format!("{name}.pdf"))
+    });
+
+    // Checked before cloning so a refused overwrite fails fast.
+    if !args.preview && !args.list_tags && !args.nvim {
+        refuse_overwrite(&output_path, args.force);
+    }
+
+    // Extract archives to a temp dir; hold it alive until after run().
+    let archive_dir = if is_archive {
+        let token = std::env::var("GITHUB_TOKEN").ok();
+        let token = github_tarball.as_ref().and(token.as_deref());
+        match extract_archive(&path, archive_url.as_deref(), token, args.max_download_size).await {
+            Ok(extracted) => Some(extracted),
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // A clone kept by an earlier run stands in for a fresh one once it checks out.
+    let reused_clone = match args.reuse_clone.take() {
+        Some(dir) if is_remote && !is_archive => {
+            if let Err(e) = gitprint::git::verify_clone(
+                &dir,
+                &path,
+                args.branch.as_deref(),
+                args.commit.as_deref(),
+                git_auth.as_ref(),
+                git_timeout,
+            )
+            .await
+            {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+            eprintln!("Reusing clone at {}", dir.display());
+            Some(dir)
+        }
+        Some(_) => {
+            eprintln!("error: --reuse-clone needs a remote repository URL");
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
+    // Clone remote URL to a temp dir; hold it alive until after run(). Retries are
+    // reported with the run's own warnings.
+    let mut clone_warnings = Vec::new();
+    let clone_depth = (!args.full_clone).then(|| args.clone_depth.unwrap_or(1));
+    let temp_dir = if is_remote && !is_archive && reused_clone.is_none() {
         match gitprint::git::TempCloneDir::for_url(
             &path,
             args.branch.as_deref(),
             args.commit.as_deref(),
+            clone_depth,
         )
         .await
         {
@@ -199,6 +588,10 @@ async fn main() {
                         t.path(),
                         args.branch.as_deref(),
                         args.commit.as_deref(),
+                        clone_depth,
+                        git_auth.as_ref(),
+                        git_timeout,
+                        &mut clone_warnings,
                     )
                     .await
                     {
@@ -217,19 +610,34 @@ async fn main() {
         None
     };
 
-    let repo_path = temp_dir
-        .as_ref()
-        .map(|t| t.path().to_path_buf())
-        .unwrap_or_else(|| PathBuf::from(&path));
+    let repo_path = match (&archive_dir, &temp_dir, &reused_clone) {
+        (Some((_, root)), _, _) => root.clone(),
+        (None, Some(t), _) => t.path().to_path_buf(),
+        (None, None, Some(dir)) => dir.clone(),
+        (None, None, None) => PathBuf::from(&path),
+    };
+    // Narrow to the URL's directory or file. If it isn't in the checked-out tree
+    // (e.g. a --commit clone), fall back to include patterns over the listing.
+    let repo_path = match url_subpath {
+        Some(sub) if repo_path.join(&sub).exists() => repo_path.join(sub),
+        Some(sub) => {
+            let sub = sub.to_string_lossy().to_string();
+            args.include.push(format!("{sub}/**"));
+            args.include.push(sub);
+            repo_path
+        }
+        None => repo_path,
+    };
 
     if is_remote && args.list_tags {
-        if let Err(e) = gitprint::git::fetch_tags(&repo_path).await {
+        if let Err(e) = gitprint::git::fetch_tags(&repo_path, git_auth.as_ref(), git_timeout).await
+        {
             eprintln!("warning: could not fetch tags: {e}");
         }
     }
 
     if args.list_tags {
-        let tags = gitprint::git::list_repo_tags(&repo_path).await;
+        let tags = gitprint::git::list_repo_tags(&repo_path, git_timeout).await;
         if tags.is_empty() {
             eprintln!("No tags found.");
         } else {
@@ -249,44 +657,175 @@ async fn main() {
         }
     }
 
-    let output_path = args.output.unwrap_or_else(|| {
-        let name = if is_remote {
-            gitprint::git::repo_name_from_url(&path)
-        } else {
-            PathBuf::from(&path)
-                .canonicalize()
-                .ok()
-                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
-                .unwrap_or_else(|| "output".to_string())
+    let config_file =
+        match gitprint::config_file::ConfigFile::discover(args.config.as_deref(), &repo_path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                std::process::exit(1);
+            }
         };
-        PathBuf::from(format!("{name}.pdf"))
+    let redact = match args
+        .redact_secrets
+        .then(|| gitprint::redact::Redactor::new(&config_file.redact_patterns))
+        .transpose()
+    {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            std::process::exit(1);
+        }
+    };
+
+    // A config file that arrived with a cloned repository or archive could run
+    // anything; only honour its hook when the user chose the file or the input is local.
+    let trusted_config = args.config.is_some() || !(is_remote || is_archive);
+    let preprocess = config_file.preprocessor().filter(|_| {
+        if !trusted_config {
+            eprintln!("warning: ignoring \"preprocess\" from the downloaded config file");
+        }
+        trusted_config
     });
 
+    if args.prose_font.len() > 3 {
+        eprintln!("error: --prose-font takes at most three fonts: regular, bold, italic");
+        std::process::exit(1);
+    }
+
+    let color_mode = if args.grayscale {
+        gitprint::types::ColorMode::Grayscale
+    } else if args.ink_saver {
+        gitprint::types::ColorMode::InkSaver
+    } else {
+        gitprint::types::ColorMode::Color
+    };
+
     let config = gitprint::types::Config {
         repo_path,
         output_path,
         include_patterns: args.include,
-        exclude_patterns: args.exclude,
+        // The project's excludes come first so a later `--exclude '!…'` can undo them.
+        exclude_patterns: config_file
+            .exclude
+            .into_iter()
+            .chain(args.exclude)
+            .collect(),
+        include_regexes: args.include_regex,
+        exclude_regexes: args.exclude_regex,
+        languages: args.lang,
+        exclude_languages: args.exclude_lang,
+        presets: args.preset,
+        no_default_excludes: args.no_default_excludes,
+        extra_default_excludes: config_file.extra_default_excludes,
         theme: args.theme,
         font_size: args.font_size,
         no_line_numbers: args.no_line_numbers,
+        global_line_numbers: args.global_line_numbers,
+        age_shading: args.age_shading,
+        layers: args.layers,
+        print_optimize: args.print_optimize,
+        highlight_max_lines: args.highlight_max_lines,
+        control_chars: args.control_chars,
+        prose_fonts: args.prose_font,
+        emoji_font: args.emoji_font,
         toc: !args.no_toc,
         file_tree: !args.no_file_tree,
         branch: args.branch,
         commit: args.commit,
+        git_timeout,
         paper_size: args.paper_size,
         landscape: args.landscape,
-        remote_url: is_remote.then(|| path.clone()),
+        remote_url: (is_remote && !is_archive_url).then(|| path.clone()),
+        pretty_data: args.pretty_data,
+        page_total: args.page_total,
+        booklet: args.booklet,
+        nup: args.nup,
+        review_layout: args.review_layout,
+        color_mode,
+        tagged: args.tagged,
+        compact: args.compact,
+        marks: args.mark,
+        grep: args.grep,
+        only_matching: args.only_matching,
+        redact,
+        untracked: args.untracked,
+        changed_only: args.changed_only,
+        since,
+        until,
+        no_dates: args.no_dates,
+        files_from,
+        max_depth: args.max_depth,
+        hidden: !args.no_hidden,
+        preprocess,
+        lang_styles: config_file.lang,
+        line_height: args.line_height,
+        density: args.density,
+        letter_spacing: args.letter_spacing,
+        symbols: args.symbols,
+        checksums: args.checksums,
+        margin_notes: args.margin_notes,
+        qr: args.qr,
+        cover_rows: args
+            .cover_rows
+            .unwrap_or_else(|| gitprint::types::CoverRow::ALL.to_vec()),
+        meta: args.meta,
+        locale: args
+            .locale
+            .unwrap_or_else(gitprint::locale::Locale::from_env),
+        commit_footer: !args.no_commit_footer,
+        running_header: !args.no_running_header,
+        chapters: args.chapters,
+        summary: !args.no_summary,
+        hotspots: args.hotspots.then(|| args.hotspots_since.clone()),
+        preface: args.preface,
+        offline: args.offline,
+        github_token: std::env::var("GITHUB_TOKEN").ok(),
+        refs: args.refs,
+        show: args.show.clone(),
+        diff_context: args.diff_context,
+        max_pages: args.max_pages,
+        max_output_size: args.max_output_size,
+        write_progress: Some(gitprint::pdf::report_progress),
     };
 
     let result = if args.preview {
-        gitprint::preview::repo(&config).await
+        eprint!("{}", warning_summary(&clone_warnings));
+        gitprint::preview::repo(&config).await.map(|()| 0)
+    } else if args.theme_gallery.is_some() {
+        gitprint::theme_gallery(&config)
+            .await
+            .map(|themes| {
+                eprintln!("{} — {themes} themes", config.output_path.display());
+                0
+            })
+            .map_err(anyhow::Error::from)
     } else {
-        gitprint::run(&config).await
+        match pick_files(&config, args.interactive).await {
+            Ok(Some(config)) => {
+                print_repo(&config, clone_warnings, args.verbose, args.strict).await
+            }
+            other => other.map(|_| 0),
+        }
     };
-    if let Err(e) = result {
-        eprintln!("error: {e}");
-        std::process::exit(1);
+    // Keep the clone when asked, or when the run failed so a retry can skip the
+    // clone; otherwise it is deleted here, before exiting.
+    if let Some(clone) = temp_dir
+        && (args.keep_clone || result.is_err())
+    {
+        let kept = clone.keep();
+        eprintln!(
+            "Clone kept at {0} (print from it again with --reuse-clone {0})",
+            kept.display()
+        );
+    }
+    match result {
+        // A distinct status lets scripts tell a cut-short PDF from a complete one.
+        Ok(truncated) if truncated > 0 => std::process::exit(3),
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
     }
 }
 
@@ -380,4 +919,83 @@ mod tests {
         // 2000-03-01 (leap year 2000, day after Feb 29)
         assert_eq!(unix_secs_to_date(951_868_800), "2000-03-01");
     }
+
+    #[test]
+    fn warnings_are_grouped_by_kind() {
+        use gitprint::types::{Warning, WarningKind};
+
+        assert_eq!(warning_summary(&[]), "");
+        let mut warnings: Vec<Warning> = (0..7)
+            .map(|i| Warning::file(WarningKind::Skipped, format!("{i}.bin"), "binary file"))
+            .collect();
+        warnings.insert(0, Warning::new(WarningKind::Remote, "GitHub: timed out"));
+        let summary = warning_summary(&warnings);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "warning: skipped files (7)");
+        assert_eq!(lines[1], "  0.bin: binary file");
+        assert_eq!(lines[6], "  \u{2026} and 2 more");
+        assert_eq!(lines[7..], ["warning: remote (1)", "  GitHub: timed out"]);
+    }
+
+    #[test]
+    fn skipped_files_are_grouped_by_reason() {
+        use gitprint::types::{SkipReason, SkippedFile};
+
+        assert_eq!(skip_summary(&[], false), "");
+        let mut skipped: Vec<SkippedFile> = (0..7)
+            .map(|i| SkippedFile::new(format!("{i}.png"), SkipReason::Binary))
+            .collect();
+        skipped.push(
+            SkippedFile::new("Cargo.lock", SkipReason::DefaultExclude)
+                .with_detail("default exclude Cargo.lock"),
+        );
+        let summary = skip_summary(&skipped, false);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(
+            lines[..2],
+            [
+                "skipped: default excludes (1)",
+                "  Cargo.lock (default exclude Cargo.lock)"
+            ]
+        );
+        assert_eq!(lines[2], "skipped: binary (7)");
+        assert_eq!(lines[8], "  \u{2026} and 2 more (-v lists them all)");
+        assert_eq!(skip_summary(&skipped, true).lines().count(), 10);
+    }
+
+    #[test]
+    fn filter_report_counts_then_names_exclusions() {
+        use gitprint::types::{RunReport, SkipReason, SkippedFile};
+
+        let report = RunReport {
+            files_listed: 10,
+            files_included: 7,
+            files_skipped: 1,
+            files_truncated: 0,
+            files_unmatched: 0,
+            pages: 3,
+            pdf_bytes: 0,
+            elapsed: std::time::Duration::ZERO,
+            warnings: vec![],
+            skipped: vec![
+                SkippedFile::new("Cargo.lock", SkipReason::DefaultExclude)
+                    .with_detail("default exclude Cargo.lock"),
+                SkippedFile::new("notes.md", SkipReason::Filter).with_detail("exclude *.md"),
+                SkippedFile::new("logo.png", SkipReason::Binary),
+            ],
+        };
+        assert_eq!(filter_report(&report, 0), "");
+        assert_eq!(
+            filter_report(&report, 1),
+            "Filters kept 8 of 10 listed files\n"
+        );
+        assert_eq!(
+            filter_report(&report, 2).lines().collect::<Vec<_>>(),
+            [
+                "excluded Cargo.lock (default exclude Cargo.lock)",
+                "excluded notes.md (exclude *.md)",
+                "Filters kept 8 of 10 listed files",
+            ]
+        );
+    }
 }
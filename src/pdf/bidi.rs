@@ -0,0 +1,216 @@
+use std::borrow::Cow;
+
+use unicode_bidi::{BidiInfo, Level};
+
+/// Joining behaviour of an Arabic letter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Joining {
+    /// Joins on neither side (hamza): isolated form only.
+    None,
+    /// Joins to the letter before it only: isolated and final forms.
+    Right,
+    /// Joins on both sides: isolated, final, initial, and medial forms.
+    Dual,
+}
+
+/// The Arabic letters U+0621–U+064A with presentation forms, in code point order.
+/// Their forms sit in the same order from U+FE80, one slot per form — so each
+/// letter's forms are found by counting the slots of the letters before it.
+const LETTERS: [(char, Joining); 36] = [
+    ('\u{0621}', Joining::None),
+    ('\u{0622}', Joining::Right),
+    ('\u{0623}', Joining::Right),
+    ('\u{0624}', Joining::Right),
+    ('\u{0625}', Joining::Right),
+    ('\u{0626}', Joining::Dual),
+    ('\u{0627}', Joining::Right),
+    ('\u{0628}', Joining::Dual),
+    ('\u{0629}', Joining::Right),
+    ('\u{062A}', Joining::Dual),
+    ('\u{062B}', Joining::Dual),
+    ('\u{062C}', Joining::Dual),
+    ('\u{062D}', Joining::Dual),
+    ('\u{062E}', Joining::Dual),
+    ('\u{062F}', Joining::Right),
+    ('\u{0630}', Joining::Right),
+    ('\u{0631}', Joining::Right),
+    ('\u{0632}', Joining::Right),
+    ('\u{0633}', Joining::Dual),
+    ('\u{0634}', Joining::Dual),
+    ('\u{0635}', Joining::Dual),
+    ('\u{0636}', Joining::Dual),
+    ('\u{0637}', Joining::Dual),
+    ('\u{0638}', Joining::Dual),
+    ('\u{0639}', Joining::Dual),
+    ('\u{063A}', Joining::Dual),
+    ('\u{0641}', Joining::Dual),
+    ('\u{0642}', Joining::Dual),
+    ('\u{0643}', Joining::Dual),
+    ('\u{0644}', Joining::Dual),
+    ('\u{0645}', Joining::Dual),
+    ('\u{0646}', Joining::Dual),
+    ('\u{0647}', Joining::Dual),
+    ('\u{0648}', Joining::Right),
+    ('\u{0649}', Joining::Right),
+    ('\u{064A}', Joining::Dual),
+];
+
+/// Tatweel, the joining stretch: joins on both sides and has no forms of its own.
+const TATWEEL: char = '\u{0640}';
+
+/// Whether `c` belongs to a right-to-left script (Hebrew, Arabic, Syriac, Thaana,
+/// N'Ko, …) or their presentation forms.
+fn is_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}')
+}
+
+/// Harakat and other marks, which sit on a letter without breaking its joins.
+fn is_transparent(c: char) -> bool {
+    matches!(c, '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}')
+}
+
+/// Joining behaviour and the code point of the isolated form, for a letter with
+/// presentation forms.
+fn letter(c: char) -> Option<(Joining, u32)> {
+    let mut form = 0xFE80;
+    LETTERS.iter().find_map(|&(base, joining)| {
+        if base == c {
+            return Some((joining, form));
+        }
+        form += match joining {
+            Joining::None => 1,
+            Joining::Right => 2,
+            Joining::Dual => 4,
+        };
+        None
+    })
+}
+
+/// Replaces Arabic letters with the presentation form their neighbours call for —
+/// isolated, final, initial, or medial — so a font without a shaping engine draws
+/// them joined. One character in, one character out.
+fn shape_arabic(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    // Whether a letter reaches over to the letter after it, or back to the one before.
+    let joins_next = |c: char| c == TATWEEL || matches!(letter(c), Some((Joining::Dual, _)));
+    let joins_prev =
+        |c: char| c == TATWEEL || matches!(letter(c), Some((Joining::Dual | Joining::Right, _)));
+    let neighbour = |range: &mut dyn Iterator<Item = usize>| {
+        range.map(|j| chars[j]).find(|&c| !is_transparent(c))
+    };
+    (0..chars.len())
+        .map(|i| {
+            let c = chars[i];
+            let Some((joining, isolated)) = letter(c) else {
+                return c;
+            };
+            let after_joiner = neighbour(&mut (0..i).rev()).is_some_and(joins_next);
+            let before_joiner = joining == Joining::Dual
+                && neighbour(&mut (i + 1..chars.len())).is_some_and(joins_prev);
+            let offset = match (joining, after_joiner, before_joiner) {
+                (Joining::None, ..) => 0,
+                (_, true, true) => 3,
+                (_, false, true) => 2,
+                (_, true, false) => 1,
+                (_, false, false) => 0,
+            };
+            char::from_u32(isolated + offset).unwrap_or(c)
+        })
+        .collect()
+}
+
+/// Returns `text` in visual order, ready to be drawn left to right: runs of
+/// Hebrew, Arabic, and other right-to-left scripts are reversed in place by the
+/// Unicode bidirectional algorithm, with Arabic letters shaped first. The base
+/// direction stays left to right, so code around an RTL comment or string keeps
+/// its layout. Text without RTL characters is returned unchanged.
+///
+/// Shaping and reordering never change the number of characters, so widths
+/// measured on the logical text still hold.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::pdf::bidi::visual;
+///
+/// assert_eq!(visual("let x = 1;"), "let x = 1;");
+/// // "// שלום" — the Hebrew word is reversed, the comment marker stays on the left.
+/// assert_eq!(visual("// \u{05E9}\u{05DC}\u{05D5}\u{05DD}"), "// \u{05DD}\u{05D5}\u{05DC}\u{05E9}");
+/// ```
+pub fn visual(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_rtl) {
+        return Cow::Borrowed(text);
+    }
+    let shaped = shape_arabic(text);
+    let info = BidiInfo::new(&shaped, Some(Level::ltr()));
+    let reordered: String = info
+        .paragraphs
+        .iter()
+        .map(|para| info.reorder_line(para, para.range.clone()))
+        .collect();
+    Cow::Owned(reordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_take_their_contextual_forms() {
+        // بيت (house): initial beh, medial yeh, final teh.
+        assert_eq!(
+            shape_arabic("\u{0628}\u{064A}\u{062A}"),
+            "\u{FE91}\u{FEF4}\u{FE96}"
+        );
+        // دار (house): dal and alef never join forward, so each starts afresh.
+        assert_eq!(
+            shape_arabic("\u{062F}\u{0627}\u{0631}"),
+            "\u{FEA9}\u{FE8D}\u{FEAD}"
+        );
+        assert_eq!(shape_arabic("\u{0628} x"), "\u{FE8F} x");
+    }
+
+    #[test]
+    fn marks_do_not_break_joins() {
+        // بَت: the fatha between beh and teh leaves them joined.
+        assert_eq!(
+            shape_arabic("\u{0628}\u{064E}\u{062A}"),
+            "\u{FE91}\u{064E}\u{FE96}"
+        );
+    }
+
+    #[test]
+    fn form_table_lines_up_with_the_presentation_block() {
+        assert_eq!(letter('\u{0621}'), Some((Joining::None, 0xFE80)));
+        assert_eq!(letter('\u{0627}'), Some((Joining::Right, 0xFE8D)));
+        assert_eq!(letter('\u{0644}'), Some((Joining::Dual, 0xFEDD)));
+        assert_eq!(letter('\u{064A}'), Some((Joining::Dual, 0xFEF1)));
+        assert_eq!(letter('a'), None);
+    }
+
+    #[test]
+    fn rtl_runs_reverse_inside_ltr_code() {
+        let hebrew = "\u{05D0}\u{05D1}";
+        assert_eq!(
+            visual(&format!("x = \"{hebrew}\"; // ok")),
+            "x = \"\u{05D1}\u{05D0}\"; // ok"
+        );
+        // Digits inside an RTL run keep their own order.
+        assert_eq!(
+            visual(&format!("{hebrew} 12")),
+            format!("12 \u{05D1}\u{05D0}")
+        );
+        assert!(matches!(visual("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn character_count_is_preserved() {
+        let line = "// \u{0645}\u{0631}\u{062D}\u{0628}\u{0627} world";
+        assert_eq!(visual(line).chars().count(), line.chars().count());
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use printpdf::{Actions, Color, Destination, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// One file listed on a chapter divider.
+#[derive(Debug, Clone)]
+pub struct ChapterEntry {
+    /// Path to the file relative to the repository root.
+    pub path: PathBuf,
+    /// Number of lines in the file.
+    pub line_count: usize,
+    /// Physical PDF page where the file begins (link target).
+    pub start_page: usize,
+    /// Page number as printed in that page's header.
+    pub page_label: String,
+    /// Pages the file spans.
+    pub pages: usize,
+}
+
+/// The chapter `path` belongs to: its first `depth` directories, or all of them
+/// for a shallower file. Files in the repository root belong to no chapter.
+pub fn chapter_of(path: &Path, depth: usize) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let chapter: PathBuf = dir.components().take(depth.max(1)).collect();
+    (!chapter.as_os_str().is_empty()).then_some(chapter)
+}
+
+/// Renders the divider page that opens chapter `number` (1-based), directory `dir`:
+/// its name, totals, and one linked row per file with lines, pages, and start page.
+pub fn render(builder: &mut PageBuilder, number: usize, dir: &Path, entries: &[ChapterEntry]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    const SIZE: f32 = 8.0;
+
+    builder.gap(80.0);
+    builder.write_centered(
+        &format!("Chapter {number}"),
+        &regular,
+        Pt(10.0),
+        gray.clone(),
+    );
+    builder.gap(6.0);
+    builder.set_role("H1");
+    builder.write_centered(
+        &format!("{}/", crate::display_path(dir)),
+        &bold,
+        Pt(22.0),
+        black.clone(),
+    );
+    builder.set_role("P");
+    builder.gap(8.0);
+
+    let lines: usize = entries.iter().map(|e| e.line_count).sum();
+    let pages: usize = entries.iter().map(|e| e.pages).sum();
+    let plural = |n: usize, word: &str| format!("{n} {word}{}", if n == 1 { "" } else { "s" });
+    builder.write_line_centered(&[Span {
+        text: format!(
+            "{} \u{00B7} {} \u{00B7} {}",
+            plural(entries.len(), "file"),
+            plural(lines, "line"),
+            plural(pages, "page"),
+        ),
+        font_id: regular.clone(),
+        size: Pt(SIZE),
+        color: gray.clone(),
+    }]);
+    builder.gap(16.0);
+    builder.draw_horizontal_rule(Color::Rgb(Rgb::new(0.72, 0.72, 0.72, None)), 0.5);
+    builder.gap(8.0);
+
+    entries.iter().for_each(|entry| {
+        let name = entry.path.strip_prefix(dir).unwrap_or(&entry.path);
+        builder.write_line_justified(
+            &[Span {
+                text: crate::display_path(name),
+                font_id: regular.clone(),
+                size: Pt(SIZE),
+                color: black.clone(),
+            }],
+            &[Span {
+                text: format!(
+                    "{} \u{00B7} {} \u{00B7} p.{}",
+                    plural(entry.line_count, "line"),
+                    plural(entry.pages, "page"),
+                    entry.page_label
+                ),
+                font_id: regular.clone(),
+                size: Pt(SIZE),
+                color: gray.clone(),
+            }],
+        );
+        builder.add_link(
+            builder.line_height(),
+            Actions::Goto(Destination::Xyz {
+                page: entry.start_page,
+                left: None,
+                top: None,
+                zoom: None,
+            }),
+        );
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn chapter_is_the_leading_directories() {
+        let chapter = |path: &str, depth| chapter_of(Path::new(path), depth);
+        assert_eq!(chapter("src/pdf/code.rs", 1), Some(PathBuf::from("src")));
+        assert_eq!(
+            chapter("src/pdf/code.rs", 2),
+            Some(PathBuf::from("src/pdf"))
+        );
+        assert_eq!(chapter("src/lib.rs", 2), Some(PathBuf::from("src")));
+        assert_eq!(chapter("Cargo.toml", 1), None);
+    }
+
+    #[test]
+    fn render_links_every_file() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let entry = |path: &str, start_page| ChapterEntry {
+            path: PathBuf::from(path),
+            line_count: 10,
+            start_page,
+            page_label: start_page.to_string(),
+            pages: 1,
+        };
+        render(
+            &mut builder,
+            1,
+            Path::new("src"),
+            &[entry("src/a.rs", 2), entry("src/b.rs", 3)],
+        );
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 1);
+        let links = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 2);
+    }
+}
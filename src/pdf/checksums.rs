@@ -0,0 +1,170 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use printpdf::{Color, FontId, Pt, Rgb};
+use sha2::{Digest, Sha256};
+
+use super::layout::{PageBuilder, Span};
+use crate::types::RepoMetadata;
+
+/// One printed file on the `--checksums` page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checksum {
+    /// Repository-relative path.
+    pub path: PathBuf,
+    /// SHA-256 of the file as stored in the repository, in lowercase hex.
+    pub sha256: String,
+}
+
+/// SHA-256 of `data` in lowercase hex, as `sha256sum` prints it.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::pdf::checksums::sha256_hex;
+///
+/// assert_eq!(
+///     sha256_hex(b"abc"),
+///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+/// );
+/// ```
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// SHA-256 over `checksums` listed in `sha256sum` format — one `<hash>  <path>` line
+/// per file, in order — so `sha256sum <files> | sha256sum` reproduces it.
+pub fn manifest_digest(checksums: &[Checksum]) -> String {
+    let listing: String = checksums
+        .iter()
+        .map(|c| format!("{}  {}\n", c.sha256, crate::display_path(&c.path)))
+        .collect();
+    sha256_hex(listing.as_bytes())
+}
+
+/// Renders the `--checksums` appendix: the commit and tree hashes when printing from
+/// git, a digest over the whole listing, then each file's SHA-256 in path order.
+pub fn render(builder: &mut PageBuilder, checksums: &[Checksum], metadata: &RepoMetadata) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Checksums", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+
+    let mut sorted = checksums.to_vec();
+    sorted.sort_by(|a, b| a.path.cmp(&b.path));
+    builder.write_line_centered(&[Span {
+        text: format!(
+            "SHA-256 of {} file{} as stored in the repository",
+            sorted.len(),
+            if sorted.len() == 1 { "" } else { "s" }
+        ),
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    const SIZE: f32 = 7.0;
+    let row = |builder: &mut PageBuilder, label: String, hash: &str, label_font: FontId| {
+        builder.write_line_justified(
+            &[Span {
+                text: label,
+                font_id: label_font,
+                size: Pt(SIZE),
+                color: black.clone(),
+            }],
+            &[Span {
+                text: hash.to_string(),
+                font_id: regular.clone(),
+                size: Pt(SIZE),
+                color: gray.clone(),
+            }],
+        );
+    };
+
+    [
+        ("Commit", metadata.commit_hash.clone()),
+        ("Tree", metadata.tree_hash.clone()),
+        ("All files", manifest_digest(&sorted)),
+    ]
+    .into_iter()
+    .filter(|(_, hash)| !hash.is_empty())
+    .for_each(|(label, hash)| row(builder, label.to_string(), &hash, bold.clone()));
+    builder.gap(6.0);
+
+    sorted.iter().for_each(|c| {
+        row(
+            builder,
+            crate::display_path(&c.path),
+            &c.sha256,
+            regular.clone(),
+        );
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn checksum(path: &str, data: &[u8]) -> Checksum {
+        Checksum {
+            path: PathBuf::from(path),
+            sha256: sha256_hex(data),
+        }
+    }
+
+    #[test]
+    fn manifest_digest_hashes_the_sha256sum_listing() {
+        let files = [checksum("a.rs", b"a"), checksum("b.rs", b"b")];
+        let listing = format!("{}  a.rs\n{}  b.rs\n", files[0].sha256, files[1].sha256);
+        assert_eq!(manifest_digest(&files), sha256_hex(listing.as_bytes()));
+        assert_eq!(manifest_digest(&[]), sha256_hex(b""));
+    }
+
+    #[test]
+    fn render_lists_hashes_in_path_order() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let metadata = RepoMetadata {
+            commit_hash: "c0ffee".into(),
+            ..RepoMetadata::default()
+        };
+        let files = [checksum("z.rs", b"z"), checksum("a.rs", b"a")];
+        render(&mut builder, &files, &metadata);
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 1);
+        let texts: Vec<&str> = pages[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::ShowText { items } => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                printpdf::TextItem::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"c0ffee"), "{texts:?}");
+        assert!(!texts.contains(&"Tree"), "{texts:?}");
+        let position = |text: &str| texts.iter().position(|t| *t == text).unwrap();
+        assert!(position("a.rs") < position("z.rs"));
+        assert!(texts.contains(&files[0].sha256.as_str()));
+    }
+}
@@ -1,9 +1,84 @@
-use printpdf::{Actions, Color, Pt, Rgb};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use printpdf::{Actions, Color, Destination, FontId, Pt, Rgb};
 
 use super::layout::{PageBuilder, Span};
-use crate::types::HighlightedLine;
+use crate::highlight::split_matches;
+use crate::symbols::Symbol;
+use crate::types::{GrepHit, HighlightedLine, HighlightedToken};
+
+/// A file header is only started if at least this many of the file's lines (or all
+/// of them, if fewer) fit below it on the same page.
+pub const KEEP_WITH_HEADER: usize = 4;
+
+/// Background tint behind lines selected with `--mark`.
+const MARK_BACKGROUND: (f32, f32, f32) = (1.0, 0.95, 0.6);
+
+/// Background box behind `--grep` matches.
+const GREP_BACKGROUND: (f32, f32, f32) = (1.0, 0.75, 0.4);
+
+/// Link after each file's path back to the table of contents.
+const TOC_LINK: &str = "\u{2191} TOC";
+
+/// Space between a file's path and its [`TOC_LINK`].
+const TOC_LINK_GAP: &str = "  ";
+
+/// Gutter gray levels for `--age-shading`: the newest lines sit on the dark end,
+/// the oldest on the light end.
+const AGE_SHADE_NEWEST: f32 = 0.3;
+const AGE_SHADE_OLDEST: f32 = 0.92;
+
+/// Per-file line decorations.
+#[derive(Default)]
+pub struct Highlights<'a> {
+    /// Line ranges drawn on a highlighter-yellow band (`--mark`).
+    pub marked: Vec<RangeInclusive<usize>>,
+    /// Pattern whose matches are boxed in orange (`--grep`).
+    pub grep: Option<&'a regex::Regex>,
+    /// Ascending line numbers whose page is reported in [`Placement::located`]
+    /// (`--symbols`).
+    pub locate: Vec<usize>,
+    /// Definitions whose name is printed in the outer margin beside their body
+    /// (`--margin-notes`), ascending by line.
+    pub outline: &'a [Symbol],
+    /// Age of each line's last change, from line 1 on: `0.0` is the newest in the
+    /// document, `1.0` the oldest (`--age-shading`). Lines past the end keep a
+    /// plain gutter.
+    pub ages: Vec<f32>,
+}
+
+/// Where lines of a file landed, as reported by [`render_file`].
+#[derive(Debug, Default)]
+pub struct Placement {
+    /// Every line containing a `--grep` match.
+    pub hits: Vec<GrepHit>,
+    /// `(page, page_label)` for each entry of [`Highlights::locate`], in order.
+    pub located: Vec<(usize, String)>,
+}
 
 /// Renders a syntax-highlighted source file into the PDF, with a file header and optional link.
+///
+/// Lines are decorated per `highlights`; every line containing a `--grep` match is
+/// returned as a [`GrepHit`] recording the page it landed on, and the pages of the
+/// lines in [`Highlights::locate`] are returned alongside.
+///
+/// Pages begun inside the file carry its path in the running header.
+///
+/// With [`Highlights::ages`], each line number sits on a gray box whose shade
+/// tracks the line's age, so recently changed code stands out as a dark streak.
+/// Builders made [`PageBuilder::with_layers`] put the gutter on layers viewers can hide.
+///
+/// The innermost definition in [`Highlights::outline`] enclosing a line is noted in
+/// the margin when it changes and again at the top of each page.
+///
+/// With `wrap`, lines wider than the page continue on extra rows under a blank
+/// line-number gutter; otherwise they run off the right edge.
+///
+/// Each file normally ends with a page break. With `compact`, files run on
+/// continuously, separated by a horizontal rule. Either way the header moves to the
+/// next page rather than being stranded without [`KEEP_WITH_HEADER`] lines of code
+/// beneath it.
 #[allow(clippy::too_many_arguments)]
 pub fn render_file(
     builder: &mut PageBuilder,
@@ -11,66 +86,275 @@ pub fn render_file(
     lines: impl Iterator<Item = HighlightedLine>,
     total_lines: usize,
     show_line_numbers: bool,
-    font_size: u8,
+    // Added to every printed line number (`--global-line-numbers`); marks, grep hits,
+    // and located lines keep the file's own numbering.
+    line_offset: usize,
+    font_size: f32,
+    wrap: bool,
     file_info: &str,
     // If `Some`, the file header becomes a clickable link to this URL (e.g. GitHub blob view).
     header_url: Option<&str>,
-) {
-    let bold = builder.font(true, false).clone();
-    let regular = builder.font(false, false).clone();
+    // Draw a QR code of `header_url` beside the header (`--qr`).
+    header_qr: bool,
+    highlights: &Highlights,
+    compact: bool,
+) -> Placement {
+    let bold = builder.prose_font(true, false).clone();
+    let regular = builder.prose_font(false, false).clone();
     let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
-    let size = Pt(font_size as f32);
+    let white = Color::Rgb(Rgb::new(1.0, 1.0, 1.0, None));
+    let size = Pt(font_size);
     let gray = Color::Rgb(Rgb::new(0.59, 0.59, 0.59, None));
-    let line_number_width = total_lines.max(1).ilog10() as usize + 1;
-
-    // File header: path left-aligned, metadata right-aligned
-    builder.write_line_justified(
-        &[Span {
-            text: file_path.to_string(),
-            font_id: bold,
-            size: Pt(font_size as f32 + 2.0),
-            color: black,
-        }],
-        &[Span {
-            text: file_info.to_string(),
-            font_id: regular,
+    let line_number_width = (total_lines + line_offset).max(1).ilog10() as usize + 1;
+    let rgb = |(r, g, b): (f32, f32, f32)| Color::Rgb(Rgb::new(r, g, b, None));
+    let (mark, found) = (rgb(MARK_BACKGROUND), rgb(GREP_BACKGROUND));
+    let mut placement = Placement::default();
+    let mut locate = highlights.locate.iter().peekable();
+    let note_font = builder.font(false, true).clone();
+    let mut definitions = highlights.outline.iter().peekable();
+    let mut scopes: Vec<&Symbol> = Vec::new();
+    let mut noted: Option<(usize, &str)> = None;
+    let gutter = if show_line_numbers {
+        line_number_width + 2
+    } else {
+        0
+    };
+    let char_width = font_size * 0.6 + builder.letter_spacing();
+    let columns = ((builder.usable_width_pt() / char_width.max(0.1)) as usize)
+        .saturating_sub(gutter)
+        .max(1);
+
+    let qr_url = header_url.filter(|_| header_qr);
+    let header_gap = 4.0 * builder.gap_scale();
+    let header = match qr_url {
+        Some(_) => super::qr::HEADER_SIZE + header_gap,
+        None => builder.line_height() + header_gap,
+    };
+    let body = total_lines.clamp(1, KEEP_WITH_HEADER) as f32 * builder.line_height();
+    if compact && !builder.at_page_top() {
+        let space = builder.line_height() * builder.gap_scale();
+        if builder.remaining_pt() < 2.0 * space + header + body {
+            builder.page_break();
+        } else {
+            builder.vertical_space(space);
+            builder.draw_horizontal_rule(gray.clone(), 0.5);
+            builder.vertical_space(space - 0.5);
+        }
+    }
+
+    builder.ensure_space(header + body);
+    builder.set_section(Some(file_path.to_string()));
+    let mut title = vec![Span {
+        text: file_path.to_string(),
+        font_id: bold.clone(),
+        size: Pt(font_size + 2.0),
+        color: black,
+    }];
+    // With a TOC, the path is followed by a link back to it: its page, x, and width.
+    let toc_link = builder.toc_page().map(|page| {
+        // Left-aligned lines space their letters; justified ones don't.
+        let spacing = if qr_url.is_some() {
+            builder.letter_spacing()
+        } else {
+            0.0
+        };
+        let spaced = |text: &str, font: &FontId, size: f32| {
+            builder.text_width(text, font, Pt(size)) + text.chars().count() as f32 * spacing
+        };
+        let x = spaced(file_path, &bold, font_size + 2.0) + spaced(TOC_LINK_GAP, &regular, 7.0);
+        title.push(Span {
+            text: format!("{TOC_LINK_GAP}{TOC_LINK}"),
+            font_id: regular.clone(),
             size: Pt(7.0),
             color: gray.clone(),
-        }],
-    );
-    if let Some(url) = header_url {
-        builder.add_link(builder.line_height(), Actions::Uri(url.to_string()));
+        });
+        (page, x, spaced(TOC_LINK, &regular, 7.0))
+    });
+    let link_back = |builder: &mut PageBuilder| {
+        if let Some((page, x, width)) = toc_link {
+            let goto = Actions::Goto(Destination::Xyz {
+                page,
+                left: None,
+                top: None,
+                zoom: None,
+            });
+            builder.add_link_at(x, width, builder.line_height(), goto);
+        }
+    };
+    let info_span = Span {
+        text: file_info.to_string(),
+        font_id: regular,
+        size: Pt(7.0),
+        color: gray.clone(),
+    };
+    match qr_url {
+        // Path over metadata on the left, the QR code at the right edge from the
+        // top of the path line down; the code starts below it.
+        Some(url) => {
+            let lh = builder.line_height();
+            let qr = super::qr::HEADER_SIZE;
+            let x = builder.usable_width_pt() - qr;
+            super::qr::draw(builder, url, x, qr - 0.8 * lh, qr);
+            builder.set_role("H1");
+            builder.write_line(&title);
+            builder.set_role("P");
+            builder.add_link(lh, Actions::Uri(url.to_string()));
+            link_back(builder);
+            builder.write_line(&[info_span]);
+            builder.vertical_space((qr - 2.0 * lh).max(0.0) + header_gap);
+        }
+        // File header: path left-aligned, metadata right-aligned
+        None => {
+            builder.set_role("H1");
+            builder.write_line_justified(&title, &[info_span]);
+            builder.set_role("P");
+            if let Some(url) = header_url {
+                builder.add_link(builder.line_height(), Actions::Uri(url.to_string()));
+            }
+            link_back(builder);
+            builder.vertical_space(header_gap);
+        }
     }
-    builder.vertical_space(4.0);
 
     lines.for_each(|line| {
-        let mut spans: Vec<Span> = Vec::with_capacity(line.tokens.len() + 1);
-
-        if show_line_numbers {
-            spans.push(Span {
-                text: format!("{:>width$}  ", line.line_number, width = line_number_width),
-                font_id: builder.font(false, false).clone(),
-                size,
-                color: gray.clone(),
-            });
+        let tokens = match highlights.grep {
+            Some(re) => split_matches(line.tokens, re),
+            None => line.tokens.into_iter().map(|t| (t, false)).collect(),
+        };
+        let is_hit = tokens.iter().any(|(_, hit)| *hit);
+        let text: String = tokens.iter().map(|(t, _)| t.text.as_str()).collect();
+        let background = highlights
+            .marked
+            .iter()
+            .any(|r| r.contains(&line.line_number))
+            .then(|| mark.clone());
+        let rows = if wrap {
+            wrap_tokens(tokens, columns)
+        } else {
+            vec![tokens]
+        };
+        let shade = line
+            .line_number
+            .checked_sub(1)
+            .and_then(|i| highlights.ages.get(i))
+            .map(|age| AGE_SHADE_NEWEST + (AGE_SHADE_OLDEST - AGE_SHADE_NEWEST) * age);
+        scopes.retain(|s| s.end_line >= line.line_number);
+        while let Some(symbol) = definitions.next_if(|s| s.line_number <= line.line_number) {
+            scopes.push(symbol);
         }
 
-        spans.extend(line.tokens.into_iter().map(|token| Span {
-            text: token.text,
-            font_id: builder.font(token.bold, token.italic).clone(),
-            size,
-            color: Color::Rgb(Rgb::new(
-                token.color.r as f32 / 255.0,
-                token.color.g as f32 / 255.0,
-                token.color.b as f32 / 255.0,
-                None,
-            )),
-        }));
+        rows.into_iter().enumerate().for_each(|(row, tokens)| {
+            let mut spans: Vec<Span> = Vec::with_capacity(tokens.len() + 1);
+            let mut span_backgrounds: Vec<Option<Color>> = Vec::new();
 
-        builder.write_line(&spans);
+            let gutter = show_line_numbers.then(|| {
+                let number = if row == 0 {
+                    (line.line_number + line_offset).to_string()
+                } else {
+                    String::new()
+                };
+                Span {
+                    text: format!("{number:>line_number_width$}  "),
+                    font_id: builder.font(false, false).clone(),
+                    size,
+                    color: match shade {
+                        Some(level) if level < 0.6 => white.clone(),
+                        _ => gray.clone(),
+                    },
+                }
+            });
+            if let Some(gutter) = &gutter {
+                spans.push(Span {
+                    text: " ".repeat(gutter.text.chars().count()),
+                    font_id: gutter.font_id.clone(),
+                    size,
+                    color: gray.clone(),
+                });
+                span_backgrounds.push(None);
+            }
+
+            tokens.into_iter().for_each(|(token, hit)| {
+                spans.push(Span {
+                    text: token.text,
+                    font_id: builder.font(token.bold, token.italic).clone(),
+                    size,
+                    color: Color::Rgb(Rgb::new(
+                        token.color.r as f32 / 255.0,
+                        token.color.g as f32 / 255.0,
+                        token.color.b as f32 / 255.0,
+                        None,
+                    )),
+                });
+                span_backgrounds.push(hit.then(|| found.clone()));
+            });
+
+            if let Some(scope) = scopes.last() {
+                builder.ensure_space(builder.line_height());
+                let here = (builder.current_page(), scope.name.as_str());
+                if noted != Some(here) {
+                    builder.write_margin_note(&scope.name, &note_font, Pt(5.0), gray.clone());
+                    noted = Some(here);
+                }
+            }
+            builder.write_line_with_span_backgrounds(&spans, background.clone(), &span_backgrounds);
+            if let Some(gutter) = &gutter {
+                builder.write_gutter(gutter, shade.map(|level| rgb((level, level, level))));
+            }
+
+            if row == 0 {
+                while locate.next_if(|&&n| n <= line.line_number).is_some() {
+                    placement
+                        .located
+                        .push((builder.current_page(), builder.current_page_label()));
+                }
+            }
+            if row == 0 && is_hit {
+                placement.hits.push(GrepHit {
+                    path: PathBuf::from(file_path),
+                    line_number: line.line_number,
+                    page: builder.current_page(),
+                    page_label: builder.current_page_label(),
+                    text: text.trim().to_string(),
+                });
+            }
+        });
     });
 
-    builder.page_break();
+    if !compact {
+        builder.page_break();
+    }
+    placement
+}
+
+/// Breaks one line's tokens into rows of at most `columns` characters, splitting
+/// tokens that straddle a row boundary. An empty line stays one empty row.
+fn wrap_tokens(
+    tokens: Vec<(HighlightedToken, bool)>,
+    columns: usize,
+) -> Vec<Vec<(HighlightedToken, bool)>> {
+    let mut rows = vec![Vec::new()];
+    let mut used = 0;
+    tokens.into_iter().for_each(|(token, hit)| {
+        let chars: Vec<char> = token.text.chars().collect();
+        let mut start = 0;
+        while start < chars.len() {
+            if used == columns {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            let take = (columns - used).min(chars.len() - start);
+            let piece = HighlightedToken {
+                text: chars[start..start + take].iter().collect(),
+                ..token.clone()
+            };
+            rows.last_mut()
+                .expect("rows is never empty")
+                .push((piece, hit));
+            used += take;
+            start += take;
+        }
+    });
+    rows
 }
 
 #[cfg(test)]
@@ -117,9 +401,14 @@ mod tests {
             sample_lines().into_iter(),
             2,
             true,
-            8,
+            0,
+            8.0,
+            false,
             "2 lines \u{00B7} 24 B \u{00B7} 2025-01-15",
             None,
+            false,
+            &Default::default(),
+            false,
         );
     }
 
@@ -135,9 +424,14 @@ mod tests {
             std::iter::empty(),
             0,
             true,
-            8,
+            0,
+            8.0,
+            false,
             "0 lines \u{00B7} 0 B",
             None,
+            false,
+            &Default::default(),
+            false,
         );
     }
 
@@ -153,9 +447,14 @@ mod tests {
             sample_lines().into_iter(),
             2,
             false,
-            8,
+            0,
+            8.0,
+            false,
             "2 lines \u{00B7} 24 B \u{00B7} 2025-01-15",
             None,
+            false,
+            &Default::default(),
+            false,
         );
     }
 
@@ -171,10 +470,49 @@ mod tests {
             sample_lines().into_iter(),
             2,
             true,
-            8,
+            0,
+            8.0,
+            false,
             "2 lines \u{00B7} 24 B \u{00B7} 2025-01-15",
             Some("https://github.com/user/repo/blob/abc123/src/main.rs"),
+            false,
+            &Default::default(),
+            false,
+        );
+    }
+
+    #[test]
+    fn header_qr_is_drawn_only_with_a_url() {
+        let polygons = |url: Option<&str>| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            super::render_file(
+                &mut builder,
+                "src/main.rs",
+                sample_lines().into_iter(),
+                2,
+                true,
+                0,
+                8.0,
+                false,
+                "2 lines",
+                url,
+                true,
+                &Default::default(),
+                false,
+            );
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+                .count()
+        };
+        assert_eq!(
+            polygons(Some("https://github.com/user/repo/blob/abc/a.rs")),
+            1
         );
+        assert_eq!(polygons(None), 0);
     }
 
     #[test]
@@ -200,9 +538,439 @@ mod tests {
             lines.into_iter(),
             100,
             true,
-            8,
+            0,
+            8.0,
+            false,
             "100 lines \u{00B7} 1.2 KB \u{00B7} 2025-01-15",
             None,
+            false,
+            &Default::default(),
+            false,
+        );
+    }
+
+    fn render_small_files(compact: bool, count: usize) -> usize {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        (0..count).for_each(|i| {
+            super::render_file(
+                &mut builder,
+                &format!("f{i}.rs"),
+                sample_lines().into_iter(),
+                2,
+                true,
+                0,
+                8.0,
+                false,
+                "2 lines",
+                None,
+                false,
+                &Default::default(),
+                compact,
+            );
+        });
+        builder.finish().len()
+    }
+
+    #[test]
+    fn compact_packs_small_files_onto_one_page() {
+        assert_eq!(render_small_files(false, 3), 3);
+        assert_eq!(render_small_files(true, 3), 1);
+    }
+
+    #[test]
+    fn compact_moves_header_that_would_be_orphaned() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        // Leave room for the separator and header, but not for the minimum lines.
+        let room = 2.0 * builder.line_height() + builder.line_height() + 4.0;
+        builder.vertical_space(builder.remaining_pt() - room);
+        super::render_file(
+            &mut builder,
+            "late.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &Default::default(),
+            true,
+        );
+        assert_eq!(builder.finish().len(), 2);
+    }
+
+    #[test]
+    fn header_moves_rather_than_end_a_page() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        // Room for the header and one line, not both of the file's lines.
+        let room = 2.0 * builder.line_height() + 4.0;
+        builder.vertical_space(builder.remaining_pt() - room);
+        super::render_file(
+            &mut builder,
+            "late.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &Default::default(),
+            false,
+        );
+        assert_eq!(builder.finish().len(), 2);
+    }
+
+    #[test]
+    fn marked_lines_get_a_background() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        super::render_file(
+            &mut builder,
+            "test.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &super::Highlights {
+                marked: vec![2..=2],
+                ..Default::default()
+            },
+            false,
+        );
+        let pages = builder.finish();
+        let bands = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+            .count();
+        assert_eq!(bands, 1);
+    }
+
+    #[test]
+    fn grep_hits_record_line_and_page() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        let re = regex::Regex::new("comment").unwrap();
+        let placement = super::render_file(
+            &mut builder,
+            "test.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &super::Highlights {
+                grep: Some(&re),
+                locate: vec![1, 2],
+                ..Default::default()
+            },
+            false,
         );
+        assert_eq!(
+            placement.located,
+            [(1, "1".to_string()), (1, "1".to_string())]
+        );
+        let hits = placement.hits;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_number, 2);
+        assert_eq!(hits[0].page, 1);
+        assert_eq!(hits[0].text, "// comment");
+        let pages = builder.finish();
+        let boxes = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+            .count();
+        assert_eq!(boxes, 1);
+    }
+
+    #[test]
+    fn margin_notes_follow_the_enclosing_definition() {
+        use crate::symbols::{Symbol, SymbolKind};
+
+        let notes = |outline: &[Symbol]| -> Vec<String> {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            super::render_file(
+                &mut builder,
+                "test.rs",
+                sample_lines().into_iter(),
+                2,
+                true,
+                0,
+                8.0,
+                false,
+                "2 lines",
+                None,
+                false,
+                &super::Highlights {
+                    outline,
+                    ..Default::default()
+                },
+                false,
+            );
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter_map(|op| match op {
+                    printpdf::Op::ShowText { items } => match items.as_slice() {
+                        [printpdf::TextItem::Text(t)] if t == "main" || t == "two" => {
+                            Some(t.clone())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        };
+        let symbol = |name: &str, line_number, end_line| Symbol {
+            kind: SymbolKind::Function,
+            name: name.to_string(),
+            line_number,
+            end_line,
+        };
+
+        assert_eq!(notes(&[symbol("main", 1, 2)]), ["main"]);
+        assert_eq!(
+            notes(&[symbol("main", 1, 1), symbol("two", 2, 2)]),
+            ["main", "two"]
+        );
+        assert!(notes(&[]).is_empty());
+    }
+
+    #[test]
+    fn line_offset_continues_numbering() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        super::render_file(
+            &mut builder,
+            "test.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            998,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &Default::default(),
+            false,
+        );
+        let texts: Vec<String> = builder.finish()[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::ShowText { items } => match items.as_slice() {
+                    [printpdf::TextItem::Text(t)] => Some(t.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert!(texts.iter().any(|t| t == " 999  "), "{texts:?}");
+        assert!(texts.iter().any(|t| t == "1000  "), "{texts:?}");
+    }
+
+    #[test]
+    fn age_shading_boxes_each_aged_line_number() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        super::render_file(
+            &mut builder,
+            "test.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &super::Highlights {
+                ages: vec![0.0],
+                ..Default::default()
+            },
+            false,
+        );
+        let pages = builder.finish();
+        let boxes = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+            .count();
+        assert_eq!(boxes, 1);
+    }
+
+    #[test]
+    fn header_links_back_to_the_toc() {
+        let toc_links = |toc_page: Option<usize>| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder =
+                pdf::create_builder(&Config::test_default(), fonts).with_toc_page(toc_page);
+            super::render_file(
+                &mut builder,
+                "src/main.rs",
+                sample_lines().into_iter(),
+                2,
+                true,
+                0,
+                8.0,
+                false,
+                "2 lines",
+                Some("https://github.com/user/repo/blob/abc/src/main.rs"),
+                false,
+                &Default::default(),
+                false,
+            );
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter(|op| {
+                    matches!(
+                        op,
+                        printpdf::Op::LinkAnnotation { link }
+                            if matches!(
+                                link.actions,
+                                printpdf::Actions::Goto(printpdf::Destination::Xyz { page: 2, .. })
+                            )
+                    )
+                })
+                .count()
+        };
+        assert_eq!(toc_links(Some(2)), 1);
+        assert_eq!(toc_links(None), 0);
+    }
+
+    #[test]
+    fn layers_wrap_the_gutter() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts).with_layers(true);
+        super::render_file(
+            &mut builder,
+            "test.rs",
+            sample_lines().into_iter(),
+            2,
+            true,
+            0,
+            8.0,
+            false,
+            "2 lines",
+            None,
+            false,
+            &super::Highlights {
+                ages: vec![0.0],
+                ..Default::default()
+            },
+            false,
+        );
+        let tags: Vec<String> = builder.finish()[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::BeginMarkedContent { tag } => Some(tag.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            ["LayerLineAges", "LayerLineNumbers", "LayerLineNumbers"]
+        );
+    }
+
+    #[test]
+    fn wrap_splits_tokens_at_the_column_limit() {
+        let token = |text: &str| HighlightedToken {
+            text: text.into(),
+            color: RgbColor { r: 0, g: 0, b: 0 },
+            bold: false,
+            italic: false,
+        };
+        let rows = super::wrap_tokens(vec![(token("abcd"), false), (token("efg"), true)], 3);
+        let texts: Vec<Vec<(&str, bool)>> = rows
+            .iter()
+            .map(|row| row.iter().map(|(t, hit)| (t.text.as_str(), *hit)).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                vec![("abc", false)],
+                vec![("d", false), ("ef", true)],
+                vec![("g", true)]
+            ]
+        );
+        assert_eq!(super::wrap_tokens(vec![], 3).len(), 1);
+    }
+
+    #[test]
+    fn wrapped_long_line_takes_extra_rows() {
+        let long = || {
+            vec![HighlightedLine {
+                line_number: 1,
+                tokens: vec![HighlightedToken {
+                    text: "x".repeat(500),
+                    color: RgbColor { r: 0, g: 0, b: 0 },
+                    bold: false,
+                    italic: false,
+                }],
+            }]
+        };
+        let remaining = |wrap| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let config = Config::test_default();
+            let mut builder = pdf::create_builder(&config, fonts);
+            super::render_file(
+                &mut builder,
+                "long.txt",
+                long().into_iter(),
+                1,
+                true,
+                0,
+                8.0,
+                wrap,
+                "1 line",
+                None,
+                false,
+                &Default::default(),
+                true,
+            );
+            builder.remaining_pt()
+        };
+        // 500 characters need five rows of about 110 columns at 8 pt on A4.
+        assert!(remaining(true) < remaining(false) - 3.0 * 10.0);
     }
 }
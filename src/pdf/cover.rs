@@ -3,11 +3,13 @@ use std::path::Path;
 use printpdf::{Actions, Color, Pt, Rgb};
 
 use super::layout::{PageBuilder, Span};
-use crate::types::RepoMetadata;
+use super::wrap::word_wrap;
+use crate::remote::{Host, RemoteRepo};
+use crate::types::{CoverRow, MetaRow, RepoMetadata};
 
 const CRATES_URL: &str = "https://crates.io/crates/gitprint";
 /// Label column width in characters (monospace font — spaces give exact alignment).
-const LABEL_COL: usize = 12;
+const LABEL_COL: usize = 16;
 /// Approximate character-width-to-font-size ratio for JetBrains Mono.
 const CHAR_WIDTH: f32 = 0.6;
 
@@ -24,39 +26,64 @@ fn github_username_from_email(email: &str) -> Option<&str> {
 
 /// Returns the URL for a specific commit on the remote.
 fn commit_link(remote_base: &str, commit_hash: &str) -> String {
-    format!("{remote_base}/commit/{commit_hash}")
+    RemoteRepo::parse(remote_base).commit_url(commit_hash)
 }
 
 /// Returns a link to the repo tree at the given commit, or the repo root if no commit.
 fn repo_tree_link(remote_base: &str, commit_hash: &str) -> String {
-    if commit_hash.is_empty() {
-        remote_base.to_string()
-    } else {
-        format!("{remote_base}/tree/{commit_hash}")
-    }
+    RemoteRepo::parse(remote_base).tree_url(commit_hash)
 }
 
 /// Returns an author profile/search link for the given email on the remote.
 ///
-/// When the email is a GitHub noreply address the username is extracted and a
-/// profile URL (`https://github.com/{user}`) is returned. Otherwise a
-/// commit-search-by-email URL is used.
+/// When the remote is on GitHub and the email is a noreply address, the username
+/// is extracted and a profile URL (`https://github.com/{user}`) is returned.
+/// Otherwise the host's commit-history-by-author URL is used.
 fn author_link(remote_base: &str, email: &str) -> String {
-    if let Some(username) = github_username_from_email(email) {
-        let host = remote_base
-            .splitn(4, '/')
-            .take(3)
-            .collect::<Vec<_>>()
-            .join("/");
-        format!("{host}/{username}")
-    } else {
-        format!("{remote_base}/commits?author={email}")
+    let repo = RemoteRepo::parse(remote_base);
+    match github_username_from_email(email) {
+        Some(username) if matches!(repo.host(), Host::GitHub | Host::Other) => {
+            let host = repo
+                .base()
+                .splitn(4, '/')
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("/");
+            format!("{host}/{username}")
+        }
+        _ => repo.author_url(email),
     }
 }
 
 /// Returns a `file://` URL for a local filesystem path.
 fn file_url(path: &Path) -> String {
-    format!("file://{}", path.display())
+    slash_file_url(&crate::display_path(path))
+}
+
+/// The `file://` URL of a `/`-separated path: a drive letter goes after a third
+/// slash (`file:///C:/repo`), a UNC server becomes the URL's host
+/// (`file://server/share`), Windows' `//?/` verbatim prefix is dropped, and
+/// anything outside the URL-safe characters is percent-encoded.
+fn slash_file_url(path: &str) -> String {
+    let path = match path.strip_prefix("//?/") {
+        Some(rest) => rest
+            .strip_prefix("UNC/")
+            .map_or_else(|| rest.to_string(), |unc| format!("//{unc}")),
+        None => path.to_string(),
+    };
+    let encoded = path.bytes().fold(String::new(), |mut out, b| {
+        if b.is_ascii_alphanumeric() || b"/:-._~@+,=".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+        out
+    });
+    match path.as_bytes() {
+        [b'/', b'/', ..] => format!("file:{encoded}"),
+        [b'/', ..] => format!("file://{encoded}"),
+        _ => format!("file:///{encoded}"),
+    }
 }
 
 /// Returns a horizontal rule string that fills `width_pt` at the given `font_size`.
@@ -67,20 +94,34 @@ fn separator_line(width_pt: f32, font_size: f32) -> String {
 
 // ── Renderer ──────────────────────────────────────────────────────────────────
 
-/// Renders the repository cover page, including metadata table and footer.
-pub fn render(builder: &mut PageBuilder, metadata: &RepoMetadata, remote_url: Option<&str>) {
-    let bold = builder.font(true, false).clone();
-    let regular = builder.font(false, false).clone();
+/// Renders the repository cover page, including metadata table and footer. With
+/// `qr`, a QR code linking to the remote tree at the commit sits below the table.
+///
+/// The table shows `rows` that have a value, in the order given, then the `meta`
+/// rows. Labels, dates, and counts follow the builder's locale.
+pub fn render(
+    builder: &mut PageBuilder,
+    metadata: &RepoMetadata,
+    remote_url: Option<&str>,
+    qr: bool,
+    rows: &[CoverRow],
+    meta: &[MetaRow],
+) {
+    let bold = builder.prose_font(true, false).clone();
+    let regular = builder.prose_font(false, false).clone();
+    let mono = builder.font(false, false).clone();
     let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
     let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
     let lh = builder.line_height();
+    let locale = builder.locale();
 
     const TABLE_SIZE: f32 = 9.0;
 
     // Use explicit remote_url if provided; otherwise fall back to the one detected
     // from git config so links work for local git repos without --remote.
     let effective_remote = remote_url.or(metadata.detected_remote_url.as_deref());
-    let remote_base = effective_remote.map(|u| u.trim_end_matches(".git"));
+    let remote = effective_remote.map(RemoteRepo::parse);
+    let remote_base = remote.as_ref().map(RemoteRepo::base);
 
     // Title links to repo tree at current commit (remote) or to the local path (local).
     let title_url: Option<String> = remote_base
@@ -104,66 +145,126 @@ pub fn render(builder: &mut PageBuilder, metadata: &RepoMetadata, remote_url: Op
         )
     };
 
+    // GitHub details (`--offline` leaves them out). A license GitHub can't identify
+    // is reported by name.
+    let github = metadata.github.as_ref();
+    let count = |n: Option<u64>| n.map(|n| locale.number(n)).unwrap_or_default();
+    let license = github
+        .and_then(|r| r.license.as_ref())
+        .map(|l| match l.spdx_id.as_deref() {
+            Some(id) if id != "NOASSERTION" => id.to_string(),
+            _ => l.name.clone(),
+        })
+        .unwrap_or_default();
+
     // ── Title ─────────────────────────────────────────────────────────────────
-    builder.vertical_space(120.0);
+    builder.gap(120.0);
+    builder.set_role("H1");
     builder.write_centered(&metadata.name, &bold, Pt(28.0), black.clone());
+    builder.set_role("P");
     if let Some(url) = title_url {
         builder.add_link(28.0 + 4.0, Actions::Uri(url));
     }
-    builder.vertical_space(32.0);
+    builder.gap(32.0);
 
     // ── Metadata table ────────────────────────────────────────────────────────
     builder.draw_horizontal_rule(Color::Rgb(Rgb::new(0.72, 0.72, 0.72, None)), 0.5);
-    builder.vertical_space(8.0);
+    builder.gap(8.0);
 
     // Rows: (label, value, optional URL). Message links to the same commit as Commit.
-    [
-        ("Branch", metadata.branch.as_str(), None::<String>),
-        (
-            "Commit",
-            metadata.commit_hash_short.as_str(),
-            commit_url.clone(),
-        ),
-        ("Author", author_display.as_str(), author_url),
-        ("Date", metadata.commit_date.as_str(), None),
-        (
-            "Message",
-            metadata.commit_message.as_str(),
-            commit_url.clone(),
-        ),
-        ("Files", &metadata.file_count.to_string(), None),
-        ("Lines", &metadata.total_lines.to_string(), None),
-        ("Repo Size", metadata.repo_size.as_str(), None),
-        ("FS Size", metadata.fs_size.as_str(), None),
-        ("FS Owner", metadata.fs_owner.as_deref().unwrap_or(""), None),
-        ("FS Group", metadata.fs_group.as_deref().unwrap_or(""), None),
-        ("Generated", metadata.generated_at.as_str(), None),
-    ]
-    .into_iter()
-    .filter(|(_, value, _)| !value.is_empty())
-    .for_each(|(label, value, url)| {
-        builder.write_line(&[
-            Span {
-                text: format!("{label:<LABEL_COL$}"),
-                font_id: bold.clone(),
-                size: Pt(TABLE_SIZE),
-                color: black.clone(),
-            },
-            Span {
-                text: value.into(),
-                font_id: regular.clone(),
-                size: Pt(TABLE_SIZE),
-                color: black.clone(),
-            },
-        ]);
-        if let Some(u) = url {
-            builder.add_link(lh, Actions::Uri(u));
+    let row = |row: CoverRow| -> (String, Option<String>) {
+        let text = |value: &str| value.to_string();
+        match row {
+            CoverRow::Branch => (text(&metadata.branch), None),
+            CoverRow::Commit => (text(&metadata.commit_hash_short), commit_url.clone()),
+            CoverRow::Author => (author_display.clone(), author_url.clone()),
+            CoverRow::Date => (locale.date(&metadata.commit_date), None),
+            CoverRow::Signature => (text(&metadata.signature), None),
+            CoverRow::Message => (text(&metadata.commit_message), commit_url.clone()),
+            CoverRow::Description => (
+                text(github.and_then(|r| r.description.as_deref()).unwrap_or("")),
+                github.map(|r| r.html_url.clone()),
+            ),
+            CoverRow::Topics => (
+                github.map(|r| r.topics.join(", ")).unwrap_or_default(),
+                None,
+            ),
+            CoverRow::Stars => (count(github.map(|r| r.stargazers_count)), None),
+            CoverRow::Forks => (count(github.map(|r| r.forks_count)), None),
+            CoverRow::OpenIssues => (count(github.map(|r| r.open_issues_count)), None),
+            CoverRow::DefaultBranch => (
+                text(
+                    github
+                        .and_then(|r| r.default_branch.as_deref())
+                        .unwrap_or(""),
+                ),
+                None,
+            ),
+            CoverRow::License => (license.clone(), None),
+            CoverRow::Files => (locale.number(metadata.file_count as u64), None),
+            CoverRow::Lines => (locale.number(metadata.total_lines as u64), None),
+            CoverRow::RepoSize => (text(&metadata.repo_size), None),
+            CoverRow::FsSize => (text(&metadata.fs_size), None),
+            CoverRow::FsOwner => (text(metadata.fs_owner.as_deref().unwrap_or("")), None),
+            CoverRow::FsGroup => (text(metadata.fs_group.as_deref().unwrap_or("")), None),
+            CoverRow::Generated => (locale.date(&metadata.generated_at), None),
         }
-    });
-
-    builder.vertical_space(4.0);
+    };
+    // `--meta` rows follow the built-in ones; a label too long for its column is
+    // shortened with `…`.
+    rows.iter()
+        .map(|&r| {
+            let (value, url) = row(r);
+            (locale.label(r).to_string(), value, url)
+        })
+        .chain(meta.iter().map(|m| {
+            let label = if m.label.chars().count() < LABEL_COL {
+                m.label.clone()
+            } else {
+                let kept: String = m.label.chars().take(LABEL_COL - 2).collect();
+                format!("{kept}\u{2026}")
+            };
+            (label, m.value.clone(), None)
+        }))
+        .filter(|(_, value, _)| !value.is_empty())
+        .for_each(|(label, value, url)| {
+            // Values start at a fixed column, whatever the width of the label's font,
+            // and wrap between words onto further lines at that column.
+            let value_x = LABEL_COL as f32 * TABLE_SIZE * CHAR_WIDTH;
+            let lines = word_wrap(&value, builder.usable_width_pt() - value_x, |c| {
+                builder.text_width(c, &regular, Pt(TABLE_SIZE))
+            });
+            builder.ensure_space(lh * lines.len() as f32);
+            lines.iter().enumerate().for_each(|(i, line)| {
+                builder.write_text_at_x(value_x, line, &regular, Pt(TABLE_SIZE), black.clone());
+                builder.write_line(&[Span {
+                    text: if i == 0 { label.clone() } else { String::new() },
+                    font_id: bold.clone(),
+                    size: Pt(TABLE_SIZE),
+                    color: black.clone(),
+                }]);
+            });
+            if let Some(u) = url {
+                builder.add_link(lh * lines.len() as f32, Actions::Uri(u));
+            }
+        });
+
+    builder.gap(4.0);
     builder.draw_horizontal_rule(Color::Rgb(Rgb::new(0.72, 0.72, 0.72, None)), 0.5);
 
+    // ── QR code (remote only: a file:// link is no use on a phone) ─────────────
+    if qr && let Some(base) = remote_base {
+        let url = repo_tree_link(base, &metadata.commit_hash);
+        let size = super::qr::COVER_SIZE;
+        let x = (builder.usable_width_pt() - size) / 2.0;
+        builder.gap(16.0);
+        builder.vertical_space(size);
+        if super::qr::draw(builder, &url, x, 0.0, size) {
+            builder.gap(12.0);
+            builder.write_centered(&url, &regular, Pt(7.0), gray.clone());
+        }
+    }
+
     // ── Footer (pushed to the bottom of the page) ─────────────────────────────
     let version = env!("CARGO_PKG_VERSION");
     let footer_text =
@@ -175,7 +276,7 @@ pub fn render(builder: &mut PageBuilder, metadata: &RepoMetadata, remote_url: Op
 
     builder.write_line(&[Span {
         text: separator_line(builder.usable_width_pt(), footer_size.0),
-        font_id: regular.clone(),
+        font_id: mono,
         size: footer_size,
         color: gray.clone(),
     }]);
@@ -190,8 +291,9 @@ pub fn render(builder: &mut PageBuilder, metadata: &RepoMetadata, remote_url: Op
 mod tests {
     use std::path::PathBuf;
 
+    use crate::locale::Locale;
     use crate::pdf;
-    use crate::types::{Config, RepoMetadata};
+    use crate::types::{Config, CoverRow, MetaRow, RepoMetadata};
 
     fn test_metadata() -> RepoMetadata {
         RepoMetadata {
@@ -199,10 +301,12 @@ mod tests {
             branch: "main".into(),
             commit_hash: "abc1234567890abcdef1234567890abcdef123456".into(),
             commit_hash_short: "abc1234".into(),
+            tree_hash: "def5678901234567890abcdef1234567890abcde".into(),
             commit_date: "2024-01-01 12:00:00 +0000".into(),
             commit_message: "initial commit".into(),
             commit_author: "Alice Dev".into(),
             commit_author_email: "alice@example.com".into(),
+            signature: "Good signature by Alice Dev".into(),
             file_count: 5,
             total_lines: 100,
             fs_owner: Some("alice".into()),
@@ -212,6 +316,7 @@ mod tests {
             fs_size: "1.5 MB".into(),
             detected_remote_url: None,
             repo_absolute_path: None,
+            github: None,
         }
     }
 
@@ -279,6 +384,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_url_encodes_and_handles_windows_forms() {
+        assert_eq!(
+            super::slash_file_url("/home/me/my repo#2/ü"),
+            "file:///home/me/my%20repo%232/%C3%BC"
+        );
+        assert_eq!(
+            super::slash_file_url("C:/Users/me/repo"),
+            "file:///C:/Users/me/repo"
+        );
+        assert_eq!(super::slash_file_url("//?/C:/repo"), "file:///C:/repo");
+        assert_eq!(
+            super::slash_file_url("//server/share/repo"),
+            "file://server/share/repo"
+        );
+        assert_eq!(
+            super::slash_file_url("//?/UNC/server/share"),
+            "file://server/share"
+        );
+    }
+
     #[test]
     fn github_username_from_noreply_email() {
         assert_eq!(
@@ -307,7 +433,14 @@ mod tests {
         let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
         let config = Config::test_default();
         let mut builder = pdf::create_builder(&config, fonts);
-        super::render(&mut builder, &test_metadata(), None);
+        super::render(
+            &mut builder,
+            &test_metadata(),
+            None,
+            false,
+            &CoverRow::ALL,
+            &[],
+        );
         assert!(!builder.finish().is_empty());
     }
 
@@ -321,6 +454,9 @@ mod tests {
             &mut builder,
             &test_metadata(),
             Some("https://github.com/user/repo"),
+            false,
+            &CoverRow::ALL,
+            &[],
         );
         assert!(!builder.finish().is_empty());
     }
@@ -333,7 +469,7 @@ mod tests {
         let mut builder = pdf::create_builder(&config, fonts);
         let mut meta = test_metadata();
         meta.detected_remote_url = Some("https://github.com/user/local-repo".into());
-        super::render(&mut builder, &meta, None);
+        super::render(&mut builder, &meta, None, false, &CoverRow::ALL, &[]);
         assert!(!builder.finish().is_empty());
     }
 
@@ -345,7 +481,7 @@ mod tests {
         let mut builder = pdf::create_builder(&config, fonts);
         let mut meta = test_metadata();
         meta.repo_absolute_path = Some(PathBuf::from("/home/user/myproject"));
-        super::render(&mut builder, &meta, None);
+        super::render(&mut builder, &meta, None, false, &CoverRow::ALL, &[]);
         assert!(!builder.finish().is_empty());
     }
 
@@ -357,7 +493,14 @@ mod tests {
         let mut builder = pdf::create_builder(&config, fonts);
         let mut meta = test_metadata();
         meta.repo_absolute_path = Some(PathBuf::from("/home/user/myproject"));
-        super::render(&mut builder, &meta, Some("https://github.com/user/repo"));
+        super::render(
+            &mut builder,
+            &meta,
+            Some("https://github.com/user/repo"),
+            false,
+            &CoverRow::ALL,
+            &[],
+        );
         assert!(!builder.finish().is_empty());
     }
 
@@ -374,10 +517,12 @@ mod tests {
                 branch: String::new(),
                 commit_hash: String::new(),
                 commit_hash_short: String::new(),
+                tree_hash: String::new(),
                 commit_date: String::new(),
                 commit_message: String::new(),
                 commit_author: String::new(),
                 commit_author_email: String::new(),
+                signature: String::new(),
                 file_count: 0,
                 total_lines: 0,
                 fs_owner: None,
@@ -387,8 +532,12 @@ mod tests {
                 fs_size: String::new(),
                 detected_remote_url: None,
                 repo_absolute_path: None,
+                github: None,
             },
             None,
+            false,
+            &CoverRow::ALL,
+            &[],
         );
     }
 
@@ -403,7 +552,152 @@ mod tests {
             &mut builder,
             &test_metadata(),
             Some("https://github.com/user/repo.git"),
+            false,
+            &CoverRow::ALL,
+            &[],
         );
         assert!(!builder.finish().is_empty());
     }
+
+    /// The text drawn on a cover showing `rows` and `meta`, in drawing order.
+    fn cover_texts(
+        metadata: &RepoMetadata,
+        rows: &[CoverRow],
+        meta: &[MetaRow],
+        locale: Locale,
+    ) -> Vec<String> {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts).with_locale(locale);
+        super::render(&mut builder, metadata, None, false, rows, meta);
+        builder.finish()[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::ShowText { items } => match items.as_slice() {
+                    [printpdf::TextItem::Text(t)] => Some(t.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn long_values_wrap_between_words() {
+        let message = "Rework the page layout engine so long values wrap ".repeat(4);
+        let metadata = RepoMetadata {
+            commit_message: message.trim().to_string(),
+            ..test_metadata()
+        };
+        let texts = cover_texts(&metadata, &CoverRow::ALL, &[], Locale::Neutral);
+        let start = texts.iter().position(|t| t.starts_with("Rework")).unwrap();
+        let end = texts.iter().rposition(|t| t.ends_with("wrap")).unwrap();
+        let lines: Vec<&str> = texts[start..=end]
+            .iter()
+            .map(String::as_str)
+            .filter(|t| !t.is_empty() && *t != "Message")
+            .collect();
+        assert!(lines.len() > 1, "{texts:?}");
+        assert_eq!(lines.join(" "), metadata.commit_message);
+    }
+
+    #[test]
+    fn rows_follow_the_configured_list_then_meta() {
+        let meta = [
+            "Ticket=SEC-1421".parse().unwrap(),
+            "A label far too long for its column=x".parse().unwrap(),
+        ];
+        let texts = cover_texts(
+            &test_metadata(),
+            &[CoverRow::Files, CoverRow::Branch],
+            &meta,
+            Locale::Neutral,
+        );
+        let position = |text: &str| texts.iter().position(|t| t == text);
+        assert!(position("Author").is_none(), "{texts:?}");
+        assert!(position("Files").is_some() && position("Files") < position("Branch"));
+        assert!(position("Branch") < position("Ticket"));
+        assert!(position("SEC-1421").is_some());
+        assert!(position("A label far to\u{2026}").is_some(), "{texts:?}");
+    }
+
+    #[test]
+    fn locale_translates_labels_dates_and_counts() {
+        let metadata = RepoMetadata {
+            total_lines: 12_345,
+            ..test_metadata()
+        };
+        let rows = [CoverRow::Date, CoverRow::Lines, CoverRow::Generated];
+        let texts = cover_texts(&metadata, &rows, &[], Locale::De);
+        [
+            "Datum",
+            "01.01.2024 12:00:00 +0000",
+            "Zeilen",
+            "12.345",
+            "Erstellt",
+        ]
+        .iter()
+        .for_each(|text| assert!(texts.iter().any(|t| t == text), "{text}: {texts:?}"));
+        let texts = cover_texts(&metadata, &rows, &[], Locale::Neutral);
+        assert!(texts.iter().any(|t| t == "12345"), "{texts:?}");
+    }
+
+    #[test]
+    fn render_cover_links_the_github_description() {
+        let links = |meta: &RepoMetadata| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            super::render(&mut builder, meta, None, false, &CoverRow::ALL, &[]);
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+                .count()
+        };
+        let mut meta = test_metadata();
+        let plain = links(&meta);
+        meta.github = Some(crate::github::GitHubRepo {
+            name: "test-repo".into(),
+            full_name: "alice/test-repo".into(),
+            html_url: "https://github.com/alice/test-repo".into(),
+            description: Some("A test repository".into()),
+            language: Some("Rust".into()),
+            stargazers_count: 12,
+            forks_count: 3,
+            pushed_at: None,
+            updated_at: None,
+            fork: false,
+            open_issues_count: 4,
+            size: 0,
+            created_at: None,
+            topics: vec!["cli".into(), "pdf".into()],
+            default_branch: Some("main".into()),
+            license: Some(crate::github::License {
+                name: "MIT License".into(),
+                spdx_id: Some("MIT".into()),
+            }),
+        });
+        assert_eq!(links(&meta), plain + 1);
+    }
+
+    #[test]
+    fn render_cover_qr_code_needs_a_remote() {
+        let qr_polygons = |remote_url: Option<&str>| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            let mut meta = test_metadata();
+            meta.repo_absolute_path = Some(PathBuf::from("/home/user/myproject"));
+            super::render(&mut builder, &meta, remote_url, true, &CoverRow::ALL, &[]);
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+                .count()
+        };
+        assert_eq!(qr_polygons(Some("https://github.com/user/repo")), 1);
+        assert_eq!(qr_polygons(None), 0);
+    }
 }
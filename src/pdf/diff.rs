@@ -2,6 +2,7 @@ use printpdf::{Actions, Color, Pt, Rgb};
 
 use super::layout::{PageBuilder, Span};
 use crate::github::CommitDetail;
+use crate::remote::RemoteRepo;
 
 // ── Color palette ──────────────────────────────────────────────────────────────
 // Green/red chosen to be distinguishable for common colorblindness types:
@@ -18,14 +19,188 @@ fn neon_red() -> Color {
 fn hunk_blue() -> Color {
     Color::Rgb(Rgb::new(0.34, 0.60, 0.96, None)) // #5799F5 — electric blue
 }
+// Backgrounds behind the words that differ within a changed line pair.
+fn added_emphasis() -> Color {
+    Color::Rgb(Rgb::new(0.72, 0.95, 0.82, None)) // #B8F2D1 — pale jade
+}
+fn removed_emphasis() -> Color {
+    Color::Rgb(Rgb::new(0.99, 0.78, 0.78, None)) // #FCC7C7 — pale red
+}
+
+/// Token pairs above which a line pair is not compared word by word; the LCS
+/// table grows with the product of both lines' token counts.
+const MAX_INTRALINE_CELLS: usize = 10_000;
+
+/// A run of a changed line's text, and whether it differs from its counterpart.
+type Segment = (String, bool);
+
+/// Splits a line into words, runs of whitespace, and single punctuation characters.
+fn tokenize(text: &str) -> Vec<&str> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        if class(c) != 2 {
+            while let Some((i, d)) = chars.next_if(|&(_, d)| class(d) == class(c)) {
+                end = i + d.len_utf8();
+            }
+        }
+        tokens.push(&text[start..end]);
+    }
+    tokens
+}
+
+/// Joins tokens into segments of like `changed` flags. Whitespace between two
+/// changed tokens counts as changed, so an edited phrase gets one box.
+fn segments(tokens: &[&str], changed: &[bool]) -> Vec<Segment> {
+    let mut out: Vec<Segment> = Vec::new();
+    tokens.iter().enumerate().for_each(|(i, token)| {
+        let bridged = token.trim().is_empty()
+            && i > 0
+            && changed[i - 1]
+            && changed.get(i + 1).copied().unwrap_or(false);
+        let flag = changed[i] || bridged;
+        match out.last_mut() {
+            Some((text, last)) if *last == flag => text.push_str(token),
+            _ => out.push((token.to_string(), flag)),
+        }
+    });
+    out
+}
+
+/// Word-level difference between a removed and an added line: the tokens outside
+/// their longest common subsequence are flagged as changed. `None` when the lines
+/// are too long to compare cheaply, or share nothing but whitespace so emphasis
+/// would cover everything.
+fn intraline(old: &str, new: &str) -> Option<(Vec<Segment>, Vec<Segment>)> {
+    let (a, b) = (tokenize(old), tokenize(new));
+    if a.len() * b.len() > MAX_INTRALINE_CELLS {
+        return None;
+    }
+    // lcs[i * width + j] is the LCS length of a[i..] and b[j..].
+    let width = b.len() + 1;
+    let mut lcs = vec![0usize; (a.len() + 1) * width];
+    (0..a.len()).rev().for_each(|i| {
+        (0..b.len()).rev().for_each(|j| {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        });
+    });
+    let (mut old_changed, mut new_changed) = (vec![true; a.len()], vec![true; b.len()]);
+    let (mut i, mut j, mut shared) = (0, 0, false);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            old_changed[i] = false;
+            new_changed[j] = false;
+            shared |= !a[i].trim().is_empty();
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    shared.then(|| (segments(&a, &old_changed), segments(&b, &new_changed)))
+}
+
+/// Unchanged lines are folded behind a marker only when at least this many would be.
+const MIN_COLLAPSED: usize = 4;
+
+/// What to print for one patch line, or for a run of unchanged lines left out.
+#[derive(Debug, PartialEq, Eq)]
+enum Row {
+    /// The patch line at this index.
+    Line(usize),
+    /// A marker standing in for this many unchanged lines.
+    Collapsed(usize),
+}
+
+/// Keeps `context` unchanged lines on each side of every change and folds the rest
+/// of each longer run of unchanged lines, within a hunk, into one marker.
+fn collapse_context(lines: &[&str], context: usize) -> Vec<Row> {
+    let unchanged = |l: &str| l.is_empty() || l.starts_with(' ');
+    let is_change = |i: usize| lines.get(i).is_some_and(|l| l.starts_with(['+', '-']));
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if !unchanged(lines[i]) {
+            rows.push(Row::Line(i));
+            i += 1;
+            continue;
+        }
+        let end = i + lines[i..].iter().take_while(|l| unchanged(l)).count();
+        let head = if i > 0 && is_change(i - 1) {
+            context
+        } else {
+            0
+        };
+        let tail = if is_change(end) { context } else { 0 };
+        let hidden = (end - i).saturating_sub(head + tail);
+        if hidden >= MIN_COLLAPSED {
+            rows.extend((i..i + head).map(Row::Line));
+            rows.push(Row::Collapsed(hidden));
+            rows.extend((end - tail..end).map(Row::Line));
+        } else {
+            rows.extend((i..end).map(Row::Line));
+        }
+        i = end;
+    }
+    rows
+}
+
+/// Word-level emphasis for each patch line: a run of removed lines followed by a
+/// run of added lines is compared pairwise, first with first. Lines without a
+/// counterpart, and context lines, get `None`.
+fn pair_changes(lines: &[&str]) -> Vec<Option<Vec<Segment>>> {
+    let mut out = vec![None; lines.len()];
+    let mut i = 0;
+    while i < lines.len() {
+        let removed = lines[i..].iter().take_while(|l| l.starts_with('-')).count();
+        if removed == 0 {
+            i += 1;
+            continue;
+        }
+        let added = lines[i + removed..]
+            .iter()
+            .take_while(|l| l.starts_with('+'))
+            .count();
+        (0..removed.min(added)).for_each(|k| {
+            let (old, new) = (i + k, i + removed + k);
+            if let Some((o, n)) = intraline(&lines[old][1..], &lines[new][1..]) {
+                out[old] = Some(o);
+                out[new] = Some(n);
+            }
+        });
+        i += removed + added;
+    }
+    out
+}
 
-/// Renders a single commit with its per-file diffs into the PDF.
+/// Renders a single commit with its per-file diffs into the PDF. The branch and
+/// file lines link into `remote` when one is given; the commit line links to
+/// `detail.html_url` unless it is empty. Runs of unchanged lines longer than
+/// `context` on either side of a change are collapsed to a marker.
 pub fn render_commit(
     builder: &mut PageBuilder,
     detail: &CommitDetail,
     repo: &str,
+    remote: Option<&RemoteRepo>,
     branch: Option<&str>,
     font_size: f32,
+    context: usize,
 ) {
     let bold = builder.font(true, false).clone();
     let regular = builder.font(false, false).clone();
@@ -59,7 +234,7 @@ pub fn render_commit(
 
     // ── Thin separator rule before each commit ─────────────────────────────────
     builder.draw_horizontal_rule(rule_gray.clone(), 0.4);
-    builder.vertical_space(7.0);
+    builder.gap(7.0);
 
     // ── Line 1: sha · message — links to the commit page ──────────────────────
     builder.write_line(&[
@@ -76,7 +251,9 @@ pub fn render_commit(
             color: black.clone(),
         },
     ]);
-    builder.add_link(builder.line_height(), Actions::Uri(detail.html_url.clone()));
+    if !detail.html_url.is_empty() {
+        builder.add_link(builder.line_height(), Actions::Uri(detail.html_url.clone()));
+    }
 
     // ── Line 2: repo (branch) · author · date · ±stats — links to repo/branch ─
     let meta_size = Pt(font_size - 1.0);
@@ -127,18 +304,18 @@ pub fn render_commit(
         },
     ]);
     builder.write_line(&meta_spans);
-    let meta_url = branch
-        .map(|b| format!("https://github.com/{repo}/tree/{b}"))
-        .unwrap_or_else(|| format!("https://github.com/{repo}"));
-    builder.add_link(builder.line_height(), Actions::Uri(meta_url));
+    if let Some(remote) = remote {
+        let meta_url = branch.map_or_else(|| remote.base().to_string(), |b| remote.tree_url(b));
+        builder.add_link(builder.line_height(), Actions::Uri(meta_url));
+    }
 
-    builder.vertical_space(5.0);
+    builder.gap(5.0);
 
     // ── Per-file diffs ─────────────────────────────────────────────────────────
     detail.files.iter().for_each(|file| {
         builder.ensure_space(builder.line_height() * 3.0);
 
-        // File header: filename + stats, links to the file at this commit on GitHub.
+        // File header: filename + stats, links to the file at this commit on the remote.
         builder.write_line(&[
             Span {
                 text: format!("  {} ", file.filename),
@@ -165,11 +342,10 @@ pub fn render_commit(
                 color: neon_red(),
             },
         ]);
-        let file_url = format!(
-            "https://github.com/{repo}/blob/{}/{}",
-            detail.sha, file.filename
-        );
-        builder.add_link(builder.line_height(), Actions::Uri(file_url));
+        if let Some(remote) = remote {
+            let file_url = remote.blob_url(&detail.sha, &file.filename);
+            builder.add_link(builder.line_height(), Actions::Uri(file_url));
+        }
 
         match &file.patch {
             None => {
@@ -181,36 +357,76 @@ pub fn render_commit(
                 }]);
             }
             Some(patch) => {
-                patch.lines().for_each(|line| {
-                    let (marker, color) = if line.starts_with('+') {
-                        ("+", neon_green())
-                    } else if line.starts_with('-') {
-                        ("-", neon_red())
-                    } else if line.starts_with("@@") {
-                        ("@", hunk_blue())
-                    } else {
-                        (" ", dark_gray.clone())
-                    };
-                    let body = if line.starts_with("@@") {
-                        line.to_string()
-                    } else {
-                        // Strip the diff prefix char; replace with padded marker.
-                        format!("{marker} {}", line.get(1..).unwrap_or(line))
-                    };
-                    builder.write_line(&[Span {
-                        text: format!("    {body}"),
-                        font_id: regular.clone(),
-                        size: Pt(font_size - 1.0),
-                        color,
-                    }]);
-                });
+                let lines: Vec<&str> = patch.lines().collect();
+                let mut emphasis = pair_changes(&lines);
+                collapse_context(&lines, context)
+                    .into_iter()
+                    .for_each(|row| {
+                        let i = match row {
+                            Row::Line(i) => i,
+                            Row::Collapsed(hidden) => {
+                                builder.write_line(&[Span {
+                                    text: format!(
+                                        "      \u{2026} {hidden} unchanged lines \u{2026}"
+                                    ),
+                                    font_id: regular.clone(),
+                                    size: Pt(font_size - 1.0),
+                                    color: gray.clone(),
+                                }]);
+                                return;
+                            }
+                        };
+                        let line = lines[i];
+                        let (marker, color) = if line.starts_with('+') {
+                            ("+", neon_green())
+                        } else if line.starts_with('-') {
+                            ("-", neon_red())
+                        } else if line.starts_with("@@") {
+                            ("@", hunk_blue())
+                        } else {
+                            (" ", dark_gray.clone())
+                        };
+                        if let Some(segments) = emphasis[i].take() {
+                            let background = if marker == "+" {
+                                added_emphasis()
+                            } else {
+                                removed_emphasis()
+                            };
+                            let span = |text: String| Span {
+                                text,
+                                font_id: regular.clone(),
+                                size: Pt(font_size - 1.0),
+                                color: color.clone(),
+                            };
+                            let (spans, backgrounds): (Vec<Span>, Vec<Option<Color>>) =
+                                std::iter::once((span(format!("    {marker} ")), None))
+                                    .chain(segments.into_iter().map(|(text, changed)| {
+                                        (span(text), changed.then(|| background.clone()))
+                                    }))
+                                    .unzip();
+                            builder.write_line_with_span_backgrounds(&spans, None, &backgrounds);
+                            return;
+                        }
+                        let body = if line.starts_with("@@") {
+                            line.to_string()
+                        } else {
+                            // Strip the diff prefix char; replace with padded marker.
+                            format!("{marker} {}", line.get(1..).unwrap_or(line))
+                        };
+                        builder.write_line(&[Span {
+                            text: format!("    {body}"),
+                            font_id: regular.clone(),
+                            size: Pt(font_size - 1.0),
+                            color,
+                        }]);
+                    });
             }
         }
 
-        builder.vertical_space(3.0);
+        builder.gap(3.0);
     });
 
-    builder.vertical_space(6.0);
+    builder.gap(6.0);
 }
 
 #[cfg(test)]
@@ -258,8 +474,10 @@ mod tests {
             &mut builder,
             &test_detail(true),
             "alice/repo",
+            Some(&RemoteRepo::parse("https://github.com/alice/repo")),
             Some("main"),
             8.0,
+            3,
         );
         assert!(!builder.finish().is_empty());
     }
@@ -270,7 +488,15 @@ mod tests {
         let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
         let config = Config::test_default();
         let mut builder = pdf::create_builder(&config, fonts);
-        super::render_commit(&mut builder, &test_detail(false), "alice/repo", None, 8.0);
+        super::render_commit(
+            &mut builder,
+            &test_detail(false),
+            "alice/repo",
+            None,
+            None,
+            8.0,
+            3,
+        );
         assert!(!builder.finish().is_empty());
     }
 
@@ -282,7 +508,124 @@ mod tests {
         let mut builder = pdf::create_builder(&config, fonts);
         let mut detail = test_detail(false);
         detail.files.clear();
-        super::render_commit(&mut builder, &detail, "alice/repo", Some("dev"), 8.0);
+        super::render_commit(
+            &mut builder,
+            &detail,
+            "alice/repo",
+            None,
+            Some("dev"),
+            8.0,
+            3,
+        );
         assert!(!builder.finish().is_empty());
     }
+
+    #[test]
+    fn tokenize_splits_words_spaces_and_punctuation() {
+        assert_eq!(
+            tokenize("let x_1 = f(a);"),
+            ["let", " ", "x_1", " ", "=", " ", "f", "(", "a", ")", ";"]
+        );
+    }
+
+    #[test]
+    fn intraline_flags_only_the_edited_words() {
+        let (old, new) = intraline("let total = count + 1;", "let total = count * 2;").unwrap();
+        assert_eq!(
+            old,
+            [
+                ("let total = count ".to_string(), false),
+                ("+ 1".to_string(), true),
+                (";".to_string(), false),
+            ]
+        );
+        assert_eq!(new[1], ("* 2".to_string(), true));
+    }
+
+    #[test]
+    fn intraline_boxes_an_edited_phrase_once() {
+        let (old, new) = intraline("call(a)", "call(big new value)").unwrap();
+        assert_eq!(old[1], ("a".to_string(), true));
+        assert_eq!(
+            new,
+            [
+                ("call(".to_string(), false),
+                ("big new value".to_string(), true),
+                (")".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn intraline_skips_unrelated_lines() {
+        assert!(intraline("fn main() {", "    println!").is_none());
+    }
+
+    #[test]
+    fn pair_changes_matches_removed_with_added_lines() {
+        let lines = ["@@ -1,3 +1,3 @@", " ctx", "-old = 1", "+old = 2", "+extra"];
+        let pairs = pair_changes(&lines);
+        assert!(pairs[0].is_none() && pairs[1].is_none() && pairs[4].is_none());
+        assert!(pairs[2].is_some() && pairs[3].is_some());
+    }
+
+    #[test]
+    fn collapse_context_folds_long_unchanged_runs() {
+        let mut lines = vec!["@@ -1,20 +1,20 @@", "-a", "+b"];
+        lines.extend([" same"; 10]);
+        lines.push("-c");
+        let rows = collapse_context(&lines, 2);
+        assert_eq!(
+            rows,
+            [
+                Row::Line(0),
+                Row::Line(1),
+                Row::Line(2),
+                Row::Line(3),
+                Row::Line(4),
+                Row::Collapsed(6),
+                Row::Line(11),
+                Row::Line(12),
+                Row::Line(13),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapse_context_keeps_short_runs_and_trims_hunk_edges() {
+        let lines = ["@@ -1 +1 @@", " x", " x", " x", " x", " x", "+y", " z"];
+        assert_eq!(
+            collapse_context(&lines, 1),
+            [
+                Row::Line(0),
+                Row::Collapsed(4),
+                Row::Line(5),
+                Row::Line(6),
+                Row::Line(7),
+            ]
+        );
+        assert_eq!(collapse_context(&lines, 3).len(), lines.len());
+    }
+
+    #[test]
+    fn render_commit_boxes_changed_words() {
+        let boxes = |patch: &str| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            let mut detail = test_detail(false);
+            detail.files[0].patch = Some(patch.to_string());
+            super::render_commit(&mut builder, &detail, "alice/repo", None, None, 8.0, 3);
+            builder.finish()[0]
+                .ops
+                .iter()
+                .filter(|op| matches!(op, printpdf::Op::DrawPolygon { .. }))
+                .count()
+        };
+        let unrelated = boxes("@@ -1 +1 @@\n-fn main() {\n+    println!");
+        assert_eq!(
+            boxes("@@ -1 +1 @@\n-let x = 1;\n+let x = 2;"),
+            unrelated + 2
+        );
+    }
 }
@@ -1,32 +1,141 @@
-use printpdf::{ParsedFont, PdfDocument};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use printpdf::{FontId, ParsedFont, PdfDocument};
 
 use super::layout::FontSet;
+use super::metrics::{FontMetrics, Metrics};
+use crate::error::{Error, Result};
+use crate::types::Config;
 
 const REGULAR: &[u8] = include_bytes!("../../fonts/JetBrainsMono-Regular.ttf");
 const BOLD: &[u8] = include_bytes!("../../fonts/JetBrainsMono-Bold.ttf");
 const ITALIC: &[u8] = include_bytes!("../../fonts/JetBrainsMono-Italic.ttf");
 const BOLD_ITALIC: &[u8] = include_bytes!("../../fonts/JetBrainsMono-BoldItalic.ttf");
 
-fn parse_font(bytes: &[u8], label: &str) -> anyhow::Result<ParsedFont> {
-    ParsedFont::from_bytes(bytes, 0, &mut Vec::new())
-        .ok_or_else(|| anyhow::anyhow!("font loading failed: {label}: failed to parse font"))
+fn parse_font(bytes: &[u8], label: &str) -> Result<ParsedFont> {
+    ParsedFont::from_bytes(bytes, 0, &mut Vec::new()).ok_or_else(|| {
+        Error::Pdf(format!(
+            "font loading failed: {label}: failed to parse font"
+        ))
+    })
 }
 
-/// Parses and registers all four JetBrains Mono variants into the PDF document.
-pub fn load_fonts(doc: &mut PdfDocument) -> anyhow::Result<FontSet> {
-    let regular = parse_font(REGULAR, "regular")?;
-    let bold = parse_font(BOLD, "bold")?;
-    let italic = parse_font(ITALIC, "italic")?;
-    let bold_italic = parse_font(BOLD_ITALIC, "bold-italic")?;
+/// Parses and registers `bytes` into the document, recording its glyph advances
+/// in `metrics`.
+fn add_font(
+    doc: &mut PdfDocument,
+    metrics: &mut Metrics,
+    bytes: &[u8],
+    label: &str,
+) -> Result<FontId> {
+    let id = doc.add_font(&parse_font(bytes, label)?);
+    if let Some(font_metrics) = FontMetrics::parse(bytes) {
+        metrics.insert(id.clone(), font_metrics);
+    }
+    Ok(id)
+}
+
+/// Reads, parses, and registers a font file from disk.
+fn load_font_file(doc: &mut PdfDocument, metrics: &mut Metrics, path: &Path) -> Result<FontId> {
+    let label = path.display().to_string();
+    let bytes = std::fs::read(path)
+        .map_err(|e| Error::Pdf(format!("font loading failed: {label}: {e}")))?;
+    add_font(doc, metrics, &bytes, &label)
+}
+
+/// Parses and registers all four JetBrains Mono variants into the PDF document,
+/// along with their glyph advances. The prose faces are JetBrains Mono too.
+pub fn load_fonts(doc: &mut PdfDocument) -> Result<FontSet> {
+    let mut metrics = Metrics::default();
+    let regular = add_font(doc, &mut metrics, REGULAR, "regular")?;
+    let bold = add_font(doc, &mut metrics, BOLD, "bold")?;
+    let italic = add_font(doc, &mut metrics, ITALIC, "italic")?;
+    let bold_italic = add_font(doc, &mut metrics, BOLD_ITALIC, "bold-italic")?;
 
     Ok(FontSet {
-        regular: doc.add_font(&regular),
-        bold: doc.add_font(&bold),
-        italic: doc.add_font(&italic),
-        bold_italic: doc.add_font(&bold_italic),
+        prose: regular.clone(),
+        prose_bold: bold.clone(),
+        prose_italic: italic.clone(),
+        regular,
+        bold,
+        italic,
+        bold_italic,
+        emoji: None,
+        metrics,
     })
 }
 
+/// The distinct characters of `text` that JetBrains Mono, the code font, has no
+/// glyph for, in order of first appearance. They print as `.notdef` boxes.
+/// Whitespace and control characters are never drawn and never reported.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::pdf::fonts::missing_code_glyphs;
+///
+/// assert!(missing_code_glyphs("fn main() {}\n").is_empty());
+/// assert_eq!(missing_code_glyphs("\u{10FFFD}a\u{10FFFD}"), ['\u{10FFFD}']);
+/// ```
+pub fn missing_code_glyphs(text: &str) -> Vec<char> {
+    static CODE: OnceLock<Option<FontMetrics>> = OnceLock::new();
+    let Some(code) = CODE.get_or_init(|| FontMetrics::parse(REGULAR)) else {
+        return vec![];
+    };
+    let mut seen = HashSet::new();
+    text.chars()
+        .filter(|&c| !c.is_whitespace() && !c.is_control() && !code.covers(c))
+        .filter(|&c| seen.insert(c))
+        .collect()
+}
+
+/// Like [`load_fonts`], but with the prose faces read from `prose`: a regular
+/// font, then optionally bold and italic ones (`--prose-font`). A missing bold
+/// or italic face falls back to the prose regular; an empty list keeps JetBrains
+/// Mono for prose.
+pub fn load_fonts_with_prose(doc: &mut PdfDocument, prose: &[PathBuf]) -> Result<FontSet> {
+    let mut fonts = load_fonts(doc)?;
+    let Some((regular, styled)) = prose.split_first() else {
+        return Ok(fonts);
+    };
+    fonts.prose = load_font_file(doc, &mut fonts.metrics, regular)?;
+    fonts.prose_bold = match styled.first() {
+        Some(path) => load_font_file(doc, &mut fonts.metrics, path)?,
+        None => fonts.prose.clone(),
+    };
+    fonts.prose_italic = match styled.get(1) {
+        Some(path) => load_font_file(doc, &mut fonts.metrics, path)?,
+        None => fonts.prose.clone(),
+    };
+    Ok(fonts)
+}
+
+/// Loads the fonts `config` asks for: the prose faces (see
+/// [`load_fonts_with_prose`]) and the emoji fallback (`--emoji-font`).
+pub fn load_configured_fonts(doc: &mut PdfDocument, config: &Config) -> Result<FontSet> {
+    let mut fonts = load_fonts_with_prose(doc, &config.prose_fonts)?;
+    if let Some(path) = &config.emoji_font {
+        fonts.emoji = Some(load_font_file(doc, &mut fonts.metrics, path)?);
+    }
+    Ok(fonts)
+}
+
+/// Whether `c` is an emoji or pictograph, or a joiner or selector inside an emoji
+/// sequence — characters `--emoji-font` is expected to draw.
+pub fn is_emoji(c: char) -> bool {
+    matches!(c,
+        '\u{200D}'
+        | '\u{20E3}'
+        | '\u{2300}'..='\u{23FF}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{FE0E}'..='\u{FE0F}'
+        | '\u{1F000}'..='\u{1FAFF}'
+        | '\u{E0020}'..='\u{E007F}')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,6 +146,60 @@ mod tests {
         assert!(load_fonts(&mut doc).is_ok());
     }
 
+    #[test]
+    fn prose_fonts_load_from_disk() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fonts");
+        let mut doc = PdfDocument::new("test");
+        let fonts =
+            load_fonts_with_prose(&mut doc, &[dir.join("JetBrainsMono-BoldItalic.ttf")]).unwrap();
+        assert!(fonts.prose != fonts.regular);
+        assert!(fonts.prose_bold == fonts.prose && fonts.prose_italic == fonts.prose);
+
+        let fonts = load_fonts_with_prose(&mut doc, &[]).unwrap();
+        assert!(fonts.prose == fonts.regular && fonts.prose_bold == fonts.bold);
+
+        let err = load_fonts_with_prose(&mut doc, &[dir.join("missing.ttf")]).unwrap_err();
+        assert!(err.to_string().contains("missing.ttf"));
+    }
+
+    #[test]
+    fn emoji_font_becomes_the_fallback() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fonts");
+        let mut doc = PdfDocument::new("test");
+        let mut config = Config::test_default();
+        assert!(
+            load_configured_fonts(&mut doc, &config)
+                .unwrap()
+                .emoji
+                .is_none()
+        );
+
+        config.emoji_font = Some(dir.join("JetBrainsMono-Italic.ttf"));
+        let fonts = load_configured_fonts(&mut doc, &config).unwrap();
+        let emoji = fonts.emoji.expect("emoji font");
+        assert!(emoji != fonts.regular);
+        assert_eq!(fonts.metrics.covers(&emoji, 'a'), Some(true));
+
+        config.emoji_font = Some(dir.join("missing.ttf"));
+        assert!(load_configured_fonts(&mut doc, &config).is_err());
+    }
+
+    #[test]
+    fn emoji_ranges_cover_common_sequences() {
+        assert!(
+            "\u{1F600}\u{1F44D}\u{1F3FD}\u{2764}\u{FE0F}\u{200D}"
+                .chars()
+                .all(is_emoji)
+        );
+        assert!(!"a\u{00E9}\u{2014}\u{6F22}".chars().any(is_emoji));
+    }
+
+    #[test]
+    fn missing_glyphs_are_reported_once_each() {
+        assert!(missing_code_glyphs("let café = \"naïve\";\t\r\n").is_empty());
+        assert_eq!(missing_code_glyphs("漢字 漢"), ['漢', '字']);
+    }
+
     #[test]
     fn embedded_font_bytes_are_substantial() {
         assert!(REGULAR.len() > 100_000);
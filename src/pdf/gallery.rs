@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use super::code;
+use super::layout::PageBuilder;
+use crate::highlight::Highlighter;
+
+/// Name the gallery's sample is highlighted as.
+pub const SAMPLE_PATH: &str = "sample.rs";
+
+/// A short Rust file touching the scopes themes color differently: comments,
+/// attributes, keywords, types, strings, numbers, macros, and lifetimes.
+pub const SAMPLE: &str = r#"//! Word frequency counter.
+
+use std::collections::HashMap;
+
+/// Words shorter than this are ignored.
+const MIN_LEN: usize = 3;
+
+#[derive(Debug, Default)]
+pub struct Counter<'a> {
+    counts: HashMap<&'a str, u32>,
+}
+
+impl<'a> Counter<'a> {
+    /// Counts every word in `text`.
+    pub fn add(&mut self, text: &'a str) {
+        for word in text.split_whitespace().filter(|w| w.len() >= MIN_LEN) {
+            *self.counts.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    pub fn top(&self, n: usize) -> Vec<(&'a str, u32)> {
+        let mut all: Vec<_> = self.counts.iter().map(|(w, c)| (*w, *c)).collect();
+        all.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        all.truncate(n);
+        all
+    }
+}
+
+fn main() {
+    let mut counter = Counter::default();
+    counter.add("the quick brown fox jumps over the lazy dog");
+    // Prints the three most frequent words.
+    for (word, count) in counter.top(3) {
+        println!("{word:>8}: {count}");
+    }
+    assert!(counter.top(0).is_empty(), "expected {} words", 0x0);
+}
+"#;
+
+/// Renders [`SAMPLE`] with `highlighter` on a page of its own, headed by the
+/// theme's name (`--theme-gallery`).
+pub fn render(builder: &mut PageBuilder, highlighter: &Highlighter, theme: &str, font_size: f32) {
+    let lines = highlighter.highlight_lines(SAMPLE, Path::new(SAMPLE_PATH));
+    code::render_file(
+        builder,
+        theme,
+        lines,
+        SAMPLE.lines().count(),
+        true,
+        0,
+        font_size,
+        false,
+        &format!("--theme \"{theme}\""),
+        None,
+        false,
+        &Default::default(),
+        false,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlight::list_themes;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn render_gives_each_theme_one_page() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let base = Highlighter::new("InspiredGitHub").unwrap();
+        let themes = list_themes();
+        themes.iter().for_each(|theme| {
+            render(&mut builder, &base.with_theme(theme).unwrap(), theme, 8.0);
+        });
+        assert_eq!(builder.finish().len(), themes.len());
+    }
+}
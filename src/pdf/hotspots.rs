@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use printpdf::{Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// Files listed on the hotspot page.
+pub const MAX_ROWS: usize = 30;
+
+/// Width of the longest bar, in block characters.
+const BAR_WIDTH: usize = 20;
+
+/// One file's churn over the `--hotspots` window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotspotEntry {
+    /// Path to the file relative to the repository root.
+    pub path: PathBuf,
+    /// Commits in the window that touched the file.
+    pub changes: usize,
+    /// Current number of lines in the file.
+    pub lines: usize,
+}
+
+/// The [`MAX_ROWS`] most frequently changed files, ties broken by size: a large
+/// file that changes often is the likelier trouble spot. Unchanged files are dropped.
+pub fn rank(mut entries: Vec<HotspotEntry>) -> Vec<HotspotEntry> {
+    entries.retain(|e| e.changes > 0);
+    entries.sort_by(|a, b| (b.changes, b.lines).cmp(&(a.changes, a.lines)));
+    entries.truncate(MAX_ROWS);
+    entries
+}
+
+/// Renders the hotspot table: one row per ranked file with its change count, a bar
+/// scaled to the busiest file, and its current line count.
+pub fn render(builder: &mut PageBuilder, since: &str, entries: &[HotspotEntry]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let bar_color = Color::Rgb(Rgb::new(0.8, 0.33, 0.2, None));
+
+    const SIZE: f32 = 7.0;
+
+    builder.set_role("H1");
+    builder.write_centered("Hotspots", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+    let caption = match entries.len() {
+        0 => format!("No file changed since {since}"),
+        _ => format!("Most frequently changed files since {since}"),
+    };
+    builder.write_line_centered(&[Span {
+        text: caption,
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    let max_changes = entries.iter().map(|e| e.changes).max().unwrap_or(1);
+    entries.iter().enumerate().for_each(|(i, entry)| {
+        let bar = "\u{2588}".repeat((entry.changes * BAR_WIDTH).div_ceil(max_changes));
+        builder.write_line_justified(
+            &[
+                Span {
+                    text: format!("{:>3}. ", i + 1),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                },
+                Span {
+                    text: crate::display_path(&entry.path),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: black.clone(),
+                },
+            ],
+            &[
+                Span {
+                    text: format!("{bar:<BAR_WIDTH$} "),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: bar_color.clone(),
+                },
+                Span {
+                    text: format!(
+                        "{:>4} change{} \u{00B7} {:>6} LOC",
+                        entry.changes,
+                        if entry.changes == 1 { " " } else { "s" },
+                        entry.lines
+                    ),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                },
+            ],
+        );
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn entry(path: &str, changes: usize, lines: usize) -> HotspotEntry {
+        HotspotEntry {
+            path: PathBuf::from(path),
+            changes,
+            lines,
+        }
+    }
+
+    #[test]
+    fn rank_orders_by_changes_then_size() {
+        let ranked = rank(vec![
+            entry("a.rs", 2, 10),
+            entry("b.rs", 5, 10),
+            entry("c.rs", 0, 900),
+            entry("d.rs", 2, 300),
+        ]);
+        let paths: Vec<_> = ranked.iter().map(|e| e.path.to_str().unwrap()).collect();
+        assert_eq!(paths, ["b.rs", "d.rs", "a.rs"]);
+    }
+
+    #[test]
+    fn rank_keeps_the_top_rows() {
+        let entries = (0..MAX_ROWS + 5).map(|n| entry("f.rs", n + 1, 1)).collect();
+        let ranked = rank(entries);
+        assert_eq!(ranked.len(), MAX_ROWS);
+        assert_eq!(ranked[0].changes, MAX_ROWS + 5);
+    }
+
+    #[test]
+    fn render_fits_one_page() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let entries: Vec<_> = (0..MAX_ROWS)
+            .map(|n| entry("src/f.rs", n + 1, 40))
+            .collect();
+        render(&mut builder, "1 year ago", &entries);
+        assert_eq!(builder.finish().len(), 1);
+    }
+}
@@ -0,0 +1,433 @@
+use printpdf::{
+    Actions, CurTransMat, Destination, Mm, Op, PaintMode, PdfPage, Pt, Rect, WindingOrder,
+};
+
+use crate::types::NUp;
+
+/// How logical pages are laid out on one physical sheet.
+#[derive(Debug, Clone, Copy)]
+struct Grid {
+    /// Logical page size in points.
+    page_w: f32,
+    page_h: f32,
+    /// Physical sheet size in points.
+    sheet_w: f32,
+    sheet_h: f32,
+    /// Physical sheet size as passed to `PdfPage::new`.
+    sheet: (Mm, Mm),
+    cols: usize,
+    rows: usize,
+}
+
+impl Grid {
+    /// Two logical pages side by side on a sheet with the page's axes swapped
+    /// (portrait A4 pages → landscape A4 sheet). Landscape pages stack vertically.
+    fn two_up(page_w: Mm, page_h: Mm) -> Self {
+        let (w, h) = (page_w.into_pt().0, page_h.into_pt().0);
+        let (cols, rows) = if w <= h { (2, 1) } else { (1, 2) };
+        Self {
+            page_w: w,
+            page_h: h,
+            sheet_w: h,
+            sheet_h: w,
+            sheet: (page_h, page_w),
+            cols,
+            rows,
+        }
+    }
+
+    /// Four logical pages in a 2×2 grid on a sheet of the same size and orientation.
+    fn four_up(page_w: Mm, page_h: Mm) -> Self {
+        let (w, h) = (page_w.into_pt().0, page_h.into_pt().0);
+        Self {
+            page_w: w,
+            page_h: h,
+            sheet_w: w,
+            sheet_h: h,
+            sheet: (page_w, page_h),
+            cols: 2,
+            rows: 2,
+        }
+    }
+
+    fn slots(&self) -> usize {
+        self.cols * self.rows
+    }
+
+    fn scale(&self) -> f32 {
+        let slot_w = self.sheet_w / self.cols as f32;
+        let slot_h = self.sheet_h / self.rows as f32;
+        (slot_w / self.page_w).min(slot_h / self.page_h)
+    }
+
+    /// Bottom-left origin of `slot` (row-major, top row first), centered in its cell.
+    fn origin(&self, slot: usize) -> (f32, f32) {
+        let (col, row) = (slot % self.cols, slot / self.cols);
+        let slot_w = self.sheet_w / self.cols as f32;
+        let slot_h = self.sheet_h / self.rows as f32;
+        let scale = self.scale();
+        let x = col as f32 * slot_w + (slot_w - self.page_w * scale) / 2.0;
+        let y = self.sheet_h - (row + 1) as f32 * slot_h + (slot_h - self.page_h * scale) / 2.0;
+        (x, y)
+    }
+}
+
+/// Saddle-stitch page order for a booklet of `page_count` pages.
+///
+/// The count is padded to a multiple of four with blanks (`None`). Each pair in the
+/// result is one side of a sheet, left then right; sides alternate front/back so a
+/// duplex print folded in half reads 1, 2, 3, … in order.
+fn booklet_order(page_count: usize) -> Vec<Option<usize>> {
+    let padded = page_count.div_ceil(4) * 4;
+    let page = |i: usize| (i < page_count).then_some(i);
+    (0..padded / 4)
+        .flat_map(|s| {
+            [
+                page(padded - 1 - 2 * s),
+                page(2 * s),
+                page(2 * s + 1),
+                page(padded - 2 - 2 * s),
+            ]
+        })
+        .collect()
+}
+
+/// Places logical pages onto sheets following `order`, one `grid.slots()` run per
+/// sheet. Each page's ops are inlined under a scale/translate transformation matrix
+/// and clipped to the page's own bounds, so nothing drawn past its edge spills into
+/// the neighbouring cell; link annotations (which live outside the content stream) are mapped by hand, and
+/// internal `Goto` targets are renumbered to the sheet that now holds the page.
+///
+/// The ops are inlined rather than wrapped in a form XObject per page: `q`/`cm`/`Q`
+/// keeps each page's graphics state to itself all the same, and the `--layers`
+/// pass, which rewrites the markers in each page's own content stream and
+/// registers them in that page's resources, keeps working on the sheets.
+fn compose(pages: Vec<PdfPage>, order: &[Option<usize>], grid: Grid) -> Vec<PdfPage> {
+    let sheet_of =
+        &order
+            .iter()
+            .enumerate()
+            .fold(vec![0; pages.len()], |mut sheet_of, (slot, page)| {
+                if let Some(&i) = page.as_ref() {
+                    sheet_of[i] = slot / grid.slots() + 1;
+                }
+                sheet_of
+            });
+    let mut ops: Vec<Option<Vec<Op>>> = pages.into_iter().map(|p| Some(p.ops)).collect();
+    let scale = grid.scale();
+    // `re W n` in the page's own coordinates, right after its `cm`.
+    let clip = Op::DrawRectangle {
+        rectangle: Rect {
+            mode: Some(PaintMode::Clip),
+            winding_order: Some(WindingOrder::NonZero),
+            ..Rect::from_wh(Pt(grid.page_w), Pt(grid.page_h))
+        },
+    };
+
+    order
+        .chunks(grid.slots())
+        .map(|sheet| {
+            let sheet_ops = sheet
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, page)| Some((slot, ops.get_mut((*page)?)?.take()?)))
+                .flat_map(|(slot, page_ops)| {
+                    let (x, y) = grid.origin(slot);
+                    std::iter::once(Op::SaveGraphicsState)
+                        .chain(std::iter::once(Op::SetTransformationMatrix {
+                            matrix: CurTransMat::Raw([scale, 0.0, 0.0, scale, x, y]),
+                        }))
+                        .chain(std::iter::once(clip.clone()))
+                        .chain(
+                            page_ops
+                                .into_iter()
+                                .map(move |op| place_link(op, x, y, scale, sheet_of)),
+                        )
+                        .chain(std::iter::once(Op::RestoreGraphicsState))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            PdfPage::new(grid.sheet.0, grid.sheet.1, sheet_ops)
+        })
+        .collect()
+}
+
+/// Moves a link annotation into its slot on the sheet; other ops pass through.
+fn place_link(op: Op, x: f32, y: f32, scale: f32, sheet_of: &[usize]) -> Op {
+    let Op::LinkAnnotation { mut link } = op else {
+        return op;
+    };
+    link.rect = Rect::from_xywh(
+        Pt(x + link.rect.x.0 * scale),
+        Pt(y + link.rect.y.0 * scale),
+        Pt(link.rect.width.0 * scale),
+        Pt(link.rect.height.0 * scale),
+    );
+    if let Actions::Goto(Destination::Xyz { page, .. }) = &mut link.actions {
+        *page = sheet_of
+            .get(page.saturating_sub(1))
+            .copied()
+            .unwrap_or(*page);
+    }
+    Op::LinkAnnotation { link }
+}
+
+/// Reorders pages into saddle-stitch signatures and 2-ups them onto sheets
+/// (`--booklet`). Print the result duplex, flipping on the short edge, then fold.
+pub fn booklet(pages: Vec<PdfPage>, page_w: Mm, page_h: Mm) -> Vec<PdfPage> {
+    let order = booklet_order(pages.len());
+    compose(pages, &order, Grid::two_up(page_w, page_h))
+}
+
+/// Places `per_sheet` consecutive pages on each sheet (`--nup`), scaled to fit.
+pub fn nup(pages: Vec<PdfPage>, page_w: Mm, page_h: Mm, per_sheet: NUp) -> Vec<PdfPage> {
+    let grid = match per_sheet {
+        NUp::Two => Grid::two_up(page_w, page_h),
+        NUp::Four => Grid::four_up(page_w, page_h),
+    };
+    let order: Vec<_> = (0..pages.len()).map(Some).collect();
+    compose(pages, &order, grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn booklet_order_eight_pages() {
+        let order: Vec<_> = booklet_order(8).into_iter().map(|p| p.unwrap()).collect();
+        // Sheet 1: front 8|1, back 2|7; sheet 2: front 6|3, back 4|5 (1-based).
+        assert_eq!(order, vec![7, 0, 1, 6, 5, 2, 3, 4]);
+    }
+
+    #[test]
+    fn booklet_order_pads_with_blanks() {
+        let order = booklet_order(5);
+        assert_eq!(order.len(), 8);
+        assert_eq!(order.iter().filter(|p| p.is_none()).count(), 3);
+        assert_eq!(order[1], Some(0));
+    }
+
+    #[test]
+    fn booklet_order_empty() {
+        assert!(booklet_order(0).is_empty());
+    }
+
+    #[test]
+    fn two_up_portrait_is_side_by_side() {
+        let grid = Grid::two_up(Mm(210.0), Mm(297.0));
+        assert_eq!((grid.cols, grid.rows), (2, 1));
+        assert!(grid.sheet_w > grid.sheet_h);
+        let (left, _) = grid.origin(0);
+        let (right, _) = grid.origin(1);
+        assert!(right > left);
+        // Scaled page fits in half the sheet.
+        assert!(grid.page_w * grid.scale() <= grid.sheet_w / 2.0 + 0.01);
+    }
+
+    #[test]
+    fn four_up_grid_fills_quadrants() {
+        let grid = Grid::four_up(Mm(210.0), Mm(297.0));
+        assert_eq!(grid.slots(), 4);
+        assert!((grid.scale() - 0.5).abs() < 0.001);
+        let (x0, y0) = grid.origin(0);
+        let (x3, y3) = grid.origin(3);
+        // Slot 0 is top-left, slot 3 bottom-right.
+        assert!(x3 > x0);
+        assert!(y3 < y0);
+    }
+
+    #[test]
+    fn nup_page_counts() {
+        let make = |n: usize| -> Vec<PdfPage> {
+            (0..n)
+                .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+                .collect()
+        };
+        assert_eq!(nup(make(5), Mm(210.0), Mm(297.0), NUp::Two).len(), 3);
+        assert_eq!(nup(make(5), Mm(210.0), Mm(297.0), NUp::Four).len(), 2);
+        assert!(nup(make(0), Mm(210.0), Mm(297.0), NUp::Four).is_empty());
+    }
+
+    #[test]
+    fn link_targets_follow_their_page() {
+        let make = |n: usize| -> Vec<PdfPage> {
+            (0..n)
+                .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+                .collect()
+        };
+        let mut pages = make(4);
+        pages[0].ops.push(Op::LinkAnnotation {
+            link: printpdf::LinkAnnotation::new(
+                Rect::from_xywh(Pt(10.0), Pt(10.0), Pt(100.0), Pt(10.0)),
+                Actions::Goto(Destination::Xyz {
+                    page: 4,
+                    left: None,
+                    top: None,
+                    zoom: None,
+                }),
+                None,
+                None,
+                None,
+            ),
+        });
+        let sheets = nup(pages, Mm(210.0), Mm(297.0), NUp::Two);
+        let target = sheets[0].ops.iter().find_map(|op| match op {
+            Op::LinkAnnotation { link } => match &link.actions {
+                Actions::Goto(Destination::Xyz { page, .. }) => Some(*page),
+                _ => None,
+            },
+            _ => None,
+        });
+        // Logical page 4 lands on sheet 2.
+        assert_eq!(target, Some(2));
+    }
+
+    /// A page whose only content is `text` at a fixed cursor.
+    fn text_page(text: &str) -> PdfPage {
+        let ops = vec![
+            Op::StartTextSection,
+            Op::SetTextCursor {
+                pos: printpdf::Point {
+                    x: Pt(72.0),
+                    y: Pt(700.0),
+                },
+            },
+            Op::ShowText {
+                items: vec![printpdf::TextItem::Text(text.to_string())],
+            },
+            Op::EndTextSection,
+        ];
+        PdfPage::new(Mm(210.0), Mm(297.0), ops)
+    }
+
+    /// Each page's text on `sheet`, in content-stream order, with the
+    /// transformation matrix it is drawn under.
+    fn placed_texts(sheet: &PdfPage) -> Vec<(String, [f32; 6])> {
+        let mut matrix = None;
+        sheet
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetTransformationMatrix {
+                    matrix: CurTransMat::Raw(m),
+                } => {
+                    matrix = Some(*m);
+                    None
+                }
+                Op::ShowText { items } => match items.as_slice() {
+                    [printpdf::TextItem::Text(t)] => Some((t.clone(), matrix?)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_content_survives_imposition() {
+        let pages: Vec<_> = (1..=3).map(|n| text_page(&format!("page {n}"))).collect();
+        let sheets = nup(pages, Mm(210.0), Mm(297.0), NUp::Two);
+        let grid = Grid::two_up(Mm(210.0), Mm(297.0));
+        let (scale, (left, y), (right, _)) = (grid.scale(), grid.origin(0), grid.origin(1));
+        assert_eq!(
+            placed_texts(&sheets[0]),
+            [
+                ("page 1".to_string(), [scale, 0.0, 0.0, scale, left, y]),
+                ("page 2".to_string(), [scale, 0.0, 0.0, scale, right, y]),
+            ]
+        );
+        assert_eq!(placed_texts(&sheets[1])[0].0, "page 3");
+        // Every page keeps its graphics state to itself.
+        sheets.iter().for_each(|sheet| {
+            let saves = sheet
+                .ops
+                .iter()
+                .filter(|op| matches!(op, Op::SaveGraphicsState))
+                .count();
+            let restores = sheet
+                .ops
+                .iter()
+                .filter(|op| matches!(op, Op::RestoreGraphicsState))
+                .count();
+            assert_eq!(saves, restores);
+        });
+
+        let pages: Vec<_> = (1..=4).map(|n| text_page(&format!("page {n}"))).collect();
+        let texts: Vec<Vec<String>> = booklet(pages, Mm(210.0), Mm(297.0))
+            .iter()
+            .map(|sheet| placed_texts(sheet).into_iter().map(|(t, _)| t).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            [["page 4", "page 1"], ["page 2", "page 3"]].map(|side| side.map(String::from))
+        );
+    }
+
+    #[test]
+    fn each_page_is_clipped_to_its_cell() {
+        // Text set past the right edge of the page would otherwise land in the
+        // next cell over.
+        let overflowing = PdfPage::new(
+            Mm(210.0),
+            Mm(297.0),
+            vec![
+                Op::StartTextSection,
+                Op::SetTextCursor {
+                    pos: printpdf::Point {
+                        x: Pt(Mm(210.0).into_pt().0 + 20.0),
+                        y: Pt(700.0),
+                    },
+                },
+                Op::ShowText {
+                    items: vec![printpdf::TextItem::Text("overflow".to_string())],
+                },
+                Op::EndTextSection,
+            ],
+        );
+        let sheets = nup(
+            vec![overflowing, text_page("page 2")],
+            Mm(210.0),
+            Mm(297.0),
+            NUp::Two,
+        );
+        let grid = Grid::two_up(Mm(210.0), Mm(297.0));
+
+        // Within each `q … Q`, the clip follows the `cm` and precedes the content.
+        let cells: Vec<&[Op]> = sheets[0]
+            .ops
+            .split(|op| matches!(op, Op::RestoreGraphicsState))
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        assert_eq!(cells.len(), 2);
+        cells.iter().for_each(|cell| {
+            assert!(matches!(cell[0], Op::SaveGraphicsState));
+            assert!(matches!(cell[1], Op::SetTransformationMatrix { .. }));
+            let Op::DrawRectangle { rectangle } = &cell[2] else {
+                panic!("expected a clip, got {:?}", cell[2]);
+            };
+            assert_eq!(
+                (
+                    rectangle.x.0,
+                    rectangle.y.0,
+                    rectangle.width.0,
+                    rectangle.height.0
+                ),
+                (0.0, 0.0, grid.page_w, grid.page_h)
+            );
+            assert_eq!(rectangle.mode, Some(PaintMode::Clip));
+            assert_eq!(rectangle.winding_order, Some(WindingOrder::NonZero));
+        });
+        assert!(cells[0].iter().any(|op| matches!(op, Op::ShowText { .. })));
+    }
+
+    #[test]
+    fn booklet_produces_one_sheet_side_per_two_pages() {
+        let pages: Vec<_> = (0..6)
+            .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+            .collect();
+        let sheets = booklet(pages, Mm(210.0), Mm(297.0));
+        // 6 pages pad to 8 → 4 sheet sides.
+        assert_eq!(sheets.len(), 4);
+    }
+}
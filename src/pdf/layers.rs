@@ -0,0 +1,247 @@
+use lopdf::{Dictionary, Document, Object, ObjectId, dictionary, text_string};
+
+use crate::error::Result;
+
+/// An optional content group: content a PDF viewer can show or hide from its layers
+/// panel without the document being regenerated (`--layers`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// The line-number gutter beside source code.
+    LineNumbers,
+    /// The gray gutter boxes of `--age-shading`.
+    LineAges,
+}
+
+impl Layer {
+    /// Every layer, in the order viewers list them.
+    pub const ALL: [Self; 2] = [Self::LineNumbers, Self::LineAges];
+
+    /// Marked-content tag [`super::layout::PageBuilder::begin_layer`] writes around
+    /// the layer's content.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::LineNumbers => "LayerLineNumbers",
+            Self::LineAges => "LayerLineAges",
+        }
+    }
+
+    /// Name shown in the viewer's layers panel.
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::LineNumbers => "Line numbers",
+            Self::LineAges => "Line age shading",
+        }
+    }
+}
+
+/// Turns the layer markers written by [`super::layout::PageBuilder::begin_layer`]
+/// into optional content groups.
+///
+/// printpdf has no optional-content support, so this runs on the serialized bytes:
+/// - every `BMC /<tag>` of a [`Layer`] becomes `BDC /OC /<tag>`, and the page's
+///   `/Resources /Properties` maps that name to the layer's `/OCG` dictionary;
+/// - the catalog gets `/OCProperties` listing the layers that appear, all shown by
+///   default.
+///
+/// A document without layer markers is returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if `pdf` cannot be parsed or a page's content stream cannot be
+/// decoded or re-encoded.
+pub fn add_optional_content(pdf: &[u8]) -> Result<Vec<u8>> {
+    let mut doc = Document::load_mem(pdf)?;
+    let groups: Vec<(Layer, ObjectId)> = Layer::ALL
+        .iter()
+        .map(|&layer| (layer, doc.new_object_id()))
+        .collect();
+    let mut used: Vec<(Layer, ObjectId)> = Vec::new();
+    let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+
+    for page_id in pages {
+        let mut content = doc.get_and_decode_page_content(page_id)?;
+        let mut properties = Dictionary::new();
+
+        for op in content
+            .operations
+            .iter_mut()
+            .filter(|op| op.operator == "BMC")
+        {
+            let Some(&(layer, group_id)) = op
+                .operands
+                .first()
+                .and_then(|o| o.as_name().ok())
+                .and_then(|name| groups.iter().find(|(l, _)| l.tag().as_bytes() == name))
+            else {
+                continue;
+            };
+            op.operator = "BDC".to_string();
+            op.operands = vec![
+                Object::Name(b"OC".to_vec()),
+                Object::Name(layer.tag().into()),
+            ];
+            properties.set(layer.tag(), group_id);
+            if !used.iter().any(|(l, _)| *l == layer) {
+                used.push((layer, group_id));
+            }
+        }
+
+        if properties.is_empty() {
+            continue;
+        }
+        doc.change_page_content(page_id, content.encode()?)?;
+        add_page_properties(&mut doc, page_id, properties)?;
+    }
+
+    if used.is_empty() {
+        return Ok(pdf.to_vec());
+    }
+    used.sort_by_key(|(layer, _)| Layer::ALL.iter().position(|l| l == layer));
+    for &(layer, group_id) in &used {
+        doc.objects.insert(
+            group_id,
+            dictionary! { "Type" => "OCG", "Name" => text_string(layer.title()) }.into(),
+        );
+    }
+    let refs: Vec<Object> = used.iter().map(|&(_, id)| Object::Reference(id)).collect();
+    doc.catalog_mut()?.set(
+        "OCProperties",
+        dictionary! {
+            "OCGs" => refs.clone(),
+            "D" => dictionary! { "Order" => refs.clone(), "ON" => refs },
+        },
+    );
+    // Optional content arrived in PDF 1.5.
+    if doc.version.as_str() < "1.5" {
+        doc.version = "1.5".to_string();
+    }
+
+    let mut out = Vec::with_capacity(pdf.len());
+    doc.save_to(&mut out)?;
+    Ok(out)
+}
+
+/// Adds `properties` to the `/Properties` of the page's resource dictionary, which
+/// may be inline or a reference.
+fn add_page_properties(
+    doc: &mut Document,
+    page_id: ObjectId,
+    properties: Dictionary,
+) -> Result<()> {
+    let shared = doc
+        .get_dictionary(page_id)?
+        .get(b"Resources")
+        .and_then(Object::as_reference)
+        .ok();
+    let resources = match shared {
+        Some(id) => doc.get_dictionary_mut(id)?,
+        None => {
+            let page = doc.get_dictionary_mut(page_id)?;
+            if !page.has(b"Resources") {
+                page.set("Resources", Dictionary::new());
+            }
+            page.get_mut(b"Resources")?.as_dict_mut()?
+        }
+    };
+    match resources
+        .get_mut(b"Properties")
+        .and_then(Object::as_dict_mut)
+    {
+        Ok(existing) => existing.extend(&properties),
+        Err(_) => resources.set("Properties", properties),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{
+        Stream,
+        content::{Content, Operation},
+    };
+
+    /// Builds a one-page PDF whose content carries the given BMC tags.
+    fn pdf_with_tags(tags: &[&str]) -> Vec<u8> {
+        let mut doc = Document::with_version("1.3");
+        let pages_id = doc.new_object_id();
+        let operations: Vec<_> = tags
+            .iter()
+            .flat_map(|tag| {
+                [
+                    Operation::new("BMC", vec![Object::Name(tag.as_bytes().to_vec())]),
+                    Operation::new("EMC", vec![]),
+                ]
+            })
+            .collect();
+        let content_id = doc.add_object(Stream::new(
+            dictionary! {},
+            Content { operations }.encode().unwrap(),
+        ));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "Resources" => dictionary! {},
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+        });
+        doc.objects.insert(
+            pages_id,
+            dictionary! { "Type" => "Pages", "Kids" => vec![page_id.into()], "Count" => 1 }.into(),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn layer_markers_become_optional_content() {
+        let pdf = pdf_with_tags(&["LayerLineNumbers", "P", "LayerLineNumbers"]);
+        let doc = Document::load_mem(&add_optional_content(&pdf).unwrap()).unwrap();
+
+        let groups = doc
+            .catalog()
+            .unwrap()
+            .get(b"OCProperties")
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get(b"OCGs")
+            .and_then(Object::as_array)
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert!(doc.version.as_str() >= "1.5");
+
+        let page_id = *doc.get_pages().get(&1).unwrap();
+        let content = doc.get_and_decode_page_content(page_id).unwrap();
+        let operators: Vec<&str> = content
+            .operations
+            .iter()
+            .map(|op| op.operator.as_str())
+            .collect();
+        assert_eq!(operators, ["BDC", "EMC", "BMC", "EMC", "BDC", "EMC"]);
+
+        let properties = doc
+            .get_dictionary(page_id)
+            .unwrap()
+            .get(b"Resources")
+            .and_then(Object::as_dict)
+            .unwrap()
+            .get(b"Properties")
+            .and_then(Object::as_dict)
+            .unwrap();
+        assert_eq!(properties.get(b"LayerLineNumbers").unwrap(), &groups[0]);
+    }
+
+    #[test]
+    fn documents_without_layers_are_unchanged() {
+        let pdf = pdf_with_tags(&["P", "Artifact"]);
+        assert_eq!(add_optional_content(&pdf).unwrap(), pdf);
+    }
+
+    #[test]
+    fn invalid_pdf_errors() {
+        assert!(add_optional_content(b"not a pdf").is_err());
+    }
+}
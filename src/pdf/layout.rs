@@ -4,6 +4,11 @@ use printpdf::{
     graphics::Point,
 };
 
+use super::layers::Layer;
+use super::metrics::Metrics;
+use crate::locale::Locale;
+use crate::types::ColorMode;
+
 /// A styled text span within a line.
 pub struct Span {
     /// The text content of this span.
@@ -27,6 +32,157 @@ pub struct FontSet {
     pub italic: FontId,
     /// Bold-italic font handle.
     pub bold_italic: FontId,
+    /// Face for the cover, TOC, headers, and Markdown prose (`--prose-font`);
+    /// the same as `regular` unless a proportional font is configured.
+    pub prose: FontId,
+    /// Bold prose face, for titles and headings.
+    pub prose_bold: FontId,
+    /// Italic prose face, for quotes.
+    pub prose_italic: FontId,
+    /// Fallback for characters the faces above have no glyph for, typically
+    /// emoji (`--emoji-font`).
+    pub emoji: Option<FontId>,
+    /// Glyph advances of the faces above, for measuring text.
+    pub metrics: Metrics,
+}
+
+/// Page-number style printed in each page header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageNumbering {
+    /// 1, 2, 3, … — body pages.
+    Arabic,
+    /// i, ii, iii, … — front matter (cover, TOC, tree).
+    Roman,
+}
+
+/// Formats `n` as a lowercase roman numeral (`4` → `"iv"`). Returns `"0"` for zero.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::pdf::layout::to_roman;
+///
+/// assert_eq!(to_roman(1), "i");
+/// assert_eq!(to_roman(14), "xiv");
+/// assert_eq!(to_roman(1999), "mcmxcix");
+/// ```
+pub fn to_roman(n: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    if n == 0 {
+        return "0".to_string();
+    }
+    NUMERALS
+        .iter()
+        .fold(
+            (String::new(), n),
+            |(mut out, mut rest), &(value, numeral)| {
+                while rest >= value {
+                    out.push_str(numeral);
+                    rest -= value;
+                }
+                (out, rest)
+            },
+        )
+        .0
+}
+
+/// Stand-in for the document's total page count in "Page X of Y" footers.
+///
+/// Pages are produced by several builders before the final count is known, so the
+/// footer is written with this placeholder and patched by [`stamp_page_totals`].
+pub const PAGE_TOTAL_PLACEHOLDER: &str = "{total}";
+
+/// Marked-content tag around each "Page X of Y" footer, so [`stamp_page_totals`]
+/// patches the footer alone and never a code line that happens to contain
+/// [`PAGE_TOTAL_PLACEHOLDER`]. Stamping removes it again.
+const PAGE_TOTAL_TAG: &str = "GitprintPageTotal";
+
+/// Half the advance width of one 7 pt header/footer character, used for centering.
+const MARKER_HALF_CHAR_WIDTH: f32 = 2.5;
+
+/// Space in points between a margin note and the page edge or text column.
+const MARGIN_NOTE_GAP: f32 = 2.0;
+
+/// Distance in points between the rules of a note column or note page: about
+/// 7 mm, college-ruled paper.
+const NOTE_RULE_SPACING: f32 = 20.0;
+
+/// Space in points between the text column and a note column's edge rule.
+const NOTE_COLUMN_GAP: f32 = 6.0;
+
+/// Replaces [`PAGE_TOTAL_PLACEHOLDER`] in every page's footer with `total`,
+/// shifting the footer so it stays centered. Only the footer the builder wrote
+/// is touched; page text elsewhere is left as it is.
+///
+/// Call once on the fully assembled page list, right before adding it to the document.
+pub fn stamp_page_totals(pages: &mut [PdfPage], total: usize) {
+    let total = total.to_string();
+    let shift = (PAGE_TOTAL_PLACEHOLDER.len() as f32 - total.len() as f32) * MARKER_HALF_CHAR_WIDTH;
+    pages.iter_mut().for_each(|page| {
+        let mut in_footer = false;
+        let mut last_cursor = None;
+        let mut ops = Vec::with_capacity(page.ops.len());
+        for mut op in std::mem::take(&mut page.ops) {
+            match &mut op {
+                Op::BeginMarkedContent { tag } if tag == PAGE_TOTAL_TAG => {
+                    in_footer = true;
+                    continue;
+                }
+                Op::EndMarkedContent if in_footer => {
+                    in_footer = false;
+                    continue;
+                }
+                Op::SetTextCursor { .. } if in_footer => last_cursor = Some(ops.len()),
+                Op::ShowText { items } if in_footer => {
+                    items.iter_mut().for_each(|item| {
+                        if let TextItem::Text(t) = item {
+                            *t = t.replace(PAGE_TOTAL_PLACEHOLDER, &total);
+                        }
+                    });
+                    if let Some(Op::SetTextCursor { pos }) =
+                        last_cursor.and_then(|c| ops.get_mut(c))
+                    {
+                        pos.x = Pt(pos.x.0 + shift);
+                    }
+                }
+                _ => {}
+            }
+            ops.push(op);
+        }
+        page.ops = ops;
+    });
+}
+
+/// Applies `mode` to an RGB color; other color spaces pass through untouched.
+fn recolor(col: &Color, mode: ColorMode) -> Color {
+    match col {
+        Color::Rgb(rgb) => {
+            let (r, g, b) = mode.apply(rgb.r, rgb.g, rgb.b);
+            Color::Rgb(Rgb::new(r, g, b, None))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Lines of `line_height` points that fit between the top and bottom margins of a
+/// page `page_height` tall: the arithmetic [`PageBuilder`] breaks pages on.
+pub fn lines_per_page(page_height: Mm, margin: Mm, line_height: f32) -> usize {
+    let usable = page_height.into_pt().0 - 2.0 * margin.into_pt().0;
+    ((usable / line_height.max(0.1)) as usize).max(1)
 }
 
 /// Builds PDF pages with simple top-to-bottom text layout.
@@ -45,6 +201,38 @@ pub struct PageBuilder {
     page_count: usize,
     pending_break: bool,
     fonts: FontSet,
+    numbering: PageNumbering,
+    /// Physical page number that is labelled "1" (or "i") in the header.
+    first_numbered_page: usize,
+    /// Emit a "Page X of Y" footer (see [`stamp_page_totals`]).
+    total_footer: bool,
+    /// Left-aligned footer text identifying the snapshot (commit hash and date).
+    footer_stamp: Option<String>,
+    /// Put the section title in the page header (`"src/lib.rs — p. 3"`).
+    running_header: bool,
+    /// Physical page of the table of contents, which file headers link back to.
+    toc_page: Option<usize>,
+    /// Section being written, e.g. the file path; see [`Self::set_section`].
+    section: Option<String>,
+    /// Section in progress at the top of the current page, shown in its header.
+    page_section: Option<String>,
+    color_mode: ColorMode,
+    /// Wrap content in marked-content sequences for a tagged PDF.
+    tagged: bool,
+    /// Structure role for text written while `tagged` is set.
+    role: &'static str,
+    /// Wrap gutter content in optional content layers (`--layers`).
+    layers: bool,
+    /// Extra points between characters in left-aligned lines (`--letter-spacing`).
+    letter_spacing: f32,
+    /// Conventions for dates and numbers written by the page renderers.
+    locale: Locale,
+    /// Factor on the fixed gaps renderers leave with [`Self::gap`] (`--density`).
+    gap_scale: f32,
+    /// Points at the right of the text column kept blank and ruled for notes.
+    note_column: f32,
+    /// Follow every page with a lined page for notes.
+    note_pages: bool,
 }
 
 impl PageBuilder {
@@ -57,7 +245,7 @@ impl PageBuilder {
         fonts: FontSet,
         starting_page: usize,
     ) -> Self {
-        let mut builder = Self {
+        Self {
             pages: Vec::new(),
             current_ops: Vec::new(),
             y: 0.0,
@@ -65,23 +253,217 @@ impl PageBuilder {
             page_height,
             margin,
             line_height,
-            page_count: starting_page.saturating_sub(1),
+            page_count: starting_page.max(1),
             pending_break: false,
             fonts,
-        };
-        builder.start_new_page();
-        builder
+            numbering: PageNumbering::Arabic,
+            first_numbered_page: 1,
+            total_footer: false,
+            footer_stamp: None,
+            running_header: false,
+            toc_page: None,
+            section: None,
+            page_section: None,
+            color_mode: ColorMode::Color,
+            tagged: false,
+            role: "P",
+            layers: false,
+            letter_spacing: 0.0,
+            locale: Locale::Neutral,
+            gap_scale: 1.0,
+            note_column: 0.0,
+            note_pages: false,
+        }
+    }
+
+    /// Wraps every text run in a marked-content sequence named after the current
+    /// structure role (see [`Self::set_role`]) and page furniture — headers,
+    /// footers, rules, fills — in `Artifact`. [`super::tagged::add_structure_tree`]
+    /// turns these into the document's structure tree after saving.
+    pub fn with_tagging(mut self, enabled: bool) -> Self {
+        self.tagged = enabled;
+        self
+    }
+
+    /// Sets the structure role (`"P"`, `"H1"`, `"Figure"`, …) for subsequent text.
+    /// Has no effect unless tagging is enabled.
+    pub fn set_role(&mut self, role: &'static str) {
+        self.role = role;
+    }
+
+    fn begin_marked(&mut self, tag: &str) {
+        if self.tagged {
+            self.current_ops.push(Op::BeginMarkedContent {
+                tag: tag.to_string(),
+            });
+        }
+    }
+
+    fn end_marked(&mut self) {
+        if self.tagged {
+            self.current_ops.push(Op::EndMarkedContent);
+        }
+    }
+
+    /// Marks content written between [`Self::begin_layer`] and [`Self::end_layer`]
+    /// as belonging to a [`Layer`]. [`super::layers::add_optional_content`] turns
+    /// these into optional content groups after saving.
+    pub fn with_layers(mut self, enabled: bool) -> Self {
+        self.layers = enabled;
+        self
+    }
+
+    /// Starts content on `layer`. Has no effect unless layers are enabled.
+    pub fn begin_layer(&mut self, layer: Layer) {
+        if self.layers {
+            self.current_ops.push(Op::BeginMarkedContent {
+                tag: layer.tag().to_string(),
+            });
+        }
+    }
+
+    /// Ends the content started by [`Self::begin_layer`].
+    pub fn end_layer(&mut self) {
+        if self.layers {
+            self.current_ops.push(Op::EndMarkedContent);
+        }
+    }
+
+    /// Adds `pt` points between characters of text written with [`Self::write_line`]
+    /// and its background variants; centered and justified text is unaffected.
+    pub fn with_letter_spacing(mut self, pt: f32) -> Self {
+        self.letter_spacing = pt;
+        self
+    }
+
+    /// Extra points between characters set by [`Self::with_letter_spacing`].
+    pub fn letter_spacing(&self) -> f32 {
+        self.letter_spacing
+    }
+
+    /// Sets the locale the cover and TOC format dates, numbers, and labels in.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Locale set by [`Self::with_locale`].
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Scales the gaps left with [`Self::gap`] by `scale`: below 1 for denser
+    /// pages, above for airier ones.
+    pub fn with_gap_scale(mut self, scale: f32) -> Self {
+        self.gap_scale = scale;
+        self
+    }
+
+    /// Factor set by [`Self::with_gap_scale`].
+    pub fn gap_scale(&self) -> f32 {
+        self.gap_scale
+    }
+
+    /// Narrows the text column by `width` and rules the strip it frees, at the right
+    /// of every page, for handwritten notes. Everything that measures against
+    /// [`Self::usable_width_pt`] — code wrapping, backgrounds, right-aligned text —
+    /// follows the narrower column.
+    pub fn with_note_column(mut self, width: Mm) -> Self {
+        self.note_column = width.into_pt().0;
+        self
+    }
+
+    /// Follows every page with a lined page for notes. Note pages are numbered
+    /// like any other, so page references and the TOC stay right.
+    pub fn with_note_pages(mut self, enabled: bool) -> Self {
+        self.note_pages = enabled;
+        self
+    }
+
+    /// Sets the color transform applied to every fill and stroke color on the
+    /// page — syntax tokens and the fixed cover/TOC palette alike.
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Enables a "Page X of Y" footer on every page. X is the physical page number;
+    /// Y is written as [`PAGE_TOTAL_PLACEHOLDER`] until [`stamp_page_totals`] runs.
+    pub fn with_total_footer(mut self, enabled: bool) -> Self {
+        self.total_footer = enabled;
+        self
+    }
+
+    /// Prints `stamp` (e.g. the short commit hash and generation date) at the left
+    /// of every page's footer, so a loose page can be traced to its snapshot.
+    pub fn with_footer_stamp(mut self, stamp: Option<String>) -> Self {
+        self.footer_stamp = stamp;
+        self
+    }
+
+    /// Records the physical page of the table of contents, so file headers can link
+    /// back to it (see [`super::code::render_file`]).
+    pub fn with_toc_page(mut self, page: Option<usize>) -> Self {
+        self.toc_page = page;
+        self
+    }
+
+    /// Physical page of the table of contents, if the document has one.
+    pub fn toc_page(&self) -> Option<usize> {
+        self.toc_page
+    }
+
+    /// Shows the section in progress at the top of each page in its header, like a
+    /// dictionary's guide words: `"src/parser/mod.rs — p. 37"` instead of `"- 37 -"`.
+    pub fn with_running_header(mut self, enabled: bool) -> Self {
+        self.running_header = enabled;
+        self
+    }
+
+    /// Starts a new section (e.g. a file) titled `title`. A page begun inside the
+    /// section carries its title in the header when the running header is enabled.
+    pub fn set_section(&mut self, title: Option<String>) {
+        if !self.pending_break && self.y == 0.0 {
+            self.page_section.clone_from(&title);
+        }
+        self.section = title;
+    }
+
+    /// Sets the header numbering style. `first_numbered_page` is the physical page
+    /// labelled "1"/"i", so body pages can restart at 1 after roman front matter.
+    ///
+    /// Headers are stamped when each page is closed, so the style applies to every
+    /// page this builder produces.
+    pub fn with_numbering(mut self, numbering: PageNumbering, first_numbered_page: usize) -> Self {
+        self.numbering = numbering;
+        self.first_numbered_page = first_numbered_page;
+        self
     }
 
-    /// The page number currently being written, accounting for a pending deferred break.
+    /// The page number currently being written, accounting for a pending deferred
+    /// break and the note page that closing this one adds.
     pub fn current_page(&self) -> usize {
         if self.pending_break {
-            self.page_count + 1
+            self.page_count + 1 + usize::from(self.note_pages)
         } else {
             self.page_count
         }
     }
 
+    /// Header label for physical page `page` under this builder's numbering style.
+    pub fn page_label(&self, page: usize) -> String {
+        let n = page.saturating_sub(self.first_numbered_page) + 1;
+        match self.numbering {
+            PageNumbering::Arabic => n.to_string(),
+            PageNumbering::Roman => to_roman(n),
+        }
+    }
+
+    /// Header label of the page currently being written (see [`Self::current_page`]).
+    pub fn current_page_label(&self) -> String {
+        self.page_label(self.current_page())
+    }
+
     fn usable_height(&self) -> f32 {
         self.page_height.into_pt().0 - 2.0 * self.margin.into_pt().0
     }
@@ -99,28 +481,153 @@ impl PageBuilder {
     }
 
     fn start_new_page(&mut self) {
-        if !self.current_ops.is_empty() {
-            self.pages.push(PdfPage::new(
-                self.page_width,
-                self.page_height,
-                std::mem::take(&mut self.current_ops),
-            ));
-        }
-
+        self.close_page();
         self.page_count += 1;
         self.y = 0.0;
+        self.page_section.clone_from(&self.section);
+    }
 
-        let header_text = format!("- {} -", self.page_count);
-        let header_x = self.page_width.into_pt().0 / 2.0 - (header_text.len() as f32 * 2.5);
+    /// Stamps the page-number header (and optional "Page X of Y" footer) onto the
+    /// current page and moves it to `pages`.
+    fn close_page(&mut self) {
+        let label = self.page_label(self.page_count);
+        let header_text = match self.page_section.as_deref().filter(|_| self.running_header) {
+            Some(title) => {
+                let suffix = format!(" \u{2014} p. {label}");
+                let room = (self.usable_width_pt() / (2.0 * MARKER_HALF_CHAR_WIDTH)) as usize;
+                let room = room.saturating_sub(suffix.chars().count()).max(1);
+                let chars = title.chars().count();
+                if chars > room {
+                    let tail: String = title.chars().skip(chars - room + 1).collect();
+                    format!("\u{2026}{tail}{suffix}")
+                } else {
+                    format!("{title}{suffix}")
+                }
+            }
+            None => format!("- {label} -"),
+        };
         let header_y = self.page_height.into_pt().0 - self.margin.into_pt().0 + 2.0;
-        let header_font = self.fonts.regular.clone();
+        let header = self.page_marker_ops(header_text, header_y);
+        self.begin_marked("Artifact");
+        self.current_ops.extend(header);
+        if self.note_column > 0.0 {
+            let x = self.left_x().0 + self.usable_width_pt() + NOTE_COLUMN_GAP;
+            let ruling = self.ruling_ops(x, self.note_column - NOTE_COLUMN_GAP, true);
+            self.current_ops.extend(ruling);
+        }
 
-        self.current_ops.extend([
+        let footer_y = self.margin.into_pt().0 - 10.0;
+        if self.total_footer {
+            let footer_text = format!("Page {} of {PAGE_TOTAL_PLACEHOLDER}", self.page_count);
+            let footer = self.page_marker_ops(footer_text, footer_y);
+            self.current_ops.push(Op::BeginMarkedContent {
+                tag: PAGE_TOTAL_TAG.to_string(),
+            });
+            self.current_ops.extend(footer);
+            self.current_ops.push(Op::EndMarkedContent);
+        }
+        if let Some(stamp) = &self.footer_stamp {
+            let stamp = self.marker_ops(stamp.clone(), self.left_x().0, footer_y);
+            self.current_ops.extend(stamp);
+        }
+        self.end_marked();
+
+        if self.color_mode != ColorMode::Color {
+            let mode = self.color_mode;
+            self.current_ops.iter_mut().for_each(|op| match op {
+                Op::SetFillColor { col } | Op::SetOutlineColor { col } => {
+                    *col = recolor(col, mode);
+                }
+                _ => {}
+            });
+        }
+
+        self.pages.push(PdfPage::new(
+            self.page_width,
+            self.page_height,
+            std::mem::take(&mut self.current_ops),
+        ));
+        if self.note_pages {
+            self.add_note_page();
+        }
+    }
+
+    /// Appends a lined page for notes, numbered after the page just closed.
+    fn add_note_page(&mut self) {
+        self.page_count += 1;
+        let header_y = self.page_height.into_pt().0 - self.margin.into_pt().0 + 2.0;
+        let header = self.page_marker_ops(
+            format!("- {} -", self.page_label(self.page_count)),
+            header_y,
+        );
+        let ruling = self.ruling_ops(
+            self.left_x().0,
+            self.page_width.into_pt().0 - 2.0 * self.margin.into_pt().0,
+            false,
+        );
+        self.begin_marked("Artifact");
+        self.current_ops.extend(header);
+        self.current_ops.extend(ruling);
+        self.end_marked();
+        self.pages.push(PdfPage::new(
+            self.page_width,
+            self.page_height,
+            std::mem::take(&mut self.current_ops),
+        ));
+    }
+
+    /// Light gray rules [`NOTE_RULE_SPACING`] apart, `width` long from `x`, down the
+    /// page between the top and bottom margins; with `edge`, a vertical rule at `x`
+    /// sets them off from the text beside them.
+    fn ruling_ops(&self, x: f32, width: f32, edge: bool) -> Vec<Op> {
+        let top = self.page_height.into_pt().0 - self.margin.into_pt().0;
+        let bottom = self.margin.into_pt().0;
+        let rule = |(x1, y1): (f32, f32), (x2, y2): (f32, f32)| Op::DrawLine {
+            line: Line {
+                points: [(x1, y1), (x2, y2)]
+                    .into_iter()
+                    .map(|(x, y)| LinePoint {
+                        p: Point { x: Pt(x), y: Pt(y) },
+                        bezier: false,
+                    })
+                    .collect(),
+                is_closed: false,
+            },
+        };
+        let rules = ((top - bottom) / NOTE_RULE_SPACING) as usize;
+        let mut ops = vec![
+            Op::SaveGraphicsState,
+            Op::SetOutlineColor {
+                col: Color::Rgb(Rgb::new(0.78, 0.78, 0.78, None)),
+            },
+            Op::SetOutlineThickness { pt: Pt(0.4) },
+        ];
+        if edge {
+            ops.push(rule((x, bottom), (x, top)));
+        }
+        ops.extend((1..=rules).map(|i| {
+            let y = top - i as f32 * NOTE_RULE_SPACING;
+            rule((x, y), (x + width, y))
+        }));
+        ops.push(Op::RestoreGraphicsState);
+        ops
+    }
+
+    /// Small gray text centered horizontally at `y_pt`, used for page numbers.
+    fn page_marker_ops(&self, text: String, y_pt: f32) -> [Op; 6] {
+        let x = self.page_width.into_pt().0 / 2.0
+            - text.chars().count() as f32 * MARKER_HALF_CHAR_WIDTH;
+        self.marker_ops(text, x, y_pt)
+    }
+
+    /// Small gray text starting at (`x_pt`, `y_pt`), used for page furniture.
+    fn marker_ops(&self, text: String, x_pt: f32, y_pt: f32) -> [Op; 6] {
+        [
             Op::StartTextSection,
             Op::SetTextCursor {
                 pos: Point {
-                    x: Pt(header_x),
-                    y: Pt(header_y),
+                    x: Pt(x_pt),
+                    y: Pt(y_pt),
                 },
             },
             Op::SetFillColor {
@@ -128,13 +635,13 @@ impl PageBuilder {
             },
             Op::SetFont {
                 size: Pt(7.0),
-                font: PdfFontHandle::External(header_font.clone()),
+                font: PdfFontHandle::External(self.fonts.prose.clone()),
             },
             Op::ShowText {
-                items: vec![TextItem::Text(header_text)],
+                items: vec![TextItem::Text(text)],
             },
             Op::EndTextSection,
-        ]);
+        ]
     }
 
     /// Flush a deferred page break: start the new page now.
@@ -155,7 +662,7 @@ impl PageBuilder {
 
     /// Width in points available for text between the two margins.
     pub fn usable_width_pt(&self) -> f32 {
-        self.page_width.into_pt().0 - 2.0 * self.margin.into_pt().0
+        self.page_width.into_pt().0 - 2.0 * self.margin.into_pt().0 - self.note_column
     }
 
     /// The line height in points used by this builder.
@@ -163,11 +670,23 @@ impl PageBuilder {
         self.line_height
     }
 
+    /// Changes the line height for lines written from now on, e.g. for a file set in
+    /// a different font size.
+    pub fn set_line_height(&mut self, line_height: f32) {
+        self.line_height = line_height;
+    }
+
     /// Remaining vertical space in points on the current page.
     pub fn remaining_pt(&self) -> f32 {
         self.usable_height() - self.y
     }
 
+    /// True when the next write lands at the top of a page (nothing written yet, or a
+    /// page break is pending).
+    pub fn at_page_top(&self) -> bool {
+        self.pending_break || self.y == 0.0
+    }
+
     /// Emits an invisible link annotation covering the last `height_pt` of vertical space written.
     ///
     /// Must be called immediately after the text it should cover (e.g. `write_line*` or
@@ -178,6 +697,18 @@ impl PageBuilder {
     /// The ascender shift is clamped to one line height so multi-row spans don't
     /// shift the entire rect up by their full height.
     pub fn add_link(&mut self, height_pt: f32, action: Actions) {
+        self.add_link_at(0.0, self.usable_width_pt(), height_pt, action);
+    }
+
+    /// Like [`add_link`](Self::add_link), but covers only `width_pt` starting
+    /// `x_offset_pt` from the left margin — one span of the line just written.
+    pub fn add_link_at(
+        &mut self,
+        x_offset_pt: f32,
+        width_pt: f32,
+        height_pt: f32,
+        action: Actions,
+    ) {
         // In printpdf, text is placed at its baseline. Visual glyphs extend
         // ~0.7× above (ascenders) and ~0.2× below (descenders) a single line.
         // Shift up by 0.8× of one line so the rect covers what users see.
@@ -186,9 +717,9 @@ impl PageBuilder {
             self.page_height.into_pt().0 - self.margin.into_pt().0 - 12.0 - self.y + ascender_shift,
         );
         let rect = Rect::from_xywh(
-            self.left_x(),
+            Pt(self.left_x().0 + x_offset_pt),
             y_bottom,
-            Pt(self.usable_width_pt()),
+            Pt(width_pt),
             Pt(height_pt),
         );
         self.current_ops.push(Op::LinkAnnotation {
@@ -202,6 +733,24 @@ impl PageBuilder {
         });
     }
 
+    /// Sets aside `n` page numbers for pages produced by another builder and spliced
+    /// in after [`finish`](Self::finish) (chapter dividers). Writing resumes on a fresh
+    /// page after them. Returns the first reserved page number.
+    pub fn reserve_pages(&mut self, n: usize) -> usize {
+        let untouched = !self.pending_break && self.y == 0.0 && self.current_ops.is_empty();
+        let first = if untouched {
+            self.page_count
+        } else {
+            self.close_page();
+            self.page_count + 1
+        };
+        self.pending_break = false;
+        self.page_count = first + n;
+        self.y = 0.0;
+        self.page_section.clone_from(&self.section);
+        first
+    }
+
     /// Mark a section boundary. The new page is created lazily on the next write,
     /// so finish() never produces a trailing empty page.
     pub fn page_break(&mut self) {
@@ -210,7 +759,71 @@ impl PageBuilder {
 
     /// Writes a line of styled spans left-aligned at the current cursor position.
     pub fn write_line(&mut self, spans: &[Span]) {
+        self.write_line_with_background(spans, None);
+    }
+
+    /// Like [`write_line`](Self::write_line), but first fills the full line width
+    /// with `background` (when `Some`), so the text sits on a tinted band.
+    pub fn write_line_with_background(&mut self, spans: &[Span], background: Option<Color>) {
+        self.write_line_with_span_backgrounds(spans, background, &[]);
+    }
+
+    /// Like [`write_line_with_background`](Self::write_line_with_background), and
+    /// additionally boxes each span whose entry in `span_backgrounds` is `Some`.
+    /// Span widths come from the fonts' glyph advances, plus any letter spacing.
+    pub fn write_line_with_span_backgrounds(
+        &mut self,
+        spans: &[Span],
+        background: Option<Color>,
+        span_backgrounds: &[Option<Color>],
+    ) {
         self.ensure_space(self.line_height);
+        // Bands span from just below the descenders to the top of the line.
+        let (below, height) = (self.line_height * 0.3, self.line_height);
+        if let Some(color) = background {
+            let width = self.usable_width_pt();
+            self.draw_filled_rect(0.0, below, width, height, color);
+        }
+        spans
+            .iter()
+            .zip(span_backgrounds)
+            .fold(0.0, |x, (span, color)| {
+                let width = self.text_width(&span.text, &span.font_id, span.size)
+                    + span.text.chars().count() as f32 * self.letter_spacing;
+                if let Some(color) = color {
+                    self.draw_filled_rect(x, below, width, height, color.clone());
+                }
+                x + width
+            });
+        self.write_spans(spans, self.role);
+        self.y += self.line_height;
+    }
+
+    /// Writes the line-number `gutter` span over the start of the line just written,
+    /// which should open with blank space as wide, on a `shade` box when given. With
+    /// [`Self::with_layers`], the number and the box go on [`Layer::LineNumbers`] and
+    /// [`Layer::LineAges`], so viewers can hide them. The number is page furniture,
+    /// not part of the code's text.
+    pub fn write_gutter(&mut self, gutter: &Span, shade: Option<Color>) {
+        self.y -= self.line_height;
+        if let Some(color) = shade {
+            let width = self.text_width(&gutter.text, &gutter.font_id, gutter.size)
+                + gutter.text.chars().count() as f32 * self.letter_spacing;
+            self.begin_layer(Layer::LineAges);
+            self.draw_filled_rect(0.0, self.line_height * 0.3, width, self.line_height, color);
+            self.end_layer();
+        }
+        self.begin_layer(Layer::LineNumbers);
+        self.write_spans(std::slice::from_ref(gutter), "Artifact");
+        self.end_layer();
+        self.y += self.line_height;
+    }
+
+    /// Emits `spans` as one text run at the cursor, marked with `role`. Each span
+    /// is put in visual order on its own (see [`super::bidi::visual`]), so an RTL
+    /// comment or string reads right to left while the tokens around it stay put.
+    fn write_spans(&mut self, spans: &[Span], role: &str) {
+        self.begin_marked(role);
 
         self.current_ops.extend([
             Op::StartTextSection,
@@ -221,24 +834,27 @@ impl PageBuilder {
                 },
             },
         ]);
+        // Character spacing is text state that outlives the text section, so it is
+        // reset afterwards to keep it off centered and justified text.
+        let spaced = self.letter_spacing != 0.0;
+        if spaced {
+            self.current_ops.push(Op::SetCharacterSpacing {
+                multiplier: self.letter_spacing,
+            });
+        }
 
-        self.current_ops.extend(spans.iter().flat_map(|span| {
-            [
-                Op::SetFillColor {
-                    col: span.color.clone(),
-                },
-                Op::SetFont {
-                    size: span.size,
-                    font: PdfFontHandle::External(span.font_id.clone()),
-                },
-                Op::ShowText {
-                    items: vec![TextItem::Text(span.text.clone())],
-                },
-            ]
-        }));
+        let ops: Vec<Op> = spans
+            .iter()
+            .flat_map(|span| self.span_ops(span, &super::bidi::visual(&span.text)))
+            .collect();
+        self.current_ops.extend(ops);
 
+        if spaced {
+            self.current_ops
+                .push(Op::SetCharacterSpacing { multiplier: 0.0 });
+        }
         self.current_ops.push(Op::EndTextSection);
-        self.y += self.line_height;
+        self.end_marked();
     }
 
     /// Advances the cursor downward by `pt` points without writing any content.
@@ -246,13 +862,100 @@ impl PageBuilder {
         self.y += pt;
     }
 
+    /// Leaves a gap of `pt` points, scaled by [`Self::with_gap_scale`], between
+    /// headings, rows, and sections. Use [`Self::vertical_space`] for space that
+    /// has to be exact.
+    pub fn gap(&mut self, pt: f32) {
+        self.vertical_space(pt * self.gap_scale);
+    }
+
+    /// Width in points of `text` set in `font_id` at `size`, from the font's glyph
+    /// advances; letter spacing is not included.
+    pub fn text_width(&self, text: &str, font_id: &FontId, size: Pt) -> f32 {
+        self.font_runs(text, font_id)
+            .iter()
+            .map(|(run, font)| self.fonts.metrics.width(font, run, size.0))
+            .sum()
+    }
+
+    /// Splits `text` into runs of a single font: `font_id`, or the emoji fallback
+    /// (`--emoji-font`) for characters only it has a glyph for. Joiners and
+    /// variation selectors stay in the run before them, so an emoji sequence is
+    /// never split across fonts.
+    fn font_runs<'a>(&'a self, text: &'a str, font_id: &'a FontId) -> Vec<(&'a str, &'a FontId)> {
+        let metrics = &self.fonts.metrics;
+        let Some(emoji) = self.fonts.emoji.as_ref().filter(|emoji| *emoji != font_id) else {
+            return vec![(text, font_id)];
+        };
+        let mut runs = Vec::new();
+        let mut start = 0;
+        let mut current = font_id;
+        for (i, c) in text.char_indices() {
+            let font = if matches!(c, '\u{200D}' | '\u{FE0E}' | '\u{FE0F}') {
+                current
+            } else if metrics.covers(font_id, c) == Some(false)
+                && metrics.covers(emoji, c) == Some(true)
+            {
+                emoji
+            } else {
+                font_id
+            };
+            if font != current {
+                if i > start {
+                    runs.push((&text[start..i], current));
+                }
+                (start, current) = (i, font);
+            }
+        }
+        runs.push((&text[start..], current));
+        runs
+    }
+
+    /// `SetFont` and `ShowText` ops drawing `text` at `size`, one pair per font run
+    /// (see [`Self::font_runs`]).
+    fn text_ops(&self, text: &str, font_id: &FontId, size: Pt) -> Vec<Op> {
+        self.font_runs(text, font_id)
+            .into_iter()
+            .flat_map(|(run, font)| {
+                [
+                    Op::SetFont {
+                        size,
+                        font: PdfFontHandle::External(font.clone()),
+                    },
+                    Op::ShowText {
+                        items: vec![TextItem::Text(run.to_string())],
+                    },
+                ]
+            })
+            .collect()
+    }
+
+    /// Ops drawing `text` in the color, font, and size of `span` — its own text,
+    /// or a reordered copy.
+    fn span_ops(&self, span: &Span, text: &str) -> Vec<Op> {
+        let mut ops = vec![Op::SetFillColor {
+            col: span.color.clone(),
+        }];
+        ops.extend(self.text_ops(text, &span.font_id, span.size));
+        ops
+    }
+
+    /// Total width of `spans` laid end to end, without letter spacing.
+    fn spans_width(&self, spans: &[Span]) -> f32 {
+        spans
+            .iter()
+            .map(|s| self.text_width(&s.text, &s.font_id, s.size))
+            .sum()
+    }
+
     /// Writes a single string centered horizontally on the current line.
     pub fn write_centered(&mut self, text: &str, font_id: &FontId, size: Pt, color: Color) {
         self.ensure_space(size.0 + 4.0);
 
-        let text_width = text.len() as f32 * size.0 * 0.6;
+        let text_width = self.text_width(text, font_id, size);
         let x = (self.page_width.into_pt().0 - text_width) / 2.0;
 
+        self.begin_marked(self.role);
         self.current_ops.extend([
             Op::StartTextSection,
             Op::SetTextCursor {
@@ -262,15 +965,11 @@ impl PageBuilder {
                 },
             },
             Op::SetFillColor { col: color },
-            Op::SetFont {
-                size,
-                font: PdfFontHandle::External(font_id.clone()),
-            },
-            Op::ShowText {
-                items: vec![TextItem::Text(text.to_string())],
-            },
-            Op::EndTextSection,
         ]);
+        let ops = self.text_ops(text, font_id, size);
+        self.current_ops.extend(ops);
+        self.current_ops.push(Op::EndTextSection);
+        self.end_marked();
 
         self.y += size.0 + 4.0;
     }
@@ -280,33 +979,23 @@ impl PageBuilder {
         self.ensure_space(self.line_height);
         let y = self.pdf_y();
 
-        let total_width: f32 = spans
-            .iter()
-            .map(|s| s.text.len() as f32 * s.size.0 * 0.6)
-            .sum();
+        let total_width = self.spans_width(spans);
         let x = ((self.page_width.into_pt().0 - total_width) / 2.0).max(0.0);
 
+        self.begin_marked(self.role);
         self.current_ops.extend([
             Op::StartTextSection,
             Op::SetTextCursor {
                 pos: Point { x: Pt(x), y },
             },
         ]);
-        self.current_ops.extend(spans.iter().flat_map(|span| {
-            [
-                Op::SetFillColor {
-                    col: span.color.clone(),
-                },
-                Op::SetFont {
-                    size: span.size,
-                    font: PdfFontHandle::External(span.font_id.clone()),
-                },
-                Op::ShowText {
-                    items: vec![TextItem::Text(span.text.clone())],
-                },
-            ]
-        }));
+        let ops: Vec<Op> = spans
+            .iter()
+            .flat_map(|span| self.span_ops(span, &span.text))
+            .collect();
+        self.current_ops.extend(ops);
         self.current_ops.push(Op::EndTextSection);
+        self.end_marked();
         self.y += self.line_height;
     }
 
@@ -316,6 +1005,7 @@ impl PageBuilder {
         let y = self.pdf_y();
 
         // Left-aligned spans
+        self.begin_marked(self.role);
         self.current_ops.extend([
             Op::StartTextSection,
             Op::SetTextCursor {
@@ -325,28 +1015,16 @@ impl PageBuilder {
                 },
             },
         ]);
-        self.current_ops.extend(left.iter().flat_map(|span| {
-            [
-                Op::SetFillColor {
-                    col: span.color.clone(),
-                },
-                Op::SetFont {
-                    size: span.size,
-                    font: PdfFontHandle::External(span.font_id.clone()),
-                },
-                Op::ShowText {
-                    items: vec![TextItem::Text(span.text.clone())],
-                },
-            ]
-        }));
+        let ops: Vec<Op> = left
+            .iter()
+            .flat_map(|span| self.span_ops(span, &span.text))
+            .collect();
+        self.current_ops.extend(ops);
         self.current_ops.push(Op::EndTextSection);
 
         // Right-aligned spans
-        let right_width: f32 = right
-            .iter()
-            .map(|s| s.text.len() as f32 * s.size.0 * 0.6)
-            .sum();
-        let right_x = self.page_width.into_pt().0 - self.margin.into_pt().0 - right_width;
+        let right_width = self.spans_width(right);
+        let right_x = self.left_x().0 + self.usable_width_pt() - right_width;
 
         self.current_ops.extend([
             Op::StartTextSection,
@@ -357,21 +1035,13 @@ impl PageBuilder {
                 },
             },
         ]);
-        self.current_ops.extend(right.iter().flat_map(|span| {
-            [
-                Op::SetFillColor {
-                    col: span.color.clone(),
-                },
-                Op::SetFont {
-                    size: span.size,
-                    font: PdfFontHandle::External(span.font_id.clone()),
-                },
-                Op::ShowText {
-                    items: vec![TextItem::Text(span.text.clone())],
-                },
-            ]
-        }));
+        let ops: Vec<Op> = right
+            .iter()
+            .flat_map(|span| self.span_ops(span, &span.text))
+            .collect();
+        self.current_ops.extend(ops);
         self.current_ops.push(Op::EndTextSection);
+        self.end_marked();
 
         self.y += self.line_height;
     }
@@ -383,6 +1053,7 @@ impl PageBuilder {
         let y = self.pdf_y();
         let left = self.left_x();
         let right = Pt(left.0 + self.usable_width_pt());
+        self.begin_marked("Artifact");
         self.current_ops.extend([
             Op::SaveGraphicsState,
             Op::SetOutlineColor { col: color },
@@ -406,6 +1077,7 @@ impl PageBuilder {
             },
             Op::RestoreGraphicsState,
         ]);
+        self.end_marked();
         self.y += thickness_pt;
     }
 
@@ -446,12 +1118,86 @@ impl PageBuilder {
             mode: PaintMode::Fill,
             winding_order: WindingOrder::NonZero,
         };
+        self.begin_marked("Artifact");
+        self.current_ops.extend([
+            Op::SaveGraphicsState,
+            Op::SetFillColor { col: color },
+            Op::DrawPolygon { polygon },
+            Op::RestoreGraphicsState,
+        ]);
+        self.end_marked();
+    }
+
+    /// Draw a square grid of `columns` cells per row (row-major from the top-left),
+    /// filling the cells flagged in `cells` with `color` — QR codes and the like.
+    ///
+    /// Positioned like [`draw_filled_rect`](Self::draw_filled_rect): `x_offset_pt`
+    /// from the left margin, bottom edge `y_below_cursor_pt` below the cursor.
+    /// Adjacent filled cells in a row are merged into one rectangle.
+    ///
+    /// Does **not** advance `y`.
+    pub fn draw_cell_grid(
+        &mut self,
+        x_offset_pt: f32,
+        y_below_cursor_pt: f32,
+        cell_pt: f32,
+        columns: usize,
+        cells: &[bool],
+        color: Color,
+    ) {
+        self.flush_break();
+        let left = self.left_x().0 + x_offset_pt;
+        let rows = cells.len().div_ceil(columns.max(1));
+        let top = self.pdf_y().0 - y_below_cursor_pt + rows as f32 * cell_pt;
+        let lp = |px: f32, py: f32| LinePoint {
+            p: Point {
+                x: Pt(px),
+                y: Pt(py),
+            },
+            bezier: false,
+        };
+        let rings: Vec<PolygonRing> = cells
+            .chunks(columns.max(1))
+            .enumerate()
+            .flat_map(|(row, flags)| {
+                let y1 = top - row as f32 * cell_pt;
+                let y0 = y1 - cell_pt;
+                let mut runs = Vec::new();
+                let mut start = None;
+                for (col, &filled) in flags.iter().chain([&false]).enumerate() {
+                    match (filled, start) {
+                        (true, None) => start = Some(col),
+                        (false, Some(first)) => {
+                            runs.push((first, col));
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                runs.into_iter().map(move |(first, end)| {
+                    let (x0, x1) = (left + first as f32 * cell_pt, left + end as f32 * cell_pt);
+                    PolygonRing {
+                        points: vec![lp(x0, y0), lp(x1, y0), lp(x1, y1), lp(x0, y1)],
+                    }
+                })
+            })
+            .collect();
+        if rings.is_empty() {
+            return;
+        }
+        let polygon = Polygon {
+            rings,
+            mode: PaintMode::Fill,
+            winding_order: WindingOrder::NonZero,
+        };
+        self.begin_marked("Artifact");
         self.current_ops.extend([
             Op::SaveGraphicsState,
             Op::SetFillColor { col: color },
             Op::DrawPolygon { polygon },
             Op::RestoreGraphicsState,
         ]);
+        self.end_marked();
     }
 
     /// Write text at a specific x offset from the left margin, at the current `y` cursor.
@@ -466,42 +1212,85 @@ impl PageBuilder {
     ) {
         self.flush_break();
         let x = Pt(self.left_x().0 + x_offset_pt);
+        self.begin_marked(self.role);
         self.current_ops.extend([
             Op::StartTextSection,
             Op::SetTextCursor {
                 pos: Point { x, y: self.pdf_y() },
             },
             Op::SetFillColor { col: color },
-            Op::SetFont {
-                size,
-                font: PdfFontHandle::External(font_id.clone()),
-            },
-            Op::ShowText {
-                items: vec![TextItem::Text(text.to_string())],
-            },
-            Op::EndTextSection,
         ]);
+        let ops = self.text_ops(text, font_id, size);
+        self.current_ops.extend(ops);
+        self.current_ops.push(Op::EndTextSection);
+        self.end_marked();
     }
 
-    /// Returns the appropriate `FontId` for the requested bold/italic combination.
-    pub fn font(&self, bold: bool, italic: bool) -> &FontId {
-        match (bold, italic) {
-            (true, true) => &self.fonts.bold_italic,
-            (true, false) => &self.fonts.bold,
-            (false, true) => &self.fonts.italic,
-            (false, false) => &self.fonts.regular,
-        }
+    /// Writes `text` in the outer margin beside the next line — the right margin on
+    /// odd pages and the left on even ones, as in a bound book — shortened with `…`
+    /// to fit. Breaks the page first if the next line will not fit, so the note
+    /// lands beside it. Does **not** advance `y`.
+    pub fn write_margin_note(&mut self, text: &str, font_id: &FontId, size: Pt, color: Color) {
+        self.ensure_space(self.line_height);
+        let room = self.margin.into_pt().0 - 2.0 * MARGIN_NOTE_GAP;
+        let fits = (room / (size.0 * 0.6)).max(1.0) as usize;
+        let text: String = if text.chars().count() > fits {
+            let kept: String = text.chars().take(fits.saturating_sub(1)).collect();
+            format!("{kept}\u{2026}")
+        } else {
+            text.to_string()
+        };
+        let width = text.chars().count() as f32 * size.0 * 0.6;
+        let x = if self.page_count % 2 == 1 {
+            self.page_width.into_pt().0 - self.margin.into_pt().0 + MARGIN_NOTE_GAP
+        } else {
+            self.margin.into_pt().0 - MARGIN_NOTE_GAP - width
+        };
+        self.begin_marked("Artifact");
+        self.current_ops.extend([
+            Op::StartTextSection,
+            Op::SetTextCursor {
+                pos: Point {
+                    x: Pt(x.max(0.0)),
+                    y: self.pdf_y(),
+                },
+            },
+            Op::SetFillColor { col: color },
+            Op::SetFont {
+                size,
+                font: PdfFontHandle::External(font_id.clone()),
+            },
+            Op::ShowText {
+                items: vec![TextItem::Text(text)],
+            },
+            Op::EndTextSection,
+        ]);
+        self.end_marked();
+    }
+
+    /// Returns the prose face (`--prose-font`) for text that isn't code: bold wins
+    /// over italic, as the prose set has no bold-italic.
+    pub fn prose_font(&self, bold: bool, italic: bool) -> &FontId {
+        match (bold, italic) {
+            (true, _) => &self.fonts.prose_bold,
+            (false, true) => &self.fonts.prose_italic,
+            (false, false) => &self.fonts.prose,
+        }
+    }
+
+    /// Returns the appropriate `FontId` for the requested bold/italic combination.
+    pub fn font(&self, bold: bool, italic: bool) -> &FontId {
+        match (bold, italic) {
+            (true, true) => &self.fonts.bold_italic,
+            (true, false) => &self.fonts.bold,
+            (false, true) => &self.fonts.italic,
+            (false, false) => &self.fonts.regular,
+        }
     }
 
     /// Finalizes all pages and returns them; no trailing empty page is produced.
     pub fn finish(mut self) -> Vec<PdfPage> {
-        if !self.current_ops.is_empty() {
-            self.pages.push(PdfPage::new(
-                self.page_width,
-                self.page_height,
-                self.current_ops,
-            ));
-        }
+        self.close_page();
         self.pages
     }
 }
@@ -516,17 +1305,25 @@ mod tests {
         let load =
             |bytes: &[u8]| printpdf::ParsedFont::from_bytes(bytes, 0, &mut Vec::new()).unwrap();
 
+        let regular = doc.add_font(&load(include_bytes!(
+            "../../fonts/JetBrainsMono-Regular.ttf"
+        )));
+        let bold = doc.add_font(&load(include_bytes!("../../fonts/JetBrainsMono-Bold.ttf")));
+        let italic = doc.add_font(&load(include_bytes!(
+            "../../fonts/JetBrainsMono-Italic.ttf"
+        )));
         let fonts = FontSet {
-            regular: doc.add_font(&load(include_bytes!(
-                "../../fonts/JetBrainsMono-Regular.ttf"
-            ))),
-            bold: doc.add_font(&load(include_bytes!("../../fonts/JetBrainsMono-Bold.ttf"))),
-            italic: doc.add_font(&load(include_bytes!(
-                "../../fonts/JetBrainsMono-Italic.ttf"
-            ))),
+            prose: regular.clone(),
+            prose_bold: bold.clone(),
+            prose_italic: italic.clone(),
+            regular,
+            bold,
+            italic,
             bold_italic: doc.add_font(&load(include_bytes!(
                 "../../fonts/JetBrainsMono-BoldItalic.ttf"
             ))),
+            emoji: None,
+            metrics: Metrics::default(),
         };
 
         (doc, fonts)
@@ -665,6 +1462,30 @@ mod tests {
         assert_eq!(builder.finish().len(), 1);
     }
 
+    #[test]
+    fn cell_grid_merges_runs_into_one_polygon() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1);
+        builder.draw_cell_grid(
+            0.0,
+            0.0,
+            1.0,
+            3,
+            &[true, true, false, false, false, true],
+            black(),
+        );
+        let pages = builder.finish();
+        let rings: Vec<usize> = pages[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::DrawPolygon { polygon } => Some(polygon.rings.len()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rings, [2]);
+    }
+
     #[test]
     fn write_text_at_x_does_not_panic() {
         let (_doc, fonts) = test_font_set();
@@ -698,6 +1519,18 @@ mod tests {
         assert_eq!(builder.line_height(), 12.5);
     }
 
+    #[test]
+    fn gaps_follow_the_gap_scale() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder =
+            PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1).with_gap_scale(0.5);
+        let before = builder.remaining_pt();
+        builder.gap(10.0);
+        assert_eq!(before - builder.remaining_pt(), 5.0);
+        builder.vertical_space(10.0);
+        assert_eq!(before - builder.remaining_pt(), 15.0);
+    }
+
     #[test]
     fn remaining_pt_decreases_after_write() {
         let (_doc, fonts) = test_font_set();
@@ -712,6 +1545,30 @@ mod tests {
         assert!(builder.remaining_pt() < before);
     }
 
+    #[test]
+    fn letter_spacing_is_set_and_reset_around_the_line() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_letter_spacing(0.5);
+        builder.write_line(&[Span {
+            text: "x".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        let spacing: Vec<f32> = builder
+            .finish()
+            .remove(0)
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetCharacterSpacing { multiplier } => Some(*multiplier),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(spacing, [0.5, 0.0]);
+    }
+
     #[test]
     fn current_page_with_pending_break() {
         let (_doc, fonts) = test_font_set();
@@ -728,6 +1585,90 @@ mod tests {
         assert_eq!(builder.current_page(), page_before + 1);
     }
 
+    #[test]
+    fn background_is_drawn_before_text() {
+        let (_doc, fonts) = test_font_set();
+        let font = fonts.regular.clone();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1);
+        builder.write_line_with_background(
+            &[Span {
+                text: "x".into(),
+                font_id: font,
+                size: Pt(8.0),
+                color: black(),
+            }],
+            Some(black()),
+        );
+        let pages = builder.finish();
+        let polygon = pages[0]
+            .ops
+            .iter()
+            .position(|op| matches!(op, Op::DrawPolygon { .. }));
+        let text = pages[0]
+            .ops
+            .iter()
+            .position(|op| matches!(op, Op::ShowText { .. }));
+        assert!(polygon.unwrap() < text.unwrap());
+    }
+
+    #[test]
+    fn span_backgrounds_box_only_flagged_spans() {
+        let (_doc, fonts) = test_font_set();
+        let font = fonts.regular.clone();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1);
+        let span = |text: &str| Span {
+            text: text.into(),
+            font_id: font.clone(),
+            size: Pt(8.0),
+            color: black(),
+        };
+        builder.write_line_with_span_backgrounds(
+            &[span("let "), span("needle"), span(" = 1;")],
+            None,
+            &[None, Some(black()), None],
+        );
+        let pages = builder.finish();
+        let boxes = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, Op::DrawPolygon { .. }))
+            .count();
+        assert_eq!(boxes, 1);
+    }
+
+    #[test]
+    fn reserved_pages_are_skipped_in_numbering() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        assert_eq!(builder.reserve_pages(1), 1);
+        builder.write_line(&[Span {
+            text: "x".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        builder.page_break();
+        assert_eq!(builder.reserve_pages(2), 3);
+        assert_eq!(builder.current_page(), 5);
+        let pages = builder.finish();
+        let headers: Vec<String> = pages
+            .iter()
+            .map(|page| page_texts(page).last().unwrap().clone())
+            .collect();
+        assert_eq!(headers, ["- 2 -", "- 5 -"]);
+    }
+
+    #[test]
+    fn at_page_top_tracks_writes_and_breaks() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1);
+        assert!(builder.at_page_top());
+        builder.vertical_space(5.0);
+        assert!(!builder.at_page_top());
+        builder.page_break();
+        assert!(builder.at_page_top());
+    }
+
     #[test]
     fn vertical_space_reduces_remaining() {
         let (_doc, fonts) = test_font_set();
@@ -772,4 +1713,454 @@ mod tests {
         let builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 5);
         assert_eq!(builder.current_page(), 5);
     }
+
+    fn page_texts(page: &PdfPage) -> Vec<String> {
+        page.ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::ShowText { items } => match items.as_slice() {
+                    [TextItem::Text(t)] => Some(t.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn text_x_positions(page: &PdfPage) -> Vec<f32> {
+        page.ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetTextCursor { pos } => Some(pos.x.0),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rtl_text_is_drawn_in_visual_order_span_by_span() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        let span = |text: &str| Span {
+            text: text.to_string(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        };
+        builder.write_line(&[span("let s = "), span("\"\u{05D0}\u{05D1}\"")]);
+        assert_eq!(
+            page_texts(&builder.finish()[0]),
+            ["let s = ", "\"\u{05D1}\u{05D0}\"", "- 1 -"]
+        );
+    }
+
+    #[test]
+    fn characters_the_font_lacks_fall_back_to_the_emoji_font() {
+        use crate::pdf::metrics::FontMetrics;
+
+        let (_doc, mut fonts) = test_font_set();
+        let emoji = fonts.bold_italic.clone();
+        fonts.metrics.insert(
+            fonts.regular.clone(),
+            FontMetrics::with_advances(&[('a', 0.6), ('b', 0.6)], 0.5),
+        );
+        fonts.metrics.insert(
+            emoji.clone(),
+            FontMetrics::with_advances(&[('\u{1F600}', 1.0)], 0.0),
+        );
+        fonts.emoji = Some(emoji.clone());
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        let line = "a\u{1F600}\u{FE0F}b";
+        assert!((builder.text_width(line, &fonts.regular, Pt(10.0)) - 22.0).abs() < 1e-3);
+        builder.write_line(&[Span {
+            text: line.to_string(),
+            font_id: fonts.regular.clone(),
+            size: Pt(10.0),
+            color: black(),
+        }]);
+        let page = &builder.finish()[0];
+        assert_eq!(page_texts(page), ["a", "\u{1F600}\u{FE0F}", "b", "- 1 -"]);
+        let drawn_in: Vec<&FontId> = page
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::SetFont {
+                    font: PdfFontHandle::External(font),
+                    ..
+                } => Some(font),
+                _ => None,
+            })
+            .collect();
+        // The last is the page header, in the prose face.
+        assert_eq!(
+            drawn_in,
+            [&fonts.regular, &emoji, &fonts.regular, &fonts.prose]
+        );
+    }
+
+    #[test]
+    fn note_column_narrows_the_text_and_is_ruled() {
+        let (_doc, fonts) = test_font_set();
+        let wide = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_note_column(Mm(60.0));
+        let column = Mm(60.0).into_pt().0;
+        assert!((wide.usable_width_pt() - builder.usable_width_pt() - column).abs() < 1e-3);
+
+        let right = |text: &str| Span {
+            text: text.to_string(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        };
+        builder.write_line_justified(&[right("left")], &[right("right")]);
+        let page = &builder.finish()[0];
+        let right_x = text_x_positions(page)[1];
+        let code_edge = Mm(10.0).into_pt().0 + wide.usable_width_pt() - column;
+        assert!(right_x < code_edge && right_x > code_edge - 30.0);
+
+        let rules: Vec<(f32, f32)> = page
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::DrawLine { line } => Some((line.points[0].p.x.0, line.points[1].p.x.0)),
+                _ => None,
+            })
+            .collect();
+        // An edge rule, then one rule per 20 pt of the 277 mm text height.
+        assert_eq!(
+            rules.len(),
+            1 + (Mm(277.0).into_pt().0 / NOTE_RULE_SPACING) as usize
+        );
+        assert!(rules.iter().all(|&(x, _)| x > code_edge));
+        assert_eq!(rules[0].0, rules[0].1);
+    }
+
+    #[test]
+    fn note_pages_follow_every_page_and_are_numbered() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_note_pages(true);
+        let line = |builder: &mut PageBuilder, text: &str| {
+            builder.write_line(&[Span {
+                text: text.to_string(),
+                font_id: fonts.regular.clone(),
+                size: Pt(8.0),
+                color: black(),
+            }])
+        };
+        line(&mut builder, "first");
+        builder.page_break();
+        assert_eq!(builder.current_page(), 3);
+        line(&mut builder, "second");
+        let pages = builder.finish();
+        let texts: Vec<Vec<String>> = pages.iter().map(page_texts).collect();
+        assert_eq!(
+            texts,
+            [
+                vec!["first".to_string(), "- 1 -".to_string()],
+                vec!["- 2 -".to_string()],
+                vec!["second".to_string(), "- 3 -".to_string()],
+                vec!["- 4 -".to_string()],
+            ]
+        );
+        assert!(
+            pages[1]
+                .ops
+                .iter()
+                .any(|op| matches!(op, Op::DrawLine { .. }))
+        );
+    }
+
+    #[test]
+    fn margin_notes_sit_in_the_outer_margin_and_are_shortened() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        builder.write_margin_note("parse_arguments", &fonts.italic, Pt(5.0), black());
+        builder.page_break();
+        builder.write_margin_note("run", &fonts.italic, Pt(5.0), black());
+        let pages = builder.finish();
+
+        let right_edge = Mm(210.0).into_pt().0 - Mm(10.0).into_pt().0;
+        assert_eq!(page_texts(&pages[0])[0], "parse_a\u{2026}");
+        assert!(text_x_positions(&pages[0])[0] > right_edge);
+        assert_eq!(page_texts(&pages[1])[0], "run");
+        assert!(text_x_positions(&pages[1])[0] < Mm(10.0).into_pt().0);
+    }
+
+    #[test]
+    fn centered_and_right_aligned_text_is_measured_by_glyph() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = crate::pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        // Three characters but six bytes: a byte count would shift it left.
+        builder.write_centered("\u{e9}\u{e9}\u{e9}", &fonts.regular, Pt(10.0), black());
+        let span = |text: &str| Span {
+            text: text.to_string(),
+            font_id: fonts.regular.clone(),
+            size: Pt(10.0),
+            color: black(),
+        };
+        builder.write_line_justified(&[span("a")], &[span("\u{e9}\u{e9}")]);
+        let pages = builder.finish();
+
+        let xs = text_x_positions(&pages[0]);
+        let page_width = Mm(210.0).into_pt().0;
+        let right_edge = page_width - Mm(10.0).into_pt().0;
+        assert!((xs[0] - (page_width - 18.0) / 2.0).abs() < 0.01, "{xs:?}");
+        assert!((xs[2] - (right_edge - 12.0)).abs() < 0.01, "{xs:?}");
+    }
+
+    #[test]
+    fn total_footer_disabled_by_default() {
+        let (_doc, fonts) = test_font_set();
+        let pages = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1).finish();
+        assert!(
+            page_texts(&pages[0])
+                .iter()
+                .all(|t| !t.contains(PAGE_TOTAL_PLACEHOLDER))
+        );
+    }
+
+    #[test]
+    fn footer_stamp_is_on_every_page_at_the_left_margin() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1)
+            .with_footer_stamp(Some("abc1234 \u{00B7} 2024-01-15".to_string()));
+        builder.page_break();
+        builder.ensure_space(1.0);
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 2);
+        pages.iter().for_each(|page| {
+            let texts = page_texts(page);
+            let i = texts
+                .iter()
+                .position(|t| t == "abc1234 \u{00B7} 2024-01-15")
+                .expect("stamp on page");
+            assert_eq!(text_x_positions(page)[i], Mm(10.0).into_pt().0);
+        });
+    }
+
+    #[test]
+    fn running_header_names_the_section_at_the_top_of_each_page() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_running_header(true);
+        let line = Span {
+            text: "x".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        };
+        builder.set_section(Some("a.rs".to_string()));
+        builder.write_line(std::slice::from_ref(&line));
+        builder.page_break();
+        builder.set_section(Some("b.rs".to_string()));
+        // Enough lines to spill onto a second page of b.rs.
+        (0..100).for_each(|_| builder.write_line(std::slice::from_ref(&line)));
+        builder.set_section(None);
+        let pages = builder.finish();
+        let headers: Vec<String> = pages
+            .iter()
+            .map(|page| page_texts(page).last().unwrap().clone())
+            .collect();
+        assert_eq!(
+            headers,
+            [
+                "a.rs \u{2014} p. 1",
+                "b.rs \u{2014} p. 2",
+                "b.rs \u{2014} p. 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn running_header_is_off_by_default() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 1);
+        builder.set_section(Some("a.rs".to_string()));
+        let pages = builder.finish();
+        assert_eq!(page_texts(&pages[0]), ["- 1 -"]);
+    }
+
+    #[test]
+    fn stamp_page_totals_patches_footers() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_total_footer(true);
+        builder.write_line(&[Span {
+            text: "a".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        builder.page_break();
+        builder.write_line(&[Span {
+            text: "b".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        let mut pages = builder.finish();
+        assert!(page_texts(&pages[1]).contains(&format!("Page 2 of {PAGE_TOTAL_PLACEHOLDER}")));
+
+        stamp_page_totals(&mut pages, 2);
+        assert!(page_texts(&pages[0]).contains(&"Page 1 of 2".to_string()));
+        assert!(page_texts(&pages[1]).contains(&"Page 2 of 2".to_string()));
+        assert!(pages.iter().flat_map(|p| &p.ops).all(|op| !matches!(
+            op,
+            Op::BeginMarkedContent { tag } if tag == PAGE_TOTAL_TAG
+        )));
+    }
+
+    #[test]
+    fn stamp_page_totals_leaves_code_alone() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_total_footer(true);
+        let code = format!("println!(\"{PAGE_TOTAL_PLACEHOLDER}\");");
+        builder.write_line(&[Span {
+            text: code.clone(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        let mut pages = builder.finish();
+        let cursors = |page: &PdfPage| -> Vec<f32> {
+            page.ops
+                .iter()
+                .filter_map(|op| match op {
+                    Op::SetTextCursor { pos } => Some(pos.x.0),
+                    _ => None,
+                })
+                .collect()
+        };
+        let before = cursors(&pages[0]);
+
+        stamp_page_totals(&mut pages, 12);
+        let texts = page_texts(&pages[0]);
+        assert!(texts.contains(&code), "{texts:?}");
+        assert!(texts.contains(&"Page 1 of 12".to_string()), "{texts:?}");
+        // Only the footer's cursor moves.
+        let after = cursors(&pages[0]);
+        let moved = before.iter().zip(&after).filter(|(a, b)| a != b).count();
+        assert_eq!(moved, 1);
+    }
+
+    #[test]
+    fn grayscale_mode_recolors_ops() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_color_mode(ColorMode::Grayscale);
+        builder.write_line(&[Span {
+            text: "x".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: Color::Rgb(Rgb::new(1.0, 0.0, 0.0, None)),
+        }]);
+        let pages = builder.finish();
+        let all_gray = pages[0].ops.iter().all(|op| match op {
+            Op::SetFillColor {
+                col: Color::Rgb(rgb),
+            } => rgb.r == rgb.g && rgb.g == rgb.b,
+            _ => true,
+        });
+        assert!(all_gray);
+    }
+
+    fn marked_tags(page: &PdfPage) -> Vec<String> {
+        page.ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::BeginMarkedContent { tag } => Some(tag.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn untagged_builder_emits_no_marked_content() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1);
+        builder.write_centered("Title", &fonts.bold, Pt(16.0), black());
+        assert!(marked_tags(&builder.finish()[0]).is_empty());
+    }
+
+    #[test]
+    fn tagged_builder_marks_roles_and_artifacts() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 1)
+            .with_tagging(true);
+        builder.set_role("H1");
+        builder.write_centered("Title", &fonts.bold, Pt(16.0), black());
+        builder.set_role("P");
+        builder.write_line(&[Span {
+            text: "body".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        builder.draw_horizontal_rule(black(), 0.5);
+        let pages = builder.finish();
+        assert_eq!(
+            marked_tags(&pages[0]),
+            vec!["H1", "P", "Artifact", "Artifact"]
+        );
+        let begins = marked_tags(&pages[0]).len();
+        let ends = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, Op::EndMarkedContent))
+            .count();
+        assert_eq!(begins, ends);
+    }
+
+    #[test]
+    fn to_roman_values() {
+        assert_eq!(to_roman(0), "0");
+        assert_eq!(to_roman(3), "iii");
+        assert_eq!(to_roman(4), "iv");
+        assert_eq!(to_roman(9), "ix");
+        assert_eq!(to_roman(40), "xl");
+        assert_eq!(to_roman(2024), "mmxxiv");
+    }
+
+    #[test]
+    fn default_numbering_is_arabic_physical() {
+        let (_doc, fonts) = test_font_set();
+        let builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 5);
+        assert_eq!(builder.current_page_label(), "5");
+    }
+
+    #[test]
+    fn arabic_numbering_restarts_at_first_numbered_page() {
+        let (_doc, fonts) = test_font_set();
+        let builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts, 4)
+            .with_numbering(PageNumbering::Arabic, 4);
+        assert_eq!(builder.current_page(), 4);
+        assert_eq!(builder.current_page_label(), "1");
+        assert_eq!(builder.page_label(6), "3");
+    }
+
+    #[test]
+    fn roman_numbering_labels_header() {
+        let (_doc, fonts) = test_font_set();
+        let mut builder = PageBuilder::new(Mm(210.0), Mm(297.0), Mm(10.0), 10.0, fonts.clone(), 2)
+            .with_numbering(PageNumbering::Roman, 1);
+        assert_eq!(builder.current_page_label(), "ii");
+        builder.write_line(&[Span {
+            text: "x".into(),
+            font_id: fonts.regular.clone(),
+            size: Pt(8.0),
+            color: black(),
+        }]);
+        let pages = builder.finish();
+        let has_header = pages[0].ops.iter().any(|op| {
+            matches!(op, Op::ShowText { items } if matches!(
+                items.as_slice(),
+                [TextItem::Text(t)] if t == "- ii -"
+            ))
+        });
+        assert!(has_header);
+    }
 }
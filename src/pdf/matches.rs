@@ -0,0 +1,135 @@
+use printpdf::{Actions, Color, Destination, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::types::GrepHit;
+
+/// Renders the `--grep` results page: one row per matching line with its location,
+/// text, and printed page number, each linking to the page it appears on.
+pub fn render(builder: &mut PageBuilder, pattern: &str, hits: &[GrepHit]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Search Results", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+
+    let mut files: Vec<_> = hits.iter().map(|h| &h.path).collect();
+    files.dedup();
+    let summary = match hits.len() {
+        0 => format!("No matches for /{pattern}/"),
+        n => format!(
+            "{n} matching line{} for /{pattern}/ in {} file{}",
+            if n == 1 { "" } else { "s" },
+            files.len(),
+            if files.len() == 1 { "" } else { "s" },
+        ),
+    };
+    builder.write_line_centered(&[Span {
+        text: summary,
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    const SIZE: f32 = 7.0;
+    const CHAR_WIDTH: f32 = 0.6;
+    const GAP_PT: f32 = 8.0;
+
+    hits.iter().for_each(|hit| {
+        let page = format!("p.{}", hit.page_label);
+        let location = format!("{}:{}  ", crate::display_path(&hit.path), hit.line_number);
+        let page_width = page.len() as f32 * SIZE * CHAR_WIDTH;
+        let max_chars = ((builder.usable_width_pt() - page_width - GAP_PT) / (SIZE * CHAR_WIDTH))
+            .max(1.0) as usize;
+        let text: String = hit
+            .text
+            .chars()
+            .take(max_chars.saturating_sub(location.chars().count()))
+            .collect();
+
+        builder.write_line_justified(
+            &[
+                Span {
+                    text: location,
+                    font_id: bold.clone(),
+                    size: Pt(SIZE),
+                    color: black.clone(),
+                },
+                Span {
+                    text,
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                },
+            ],
+            &[Span {
+                text: page,
+                font_id: regular.clone(),
+                size: Pt(SIZE),
+                color: gray.clone(),
+            }],
+        );
+        builder.add_link(
+            builder.line_height(),
+            Actions::Goto(Destination::Xyz {
+                page: hit.page,
+                left: None,
+                top: None,
+                zoom: None,
+            }),
+        );
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn hit(path: &str, line_number: usize, page: usize) -> GrepHit {
+        GrepHit {
+            path: PathBuf::from(path),
+            line_number,
+            page,
+            page_label: page.to_string(),
+            text: "let needle = haystack.find(x);".into(),
+        }
+    }
+
+    #[test]
+    fn render_links_every_hit() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(
+            &mut builder,
+            "needle",
+            &[hit("a.rs", 3, 4), hit("a.rs", 9, 5), hit("b.rs", 1, 7)],
+        );
+        let pages = builder.finish();
+        let links = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 3);
+    }
+
+    #[test]
+    fn render_without_hits() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(&mut builder, "needle", &[]);
+        assert_eq!(builder.finish().len(), 1);
+    }
+}
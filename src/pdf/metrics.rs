@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use printpdf::FontId;
+
+/// Advance width, in ems, assumed for text in a font without [`FontMetrics`] — the
+/// monospace advance of JetBrains Mono.
+pub const FALLBACK_ADVANCE: f32 = 0.6;
+
+/// Horizontal glyph advances of one font, read from its `cmap` and `hmtx` tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontMetrics {
+    /// Advance of each mapped character, in ems.
+    advances: HashMap<char, f32>,
+    /// Advance of `.notdef`, drawn for characters the font lacks.
+    missing: f32,
+}
+
+impl FontMetrics {
+    /// Reads the advances of every character `font` maps. Returns `None` if the
+    /// font can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::pdf::metrics::FontMetrics;
+    ///
+    /// let mono = include_bytes!("../../fonts/JetBrainsMono-Regular.ttf");
+    /// let metrics = FontMetrics::parse(mono).unwrap();
+    /// assert_eq!(metrics.width("abc", 10.0), 18.0);
+    /// assert!(FontMetrics::parse(b"not a font").is_none());
+    /// ```
+    pub fn parse(font: &[u8]) -> Option<Self> {
+        let face = ttf_parser::Face::parse(font, 0).ok()?;
+        let em = f32::from(face.units_per_em().max(1));
+        let advance = |glyph| face.glyph_hor_advance(glyph).map(|a| f32::from(a) / em);
+        let mut advances = HashMap::new();
+        face.tables()
+            .cmap
+            .into_iter()
+            .flat_map(|cmap| cmap.subtables)
+            .filter(|subtable| subtable.is_unicode())
+            .for_each(|subtable| {
+                subtable.codepoints(|code_point| {
+                    let Some(c) = char::from_u32(code_point) else {
+                        return;
+                    };
+                    if let Some(width) = subtable.glyph_index(code_point).and_then(advance) {
+                        advances.entry(c).or_insert(width);
+                    }
+                });
+            });
+        Some(Self {
+            advances,
+            missing: advance(ttf_parser::GlyphId(0)).unwrap_or(FALLBACK_ADVANCE),
+        })
+    }
+
+    /// Metrics that map only the characters in `advances`, for tests.
+    #[cfg(test)]
+    pub(crate) fn with_advances(advances: &[(char, f32)], missing: f32) -> Self {
+        Self {
+            advances: advances.iter().copied().collect(),
+            missing,
+        }
+    }
+
+    /// Whether the font has a glyph for `c`.
+    pub fn covers(&self, c: char) -> bool {
+        self.advances.contains_key(&c)
+    }
+
+    /// Width of `text` set at `size` points, without letter spacing.
+    pub fn width(&self, text: &str, size: f32) -> f32 {
+        text.chars()
+            .map(|c| self.advances.get(&c).copied().unwrap_or(self.missing))
+            .sum::<f32>()
+            * size
+    }
+}
+
+/// The [`FontMetrics`] of each font registered in a document, by handle.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics(Arc<Vec<(FontId, FontMetrics)>>);
+
+impl Metrics {
+    /// Records `metrics` for the font registered as `font`.
+    pub fn insert(&mut self, font: FontId, metrics: FontMetrics) {
+        Arc::make_mut(&mut self.0).push((font, metrics));
+    }
+
+    /// Width of `text` set in `font` at `size` points. A font with no recorded
+    /// metrics is measured as monospace at [`FALLBACK_ADVANCE`].
+    pub fn width(&self, font: &FontId, text: &str, size: f32) -> f32 {
+        self.0.iter().find(|(id, _)| id == font).map_or_else(
+            || text.chars().count() as f32 * size * FALLBACK_ADVANCE,
+            |(_, metrics)| metrics.width(text, size),
+        )
+    }
+
+    /// Whether `font` has a glyph for `c`; `None` for a font with no recorded
+    /// metrics.
+    pub fn covers(&self, font: &FontId, c: char) -> Option<bool> {
+        self.0
+            .iter()
+            .find(|(id, _)| id == font)
+            .map(|(_, metrics)| metrics.covers(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_characters_take_the_notdef_advance() {
+        let mono =
+            FontMetrics::parse(include_bytes!("../../fonts/JetBrainsMono-Bold.ttf")).unwrap();
+        assert_eq!(mono.width("\u{10FFFD}", 10.0), mono.missing * 10.0);
+        assert_eq!(mono.width("", 10.0), 0.0);
+        assert!(mono.covers('a') && !mono.covers('\u{10FFFD}'));
+    }
+
+    #[test]
+    fn unregistered_fonts_measure_as_monospace() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let font = crate::pdf::fonts::load_fonts(&mut doc).unwrap().regular;
+        let mut metrics = Metrics::default();
+        assert!((metrics.width(&font, "héllo", 10.0) - 30.0).abs() < 1e-3);
+        metrics.insert(
+            font.clone(),
+            FontMetrics {
+                advances: HashMap::from([('i', 0.25)]),
+                missing: 0.5,
+            },
+        );
+        assert_eq!(metrics.width(&font, "ii?", 10.0), 10.0);
+        assert_eq!(metrics.covers(&font, 'i'), Some(true));
+        assert_eq!(metrics.covers(&font, '?'), Some(false));
+        assert_eq!(Metrics::default().covers(&font, 'i'), None);
+    }
+}
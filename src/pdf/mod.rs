@@ -1,3 +1,9 @@
+/// Right-to-left reordering and Arabic shaping for drawn text.
+pub mod bidi;
+/// `--chapters` directory divider page rendering.
+pub mod chapter;
+/// `--checksums` SHA-256 appendix rendering.
+pub mod checksums;
 /// Syntax-highlighted source code rendering.
 pub mod code;
 /// Repository cover page rendering.
@@ -6,26 +12,77 @@ pub mod cover;
 pub mod diff;
 /// Embedded JetBrains Mono font loading.
 pub mod fonts;
+/// `--theme-gallery` sample page rendering.
+pub mod gallery;
+/// `--hotspots` code-churn page rendering.
+pub mod hotspots;
+/// Page imposition passes (booklet signatures, N-up) over assembled pages.
+pub mod impose;
+/// Optional content layers (`--layers`) post-processing.
+pub mod layers;
 /// Core page-layout engine (`PageBuilder`).
 pub mod layout;
+/// `--grep` search-results page rendering.
+pub mod matches;
+/// Glyph-advance text measurement from the embedded fonts.
+pub mod metrics;
+/// Markdown prose rendering for the `--preface` README.
+pub mod prose;
+/// QR code encoding and drawing for `--qr`.
+pub mod qr;
+/// `--redact-secrets` redaction log page rendering.
+pub mod redactions;
+/// `--refs` tags and branches overview page rendering.
+pub mod refs;
+/// Deterministic-output normalization for `SOURCE_DATE_EPOCH` builds.
+pub mod reproducible;
+/// Repository statistics summary page rendering.
+pub mod summary;
+/// `--symbols` index page rendering.
+pub mod symbols;
+/// Tagged-PDF structure tree post-processing.
+pub mod tagged;
 /// Table of contents rendering.
 pub mod toc;
 /// Directory tree visualization.
 pub mod tree;
+/// `--max-pages` / `--max-output-size` truncation notice rendering.
+pub mod truncated;
 /// GitHub user activity feed rendering.
 pub mod user_activity;
 /// User report cover page rendering.
 pub mod user_cover;
+/// User report issue activity rendering.
+pub mod user_issues;
+/// User organization list rendering.
+pub mod user_orgs;
 /// User repository list rendering.
 pub mod user_repos;
+/// User report code-review statistics rendering.
+pub mod user_reviews;
+/// Team report summary table rendering.
+pub mod user_team;
+/// Word-boundary line wrapping for tables and lists.
+pub mod wrap;
 
 use std::path::Path;
+#[cfg(feature = "native")]
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use printpdf::{Mm, PdfDocument, PdfSaveOptions};
+use printpdf::{Mm, PdfDocument, PdfPage, PdfSaveOptions};
 
-use crate::types::{Config, PaperSize, UserReportConfig};
+#[cfg(feature = "native")]
+use crate::error::Error;
+use crate::error::Result;
+use crate::types::{Config, PaperSize, ReviewLayout, UserReportConfig};
 use layout::{FontSet, PageBuilder};
 
+/// Page margin on every side.
+const MARGIN: Mm = Mm(10.0);
+
+/// Width of the ruled note column `--review-layout margin` takes from the code.
+const REVIEW_COLUMN: Mm = Mm(60.0);
+
 fn paper_dimensions(config: &Config) -> (Mm, Mm) {
     let (w, h) = match config.paper_size {
         PaperSize::A4 => (Mm(210.0), Mm(297.0)),
@@ -35,6 +92,22 @@ fn paper_dimensions(config: &Config) -> (Mm, Mm) {
     if config.landscape { (h, w) } else { (w, h) }
 }
 
+/// Baseline-to-baseline distance for text set at `font_size`: `--line-height` times
+/// the size, or the size plus the `--density` leading (2 pt by default).
+pub fn line_height(config: &Config, font_size: f64) -> f32 {
+    config
+        .line_height
+        .map_or(font_size + config.density.leading(), |factor| {
+            font_size * factor
+        }) as f32
+}
+
+/// Lines of code set at `font_size` that fit on one page.
+pub fn lines_per_page(config: &Config, font_size: f64) -> usize {
+    let (_, h) = paper_dimensions(config);
+    layout::lines_per_page(h, MARGIN, line_height(config, font_size))
+}
+
 /// Creates a `PageBuilder` starting at page 1 for the given config and font set.
 pub fn create_builder(config: &Config, fonts: FontSet) -> PageBuilder {
     create_builder_at_page(config, fonts, 1)
@@ -47,8 +120,26 @@ pub fn create_builder_at_page(
     starting_page: usize,
 ) -> PageBuilder {
     let (w, h) = paper_dimensions(config);
-    let line_height = config.font_size as f32 + 2.0;
-    PageBuilder::new(w, h, Mm(10.0), line_height, fonts, starting_page)
+    let line_height = line_height(config, config.font_size);
+    PageBuilder::new(w, h, MARGIN, line_height, fonts, starting_page)
+        .with_total_footer(config.page_total)
+        .with_running_header(config.running_header)
+        .with_color_mode(config.color_mode)
+        .with_tagging(config.tagged)
+        .with_layers(config.layers)
+        .with_locale(config.locale)
+        .with_gap_scale(config.density.gap_scale())
+}
+
+/// Leaves room for review comments on the code pages `builder` writes, as
+/// `--review-layout` asks: a ruled note column beside narrower code, or a lined
+/// note page after each page.
+pub fn with_review_layout(builder: PageBuilder, config: &Config) -> PageBuilder {
+    match config.review_layout {
+        Some(ReviewLayout::Margin) => builder.with_note_column(REVIEW_COLUMN),
+        Some(ReviewLayout::Pages) => builder.with_note_pages(true),
+        None => builder,
+    }
 }
 
 /// Creates a `PageBuilder` for a user report starting at page 1.
@@ -72,17 +163,245 @@ pub fn create_user_builder_at_page(
     PageBuilder::new(w, h, Mm(10.0), line_height, fonts, starting_page)
 }
 
-/// Serializes a `PdfDocument` to bytes and writes it to `path` asynchronously.
-pub async fn save_pdf(doc: &PdfDocument, path: &Path) -> anyhow::Result<()> {
+/// Applies the configured imposition pass (`--booklet` or `--nup`) to the fully
+/// assembled, numbered page list. Returns the pages unchanged when neither is set.
+pub fn impose(config: &Config, pages: Vec<PdfPage>) -> Vec<PdfPage> {
+    let (w, h) = paper_dimensions(config);
+    if config.booklet {
+        return impose::booklet(pages, w, h);
+    }
+    match config.nup {
+        Some(per_sheet) => impose::nup(pages, w, h, per_sheet),
+        None => pages,
+    }
+}
+
+/// Post-processing passes run over a serialized document.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveOptions<'a> {
+    /// Add a structure tree (`--tagged`), with alt text for each `Figure` element in
+    /// document order.
+    pub figure_alts: Option<&'a [String]>,
+    /// Register the optional content layers written with `--layers`.
+    pub layers: bool,
+    /// Called with the path, bytes written, and total as a large output passes
+    /// each tenth of its write.
+    pub progress: Option<fn(&Path, u64, u64)>,
+}
+
+impl<'a> SaveOptions<'a> {
+    /// The passes `config` asks for, with `figure_alts` used when it is tagged.
+    pub fn for_config(config: &Config, figure_alts: &'a [String]) -> Self {
+        Self {
+            figure_alts: config.tagged.then_some(figure_alts),
+            layers: config.layers,
+            progress: config.write_progress,
+        }
+    }
+}
+
+/// Serializes a `PdfDocument` to bytes, running the post-processing passes in
+/// `options`.
+pub fn to_bytes(doc: &PdfDocument, options: &SaveOptions) -> Result<Vec<u8>> {
     let mut warnings = Vec::new();
     let bytes = doc.save(&PdfSaveOptions::default(), &mut warnings);
-    tokio::fs::write(path, bytes).await.map_err(Into::into)
+    // Layers first: their markers become BDC, which the structure tree leaves alone.
+    let bytes = if options.layers {
+        layers::add_optional_content(&bytes)?
+    } else {
+        bytes
+    };
+    let bytes = match options.figure_alts {
+        Some(alts) => tagged::add_structure_tree(&bytes, alts)?,
+        None => bytes,
+    };
+    finalize(bytes)
+}
+
+/// Serializes a `PdfDocument` to bytes and writes it to `path` asynchronously.
+#[cfg(feature = "native")]
+pub async fn save_pdf(doc: &PdfDocument, path: &Path) -> Result<()> {
+    save_pdf_with(doc, path, &SaveOptions::default()).await
+}
+
+/// Like [`save_pdf`], but adds a structure tree (`--tagged`) before writing.
+/// `figure_alts` supplies alt text for each `Figure` element, in document order.
+#[cfg(feature = "native")]
+pub async fn save_pdf_tagged(doc: &PdfDocument, path: &Path, figure_alts: &[String]) -> Result<()> {
+    let options = SaveOptions {
+        figure_alts: Some(figure_alts),
+        ..SaveOptions::default()
+    };
+    save_pdf_with(doc, path, &options).await
+}
+
+/// Like [`save_pdf`], running the post-processing passes in `options` first.
+///
+/// This does not stream: printpdf serializes the whole document into memory, and
+/// the passes work on those bytes, so peak memory holds the complete PDF. What it
+/// does is keep that CPU-bound step off the async workers — on a multi-threaded
+/// runtime it runs in [`block_in_place`](tokio::task::block_in_place), so the
+/// worker's other tasks move to another thread meanwhile — and then write the
+/// finished bytes to disk a chunk at a time, reporting progress between chunks.
+#[cfg(feature = "native")]
+pub async fn save_pdf_with(
+    doc: &PdfDocument,
+    path: &Path,
+    options: &SaveOptions<'_>,
+) -> Result<()> {
+    let bytes = off_runtime(|| to_bytes(doc, options))?;
+    let progress = options.progress.map(|report| Progress {
+        report,
+        min_bytes: PROGRESS_MIN_BYTES,
+    });
+    write_atomic_reporting(path, &bytes, WRITE_CHUNK, progress).await
+}
+
+/// Runs `f` in [`block_in_place`](tokio::task::block_in_place) on a multi-threaded
+/// runtime, or directly anywhere else (a current-thread runtime can't give up its
+/// only worker).
+#[cfg(feature = "native")]
+fn off_runtime<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Prints `Writing out.pdf: 120.0 MB of 300.0 MB` to stderr — the CLI's
+/// [`Config::write_progress`].
+pub fn report_progress(path: &Path, written: u64, total: u64) {
+    eprintln!(
+        "Writing {}: {} of {}",
+        path.display(),
+        crate::format_size(written),
+        crate::format_size(total)
+    );
+}
+
+/// Makes serialized output byte-for-byte reproducible when `SOURCE_DATE_EPOCH` is set.
+fn finalize(bytes: Vec<u8>) -> Result<Vec<u8>> {
+    match crate::source_date_epoch() {
+        Some(epoch) => reproducible::normalize(&bytes, epoch),
+        None => Ok(bytes),
+    }
+}
+
+/// Distinguishes the temp files of concurrent [`write_atomic`] calls in one process.
+#[cfg(feature = "native")]
+static TEMP_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Bytes handed to each `write` while saving.
+#[cfg(feature = "native")]
+const WRITE_CHUNK: usize = 1 << 20;
+
+/// Outputs at least this large report their write progress.
+#[cfg(feature = "native")]
+const PROGRESS_MIN_BYTES: u64 = 64 << 20;
+
+/// Writes `bytes` to a temporary file next to `path`, syncs it, then renames it over
+/// `path`. An interrupted run leaves either the previous file or none — never a
+/// truncated PDF. The temp file is removed if any step fails or the future is
+/// dropped (cancelled) before the rename.
+///
+/// Every call writes its own temp file, named after the process, a per-process
+/// sequence number and the clock, so concurrent runs writing the same `path` —
+/// CI matrix jobs sharing a default `<repo>.pdf`, or tasks in one process — never
+/// interleave: the last rename wins and `path` is always one complete PDF.
+#[cfg(feature = "native")]
+pub async fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    write_atomic_reporting(path, bytes, WRITE_CHUNK, None).await
+}
+
+/// When a write reports its progress: `report` is called as an output of
+/// `min_bytes` or more passes each tenth of it.
+#[cfg(feature = "native")]
+#[derive(Clone, Copy)]
+struct Progress {
+    report: fn(&Path, u64, u64),
+    min_bytes: u64,
+}
+
+/// [`write_atomic`], handing `bytes` to the file `chunk` bytes at a time and
+/// reporting through `progress` between chunks.
+#[cfg(feature = "native")]
+async fn write_atomic_reporting(
+    path: &Path,
+    bytes: &[u8],
+    chunk: usize,
+    progress: Option<Progress>,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .ok_or_else(|| Error::UnsupportedPath(path.to_path_buf()))?;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let tmp = dir.join(format!(
+        ".{}.{}-{}-{nanos:08x}.tmp",
+        name.to_string_lossy(),
+        std::process::id(),
+        TEMP_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut guard = RemoveOnDrop(Some(&tmp));
+    let total = bytes.len() as u64;
+    let progress = progress.filter(|p| total >= p.min_bytes);
+    let result = async {
+        let mut file = tokio::fs::File::create(&tmp).await?;
+        let (mut written, mut tenths) = (0, 0);
+        for piece in bytes.chunks(chunk) {
+            file.write_all(piece).await?;
+            written += piece.len() as u64;
+            if let Some(progress) = progress
+                && written * 10 / total > tenths
+            {
+                tenths = written * 10 / total;
+                (progress.report)(path, written, total);
+            }
+        }
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&tmp, path).await
+    }
+    .await;
+    if result.is_ok() {
+        guard.0 = None;
+    }
+    result.map_err(|e| {
+        Error::Io(std::io::Error::new(
+            e.kind(),
+            format!("failed to write {}: {e}", path.display()),
+        ))
+    })
+}
+
+/// Deletes the temp file it holds when dropped; cleared once the rename succeeds.
+#[cfg(feature = "native")]
+struct RemoveOnDrop<'a>(Option<&'a Path>);
+
+#[cfg(feature = "native")]
+impl Drop for RemoveOnDrop<'_> {
+    fn drop(&mut self) {
+        // Drop is synchronous — tokio::fs cannot be awaited here.
+        if let Some(path) = self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::Config;
+    use crate::types::{Config, Density, NUp};
 
     #[test]
     fn paper_dimensions_a4() {
@@ -92,6 +411,49 @@ mod tests {
         assert_eq!(h.0, 297.0);
     }
 
+    #[test]
+    fn line_height_defaults_to_size_plus_two() {
+        let mut config = Config::test_default();
+        assert_eq!(line_height(&config, 8.0), 10.0);
+        config.line_height = Some(1.5);
+        assert_eq!(line_height(&config, 8.0), 12.0);
+    }
+
+    #[test]
+    fn save_options_report_progress_only_when_configured() {
+        let mut config = Config::test_default();
+        assert!(SaveOptions::for_config(&config, &[]).progress.is_none());
+        config.write_progress = Some(report_progress);
+        assert!(SaveOptions::for_config(&config, &[]).progress.is_some());
+    }
+
+    #[test]
+    fn density_sets_leading_and_gaps() {
+        let mut config = Config::test_default();
+        config.density = Density::Compact;
+        assert_eq!(line_height(&config, 8.0), 9.0);
+        assert_eq!(lines_per_page(&config, 8.0), 87);
+        config.density = Density::Relaxed;
+        assert_eq!(line_height(&config, 8.0), 12.0);
+        // An explicit --line-height wins over the preset's leading.
+        config.line_height = Some(1.0);
+        assert_eq!(line_height(&config, 8.0), 8.0);
+
+        let mut doc = PdfDocument::new("test");
+        let fonts = fonts::load_fonts(&mut doc).unwrap();
+        assert_eq!(create_builder(&config, fonts).gap_scale(), 1.5);
+    }
+
+    #[test]
+    fn lines_per_page_follows_paper_and_size() {
+        let mut config = Config::test_default();
+        // A4: 297 mm less two 10 mm margins is 785 pt; 8 pt text on 10 pt lines.
+        assert_eq!(lines_per_page(&config, 8.0), 78);
+        assert_eq!(lines_per_page(&config, 10.0), 65);
+        config.landscape = true;
+        assert_eq!(lines_per_page(&config, 8.0), 53);
+    }
+
     #[test]
     fn paper_dimensions_letter() {
         let mut config = Config::test_default();
@@ -110,6 +472,35 @@ mod tests {
         assert_eq!(h.0, 210.0);
     }
 
+    #[test]
+    fn impose_is_noop_without_booklet() {
+        let config = Config::test_default();
+        let pages = (0..3)
+            .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+            .collect();
+        assert_eq!(impose(&config, pages).len(), 3);
+    }
+
+    #[test]
+    fn impose_booklet_two_ups_pages() {
+        let mut config = Config::test_default();
+        config.booklet = true;
+        let pages = (0..8)
+            .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+            .collect();
+        assert_eq!(impose(&config, pages).len(), 4);
+    }
+
+    #[test]
+    fn impose_nup_four() {
+        let mut config = Config::test_default();
+        config.nup = Some(NUp::Four);
+        let pages = (0..9)
+            .map(|_| PdfPage::new(Mm(210.0), Mm(297.0), vec![]))
+            .collect();
+        assert_eq!(impose(&config, pages).len(), 3);
+    }
+
     #[tokio::test]
     async fn save_pdf_to_tempfile() {
         let mut doc = PdfDocument::new("test");
@@ -125,6 +516,76 @@ mod tests {
         assert!(std::fs::metadata(&path).unwrap().len() > 0);
     }
 
+    #[tokio::test]
+    async fn write_atomic_replaces_and_leaves_no_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.pdf");
+        std::fs::write(&path, b"old").unwrap();
+
+        write_atomic(&path, b"new contents").await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new contents");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_writes_to_one_path_never_interleave() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.pdf");
+        let a = vec![b'a'; 256 * 1024];
+        let b = vec![b'b'; 256 * 1024];
+
+        let (ra, rb) = tokio::join!(write_atomic(&path, &a), write_atomic(&path, &b));
+        ra.unwrap();
+        rb.unwrap();
+        let written = std::fs::read(&path).unwrap();
+        assert!(written == a || written == b, "output mixes both writers");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn large_writes_report_each_tenth() {
+        static CALLS: std::sync::Mutex<Vec<(u64, u64)>> = std::sync::Mutex::new(Vec::new());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.pdf");
+        let bytes = vec![b'x'; 5 * 1024];
+        let progress = Progress {
+            report: |_, written, total| CALLS.lock().unwrap().push((written, total)),
+            min_bytes: 4 * 1024,
+        };
+
+        write_atomic_reporting(&path, &bytes[..1024], 512, Some(progress))
+            .await
+            .unwrap();
+        assert!(CALLS.lock().unwrap().is_empty(), "small outputs stay quiet");
+
+        write_atomic_reporting(&path, &bytes, 512, Some(progress))
+            .await
+            .unwrap();
+        let calls = CALLS.lock().unwrap();
+        assert_eq!(calls.len(), 10);
+        assert_eq!(
+            calls.last(),
+            Some(&(bytes.len() as u64, bytes.len() as u64))
+        );
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), bytes.len() as u64);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn save_pdf_serializes_on_a_multi_thread_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.pdf");
+        let mut doc = PdfDocument::new("test");
+        let _ = fonts::load_fonts(&mut doc).unwrap();
+        save_pdf(&doc, &path).await.unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[tokio::test]
+    async fn write_atomic_missing_dir_errors() {
+        let result = write_atomic(Path::new("/nonexistent/dir/out.pdf"), b"x").await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn save_pdf_invalid_path() {
         let mut doc = PdfDocument::new("test");
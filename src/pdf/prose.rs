@@ -0,0 +1,377 @@
+use std::sync::LazyLock;
+
+use printpdf::{Color, Pt, Rgb};
+use regex::Regex;
+
+use super::layout::{PageBuilder, Span};
+
+/// Approximate glyph width as a fraction of the font size (monospace).
+const CHAR_WIDTH: f32 = 0.6;
+
+/// Body text size, in points.
+const BODY_SIZE: f32 = 9.0;
+
+/// Fenced code and table size, in points.
+const CODE_SIZE: f32 = 7.5;
+
+/// One block of a Markdown document, with inline markup already stripped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    /// A list item: nesting depth (0 at the margin), bullet or number, text.
+    Item(usize, String, String),
+    Quote(String),
+    /// Fenced or indented code, and pipe tables, printed line for line.
+    Code(Vec<String>),
+    Rule,
+}
+
+static IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[[^\]]*\]\([^)]*\)").unwrap());
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"</?[A-Za-z][^>]*>").unwrap());
+static ORDERED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+[.)])\s+(.*)$").unwrap());
+
+/// Plain text of a Markdown span: images and HTML tags dropped, links reduced to
+/// their text, emphasis and code markers removed.
+fn inline(text: &str) -> String {
+    let text = IMAGE.replace_all(text, "");
+    let text = LINK.replace_all(&text, "$1");
+    let text = TAG.replace_all(&text, "");
+    text.replace("**", "")
+        .replace("__", "")
+        .replace('`', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The level and title of an ATX heading (`## Usage`).
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')))
+        .then(|| (level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// Whether `line` is a thematic break: three or more `-`, `*`, or `_`.
+fn is_rule(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && ['-', '*', '_'].iter().any(|m| marks.iter().all(|c| c == m))
+}
+
+/// Splits `markdown` into blocks. Covers what READMEs use: ATX and setext
+/// headings, paragraphs, nested lists, block quotes, fences, tables, and rules.
+fn parse(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut fence: Option<(&str, Vec<String>)> = None;
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        let text = inline(&paragraph.join(" "));
+        if !text.is_empty() {
+            blocks.push(Block::Paragraph(text));
+        }
+        paragraph.clear();
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if let Some((marker, code)) = &mut fence {
+            if trimmed.starts_with(*marker) {
+                blocks.push(Block::Code(std::mem::take(code)));
+                fence = None;
+            } else {
+                code.push(line.to_string());
+            }
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            flush(&mut paragraph, &mut blocks);
+            fence = Some((&trimmed[..3], Vec::new()));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else if !paragraph.is_empty()
+            && (trimmed.chars().all(|c| c == '=') || trimmed.chars().all(|c| c == '-'))
+        {
+            let level = if trimmed.starts_with('=') { 1 } else { 2 };
+            blocks.push(Block::Heading(level, inline(&paragraph.join(" "))));
+            paragraph.clear();
+        } else if let Some((level, title)) = atx_heading(trimmed).filter(|_| indent < 4) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(level, inline(title)));
+        } else if is_rule(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Rule);
+        } else if let Some(rest) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+        {
+            flush(&mut paragraph, &mut blocks);
+            let rest = rest
+                .strip_prefix("[ ] ")
+                .map(|r| format!("\u{2610} {r}"))
+                .or_else(|| rest.strip_prefix("[x] ").map(|r| format!("\u{2611} {r}")))
+                .unwrap_or_else(|| rest.to_string());
+            blocks.push(Block::Item(
+                indent / 2,
+                "\u{2022}".to_string(),
+                inline(&rest),
+            ));
+        } else if let Some(caps) = ORDERED.captures(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Item(
+                indent / 2,
+                caps[1].to_string(),
+                inline(&caps[2]),
+            ));
+        } else if let Some(rest) = trimmed.strip_prefix('>') {
+            flush(&mut paragraph, &mut blocks);
+            match blocks.last_mut() {
+                Some(Block::Quote(text)) if !rest.trim().is_empty() => {
+                    text.push(' ');
+                    text.push_str(&inline(rest));
+                }
+                _ => blocks.push(Block::Quote(inline(rest))),
+            }
+        } else if let Some(Block::Item(_, _, text)) = blocks.last_mut()
+            && paragraph.is_empty()
+            && indent > 0
+        {
+            // A lazy continuation of the list item above.
+            text.push(' ');
+            text.push_str(&inline(trimmed));
+        } else if trimmed.starts_with('|') || (indent >= 4 && paragraph.is_empty()) {
+            flush(&mut paragraph, &mut blocks);
+            let text = if trimmed.starts_with('|') {
+                line.to_string()
+            } else {
+                line[4.min(line.len())..].to_string()
+            };
+            match blocks.last_mut() {
+                Some(Block::Code(lines)) => lines.push(text),
+                _ => blocks.push(Block::Code(vec![text])),
+            }
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    if let Some((_, code)) = fence {
+        blocks.push(Block::Code(code));
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Word-wrap `text` into lines of at most `max_chars` characters, breaking at
+/// word boundaries; a word longer than a line gets a line of its own.
+fn word_wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    text.split_whitespace().for_each(|word| {
+        let len = current.chars().count();
+        if len > 0 && len + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    });
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Renders a Markdown document as typeset prose: headings in bold, paragraphs and
+/// list items wrapped to the page width, code blocks and tables in small print.
+/// Text is set in the prose faces; code blocks stay monospace.
+pub fn render(builder: &mut PageBuilder, markdown: &str) {
+    let bold = builder.prose_font(true, false).clone();
+    let regular = builder.prose_font(false, false).clone();
+    let italic = builder.prose_font(false, true).clone();
+    let mono = builder.font(false, false).clone();
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = Color::Rgb(Rgb::new(0.4, 0.4, 0.4, None));
+    let code_bg = Color::Rgb(Rgb::new(0.95, 0.95, 0.95, None));
+    let rule = Color::Rgb(Rgb::new(0.72, 0.72, 0.72, None));
+
+    let width = builder.usable_width_pt();
+    let chars = |size: f32, indent: usize| {
+        ((width / (size * CHAR_WIDTH)) as usize)
+            .saturating_sub(indent)
+            .max(10)
+    };
+    let span = |text: String, font_id: &printpdf::FontId, size: f32, color: &Color| Span {
+        text,
+        font_id: font_id.clone(),
+        size: Pt(size),
+        color: color.clone(),
+    };
+
+    parse(markdown).into_iter().for_each(|block| match block {
+        Block::Heading(level, title) => {
+            let size = match level {
+                1 => 16.0,
+                2 => 13.0,
+                _ => 11.0,
+            };
+            builder.gap(if level == 1 { 10.0 } else { 6.0 });
+            // Keep a heading with the first lines of what follows.
+            builder.ensure_space(4.0 * (size + 4.0));
+            builder.set_role(match level {
+                1 => "H1",
+                2 => "H2",
+                _ => "H3",
+            });
+            builder.set_line_height(size + 4.0);
+            word_wrap(&title, chars(size, 0))
+                .into_iter()
+                .for_each(|line| builder.write_line(&[span(line, &bold, size, &black)]));
+            builder.set_role("P");
+            if level <= 2 {
+                builder.draw_horizontal_rule(rule.clone(), 0.5);
+            }
+            builder.gap(4.0);
+        }
+        Block::Paragraph(text) => {
+            builder.set_line_height(BODY_SIZE + 3.0);
+            word_wrap(&text, chars(BODY_SIZE, 0))
+                .into_iter()
+                .for_each(|line| builder.write_line(&[span(line, &regular, BODY_SIZE, &black)]));
+            builder.gap(5.0);
+        }
+        Block::Item(depth, marker, text) => {
+            builder.set_line_height(BODY_SIZE + 3.0);
+            let indent = 2 + depth * 3;
+            let hang = indent + marker.chars().count() + 1;
+            word_wrap(&text, chars(BODY_SIZE, hang))
+                .into_iter()
+                .enumerate()
+                .for_each(|(i, line)| {
+                    let lead = match i {
+                        0 => format!("{}{marker} ", " ".repeat(indent)),
+                        _ => " ".repeat(hang),
+                    };
+                    builder.write_line(&[
+                        span(lead, &regular, BODY_SIZE, &gray),
+                        span(line, &regular, BODY_SIZE, &black),
+                    ]);
+                });
+        }
+        Block::Quote(text) => {
+            builder.set_line_height(BODY_SIZE + 3.0);
+            word_wrap(&text, chars(BODY_SIZE, 2))
+                .into_iter()
+                .for_each(|line| {
+                    builder.write_line(&[
+                        span("\u{2502} ".to_string(), &regular, BODY_SIZE, &gray),
+                        span(line, &italic, BODY_SIZE, &gray),
+                    ]);
+                });
+            builder.gap(5.0);
+        }
+        Block::Code(lines) => {
+            builder.set_line_height(CODE_SIZE + 2.5);
+            let max = chars(CODE_SIZE, 1);
+            lines.iter().for_each(|line| {
+                let line = line.replace('\t', "    ");
+                let mut rest: &str = &line;
+                loop {
+                    let split = rest.char_indices().nth(max).map_or(rest.len(), |(i, _)| i);
+                    builder.write_line_with_background(
+                        &[span(
+                            format!(" {}", &rest[..split]),
+                            &mono,
+                            CODE_SIZE,
+                            &black,
+                        )],
+                        Some(code_bg.clone()),
+                    );
+                    rest = &rest[split..];
+                    if rest.is_empty() {
+                        break;
+                    }
+                }
+            });
+            builder.gap(6.0);
+        }
+        Block::Rule => {
+            builder.gap(4.0);
+            builder.draw_horizontal_rule(rule.clone(), 0.5);
+            builder.gap(6.0);
+        }
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn inline_markup_is_stripped() {
+        assert_eq!(
+            inline("[![CI](b.svg)](ci) See **the** [docs](https://x) for `run()`"),
+            "See the docs for run()"
+        );
+        assert_eq!(inline("<div align=\"center\">"), "");
+    }
+
+    #[test]
+    fn parse_recognizes_readme_blocks() {
+        let blocks = parse(
+            "# gitprint\n\nConvert repos\ninto PDFs.\n\n## Usage\n\n- one\n  - nested\n1. first\n\n```sh\ngitprint .\n```\n\n> quoted\n> text\n\n---\n",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading(1, "gitprint".into()),
+                Block::Paragraph("Convert repos into PDFs.".into()),
+                Block::Heading(2, "Usage".into()),
+                Block::Item(0, "\u{2022}".into(), "one".into()),
+                Block::Item(1, "\u{2022}".into(), "nested".into()),
+                Block::Item(0, "1.".into(), "first".into()),
+                Block::Code(vec!["gitprint .".into()]),
+                Block::Quote("quoted text".into()),
+                Block::Rule,
+            ]
+        );
+    }
+
+    #[test]
+    fn setext_headings_and_tables() {
+        assert_eq!(
+            parse("Title\n=====\n\n| a | b |\n|---|---|\n"),
+            vec![
+                Block::Heading(1, "Title".into()),
+                Block::Code(vec!["| a | b |".into(), "|---|---|".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn word_wrap_breaks_at_spaces() {
+        assert_eq!(
+            word_wrap("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(word_wrap("unbreakable", 4), vec!["unbreakable"]);
+        assert!(word_wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn render_long_readme_spans_pages() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let readme = "# Title\n\n".to_string() + &"Some prose here.\n\n".repeat(200);
+        render(&mut builder, &readme);
+        assert!(builder.finish().len() > 1);
+    }
+}
@@ -0,0 +1,76 @@
+use printpdf::{Color, Rgb};
+use qrcode::QrCode;
+
+use super::layout::PageBuilder;
+
+/// Side of the QR code beside each file header, in points (about 17 mm).
+pub const HEADER_SIZE: f32 = 48.0;
+
+/// Side of the QR code on the cover, in points.
+pub const COVER_SIZE: f32 = 96.0;
+
+/// A QR code's modules, row-major from the top-left; `true` is dark.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modules {
+    /// Modules per side.
+    pub width: usize,
+    /// `width * width` flags.
+    pub dark: Vec<bool>,
+}
+
+/// Encodes `data` as a QR code, or `None` if it is too long for one.
+pub fn encode(data: &str) -> Option<Modules> {
+    let code = QrCode::new(data.as_bytes()).ok()?;
+    Some(Modules {
+        width: code.width(),
+        dark: code
+            .to_colors()
+            .into_iter()
+            .map(|c| c == qrcode::Color::Dark)
+            .collect(),
+    })
+}
+
+/// Draws a `size_pt`-square QR code of `url` in black, `x_offset_pt` from the left
+/// margin with its bottom edge `y_below_cursor_pt` below the cursor. Returns
+/// `false`, drawing nothing, if `url` cannot be encoded. Does **not** advance `y`.
+pub fn draw(
+    builder: &mut PageBuilder,
+    url: &str,
+    x_offset_pt: f32,
+    y_below_cursor_pt: f32,
+    size_pt: f32,
+) -> bool {
+    let Some(modules) = encode(url) else {
+        return false;
+    };
+    builder.draw_cell_grid(
+        x_offset_pt,
+        y_below_cursor_pt,
+        size_pt / modules.width as f32,
+        modules.width,
+        &modules.dark,
+        Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)),
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_a_square_with_finder_patterns() {
+        let modules = encode("https://github.com/user/repo").unwrap();
+        assert_eq!(modules.dark.len(), modules.width * modules.width);
+        // The top-left finder pattern: a dark 7-module ring around a light one.
+        let row = |y: usize| &modules.dark[y * modules.width..y * modules.width + 7];
+        assert_eq!(row(0), [true; 7]);
+        assert_eq!(row(1), [true, false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn oversized_data_is_rejected() {
+        assert!(encode(&"x".repeat(8000)).is_none());
+    }
+}
@@ -0,0 +1,98 @@
+use printpdf::{Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::types::Redaction;
+
+/// Renders the `--redact-secrets` log page: one row per blanked-out secret with its
+/// location and the rule that matched. The secret itself never appears.
+pub fn render(builder: &mut PageBuilder, redactions: &[Redaction]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Redaction Log", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+
+    let mut files: Vec<_> = redactions.iter().map(|r| &r.path).collect();
+    files.dedup();
+    let summary = match redactions.len() {
+        0 => "No secrets found".to_string(),
+        n => format!(
+            "{n} secret{} redacted in {} file{}",
+            if n == 1 { "" } else { "s" },
+            files.len(),
+            if files.len() == 1 { "" } else { "s" },
+        ),
+    };
+    builder.write_line_centered(&[Span {
+        text: summary,
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    const SIZE: f32 = 7.0;
+    redactions.iter().for_each(|r| {
+        builder.write_line_justified(
+            &[Span {
+                text: format!("{}:{}", crate::display_path(&r.path), r.line_number),
+                font_id: bold.clone(),
+                size: Pt(SIZE),
+                color: black.clone(),
+            }],
+            &[Span {
+                text: r.kind.clone(),
+                font_id: regular.clone(),
+                size: Pt(SIZE),
+                color: gray.clone(),
+            }],
+        );
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn render_lists_location_and_kind_but_not_secret() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(
+            &mut builder,
+            &[Redaction {
+                path: PathBuf::from(".env"),
+                line_number: 3,
+                kind: "AWS access key".into(),
+            }],
+        );
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 1);
+        let text: String = pages[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::ShowText { items } => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                printpdf::TextItem::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(text.contains(".env:3"), "got: {text}");
+        assert!(text.contains("AWS access key"), "got: {text}");
+    }
+}
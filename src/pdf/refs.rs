@@ -0,0 +1,162 @@
+use printpdf::{Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::types::{GitRef, RefOverview};
+
+/// Approximate glyph width as a fraction of the font size (monospace).
+const CHAR_WIDTH: f32 = 0.6;
+
+/// Row text size, in points.
+const SIZE: f32 = 7.0;
+
+/// Shortens `text` to at most `max_chars` characters, marking a cut with `…`.
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{kept}\u{2026}")
+}
+
+/// How a branch compares with the default branch: `↑ahead ↓behind`.
+fn divergence(git_ref: &GitRef, default_branch: &str) -> String {
+    match git_ref.ahead_behind {
+        _ if git_ref.name == default_branch => "default".to_string(),
+        Some((0, 0)) => "even".to_string(),
+        Some((ahead, behind)) => format!("\u{2191}{ahead} \u{2193}{behind}"),
+        None => String::new(),
+    }
+}
+
+/// Renders the `--refs` overview: every tag with the commit it marks, then every
+/// branch with its last commit and distance from the default branch.
+pub fn render(builder: &mut PageBuilder, refs: &RefOverview) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Tags and Branches", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+    builder.write_line_centered(&[Span {
+        text: format!(
+            "{} tag{} \u{00B7} {} branch{} \u{00B7} compared with {}",
+            refs.tags.len(),
+            if refs.tags.len() == 1 { "" } else { "s" },
+            refs.branches.len(),
+            if refs.branches.len() == 1 { "" } else { "es" },
+            refs.default_branch,
+        ),
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    let width = builder.usable_width_pt();
+    let mut section = |title: &str, rows: &[GitRef], status: &dyn Fn(&GitRef) -> String| {
+        if rows.is_empty() {
+            return;
+        }
+        let name_width = rows
+            .iter()
+            .map(|r| r.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(32);
+        builder.ensure_space(3.0 * builder.line_height());
+        builder.set_role("H2");
+        builder.write_line(&[Span {
+            text: title.to_string(),
+            font_id: bold.clone(),
+            size: Pt(SIZE + 1.0),
+            color: black.clone(),
+        }]);
+        builder.set_role("P");
+        rows.iter().for_each(|git_ref| {
+            let meta = format!("{}  {}  {}", status(git_ref), git_ref.commit, git_ref.date);
+            let meta = meta.trim_start().to_string();
+            let used = 2 + name_width + 2 + meta.chars().count() + 2;
+            let room = (width / (SIZE * CHAR_WIDTH)) as usize;
+            let subject = truncate(&git_ref.subject, room.saturating_sub(used).max(8));
+            builder.write_line_justified(
+                &[
+                    Span {
+                        text: format!("  {:<name_width$}  ", truncate(&git_ref.name, name_width)),
+                        font_id: regular.clone(),
+                        size: Pt(SIZE),
+                        color: black.clone(),
+                    },
+                    Span {
+                        text: subject,
+                        font_id: regular.clone(),
+                        size: Pt(SIZE),
+                        color: gray.clone(),
+                    },
+                ],
+                &[Span {
+                    text: meta,
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                }],
+            );
+        });
+        builder.gap(8.0);
+    };
+
+    section("Tags", &refs.tags, &|_| String::new());
+    section("Branches", &refs.branches, &|r| {
+        divergence(r, &refs.default_branch)
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn git_ref(name: &str, ahead_behind: Option<(usize, usize)>) -> GitRef {
+        GitRef {
+            name: name.to_string(),
+            date: "2024-03-01".to_string(),
+            commit: "abc1234".to_string(),
+            subject: "Add a feature".to_string(),
+            ahead_behind,
+        }
+    }
+
+    #[test]
+    fn divergence_from_the_default_branch() {
+        let status = |name, counts| divergence(&git_ref(name, counts), "origin/main");
+        assert_eq!(status("origin/main", Some((0, 0))), "default");
+        assert_eq!(status("main", Some((0, 0))), "even");
+        assert_eq!(status("topic", Some((2, 7))), "\u{2191}2 \u{2193}7");
+        assert_eq!(status("v1.0", None), "");
+    }
+
+    #[test]
+    fn truncate_marks_the_cut() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a long subject", 6), "a lon\u{2026}");
+    }
+
+    #[test]
+    fn render_lists_tags_and_branches() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let refs = RefOverview {
+            default_branch: "origin/main".to_string(),
+            tags: vec![git_ref("v1.0", None)],
+            branches: vec![git_ref("origin/main", Some((0, 0))), git_ref("topic", None)],
+        };
+        render(&mut builder, &refs);
+        assert_eq!(builder.finish().len(), 1);
+    }
+}
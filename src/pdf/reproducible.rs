@@ -0,0 +1,400 @@
+use std::collections::{BTreeMap, HashMap};
+
+use lopdf::{Dictionary, Document, Object, ObjectId, content::Content};
+
+use crate::error::Result;
+
+/// Rewrites a serialized PDF so identical input always yields identical bytes
+/// (`SOURCE_DATE_EPOCH` builds).
+///
+/// printpdf stamps wall-clock dates, random document IDs, random font resource
+/// names, and random subset tags, and its object numbering follows hash-map order.
+/// This pass:
+/// - sets `/CreationDate` and `/ModDate` in the Info dictionary to `epoch`;
+/// - drops the XMP `/Metadata` stream (it repeats the dates alongside random UUIDs);
+/// - renames font resources to `F0`, `F1`, … in order of first use, and gives
+///   embedded subsets a matching fixed tag;
+/// - sorts dictionary keys, renumbers objects in traversal order from the trailer,
+///   and derives the trailer `/ID` from the page contents.
+///
+/// # Errors
+///
+/// Returns an error if `pdf` cannot be parsed or a page's content stream cannot be
+/// decoded or re-encoded.
+pub fn normalize(pdf: &[u8], epoch: u64) -> Result<Vec<u8>> {
+    let mut doc = Document::load_mem(pdf)?;
+
+    let date = Object::string_literal(pdf_date(epoch));
+    if let Ok(info_id) = doc.trailer.get(b"Info").and_then(Object::as_reference) {
+        let info = doc.get_object_mut(info_id)?.as_dict_mut()?;
+        info.set("CreationDate", date.clone());
+        info.set("ModDate", date);
+    }
+    if let Some(Object::Reference(xmp)) = doc.catalog_mut()?.remove(b"Metadata") {
+        doc.objects.remove(&xmp);
+    }
+
+    rename_fonts(&mut doc)?;
+    doc.objects.values_mut().for_each(sort_keys);
+    sort_dict(&mut doc.trailer);
+    renumber(&mut doc);
+
+    let id = Object::String(
+        content_hash(&doc).to_be_bytes().to_vec(),
+        lopdf::StringFormat::Hexadecimal,
+    );
+    doc.trailer.set("ID", vec![id.clone(), id]);
+
+    let mut out = Vec::with_capacity(pdf.len());
+    doc.save_to(&mut out)?;
+    Ok(out)
+}
+
+/// `D:YYYYMMDDHHmmSSZ` for a Unix timestamp, as used in PDF date strings.
+fn pdf_date(epoch: u64) -> String {
+    let digits: String = crate::format_utc(epoch)
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    format!("D:{digits}Z")
+}
+
+/// Location of a `/Font` resource dictionary: the object that holds it, plus the
+/// keys leading from that object to it (empty when the object *is* the dictionary).
+type FontDictPath = (ObjectId, Vec<&'static [u8]>);
+
+/// Renames every page's font resources to `F<n>`, numbered by first use across the
+/// document (unused fonts follow, ordered by `/BaseFont`), and rewrites `Tf`
+/// operators to match.
+fn rename_fonts(doc: &mut Document) -> Result<()> {
+    let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+    let mut renames: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+    let mut contents = Vec::with_capacity(pages.len());
+
+    for &page_id in &pages {
+        let content = doc.get_and_decode_page_content(page_id)?;
+        content
+            .operations
+            .iter()
+            .filter(|op| op.operator == "Tf")
+            .filter_map(|op| op.operands.first()?.as_name().ok())
+            .for_each(|name| {
+                let next = format!("F{}", renames.len()).into_bytes();
+                renames.entry(name.to_vec()).or_insert(next);
+            });
+        contents.push(content);
+    }
+
+    let mut font_dicts: Vec<FontDictPath> = pages
+        .iter()
+        .filter_map(|&page_id| font_dict_path(doc, page_id))
+        .collect();
+    font_dicts.dedup();
+    let mut unused: Vec<(Vec<u8>, Vec<u8>)> = font_dicts
+        .iter()
+        .filter_map(|path| dict_at(doc, path))
+        .flat_map(|fonts| fonts.iter())
+        .filter(|(name, _)| !renames.contains_key(*name))
+        .map(|(name, font)| (base_font(doc, font), name.clone()))
+        .collect();
+    unused.sort();
+    unused.dedup();
+    unused.into_iter().for_each(|(_, name)| {
+        let next = format!("F{}", renames.len()).into_bytes();
+        renames.entry(name).or_insert(next);
+    });
+
+    for (&page_id, mut content) in pages.iter().zip(contents) {
+        content
+            .operations
+            .iter_mut()
+            .filter(|op| op.operator == "Tf")
+            .filter_map(|op| op.operands.first_mut())
+            .for_each(|name| {
+                if let Object::Name(n) = name
+                    && let Some(new) = renames.get(n)
+                {
+                    *n = new.clone();
+                }
+            });
+        doc.change_page_content(page_id, content.encode()?)?;
+    }
+
+    let mut retagged: BTreeMap<ObjectId, Vec<u8>> = BTreeMap::new();
+    for path in &font_dicts {
+        let Some(fonts) = dict_at_mut(doc, path) else {
+            continue;
+        };
+        let renamed: Dictionary = fonts
+            .iter()
+            .map(|(name, font)| {
+                let new = renames.get(name).cloned().unwrap_or_else(|| name.clone());
+                if let Object::Reference(id) = font {
+                    retagged.insert(*id, new.clone());
+                }
+                (new, font.clone())
+            })
+            .collect();
+        *fonts = renamed;
+    }
+    retagged
+        .into_iter()
+        .for_each(|(font_id, name)| retag_subset(doc, font_id, &name));
+    Ok(())
+}
+
+/// Finds a page's `/Font` dictionary, following `/Resources` and `/Font` whether
+/// they are inline or indirect.
+fn font_dict_path(doc: &Document, page_id: ObjectId) -> Option<FontDictPath> {
+    let page = doc.get_object(page_id).ok()?.as_dict().ok()?;
+    let (owner, mut keys, resources) = match page.get(b"Resources").ok()? {
+        Object::Reference(id) => (*id, vec![], doc.get_object(*id).ok()?.as_dict().ok()?),
+        Object::Dictionary(d) => (page_id, vec![&b"Resources"[..]], d),
+        _ => return None,
+    };
+    match resources.get(b"Font").ok()? {
+        Object::Reference(id) => Some((*id, vec![])),
+        Object::Dictionary(_) => {
+            keys.push(b"Font");
+            Some((owner, keys))
+        }
+        _ => None,
+    }
+}
+
+fn dict_at<'a>(doc: &'a Document, (id, keys): &FontDictPath) -> Option<&'a Dictionary> {
+    keys.iter()
+        .try_fold(doc.get_object(*id).ok()?.as_dict().ok()?, |dict, key| {
+            dict.get(key).ok()?.as_dict().ok()
+        })
+}
+
+fn dict_at_mut<'a>(doc: &'a mut Document, (id, keys): &FontDictPath) -> Option<&'a mut Dictionary> {
+    keys.iter().try_fold(
+        doc.get_object_mut(*id).ok()?.as_dict_mut().ok()?,
+        |dict, key| dict.get_mut(key).ok()?.as_dict_mut().ok(),
+    )
+}
+
+fn base_font(doc: &Document, font: &Object) -> Vec<u8> {
+    let dict = match font {
+        Object::Reference(id) => doc.get_object(*id).and_then(Object::as_dict).ok(),
+        other => other.as_dict().ok(),
+    };
+    dict.and_then(|d| d.get(b"BaseFont").and_then(Object::as_name).ok())
+        .map(<[u8]>::to_vec)
+        .unwrap_or_default()
+}
+
+/// Replaces a random `ABCDEF+` subset prefix on the font's `/BaseFont` (and its
+/// descendant and descriptor `/FontName`s) with a tag derived from `name`.
+fn retag_subset(doc: &mut Document, font_id: ObjectId, name: &[u8]) {
+    let index: usize = std::str::from_utf8(name.get(1..).unwrap_or_default())
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+    let tag = format!("GPFNT{}", (b'A' + (index % 26) as u8) as char);
+
+    let mut ids = vec![font_id];
+    let mut i = 0;
+    while let Some(&id) = ids.get(i) {
+        i += 1;
+        let Ok(dict) = doc.get_object_mut(id).and_then(Object::as_dict_mut) else {
+            continue;
+        };
+        for key in [&b"BaseFont"[..], b"FontName"] {
+            if let Ok(Object::Name(n)) = dict.get_mut(key)
+                && n.len() > 7
+                && n[6] == b'+'
+                && n[..6].iter().all(u8::is_ascii_uppercase)
+            {
+                n[..6].copy_from_slice(tag.as_bytes());
+            }
+        }
+        if let Ok(Object::Reference(desc)) = dict.get(b"FontDescriptor") {
+            ids.push(*desc);
+        }
+        if let Ok(kids) = dict.get(b"DescendantFonts").and_then(Object::as_array) {
+            ids.extend(kids.iter().filter_map(|k| k.as_reference().ok()));
+        }
+    }
+}
+
+/// Sorts dictionary keys recursively so serialization order is independent of the
+/// insertion order printpdf happened to use.
+fn sort_keys(object: &mut Object) {
+    match object {
+        Object::Dictionary(d) => sort_dict(d),
+        Object::Stream(s) => sort_dict(&mut s.dict),
+        Object::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+fn sort_dict(dict: &mut Dictionary) {
+    let mut entries: Vec<(Vec<u8>, Object)> =
+        dict.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.iter_mut().for_each(|(_, v)| sort_keys(v));
+    *dict = entries.into_iter().collect();
+}
+
+/// Renumbers objects 1, 2, 3, … in breadth-first order from the trailer, dropping
+/// unreachable ones.
+fn renumber(doc: &mut Document) {
+    let order = doc.traverse_objects(|_| {});
+    let ids: HashMap<ObjectId, ObjectId> = order
+        .iter()
+        .enumerate()
+        .map(|(i, &old)| (old, (i as u32 + 1, 0)))
+        .collect();
+    let mut objects = std::mem::take(&mut doc.objects);
+    doc.objects = order
+        .iter()
+        .filter_map(|old| {
+            let mut object = objects.remove(old)?;
+            remap(&mut object, &ids);
+            Some((ids[old], object))
+        })
+        .collect();
+    doc.trailer
+        .iter_mut()
+        .for_each(|(_, value)| remap(value, &ids));
+    doc.max_id = order.len() as u32;
+}
+
+fn remap(object: &mut Object, ids: &HashMap<ObjectId, ObjectId>) {
+    match object {
+        Object::Reference(id) => *id = ids.get(id).copied().unwrap_or(*id),
+        Object::Array(items) => items.iter_mut().for_each(|o| remap(o, ids)),
+        Object::Dictionary(d) => d.iter_mut().for_each(|(_, o)| remap(o, ids)),
+        Object::Stream(s) => s.dict.iter_mut().for_each(|(_, o)| remap(o, ids)),
+        _ => {}
+    }
+}
+
+/// FNV-1a over every page's decoded content, as a 128-bit file identifier.
+fn content_hash(doc: &Document) -> u128 {
+    const PRIME: u128 = 0x0000000001000000000000000000013B;
+    doc.get_pages()
+        .into_values()
+        .filter_map(|id| doc.get_and_decode_page_content(id).ok())
+        .filter_map(|c: Content| c.encode().ok())
+        .flatten()
+        .fold(0x6c62272e07bb014262b821756295c58d, |hash, byte| {
+            (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{Stream, content::Operation, dictionary};
+
+    /// A one-page PDF shaped like printpdf output: randomly named font resources,
+    /// a subset-tagged font, wall-clock dates, an XMP stream, and a random ID.
+    fn sample_pdf(font_name: &str, subset: &str, stamp: &str, extra_objects: usize) -> Vec<u8> {
+        let mut doc = Document::with_version("1.7");
+        // Shift object numbers the way differing hash-map order would.
+        (0..extra_objects).for_each(|_| {
+            doc.add_object(Object::Null);
+        });
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "BaseFont" => format!("{subset}+JetBrainsMono-Regular").as_str(),
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { font_name => font_id },
+        });
+        let content = Content {
+            operations: vec![
+                Operation::new("BT", vec![]),
+                Operation::new(
+                    "Tf",
+                    vec![Object::Name(font_name.as_bytes().to_vec()), 10.into()],
+                ),
+                Operation::new("Tj", vec![Object::string_literal("hello")]),
+                Operation::new("ET", vec![]),
+            ],
+        };
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+            "Resources" => resources_id,
+        });
+        doc.objects.insert(
+            pages_id,
+            dictionary! { "Type" => "Pages", "Kids" => vec![page_id.into()], "Count" => 1 }.into(),
+        );
+        let xmp_id = doc.add_object(Stream::new(dictionary! {}, stamp.as_bytes().to_vec()));
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "Metadata" => xmp_id,
+        });
+        let info_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal("repo"),
+            "CreationDate" => Object::string_literal(stamp),
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.trailer.set("Info", info_id);
+        doc.trailer.set(
+            "ID",
+            vec![Object::string_literal(stamp), Object::string_literal(stamp)],
+        );
+        doc.objects.retain(|_, o| !matches!(o, Object::Null));
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn random_names_dates_and_numbering_normalize_identically() {
+        let a = sample_pdf("Fqwerty", "ABCDEF", "D:20240101", 0);
+        let b = sample_pdf("Fzxcvbn", "QWERTY", "D:20250505", 3);
+        assert_ne!(a, b);
+        assert_eq!(normalize(&a, 0).unwrap(), normalize(&b, 0).unwrap());
+    }
+
+    #[test]
+    fn epoch_sets_dates_and_drops_xmp() {
+        let pdf = normalize(&sample_pdf("Fx", "ABCDEF", "now", 0), 1_700_000_000).unwrap();
+        let doc = Document::load_mem(&pdf).unwrap();
+        let info_id = doc.trailer.get(b"Info").unwrap().as_reference().unwrap();
+        let info = doc.get_dictionary(info_id).unwrap();
+        assert_eq!(
+            info.get(b"CreationDate").unwrap().as_str().unwrap(),
+            b"D:20231114221320Z"
+        );
+        assert!(doc.catalog().unwrap().get(b"Metadata").is_err());
+    }
+
+    #[test]
+    fn fonts_are_renamed_in_content_and_resources() {
+        let pdf = normalize(&sample_pdf("Fx", "ABCDEF", "now", 0), 0).unwrap();
+        let doc = Document::load_mem(&pdf).unwrap();
+        let page = *doc.get_pages().get(&1).unwrap();
+        let content = doc.get_and_decode_page_content(page).unwrap();
+        let tf = content
+            .operations
+            .iter()
+            .find(|op| op.operator == "Tf")
+            .unwrap();
+        assert_eq!(tf.operands[0].as_name().unwrap(), b"F0");
+        let fonts = doc.get_page_fonts(page).unwrap();
+        let font = fonts.get(b"F0".as_slice()).unwrap();
+        assert_eq!(
+            font.get(b"BaseFont").unwrap().as_name().unwrap(),
+            b"GPFNTA+JetBrainsMono-Regular"
+        );
+    }
+
+    #[test]
+    fn pdf_date_format() {
+        assert_eq!(pdf_date(0), "D:19700101000000Z");
+    }
+}
@@ -0,0 +1,255 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use printpdf::{Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::format_size;
+
+/// Rows shown in each ranked section.
+const TOP_N: usize = 5;
+
+/// Extensions shown in the histogram; the rest are folded into "other".
+const HISTOGRAM_ROWS: usize = 10;
+
+/// Width of the longest histogram bar, in block characters.
+const BAR_WIDTH: usize = 24;
+
+/// The per-file figures the summary page is computed from.
+#[derive(Debug, Clone)]
+pub struct FileStat {
+    /// Path to the file relative to the repository root.
+    pub path: PathBuf,
+    /// Number of lines in the file.
+    pub lines: usize,
+    /// Size of the printed content in bytes.
+    pub bytes: u64,
+    /// Date the file was last modified (YYYY-MM-DD), or empty if unknown.
+    pub last_modified: String,
+}
+
+/// Files and lines per extension, most lines first. Files without an extension
+/// count under their file name (`Makefile`, `Dockerfile`).
+pub fn extension_histogram(files: &[FileStat]) -> Vec<(String, usize, usize)> {
+    let mut by_ext: HashMap<String, (usize, usize)> = HashMap::new();
+    files.iter().for_each(|f| {
+        let key = f
+            .path
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .or_else(|| f.path.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        let entry = by_ext.entry(key).or_default();
+        entry.0 += 1;
+        entry.1 += f.lines;
+    });
+    let mut rows: Vec<_> = by_ext
+        .into_iter()
+        .map(|(ext, (files, lines))| (ext, files, lines))
+        .collect();
+    rows.sort_by(|a, b| {
+        (Reverse(a.2), Reverse(a.1), &a.0).cmp(&(Reverse(b.2), Reverse(b.1), &b.0))
+    });
+    rows
+}
+
+/// The `TOP_N` files with the greatest `key`, ties in document order.
+fn top_by<K: Ord>(
+    files: &[FileStat],
+    key: impl Fn(&FileStat) -> K,
+) -> impl Iterator<Item = &FileStat> {
+    let mut sorted: Vec<&FileStat> = files.iter().collect();
+    sorted.sort_by_key(|f| Reverse(key(f)));
+    sorted.into_iter().take(TOP_N)
+}
+
+/// Renders the repository summary: totals, the largest, longest, oldest, and
+/// newest files, and a histogram of lines per extension.
+pub fn render(builder: &mut PageBuilder, files: &[FileStat]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    const SIZE: f32 = 7.0;
+
+    builder.set_role("H1");
+    builder.write_centered("Repository Summary", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+
+    let lines: usize = files.iter().map(|f| f.lines).sum();
+    let bytes: u64 = files.iter().map(|f| f.bytes).sum();
+    let average = bytes.checked_div(files.len() as u64).unwrap_or(0);
+    builder.write_line_centered(&[Span {
+        text: format!(
+            "{} file{} \u{00B7} {lines} LOC \u{00B7} {} \u{00B7} {} per file on average",
+            files.len(),
+            if files.len() == 1 { "" } else { "s" },
+            format_size(bytes),
+            format_size(average),
+        ),
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    let mut section = |title: &str, rows: Vec<(String, String)>| {
+        if rows.is_empty() {
+            return;
+        }
+        // Keep a heading with at least its first row.
+        builder.ensure_space(3.0 * builder.line_height());
+        builder.set_role("H2");
+        builder.write_line(&[Span {
+            text: title.to_string(),
+            font_id: bold.clone(),
+            size: Pt(SIZE + 1.0),
+            color: black.clone(),
+        }]);
+        builder.set_role("P");
+        rows.into_iter().for_each(|(left, right)| {
+            builder.write_line_justified(
+                &[Span {
+                    text: format!("  {left}"),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: black.clone(),
+                }],
+                &[Span {
+                    text: right,
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                }],
+            );
+        });
+        builder.gap(8.0);
+    };
+
+    let row = |f: &FileStat, value: String| (crate::display_path(&f.path), value);
+    section(
+        "Largest files",
+        top_by(files, |f| f.bytes)
+            .map(|f| row(f, format_size(f.bytes)))
+            .collect(),
+    );
+    section(
+        "Longest files",
+        top_by(files, |f| f.lines)
+            .map(|f| row(f, format!("{} LOC", f.lines)))
+            .collect(),
+    );
+
+    let mut dated: Vec<&FileStat> = files
+        .iter()
+        .filter(|f| !f.last_modified.is_empty())
+        .collect();
+    dated.sort_by(|a, b| a.last_modified.cmp(&b.last_modified));
+    section(
+        "Oldest files",
+        dated
+            .iter()
+            .take(TOP_N)
+            .map(|f| row(f, f.last_modified.clone()))
+            .collect(),
+    );
+    section(
+        "Newest files",
+        dated
+            .iter()
+            .rev()
+            .take(TOP_N)
+            .map(|f| row(f, f.last_modified.clone()))
+            .collect(),
+    );
+
+    let histogram = extension_histogram(files);
+    let max_lines = histogram.first().map_or(0, |row| row.2).max(1);
+    let (shown, rest) = histogram.split_at(histogram.len().min(HISTOGRAM_ROWS));
+    let mut rows: Vec<(String, String)> = shown
+        .iter()
+        .map(|(ext, n, lines)| {
+            let bar = "\u{2588}".repeat((lines * BAR_WIDTH).div_ceil(max_lines).max(1));
+            (
+                format!("{ext:<12} {bar}"),
+                format!(
+                    "{n} file{} \u{00B7} {lines} LOC",
+                    if *n == 1 { "" } else { "s" }
+                ),
+            )
+        })
+        .collect();
+    if !rest.is_empty() {
+        let (n, lines) = rest
+            .iter()
+            .fold((0, 0), |acc, r| (acc.0 + r.1, acc.1 + r.2));
+        rows.push((
+            format!("{:<12}", "other"),
+            format!(
+                "{n} file{} \u{00B7} {lines} LOC",
+                if n == 1 { "" } else { "s" }
+            ),
+        ));
+    }
+    section("Lines by extension", rows);
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn stat(path: &str, lines: usize, last_modified: &str) -> FileStat {
+        FileStat {
+            path: PathBuf::from(path),
+            lines,
+            bytes: lines as u64 * 30,
+            last_modified: last_modified.to_string(),
+        }
+    }
+
+    #[test]
+    fn histogram_groups_by_extension_most_lines_first() {
+        let files = [
+            stat("src/a.rs", 10, ""),
+            stat("src/b.rs", 20, ""),
+            stat("README.md", 50, ""),
+            stat("Makefile", 5, ""),
+        ];
+        assert_eq!(
+            extension_histogram(&files),
+            vec![
+                (".md".to_string(), 1, 50),
+                (".rs".to_string(), 2, 30),
+                ("Makefile".to_string(), 1, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_fits_one_page() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(
+            &mut builder,
+            &[stat("src/a.rs", 10, "2024-01-01"), stat("b.txt", 3, "")],
+        );
+        assert_eq!(builder.finish().len(), 1);
+    }
+
+    #[test]
+    fn render_empty_repository() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(&mut builder, &[]);
+        assert_eq!(builder.finish().len(), 1);
+    }
+}
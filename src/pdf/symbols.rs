@@ -0,0 +1,146 @@
+use printpdf::{Actions, Color, Destination, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::types::SymbolEntry;
+
+/// Renders the `--symbols` index: definitions grouped under their file in document
+/// order, each row showing kind, name, and line with a linked page number.
+pub fn render(builder: &mut PageBuilder, entries: &[SymbolEntry]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Symbol Index", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+
+    let mut files: Vec<_> = entries.iter().map(|e| &e.path).collect();
+    files.dedup();
+    let summary = match entries.len() {
+        0 => "No functions, types, or classes found".to_string(),
+        n => format!(
+            "{n} symbol{} in {} file{}",
+            if n == 1 { "" } else { "s" },
+            files.len(),
+            if files.len() == 1 { "" } else { "s" },
+        ),
+    };
+    builder.write_line_centered(&[Span {
+        text: summary,
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    const SIZE: f32 = 7.0;
+
+    entries.chunk_by(|a, b| a.path == b.path).for_each(|group| {
+        // Keep a file's heading with at least its first symbol.
+        builder.ensure_space(3.0 * builder.line_height());
+        builder.set_role("H2");
+        builder.write_line(&[Span {
+            text: crate::display_path(&group[0].path),
+            font_id: bold.clone(),
+            size: Pt(SIZE + 1.0),
+            color: black.clone(),
+        }]);
+        builder.set_role("P");
+        group.iter().for_each(|entry| {
+            builder.write_line_justified(
+                &[
+                    Span {
+                        text: format!("  {:<6}", entry.kind.label()),
+                        font_id: regular.clone(),
+                        size: Pt(SIZE),
+                        color: gray.clone(),
+                    },
+                    Span {
+                        text: entry.name.clone(),
+                        font_id: bold.clone(),
+                        size: Pt(SIZE),
+                        color: black.clone(),
+                    },
+                    Span {
+                        text: format!("  :{}", entry.line_number),
+                        font_id: regular.clone(),
+                        size: Pt(SIZE),
+                        color: gray.clone(),
+                    },
+                ],
+                &[Span {
+                    text: format!("p.{}", entry.page_label),
+                    font_id: regular.clone(),
+                    size: Pt(SIZE),
+                    color: gray.clone(),
+                }],
+            );
+            builder.add_link(
+                builder.line_height(),
+                Actions::Goto(Destination::Xyz {
+                    page: entry.page,
+                    left: None,
+                    top: None,
+                    zoom: None,
+                }),
+            );
+        });
+        builder.gap(4.0);
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::pdf;
+    use crate::symbols::SymbolKind;
+    use crate::types::Config;
+
+    fn entry(path: &str, name: &str, page: usize) -> SymbolEntry {
+        SymbolEntry {
+            path: PathBuf::from(path),
+            kind: SymbolKind::Function,
+            name: name.into(),
+            line_number: 1,
+            page,
+            page_label: page.to_string(),
+        }
+    }
+
+    #[test]
+    fn render_links_every_symbol() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(
+            &mut builder,
+            &[
+                entry("a.rs", "new", 3),
+                entry("a.rs", "run", 4),
+                entry("b.rs", "main", 7),
+            ],
+        );
+        let pages = builder.finish();
+        let links = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 3);
+    }
+
+    #[test]
+    fn render_without_symbols() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(&mut builder, &[]);
+        assert_eq!(builder.finish().len(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+use lopdf::{Dictionary, Document, Object, ObjectId, dictionary, text_string};
+
+use crate::error::Result;
+
+/// Turns the marked-content sequences written by a tagging [`super::layout::PageBuilder`]
+/// into a PDF structure tree, producing a tagged PDF readable by screen readers.
+///
+/// printpdf has no structure-tree support, so this runs on the serialized bytes:
+/// - every `BMC /<Role>` becomes `BDC /<Role> <</MCID n>>` and gets a `StructElem`
+///   (one per text run, in content order — which is the reading order);
+/// - consecutive `Figure` runs, even across pages, merge into one figure element and
+///   receive the next entry of `figure_alts` as `/Alt` text;
+/// - `BMC /Artifact` (headers, footers, rules) is left out of the tree;
+/// - the catalog gets `/StructTreeRoot`, `/MarkInfo`, `/Lang`, and a `/ParentTree`
+///   mapping each page's MCIDs back to their elements.
+///
+/// # Errors
+///
+/// Returns an error if `pdf` cannot be parsed or a page's content stream cannot be
+/// decoded or re-encoded.
+pub fn add_structure_tree(pdf: &[u8], figure_alts: &[String]) -> Result<Vec<u8>> {
+    let mut doc = Document::load_mem(pdf)?;
+    let root_id = doc.new_object_id();
+    let document_id = doc.new_object_id();
+
+    // (element id, role, marked-content references)
+    let mut elems: Vec<(ObjectId, Vec<u8>, Vec<Object>)> = Vec::new();
+    let mut parent_tree: Vec<Object> = Vec::new();
+    let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+
+    for (index, &page_id) in pages.iter().enumerate() {
+        let mut content = doc.get_and_decode_page_content(page_id)?;
+        let mut page_parents: Vec<Object> = Vec::new();
+
+        for op in content
+            .operations
+            .iter_mut()
+            .filter(|op| op.operator == "BMC")
+        {
+            let role = op
+                .operands
+                .first()
+                .and_then(|o| o.as_name().ok())
+                .unwrap_or(b"P")
+                .to_vec();
+            if role == b"Artifact" {
+                continue;
+            }
+            let mcid = page_parents.len() as i64;
+            op.operator = "BDC".to_string();
+            op.operands.push(dictionary! { "MCID" => mcid }.into());
+
+            let continues_figure =
+                role == b"Figure" && elems.last().is_some_and(|(_, r, _)| r == b"Figure");
+            if !continues_figure {
+                elems.push((doc.new_object_id(), role, Vec::new()));
+            }
+            if let Some((elem_id, _, kids)) = elems.last_mut() {
+                kids.push(dictionary! { "Type" => "MCR", "Pg" => page_id, "MCID" => mcid }.into());
+                page_parents.push(Object::Reference(*elem_id));
+            }
+        }
+
+        doc.change_page_content(page_id, content.encode()?)?;
+        let page = doc.get_object_mut(page_id)?.as_dict_mut()?;
+        page.set("StructParents", index as i64);
+        page.set("Tabs", "S");
+        parent_tree.push(Object::Integer(index as i64));
+        parent_tree.push(Object::Array(page_parents));
+    }
+
+    let mut alts = figure_alts.iter();
+    let kids: Vec<Object> = elems
+        .into_iter()
+        .map(|(id, role, refs)| {
+            let mut elem = Dictionary::new();
+            elem.set("Type", "StructElem");
+            if role == b"Figure"
+                && let Some(alt) = alts.next()
+            {
+                elem.set("Alt", text_string(alt));
+            }
+            elem.set("S", Object::Name(role));
+            elem.set("P", document_id);
+            elem.set("K", refs);
+            doc.objects.insert(id, elem.into());
+            Object::Reference(id)
+        })
+        .collect();
+
+    doc.objects.insert(
+        document_id,
+        dictionary! { "Type" => "StructElem", "S" => "Document", "P" => root_id, "K" => kids }
+            .into(),
+    );
+    doc.objects.insert(
+        root_id,
+        dictionary! {
+            "Type" => "StructTreeRoot",
+            "K" => document_id,
+            "ParentTree" => dictionary! { "Nums" => parent_tree },
+            "ParentTreeNextKey" => pages.len() as i64,
+        }
+        .into(),
+    );
+
+    let catalog = doc.catalog_mut()?;
+    catalog.set("StructTreeRoot", root_id);
+    catalog.set("MarkInfo", dictionary! { "Marked" => true });
+    catalog.set("Lang", text_string("en"));
+    catalog.set(
+        "ViewerPreferences",
+        dictionary! { "DisplayDocTitle" => true },
+    );
+
+    let mut out = Vec::with_capacity(pdf.len());
+    doc.save_to(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{
+        Stream,
+        content::{Content, Operation},
+    };
+
+    /// Builds a minimal PDF whose page contents carry the given BMC tags.
+    fn pdf_with_tags(pages: &[&[&str]]) -> Vec<u8> {
+        let mut doc = Document::with_version("1.7");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|tags| {
+                let operations: Vec<_> = tags
+                    .iter()
+                    .flat_map(|tag| {
+                        [
+                            Operation::new("BMC", vec![Object::Name(tag.as_bytes().to_vec())]),
+                            Operation::new("EMC", vec![]),
+                        ]
+                    })
+                    .collect();
+                let content = Content { operations };
+                let content_id =
+                    doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "Contents" => content_id,
+                    "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+                })
+                .into()
+            })
+            .collect();
+        let count = kids.len() as i64;
+        doc.objects.insert(
+            pages_id,
+            dictionary! { "Type" => "Pages", "Kids" => kids, "Count" => count }.into(),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        let mut out = Vec::new();
+        doc.save_to(&mut out).unwrap();
+        out
+    }
+
+    fn struct_elems(doc: &Document) -> Vec<&Dictionary> {
+        doc.objects
+            .values()
+            .filter_map(|o| o.as_dict().ok())
+            .filter(|d| d.get(b"Type").and_then(Object::as_name).ok() == Some(b"StructElem"))
+            .filter(|d| d.get(b"S").and_then(Object::as_name).ok() != Some(b"Document"))
+            .collect()
+    }
+
+    #[test]
+    fn builds_structure_tree() {
+        let pdf = pdf_with_tags(&[&["Artifact", "H1", "P", "P"], &["Artifact", "P"]]);
+        let tagged = add_structure_tree(&pdf, &[]).unwrap();
+        let doc = Document::load_mem(&tagged).unwrap();
+
+        let catalog = doc.catalog().unwrap();
+        assert!(catalog.get(b"StructTreeRoot").is_ok());
+        assert!(catalog.get(b"MarkInfo").is_ok());
+        // H1 + 3 × P; artifacts are excluded.
+        assert_eq!(struct_elems(&doc).len(), 4);
+
+        let first_page = *doc.get_pages().get(&1).unwrap();
+        let content = doc.get_and_decode_page_content(first_page).unwrap();
+        let bdc = content
+            .operations
+            .iter()
+            .filter(|op| op.operator == "BDC")
+            .count();
+        assert_eq!(bdc, 3);
+        assert!(content.operations.iter().any(|op| op.operator == "BMC"));
+    }
+
+    #[test]
+    fn figures_merge_across_pages_and_get_alt_text() {
+        let pdf = pdf_with_tags(&[&["H1", "Figure", "Figure"], &["Figure", "P"]]);
+        let tagged = add_structure_tree(&pdf, &["Directory tree".to_string()]).unwrap();
+        let doc = Document::load_mem(&tagged).unwrap();
+
+        let figures: Vec<_> = struct_elems(&doc)
+            .into_iter()
+            .filter(|d| d.get(b"S").and_then(Object::as_name).ok() == Some(b"Figure"))
+            .collect();
+        assert_eq!(figures.len(), 1);
+        assert_eq!(figures[0].get(b"K").unwrap().as_array().unwrap().len(), 3);
+        assert!(figures[0].get(b"Alt").is_ok());
+    }
+
+    #[test]
+    fn invalid_pdf_errors() {
+        assert!(add_structure_tree(b"not a pdf", &[]).is_err());
+    }
+}
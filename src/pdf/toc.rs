@@ -1,8 +1,18 @@
 use std::path::PathBuf;
 
-use printpdf::{Actions, Color, Destination, Pt, Rgb};
+use printpdf::{Actions, Color, Destination, FontId, Pt, Rgb};
 
 use super::layout::{PageBuilder, Span};
+use super::wrap::word_wrap;
+
+/// Indent of the lines a long path continues on.
+const CONTINUATION_INDENT: &str = "    ";
+
+/// The widest page range and page count a row can show. Paths wrap as if the
+/// row's meta were this wide, so the TOC's length doesn't depend on its page
+/// numbers: it is sized before the content is laid out and they are known.
+const WIDEST_RANGE: &str = "p.10000\u{2013}99999";
+const WIDEST_PAGES: &str = "99999 pages";
 
 /// A single entry in the Table of Contents.
 pub struct TocEntry {
@@ -12,96 +22,180 @@ pub struct TocEntry {
     pub line_count: usize,
     /// Human-readable file size (e.g. "4.2 KB").
     pub size_str: String,
-    /// Date the file was last modified (YYYY-MM-DD).
+    /// Date the file was last modified (YYYY-MM-DD); empty when unknown, and left
+    /// out of the row.
     pub last_modified: String,
-    /// PDF page number where this file's content begins.
+    /// Physical PDF page where this file's content begins (link target).
     pub start_page: usize,
+    /// Page number as printed in that page's header (arabic, counted from the
+    /// first content page).
+    pub page_label: String,
+    /// Number of pages the file spans; `0` until it has been rendered.
+    pub pages: usize,
+    /// Number of the file's first line when line numbers run on across files
+    /// (`--global-line-numbers`).
+    pub first_line: Option<usize>,
 }
 
-/// Split `text` into chunks of at most `max_chars` characters each.
-fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
-    if max_chars == 0 || text.is_empty() {
-        return vec![text.to_string()];
-    }
-    let mut chunks = Vec::new();
-    let mut remaining = text;
-    while !remaining.is_empty() {
-        let split_at = remaining
-            .char_indices()
-            .nth(max_chars)
-            .map(|(i, _)| i)
-            .unwrap_or(remaining.len());
-        chunks.push(remaining[..split_at].to_string());
-        remaining = &remaining[split_at..];
+/// Page range as printed in the TOC: `p.12` for a single page, `p.12–19` when
+/// `pages` run on from the arabic `first_label`.
+fn page_range(first_label: &str, pages: usize) -> String {
+    match first_label.parse::<usize>() {
+        Ok(first) if pages > 1 => format!("p.{first}\u{2013}{}", first + pages - 1),
+        _ => format!("p.{first_label}"),
     }
-    chunks
 }
 
 /// Renders the table of contents page with clickable internal links for each entry.
+///
+/// Each file shows the range of pages it spans. Consecutive files in the same
+/// directory are followed by a subtotal row with the directory's page range, page
+/// count, and lines, so one module can be pulled out of a thick printout. A
+/// subtotal row never starts a page on its own. Dates and line counts follow the
+/// builder's locale.
 pub fn render(builder: &mut PageBuilder, entries: &[TocEntry]) {
-    let bold = builder.font(true, false).clone();
-    let regular = builder.font(false, false).clone();
+    let bold = builder.prose_font(true, false).clone();
+    let regular = builder.prose_font(false, false).clone();
     let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
     let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let locale = builder.locale();
 
-    builder.write_centered("Table of Contents", &bold, Pt(16.0), black);
-    builder.vertical_space(10.0);
+    builder.set_role("H1");
+    builder.write_centered("Table of Contents", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(10.0);
+
+    entries
+        .chunk_by(|a, b| a.path.parent() == b.path.parent())
+        .for_each(|run| {
+            run.iter().enumerate().for_each(|(i, entry)| {
+                let first_line = entry
+                    .first_line
+                    .map(|n| format!("l.{n}  "))
+                    .unwrap_or_default();
+                let date = match entry.last_modified.as_str() {
+                    "" => String::new(),
+                    date => format!(" \u{00B7} {}", locale.date(date)),
+                };
+                let meta = |range: &str| {
+                    format!(
+                        "{range}  {first_line}{} LOC \u{00B7} {}{date}",
+                        locale.number(entry.line_count as u64),
+                        entry.size_str,
+                    )
+                };
+                let path = crate::display_path(&entry.path);
+                // The directory's last file carries its subtotal row with it.
+                let keep = usize::from(run.len() > 1 && i + 1 == run.len());
+                write_row(
+                    builder,
+                    &path,
+                    meta(&page_range(&entry.page_label, entry.pages)),
+                    &meta(WIDEST_RANGE),
+                    &regular,
+                    &gray,
+                    entry.start_page,
+                    keep,
+                );
+            });
+            if let [first, .., last] = run {
+                let pages = (last.start_page + last.pages.max(1)).saturating_sub(first.start_page);
+                let lines: usize = run.iter().map(|e| e.line_count).sum();
+                let dir = first
+                    .path
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .map_or_else(
+                        || "./".to_string(),
+                        |dir| format!("{}/", crate::display_path(dir)),
+                    );
+                let lines = locale.number(lines as u64);
+                let meta = format!(
+                    "{} \u{00B7} {pages} page{} \u{00B7} {lines} LOC",
+                    page_range(&first.page_label, pages),
+                    if pages == 1 { "" } else { "s" },
+                );
+                let widest = format!("{WIDEST_RANGE} \u{00B7} {WIDEST_PAGES} \u{00B7} {lines} LOC");
+                write_row(
+                    builder,
+                    &dir,
+                    meta,
+                    &widest,
+                    &bold,
+                    &black,
+                    first.start_page,
+                    0,
+                );
+                builder.gap(4.0);
+            }
+        });
 
-    // Approximate character width factors (monospace font approximation).
+    builder.page_break();
+}
+
+/// Writes one TOC row — `path` on the left, wrapping after a `/` and continuing
+/// indented if needed, `meta` on the right — linked to `page`. The path wraps
+/// as if `meta` were at least as wide as `widest_meta`. The row is kept on one
+/// page together with the `keep_with_next` lines that follow it.
+#[allow(clippy::too_many_arguments)]
+fn write_row(
+    builder: &mut PageBuilder,
+    path: &str,
+    meta: String,
+    widest_meta: &str,
+    font: &FontId,
+    color: &Color,
+    page: usize,
+    keep_with_next: usize,
+) {
     const PATH_SIZE: f32 = 8.0;
     const META_SIZE: f32 = 7.0;
-    const CHAR_WIDTH: f32 = 0.6;
     const GAP_PT: f32 = 8.0;
 
-    entries.iter().for_each(|entry| {
-        let meta = format!(
-            "p.{}  {} LOC \u{00B7} {} \u{00B7} {}",
-            entry.start_page, entry.line_count, entry.size_str, entry.last_modified
-        );
-        let meta_width = meta.len() as f32 * META_SIZE * CHAR_WIDTH;
-        let available_left = builder.usable_width_pt() - meta_width - GAP_PT;
-        let max_chars = (available_left / (PATH_SIZE * CHAR_WIDTH)).max(1.0) as usize;
-
-        let path_str = entry.path.display().to_string();
-        let chunks = wrap_text(&path_str, max_chars);
-        let row_count = chunks.len();
-
-        // First chunk shares the line with meta; remaining chunks are on their own lines.
-        builder.write_line_justified(
-            &[Span {
-                text: chunks[0].clone(),
-                font_id: regular.clone(),
-                size: Pt(PATH_SIZE),
-                color: gray.clone(),
-            }],
-            &[Span {
-                text: meta,
-                font_id: regular.clone(),
-                size: Pt(META_SIZE),
-                color: gray.clone(),
-            }],
-        );
-        chunks[1..].iter().for_each(|chunk| {
-            builder.write_line(&[Span {
-                text: chunk.clone(),
-                font_id: regular.clone(),
-                size: Pt(PATH_SIZE),
-                color: gray.clone(),
-            }]);
-        });
+    let meta_font = builder.prose_font(false, false).clone();
+    let meta_width = builder
+        .text_width(&meta, &meta_font, Pt(META_SIZE))
+        .max(builder.text_width(widest_meta, &meta_font, Pt(META_SIZE)));
+    let available_left = builder.usable_width_pt() - meta_width - GAP_PT;
+    let chunks = word_wrap(path, available_left, |c| {
+        builder.text_width(c, font, Pt(PATH_SIZE))
+    });
+    let row_count = chunks.len();
+    builder.ensure_space((row_count + keep_with_next) as f32 * builder.line_height());
 
-        builder.add_link(
-            builder.line_height() * row_count as f32,
-            Actions::Goto(Destination::Xyz {
-                page: entry.start_page,
-                left: None,
-                top: None,
-                zoom: None,
-            }),
-        );
+    // First chunk shares the line with meta; remaining chunks are on their own lines.
+    builder.write_line_justified(
+        &[Span {
+            text: chunks[0].clone(),
+            font_id: font.clone(),
+            size: Pt(PATH_SIZE),
+            color: color.clone(),
+        }],
+        &[Span {
+            text: meta,
+            font_id: meta_font.clone(),
+            size: Pt(META_SIZE),
+            color: color.clone(),
+        }],
+    );
+    chunks[1..].iter().for_each(|chunk| {
+        builder.write_line(&[Span {
+            text: format!("{CONTINUATION_INDENT}{chunk}"),
+            font_id: font.clone(),
+            size: Pt(PATH_SIZE),
+            color: color.clone(),
+        }]);
     });
 
-    builder.page_break();
+    builder.add_link(
+        builder.line_height() * row_count as f32,
+        Actions::Goto(Destination::Xyz {
+            page,
+            left: None,
+            top: None,
+            zoom: None,
+        }),
+    );
 }
 
 #[cfg(test)]
@@ -117,31 +211,130 @@ mod tests {
             size_str: "1.2 KB".to_string(),
             last_modified: "2024-01-15".to_string(),
             start_page: page,
+            page_label: page.to_string(),
+            pages: 1,
+            first_line: None,
         }
     }
 
     #[test]
-    fn wrap_text_short() {
-        let chunks = super::wrap_text("short", 20);
-        assert_eq!(chunks, vec!["short"]);
+    fn page_range_spans_the_file() {
+        assert_eq!(super::page_range("12", 8), "p.12\u{2013}19");
+        assert_eq!(super::page_range("12", 1), "p.12");
+        assert_eq!(super::page_range("12", 0), "p.12");
+        assert_eq!(super::page_range("iv", 3), "p.iv");
+    }
+
+    #[test]
+    fn directories_get_subtotal_rows() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let mut entries = vec![
+            make_entry("README.md", 10, 1),
+            make_entry("src/lib.rs", 50, 2),
+            make_entry("src/main.rs", 20, 5),
+        ];
+        entries[1].pages = 3;
+        super::render(&mut builder, &entries);
+        let texts: Vec<String> = builder.finish()[0]
+            .ops
+            .iter()
+            .filter_map(|op| match op {
+                printpdf::Op::ShowText { items } => match items.as_slice() {
+                    [printpdf::TextItem::Text(t)] => Some(t.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert!(texts.iter().any(|t| t.starts_with("p.2\u{2013}4  50 LOC")));
+        let subtotal = texts
+            .iter()
+            .position(|t| t == "src/")
+            .expect("src/ subtotal");
+        assert_eq!(
+            texts[subtotal + 1],
+            "p.2\u{2013}5 \u{00B7} 4 pages \u{00B7} 70 LOC"
+        );
+        assert!(
+            !texts.iter().any(|t| t == "./"),
+            "a lone file gets no subtotal"
+        );
+    }
+
+    #[test]
+    fn dates_and_counts_follow_the_locale() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts)
+            .with_locale(crate::locale::Locale::De);
+        super::render(&mut builder, &[make_entry("main.rs", 12_345, 3)]);
+        let shown = builder.finish()[0].ops.iter().any(|op| {
+            matches!(op, printpdf::Op::ShowText { items }
+                if matches!(items.as_slice(), [printpdf::TextItem::Text(t)]
+                    if t == "p.3  12.345 LOC \u{00B7} 1.2 KB \u{00B7} 15.01.2024"))
+        });
+        assert!(shown);
     }
 
     #[test]
-    fn wrap_text_exact() {
-        let chunks = super::wrap_text("1234567890", 10);
-        assert_eq!(chunks, vec!["1234567890"]);
+    fn unknown_dates_are_left_out() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let mut entry = make_entry("main.rs", 12, 3);
+        entry.last_modified.clear();
+        super::render(&mut builder, &[entry]);
+        let shown = builder.finish()[0].ops.iter().any(|op| {
+            matches!(op, printpdf::Op::ShowText { items }
+                if matches!(items.as_slice(), [printpdf::TextItem::Text(t)]
+                    if t == "p.3  12 LOC \u{00B7} 1.2 KB"))
+        });
+        assert!(shown);
     }
 
     #[test]
-    fn wrap_text_overflow() {
-        let chunks = super::wrap_text("1234567890ab", 10);
-        assert_eq!(chunks, vec!["1234567890", "ab"]);
+    fn row_moves_to_keep_its_subtotal() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let font = builder.prose_font(false, false).clone();
+        let gray = printpdf::Color::Rgb(printpdf::Rgb::new(0.5, 0.5, 0.5, None));
+        // Room for the row alone, not for the subtotal after it.
+        builder.vertical_space(builder.remaining_pt() - 1.5 * builder.line_height());
+        super::write_row(&mut builder, "a.rs", "p.1".into(), "", &font, &gray, 1, 1);
+        assert_eq!(builder.current_page(), 2);
+        super::write_row(&mut builder, "b.rs", "p.2".into(), "", &font, &gray, 2, 0);
+        assert_eq!(builder.finish().len(), 2);
     }
 
     #[test]
-    fn wrap_text_empty() {
-        let chunks = super::wrap_text("", 10);
-        assert_eq!(chunks, vec![""]);
+    fn page_numbers_never_change_the_length() {
+        // Paths one segment apart, so some sit right at the wrap width.
+        let entries = |label: &str, pages: usize| -> Vec<super::TocEntry> {
+            (10..40)
+                .map(|n| super::TocEntry {
+                    page_label: label.to_string(),
+                    pages,
+                    ..make_entry(&format!("src/{}mod.rs", "ab/".repeat(n)), 10, 1)
+                })
+                .collect()
+        };
+        let render = |entries: &[super::TocEntry]| {
+            let mut doc = printpdf::PdfDocument::new("test");
+            let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+            let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+            super::render(&mut builder, entries);
+            let pages = builder.finish();
+            let lines = pages
+                .iter()
+                .flat_map(|page| &page.ops)
+                .filter(|op| matches!(op, printpdf::Op::ShowText { .. }))
+                .count();
+            (pages.len(), lines)
+        };
+        assert_eq!(render(&entries("0", 0)), render(&entries("12345", 40)));
     }
 
     #[test]
@@ -50,8 +50,11 @@ pub fn render(builder: &mut PageBuilder, paths: &[PathBuf]) {
     let regular = builder.font(false, false).clone();
     let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
 
+    builder.set_role("H1");
     builder.write_centered("File Tree", &bold, Pt(16.0), black.clone());
-    builder.vertical_space(10.0);
+    builder.gap(10.0);
+    // Consecutive Figure runs are merged into one figure with alt text when tagged.
+    builder.set_role("Figure");
 
     let mut root = Tree::new();
     paths.iter().for_each(|p| {
@@ -71,6 +74,7 @@ pub fn render(builder: &mut PageBuilder, paths: &[PathBuf]) {
         }]);
     });
 
+    builder.set_role("P");
     builder.page_break();
 }
 
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use printpdf::{Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// Renders the notice that closes a document cut short by `--max-pages` or
+/// `--max-output-size`: how many files made it in, which limit stopped the rest,
+/// and the paths left out.
+pub fn render(
+    builder: &mut PageBuilder,
+    rendered: usize,
+    total: usize,
+    limit: &str,
+    omitted: &[PathBuf],
+) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+
+    builder.set_role("H1");
+    builder.write_centered("Output Truncated", &bold, Pt(16.0), black.clone());
+    builder.set_role("P");
+    builder.gap(4.0);
+    builder.write_line_centered(&[Span {
+        text: format!("{rendered} of {total} files rendered \u{00B7} {limit} reached"),
+        font_id: regular.clone(),
+        size: Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.gap(10.0);
+
+    omitted.iter().for_each(|path| {
+        builder.write_line(&[Span {
+            text: crate::display_path(path),
+            font_id: regular.clone(),
+            size: Pt(7.0),
+            color: black.clone(),
+        }]);
+    });
+
+    builder.page_break();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn render_lists_the_omitted_files() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let omitted: Vec<PathBuf> = (0..150)
+            .map(|i| PathBuf::from(format!("f{i}.rs")))
+            .collect();
+        render(&mut builder, 2, 152, "--max-pages 10", &omitted);
+        assert_eq!(builder.finish().len(), 2);
+    }
+}
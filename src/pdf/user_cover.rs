@@ -1,7 +1,8 @@
 use printpdf::{Actions, Color, Pt, Rgb};
 
 use super::layout::{PageBuilder, Span};
-use crate::github::GitHubUser;
+use super::wrap::word_wrap;
+use crate::github::{GitHubOrg, GitHubUser};
 
 const CRATES_URL: &str = "https://crates.io/crates/gitprint";
 const LABEL_COL: usize = 14;
@@ -12,31 +13,26 @@ fn separator_line(width_pt: f32, font_size: f32) -> String {
     "─".repeat(chars)
 }
 
-/// Word-wrap `text` into lines of at most `max_chars` characters, breaking at word boundaries.
-fn word_wrap(text: &str, max_chars: usize) -> Vec<String> {
-    if max_chars == 0 {
-        return vec![text.to_string()];
-    }
-    let (mut lines, last) = text.split_whitespace().fold(
-        (Vec::<String>::new(), String::new()),
-        |(mut lines, mut cur), word| {
-            if !cur.is_empty() && cur.len() + 1 + word.len() > max_chars {
-                lines.push(std::mem::take(&mut cur));
-            } else if !cur.is_empty() {
-                cur.push(' ');
-            }
-            cur.push_str(word);
-            (lines, cur)
-        },
-    );
-    if !last.is_empty() {
-        lines.push(last);
-    }
-    lines
+/// Contribution totals for the strip under the cover's metadata table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ContributionStats {
+    /// Contributions over the report period.
+    pub total: u64,
+    /// Consecutive days with contributions up to the end of the period.
+    pub current_streak: usize,
+    /// Most consecutive days with contributions in the period.
+    pub longest_streak: usize,
 }
 
-/// Renders the user report cover page with profile info, metadata table, and footer.
-pub fn render(builder: &mut PageBuilder, user: &GitHubUser, total_stars: u64) {
+/// Renders the user report cover page with profile info, metadata table, an optional
+/// contributions strip, and footer.
+pub fn render(
+    builder: &mut PageBuilder,
+    user: &GitHubUser,
+    total_stars: u64,
+    orgs: &[GitHubOrg],
+    contributions: Option<&ContributionStats>,
+) {
     let bold = builder.font(true, false).clone();
     let regular = builder.font(false, false).clone();
     let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
@@ -80,20 +76,23 @@ pub fn render(builder: &mut PageBuilder, user: &GitHubUser, total_stars: u64) {
         .unwrap_or(&user.created_at)
         .to_string();
 
-    let value_col_max_chars = ((builder.usable_width_pt()
-        - LABEL_COL as f32 * TABLE_SIZE * CHAR_WIDTH)
-        / (TABLE_SIZE * CHAR_WIDTH))
-        .max(1.0) as usize;
+    let value_width = builder.usable_width_pt() - LABEL_COL as f32 * TABLE_SIZE * CHAR_WIDTH;
 
     let email_url = user.email.as_ref().map(|e| format!("mailto:{e}"));
     let repos_url = format!("{}?tab=repositories", user.html_url);
     let followers_url = format!("{}?tab=followers", user.html_url);
     let following_url = format!("{}?tab=following", user.html_url);
+    let orgs_str = orgs
+        .iter()
+        .map(|o| o.login.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
 
     [
         ("Bio", user.bio.as_deref().unwrap_or(""), None::<String>),
         ("Location", user.location.as_deref().unwrap_or(""), None),
         ("Company", user.company.as_deref().unwrap_or(""), None),
+        ("Organizations", &orgs_str, None),
         (
             "Blog",
             user.blog.as_deref().unwrap_or(""),
@@ -120,38 +119,83 @@ pub fn render(builder: &mut PageBuilder, user: &GitHubUser, total_stars: u64) {
     .into_iter()
     .filter(|(_, value, _)| !value.is_empty())
     .for_each(|(label, value, url)| {
-        word_wrap(value, value_col_max_chars)
-            .into_iter()
-            .enumerate()
-            .for_each(|(i, line)| {
-                let label_text = if i == 0 {
-                    format!("{label:<LABEL_COL$}")
-                } else {
-                    " ".repeat(LABEL_COL)
-                };
-                builder.write_line(&[
-                    Span {
-                        text: label_text,
-                        font_id: bold.clone(),
-                        size: Pt(TABLE_SIZE),
-                        color: black.clone(),
-                    },
-                    Span {
-                        text: line,
-                        font_id: regular.clone(),
-                        size: Pt(TABLE_SIZE),
-                        color: black.clone(),
-                    },
-                ]);
-            });
+        let lines = word_wrap(value, value_width, |c| {
+            builder.text_width(c, &regular, Pt(TABLE_SIZE))
+        });
+        let rows = lines.len();
+        builder.ensure_space(lh * rows as f32);
+        lines.into_iter().enumerate().for_each(|(i, line)| {
+            let label_text = if i == 0 {
+                format!("{label:<LABEL_COL$}")
+            } else {
+                " ".repeat(LABEL_COL)
+            };
+            builder.write_line(&[
+                Span {
+                    text: label_text,
+                    font_id: bold.clone(),
+                    size: Pt(TABLE_SIZE),
+                    color: black.clone(),
+                },
+                Span {
+                    text: line,
+                    font_id: regular.clone(),
+                    size: Pt(TABLE_SIZE),
+                    color: black.clone(),
+                },
+            ]);
+        });
         if let Some(u) = url {
-            builder.add_link(lh, Actions::Uri(u));
+            builder.add_link(lh * rows as f32, Actions::Uri(u));
         }
     });
 
     builder.vertical_space(4.0);
     builder.draw_horizontal_rule(Color::Rgb(Rgb::new(0.75, 0.75, 0.75, None)), 0.5);
 
+    // ── Contributions strip ────────────────────────────────────────────────────
+    if let Some(stats) = contributions {
+        let days = |n: usize| format!(" day{}", if n == 1 { "" } else { "s" });
+        let spans: Vec<Span> = [
+            (stats.total.to_string(), " contributions".to_string()),
+            (
+                stats.current_streak.to_string(),
+                days(stats.current_streak) + " current streak",
+            ),
+            (
+                stats.longest_streak.to_string(),
+                days(stats.longest_streak) + " longest streak",
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, (figure, label))| {
+            let separator = (i > 0).then(|| Span {
+                text: "  \u{00B7}  ".to_string(),
+                font_id: regular.clone(),
+                size: Pt(TABLE_SIZE),
+                color: gray.clone(),
+            });
+            separator.into_iter().chain([
+                Span {
+                    text: figure,
+                    font_id: bold.clone(),
+                    size: Pt(TABLE_SIZE),
+                    color: black.clone(),
+                },
+                Span {
+                    text: label,
+                    font_id: regular.clone(),
+                    size: Pt(TABLE_SIZE),
+                    color: gray.clone(),
+                },
+            ])
+        })
+        .collect();
+        builder.vertical_space(8.0);
+        builder.write_line_centered(&spans);
+    }
+
     // ── Footer ─────────────────────────────────────────────────────────────────
     let version = env!("CARGO_PKG_VERSION");
     let footer_text =
@@ -205,12 +249,17 @@ mod tests {
             last_repos: 5,
             last_commits: 5,
             no_diffs: false,
+            diff_context: 3,
+            full_diffs: false,
+            full_diffs_limit: 100,
             font_size: 8.0,
             github_token: None,
+            cache: None,
             since: None,
             until: None,
             activity: crate::types::ActivityFilter::All,
             events: 30,
+            format: crate::types::ReportFormat::Pdf,
         }
     }
 
@@ -220,7 +269,23 @@ mod tests {
         let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
         let config = Config::test_default();
         let mut builder = pdf::create_builder(&config, fonts);
-        super::render(&mut builder, &test_user(), 1337);
+        let orgs = [GitHubOrg {
+            login: "acme".to_string(),
+            description: None,
+            role: None,
+        }];
+        let contributions = ContributionStats {
+            total: 812,
+            current_streak: 4,
+            longest_streak: 31,
+        };
+        super::render(
+            &mut builder,
+            &test_user(),
+            1337,
+            &orgs,
+            Some(&contributions),
+        );
         assert!(!builder.finish().is_empty());
     }
 
@@ -232,7 +297,7 @@ mod tests {
         let mut builder = pdf::create_builder(&config, fonts);
         let mut user = test_user();
         user.name = None;
-        super::render(&mut builder, &user, 0);
+        super::render(&mut builder, &user, 0, &[], None);
         assert!(!builder.finish().is_empty());
     }
 
@@ -256,7 +321,7 @@ mod tests {
             created_at: "2020-01-01T00:00:00Z".to_string(),
             html_url: "https://github.com/bob".to_string(),
         };
-        super::render(&mut builder, &user, 0);
+        super::render(&mut builder, &user, 0, &[], None);
         assert!(!builder.finish().is_empty());
     }
 }
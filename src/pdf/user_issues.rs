@@ -0,0 +1,174 @@
+use printpdf::{Actions, Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// Issue activity in one repository over the report window.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IssueRepoStats {
+    /// `owner/repo`.
+    pub repo: String,
+    /// Issues opened (or reopened).
+    pub opened: usize,
+    /// Issues closed.
+    pub closed: usize,
+    /// Comments left on issues.
+    pub commented: usize,
+}
+
+impl IssueRepoStats {
+    /// Events counted for the repository.
+    pub fn total(&self) -> usize {
+        self.opened + self.closed + self.commented
+    }
+}
+
+/// An issue the user took part in, with how often.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct IssueThread {
+    /// `owner/repo`.
+    pub repo: String,
+    /// Issue number.
+    pub number: u64,
+    /// Issue title.
+    pub title: String,
+    /// Link to the issue on GitHub.
+    pub html_url: String,
+    /// Events the user generated on the issue.
+    pub events: usize,
+}
+
+/// A user's issue activity: per-repository counts and the busiest threads.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IssueStats {
+    /// Repositories with issue activity, busiest first.
+    pub repos: Vec<IssueRepoStats>,
+    /// Issues with the most activity, busiest first.
+    pub threads: Vec<IssueThread>,
+}
+
+/// Widest repository name in the table, in characters.
+const REPO_WIDTH: usize = 40;
+
+/// Renders the "Issues" section: an opened / closed / commented table per
+/// repository, then the most active threads, each linking to the issue.
+pub fn render(builder: &mut PageBuilder, stats: &IssueStats) {
+    if stats.repos.is_empty() {
+        return;
+    }
+
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let rule_gray = Color::Rgb(Rgb::new(0.85, 0.85, 0.85, None));
+
+    builder.ensure_space(builder.line_height() * 6.0);
+    builder.write_centered("Issues", &bold, Pt(14.0), black.clone());
+    builder.vertical_space(8.0);
+    builder.draw_horizontal_rule(rule_gray.clone(), 0.5);
+    builder.vertical_space(8.0);
+
+    let width = stats
+        .repos
+        .iter()
+        .map(|r| r.repo.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp("Repository".len(), REPO_WIDTH);
+    let cell = |text: String, bold_cell: bool, color: &Color| Span {
+        text,
+        font_id: (if bold_cell { &bold } else { &regular }).clone(),
+        size: Pt(8.0),
+        color: color.clone(),
+    };
+    builder.write_line(&[cell(
+        format!(
+            "{:<width$}  {:>6}  {:>6}  {:>8}",
+            "Repository", "Opened", "Closed", "Comments"
+        ),
+        true,
+        &black,
+    )]);
+    stats.repos.iter().for_each(|r| {
+        let name: String = r.repo.chars().take(width).collect();
+        builder.write_line(&[
+            cell(format!("{name:<width$}  "), false, &black),
+            cell(
+                format!("{:>6}  {:>6}  {:>8}", r.opened, r.closed, r.commented),
+                false,
+                &gray,
+            ),
+        ]);
+    });
+
+    if !stats.threads.is_empty() {
+        builder.vertical_space(6.0);
+        builder.write_line(&[cell("Most active threads".to_string(), true, &black)]);
+        stats.threads.iter().for_each(|t| {
+            builder.write_line_justified(
+                &[cell(format!("  #{} {}", t.number, t.title), false, &black)],
+                &[cell(
+                    format!(
+                        "{} \u{00B7} {} event{}",
+                        t.repo,
+                        t.events,
+                        if t.events == 1 { "" } else { "s" }
+                    ),
+                    false,
+                    &gray,
+                )],
+            );
+            if !t.html_url.is_empty() {
+                builder.add_link(builder.line_height(), Actions::Uri(t.html_url.clone()));
+            }
+        });
+    }
+
+    builder.vertical_space(12.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn render_links_each_thread() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let stats = IssueStats {
+            repos: vec![IssueRepoStats {
+                repo: "alice/a".to_string(),
+                opened: 2,
+                closed: 1,
+                commented: 4,
+            }],
+            threads: vec![IssueThread {
+                repo: "alice/a".to_string(),
+                number: 12,
+                title: "Crash on empty input".to_string(),
+                html_url: "https://github.com/alice/a/issues/12".to_string(),
+                events: 3,
+            }],
+        };
+        render(&mut builder, &stats);
+        let links = builder.finish()[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 1);
+    }
+
+    #[test]
+    fn render_skips_empty_stats() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let page = builder.current_page();
+        render(&mut builder, &IssueStats::default());
+        assert_eq!(builder.current_page(), page);
+    }
+}
@@ -0,0 +1,87 @@
+use printpdf::{Actions, Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+use crate::github::GitHubOrg;
+
+/// Renders the "Organizations" section: one entry per organization with its name
+/// (linking to its GitHub page), the user's role when known, and its description.
+pub fn render(builder: &mut PageBuilder, orgs: &[GitHubOrg]) {
+    if orgs.is_empty() {
+        return;
+    }
+
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let italic = builder.font(false, true).clone();
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let rule_gray = Color::Rgb(Rgb::new(0.85, 0.85, 0.85, None));
+
+    builder.ensure_space(builder.line_height() * 4.0);
+    builder.write_centered("Organizations", &bold, Pt(14.0), black.clone());
+    builder.vertical_space(8.0);
+    builder.draw_horizontal_rule(rule_gray, 0.5);
+    builder.vertical_space(8.0);
+
+    orgs.iter().for_each(|org| {
+        builder.ensure_space(builder.line_height() * 2.0);
+        builder.write_line_justified(
+            &[Span {
+                text: org.login.clone(),
+                font_id: bold.clone(),
+                size: Pt(9.0),
+                color: black.clone(),
+            }],
+            &[Span {
+                text: org.role.clone().unwrap_or_default(),
+                font_id: regular.clone(),
+                size: Pt(8.0),
+                color: gray.clone(),
+            }],
+        );
+        builder.add_link(
+            builder.line_height(),
+            Actions::Uri(format!("https://github.com/{}", org.login)),
+        );
+        if let Some(description) = org.description.as_deref().filter(|d| !d.is_empty()) {
+            builder.write_line(&[Span {
+                text: format!("  {description}"),
+                font_id: italic.clone(),
+                size: Pt(7.5),
+                color: gray.clone(),
+            }]);
+        }
+        builder.vertical_space(4.0);
+    });
+
+    builder.vertical_space(8.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    #[test]
+    fn render_links_each_org() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let org = |login: &str, role: Option<&str>| GitHubOrg {
+            login: login.to_string(),
+            description: Some("Widgets".to_string()),
+            role: role.map(str::to_string),
+        };
+        render(
+            &mut builder,
+            &[org("acme", Some("admin")), org("tools", None)],
+        );
+        let links = builder.finish()[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 2);
+    }
+}
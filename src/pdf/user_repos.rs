@@ -5,6 +5,10 @@ use printpdf::{Actions, Color, Pt, Rgb};
 use super::layout::{PageBuilder, Span};
 use crate::github::{GitHubEvent, GitHubRepo};
 
+/// Lines reserved for a repository entry before it is started, so its name is not
+/// separated from its details; a section title also keeps the first entry with it.
+const ENTRY_LINES: f32 = 5.0;
+
 /// Renders a titled section listing repositories with stats and recent activity context.
 pub fn render(
     builder: &mut PageBuilder,
@@ -42,7 +46,7 @@ pub fn render(
     let gold = Color::Rgb(Rgb::new(0.90, 0.72, 0.10, None));
     let rule_gray = Color::Rgb(Rgb::new(0.85, 0.85, 0.85, None));
 
-    builder.ensure_space(builder.line_height() * 3.0);
+    builder.ensure_space(builder.line_height() * (3.0 + ENTRY_LINES) + 16.0);
     builder.write_centered(title, &bold, Pt(14.0), black.clone());
     builder.vertical_space(8.0);
     builder.draw_horizontal_rule(rule_gray.clone(), 0.5);
@@ -56,7 +60,7 @@ pub fn render(
             builder.vertical_space(8.0);
         }
 
-        builder.ensure_space(builder.line_height() * 5.0);
+        builder.ensure_space(builder.line_height() * ENTRY_LINES);
 
         // ── Row 1: name (left) + stats (right) ─────────────────────────────
         let fork_tag = if repo.fork { " [fork]" } else { "" };
@@ -298,6 +302,31 @@ mod tests {
         assert!(!builder.finish().is_empty());
     }
 
+    #[test]
+    fn title_moves_with_its_first_repo() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let config = Config::test_default();
+        let mut builder = pdf::create_builder(&config, fonts);
+        // Room for the title, but not for an entry beneath it.
+        builder.vertical_space(builder.remaining_pt() - 4.0 * builder.line_height());
+        super::render(
+            &mut builder,
+            "Top Starred Repositories",
+            &[test_repo("gitprint", 500)],
+            &[],
+            &std::collections::HashMap::new(),
+        );
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 2);
+        assert!(
+            !pages[0]
+                .ops
+                .iter()
+                .any(|op| matches!(op, printpdf::Op::ShowText { .. }))
+        );
+    }
+
     #[test]
     fn render_repos_empty_is_noop() {
         let mut doc = printpdf::PdfDocument::new("test");
@@ -0,0 +1,176 @@
+use printpdf::{Actions, Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// A user's pull-request review activity over the report window.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct ReviewStats {
+    /// Distinct pull requests reviewed.
+    pub prs_reviewed: u64,
+    /// Reviews submitted, whatever their verdict.
+    pub reviews: usize,
+    /// Reviews that approved the pull request.
+    pub approvals: usize,
+    /// Reviews that requested changes.
+    pub changes_requested: usize,
+    /// Inline review comments left.
+    pub comments: usize,
+    /// Repositories with the most reviewed pull requests, busiest first.
+    pub top_repos: Vec<(String, usize)>,
+}
+
+impl ReviewStats {
+    /// Whether there is anything to report.
+    pub fn is_empty(&self) -> bool {
+        self.prs_reviewed == 0 && self.reviews == 0 && self.comments == 0
+    }
+
+    /// Inline comments per submitted review, or `None` before the first review.
+    pub fn comments_per_review(&self) -> Option<f64> {
+        (self.reviews > 0).then(|| self.comments as f64 / self.reviews as f64)
+    }
+}
+
+/// Renders the "Code Review" section: review counts by verdict, comment density,
+/// and the repositories reviewed most, each linking to its pull requests.
+pub fn render(builder: &mut PageBuilder, stats: &ReviewStats) {
+    if stats.is_empty() {
+        return;
+    }
+
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let rule_gray = Color::Rgb(Rgb::new(0.85, 0.85, 0.85, None));
+
+    builder.ensure_space(builder.line_height() * 8.0);
+    builder.write_centered("Code Review", &bold, Pt(14.0), black.clone());
+    builder.vertical_space(8.0);
+    builder.draw_horizontal_rule(rule_gray.clone(), 0.5);
+    builder.vertical_space(8.0);
+
+    let row = |builder: &mut PageBuilder, label: &str, value: String| {
+        builder.write_line_justified(
+            &[Span {
+                text: label.to_string(),
+                font_id: regular.clone(),
+                size: Pt(8.5),
+                color: black.clone(),
+            }],
+            &[Span {
+                text: value,
+                font_id: bold.clone(),
+                size: Pt(8.5),
+                color: black.clone(),
+            }],
+        );
+    };
+    row(
+        builder,
+        "Pull requests reviewed",
+        stats.prs_reviewed.to_string(),
+    );
+    row(builder, "Reviews submitted", stats.reviews.to_string());
+    row(builder, "Approvals", stats.approvals.to_string());
+    row(
+        builder,
+        "Changes requested",
+        stats.changes_requested.to_string(),
+    );
+    row(
+        builder,
+        "Comment-only reviews",
+        stats
+            .reviews
+            .saturating_sub(stats.approvals + stats.changes_requested)
+            .to_string(),
+    );
+    row(
+        builder,
+        "Review comments per review",
+        stats
+            .comments_per_review()
+            .map_or_else(|| "\u{2014}".to_string(), |avg| format!("{avg:.1}")),
+    );
+
+    if !stats.top_repos.is_empty() {
+        builder.vertical_space(6.0);
+        builder.write_line(&[Span {
+            text: "Most reviewed repositories".to_string(),
+            font_id: bold.clone(),
+            size: Pt(9.0),
+            color: black.clone(),
+        }]);
+        stats.top_repos.iter().for_each(|(repo, prs)| {
+            builder.write_line_justified(
+                &[Span {
+                    text: format!("  {repo}"),
+                    font_id: regular.clone(),
+                    size: Pt(8.0),
+                    color: black.clone(),
+                }],
+                &[Span {
+                    text: format!("{prs} PR{}", if *prs == 1 { "" } else { "s" }),
+                    font_id: regular.clone(),
+                    size: Pt(8.0),
+                    color: gray.clone(),
+                }],
+            );
+            builder.add_link(
+                builder.line_height(),
+                Actions::Uri(format!("https://github.com/{repo}/pulls")),
+            );
+        });
+    }
+
+    builder.vertical_space(12.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn stats() -> ReviewStats {
+        ReviewStats {
+            prs_reviewed: 4,
+            reviews: 5,
+            approvals: 3,
+            changes_requested: 1,
+            comments: 6,
+            top_repos: vec![("alice/a".to_string(), 3), ("bob/b".to_string(), 1)],
+        }
+    }
+
+    #[test]
+    fn comments_per_review_needs_a_review() {
+        assert_eq!(stats().comments_per_review(), Some(1.2));
+        assert_eq!(ReviewStats::default().comments_per_review(), None);
+    }
+
+    #[test]
+    fn render_links_each_top_repo() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(&mut builder, &stats());
+        let links = builder.finish()[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 2);
+    }
+
+    #[test]
+    fn render_skips_empty_stats() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        let page = builder.current_page();
+        render(&mut builder, &ReviewStats::default());
+        assert_eq!(builder.current_page(), page);
+    }
+}
@@ -0,0 +1,169 @@
+use printpdf::{Actions, Color, Pt, Rgb};
+
+use super::layout::{PageBuilder, Span};
+
+/// One member's figures on the team summary page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct TeamRow {
+    /// GitHub login.
+    pub login: String,
+    /// Display name, when the profile sets one.
+    pub name: Option<String>,
+    /// Commits pushed in the report window.
+    pub commits: u64,
+    /// Pull requests opened in the report window.
+    pub pull_requests: usize,
+    /// Reviews submitted in the report window.
+    pub reviews: usize,
+    /// Stars across the member's top repositories.
+    pub stars: u64,
+}
+
+/// Widest member column, in characters.
+const MEMBER_WIDTH: usize = 32;
+
+/// Renders the team report's opening page: one row per member comparing commits,
+/// pull requests, reviews, and stars, with a totals row. Each member links to
+/// their GitHub profile.
+pub fn render(builder: &mut PageBuilder, period: &str, rows: &[TeamRow]) {
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let black = Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = Color::Rgb(Rgb::new(0.47, 0.47, 0.47, None));
+    let rule_gray = Color::Rgb(Rgb::new(0.75, 0.75, 0.75, None));
+
+    const SIZE: f32 = 9.0;
+
+    builder.vertical_space(80.0);
+    builder.set_role("H1");
+    builder.write_centered("Team Report", &bold, Pt(24.0), black.clone());
+    builder.set_role("P");
+    builder.vertical_space(6.0);
+    builder.write_line_centered(&[Span {
+        text: format!(
+            "{} member{} \u{00B7} {period}",
+            rows.len(),
+            if rows.len() == 1 { "" } else { "s" }
+        ),
+        font_id: regular.clone(),
+        size: Pt(10.0),
+        color: gray.clone(),
+    }]);
+    builder.vertical_space(24.0);
+
+    let width = rows
+        .iter()
+        .map(|r| member(r).chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp("Member".len(), MEMBER_WIDTH);
+    let line = |name: &str, figures: String, font: &printpdf::FontId, color: &Color| {
+        let name: String = name.chars().take(width).collect();
+        [
+            Span {
+                text: format!("{name:<width$}"),
+                font_id: font.clone(),
+                size: Pt(SIZE),
+                color: black.clone(),
+            },
+            Span {
+                text: figures,
+                font_id: font.clone(),
+                size: Pt(SIZE),
+                color: color.clone(),
+            },
+        ]
+    };
+    let figures = |commits: u64, prs: usize, reviews: usize, stars: u64| {
+        format!("{commits:>9}{prs:>7}{reviews:>9}{stars:>8}")
+    };
+
+    builder.write_line(&line(
+        "Member",
+        format!("{:>9}{:>7}{:>9}{:>8}", "Commits", "PRs", "Reviews", "Stars"),
+        &bold,
+        &black,
+    ));
+    builder.draw_horizontal_rule(rule_gray.clone(), 0.5);
+    builder.vertical_space(4.0);
+    rows.iter().for_each(|r| {
+        builder.write_line(&line(
+            &member(r),
+            figures(r.commits, r.pull_requests, r.reviews, r.stars),
+            &regular,
+            &gray,
+        ));
+        builder.add_link(
+            builder.line_height(),
+            Actions::Uri(format!("https://github.com/{}", r.login)),
+        );
+    });
+    builder.vertical_space(4.0);
+    builder.draw_horizontal_rule(rule_gray, 0.5);
+    builder.vertical_space(4.0);
+    builder.write_line(&line(
+        "Total",
+        figures(
+            rows.iter().map(|r| r.commits).sum(),
+            rows.iter().map(|r| r.pull_requests).sum(),
+            rows.iter().map(|r| r.reviews).sum(),
+            rows.iter().map(|r| r.stars).sum(),
+        ),
+        &bold,
+        &black,
+    ));
+
+    builder.page_break();
+}
+
+/// `Name (@login)`, or `@login` when the profile has no name.
+fn member(row: &TeamRow) -> String {
+    match row.name.as_deref().filter(|n| !n.is_empty()) {
+        Some(name) => format!("{name} (@{})", row.login),
+        None => format!("@{}", row.login),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf;
+    use crate::types::Config;
+
+    fn row(login: &str, name: Option<&str>) -> TeamRow {
+        TeamRow {
+            login: login.to_string(),
+            name: name.map(str::to_string),
+            commits: 12,
+            pull_requests: 3,
+            reviews: 5,
+            stars: 40,
+        }
+    }
+
+    #[test]
+    fn member_prefers_the_display_name() {
+        assert_eq!(member(&row("alice", Some("Alice"))), "Alice (@alice)");
+        assert_eq!(member(&row("bob", None)), "@bob");
+    }
+
+    #[test]
+    fn render_links_each_member() {
+        let mut doc = printpdf::PdfDocument::new("test");
+        let fonts = pdf::fonts::load_fonts(&mut doc).unwrap();
+        let mut builder = pdf::create_builder(&Config::test_default(), fonts);
+        render(
+            &mut builder,
+            "last 90 days",
+            &[row("alice", Some("Alice")), row("bob", None)],
+        );
+        let pages = builder.finish();
+        assert_eq!(pages.len(), 1);
+        let links = pages[0]
+            .ops
+            .iter()
+            .filter(|op| matches!(op, printpdf::Op::LinkAnnotation { .. }))
+            .count();
+        assert_eq!(links, 2);
+    }
+}
@@ -0,0 +1,96 @@
+/// Breaks `text` into lines no wider than `max_width`, measuring each character with
+/// `width`.
+///
+/// Lines break after whitespace or a `/`, so prose wraps between words and paths
+/// between their components; whitespace at a break is dropped. A word wider than a
+/// whole line is split between characters rather than running off the page. Always
+/// returns at least one line.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::pdf::wrap::word_wrap;
+///
+/// let chars = |c: &str| c.chars().count() as f32;
+/// assert_eq!(word_wrap("fix the parser", 8.0, chars), ["fix the", "parser"]);
+/// assert_eq!(word_wrap("src/pdf/toc.rs", 8.0, chars), ["src/pdf/", "toc.rs"]);
+/// ```
+pub fn word_wrap(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> Vec<String> {
+    let advance = |c: char| width(c.encode_utf8(&mut [0; 4]));
+    let mut lines = vec![String::new()];
+    let mut used = 0.0;
+    text.split_inclusive(|c: char| c.is_whitespace() || c == '/')
+        .for_each(|word| {
+            let visible: f32 = word.trim_end().chars().map(advance).sum();
+            if used + visible > max_width && lines.last().is_some_and(|line| !line.is_empty()) {
+                lines.push(String::new());
+                used = 0.0;
+            }
+            word.chars().for_each(|c| {
+                let w = advance(c);
+                let started = lines.last().is_some_and(|line| !line.is_empty());
+                if c.is_whitespace() && !started {
+                    return;
+                }
+                if used + w > max_width && started && !c.is_whitespace() {
+                    lines.push(String::new());
+                    used = 0.0;
+                }
+                if let Some(line) = lines.last_mut() {
+                    line.push(c);
+                }
+                used += w;
+            });
+        });
+    lines
+        .iter_mut()
+        .for_each(|line| line.truncate(line.trim_end().len()));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::word_wrap;
+
+    /// Wraps at `max_chars` characters, every character one unit wide.
+    fn by_chars(text: &str, max_chars: usize) -> Vec<String> {
+        word_wrap(text, max_chars as f32, |c| c.chars().count() as f32)
+    }
+
+    #[test]
+    fn short_text_is_one_line() {
+        assert_eq!(by_chars("short", 20), ["short"]);
+        assert_eq!(by_chars("1234567890", 10), ["1234567890"]);
+        assert_eq!(by_chars("", 10), [""]);
+    }
+
+    #[test]
+    fn breaks_between_words() {
+        assert_eq!(
+            by_chars("the quick  brown fox", 10),
+            ["the quick", "brown fox"]
+        );
+        assert_eq!(by_chars("a very long", 6), ["a very", "long"]);
+    }
+
+    #[test]
+    fn paths_break_after_a_slash() {
+        assert_eq!(by_chars("src/pdf/layout.rs", 12), ["src/pdf/", "layout.rs"]);
+    }
+
+    #[test]
+    fn overlong_words_split_between_characters() {
+        assert_eq!(by_chars("1234567890ab", 10), ["1234567890", "ab"]);
+        assert_eq!(
+            by_chars("see 1234567890ab", 10),
+            ["see", "1234567890", "ab"]
+        );
+    }
+
+    #[test]
+    fn measures_each_character() {
+        let width = |c: &str| if c == "i" { 0.25 } else { 1.0 };
+        assert_eq!(word_wrap("iiiiiiiiab", 3.0, width), ["iiiiiiiia", "b"]);
+        assert_eq!(word_wrap("ab", 0.5, width), ["a", "b"]);
+    }
+}
@@ -0,0 +1,431 @@
+//! `--interactive`: a terminal checklist of the files a run would print, with a
+//! running page estimate, to pick from before anything is rendered.
+
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Component, PathBuf};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::error::Error;
+use crate::types::{Candidate, Config};
+
+/// Lines below the list: the running totals and the key help.
+const FOOTER_LINES: usize = 2;
+
+/// A line of the checklist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Row {
+    /// A directory, checking or unchecking every file under it at once.
+    Dir {
+        name: String,
+        depth: usize,
+        /// The files under it, as indices into [`Picker::files`].
+        files: Range<usize>,
+    },
+    /// A file, by index into [`Picker::files`].
+    File { index: usize, depth: usize },
+}
+
+/// Whether the picker is still open, or how it was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Open,
+    Confirmed,
+    Cancelled,
+}
+
+/// The checklist: files under their directories, every file checked to start.
+#[derive(Debug)]
+pub struct Picker {
+    /// Files in path order, so each directory's files are a contiguous range.
+    files: Vec<Candidate>,
+    checked: Vec<bool>,
+    rows: Vec<Row>,
+    /// Row the cursor is on.
+    cursor: usize,
+    /// First row on screen.
+    top: usize,
+}
+
+impl Picker {
+    /// A picker over `files`, all checked, laid out as a tree.
+    pub fn new(mut files: Vec<Candidate>) -> Self {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let rows = tree_rows(&files);
+        Self {
+            checked: vec![true; files.len()],
+            files,
+            rows,
+            cursor: 0,
+            top: 0,
+        }
+    }
+
+    /// The checked files' paths, in path order.
+    pub fn selected(&self) -> Vec<PathBuf> {
+        self.checked_files().map(|file| file.path.clone()).collect()
+    }
+
+    fn checked_files(&self) -> impl Iterator<Item = &Candidate> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .filter_map(|(file, &checked)| checked.then_some(file))
+    }
+
+    /// Checks the row under the cursor, or unchecks it if it is fully checked; on
+    /// a directory, that applies to every file under it.
+    fn toggle(&mut self) {
+        let range = match self.rows.get(self.cursor) {
+            Some(Row::Dir { files, .. }) => files.clone(),
+            Some(Row::File { index, .. }) => *index..index + 1,
+            None => return,
+        };
+        let all = self.checked[range.clone()].iter().all(|&c| c);
+        self.checked[range].iter_mut().for_each(|c| *c = !all);
+    }
+
+    /// Checks every file, or unchecks them all if they already are.
+    fn toggle_all(&mut self) {
+        let all = self.checked.iter().all(|&c| c);
+        self.checked.iter_mut().for_each(|c| *c = !all);
+    }
+
+    /// Moves the cursor `delta` rows, stopping at either end.
+    fn move_by(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// Applies a key press; `page` is how many rows a page key moves.
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers, page: usize) -> State {
+        let page = page.max(1) as isize;
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return State::Cancelled;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-page),
+            KeyCode::PageDown => self.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX),
+            KeyCode::Char(' ') => self.toggle(),
+            KeyCode::Char('a') => self.toggle_all(),
+            KeyCode::Enter => return State::Confirmed,
+            KeyCode::Esc | KeyCode::Char('q') => return State::Cancelled,
+            _ => {}
+        }
+        State::Open
+    }
+
+    /// The check box, indent, and name of `row`.
+    fn row_text(&self, row: &Row) -> String {
+        let (depth, mark, label) = match row {
+            Row::Dir { name, depth, files } => {
+                let checked = self.checked[files.clone()].iter().filter(|&&c| c).count();
+                let mark = match checked {
+                    0 => ' ',
+                    n if n == files.len() => 'x',
+                    _ => '-',
+                };
+                (*depth, mark, format!("{name}/"))
+            }
+            Row::File { index, depth } => {
+                let file = &self.files[*index];
+                let name = file.path.file_name().unwrap_or_default().to_string_lossy();
+                let mark = if self.checked[*index] { 'x' } else { ' ' };
+                (*depth, mark, format!("{name}  {} lines", file.lines))
+            }
+        };
+        format!("{}[{mark}] {label}", "  ".repeat(depth))
+    }
+
+    /// Files, lines, and estimated pages and size of the checked files.
+    fn totals(&self, config: &Config) -> String {
+        let counts: Vec<(usize, u64)> = self
+            .checked_files()
+            .map(|file| (file.lines, file.bytes))
+            .collect();
+        let estimate = crate::estimate_size(config, &counts, true);
+        format!(
+            "{} of {} files · {} lines · ~{} pages · ~{}",
+            estimate.files,
+            self.files.len(),
+            estimate.lines,
+            estimate.pages,
+            crate::format_size(estimate.approx_bytes),
+        )
+    }
+
+    /// Scrolls so the cursor is within `height` rows from the top.
+    fn scroll(&mut self, height: usize) {
+        let height = height.max(1);
+        if self.cursor < self.top {
+            self.top = self.cursor;
+        } else if self.cursor >= self.top + height {
+            self.top = self.cursor + 1 - height;
+        }
+    }
+
+    fn draw(&mut self, out: &mut impl Write, config: &Config) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (usize::from(width), usize::from(height));
+        let list_height = height.saturating_sub(FOOTER_LINES).max(1);
+        self.scroll(list_height);
+        let fit = |text: String| text.chars().take(width).collect::<String>();
+
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        for (line, i) in (self.top..self.rows.len()).take(list_height).enumerate() {
+            let text = fit(self.row_text(&self.rows[i]));
+            queue!(out, cursor::MoveTo(0, line as u16))?;
+            if i == self.cursor {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(text),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(text))?;
+            }
+        }
+        let footer = height.saturating_sub(FOOTER_LINES) as u16;
+        queue!(
+            out,
+            cursor::MoveTo(0, footer),
+            SetAttribute(Attribute::Bold),
+            Print(fit(self.totals(config))),
+            SetAttribute(Attribute::Reset),
+            cursor::MoveTo(0, footer + 1),
+            Print(fit(
+                "↑↓ move · space check · a all · enter print · q cancel".to_string()
+            )),
+        )?;
+        out.flush()
+    }
+
+    /// Shows the checklist on the terminal until it is confirmed, returning the
+    /// checked paths, or cancelled, returning `None`. Blocks on key presses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal can't be put in raw mode or read.
+    pub fn run(mut self, config: &Config) -> Result<Option<Vec<PathBuf>>, Error> {
+        let _screen = Screen::enter()?;
+        let mut out = io::stderr();
+        loop {
+            self.draw(&mut out, config)?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            let page = usize::from(terminal::size()?.1).saturating_sub(FOOTER_LINES);
+            match self.key(key.code, key.modifiers, page) {
+                State::Open => {}
+                State::Confirmed => return Ok(Some(self.selected())),
+                State::Cancelled => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Raw mode on an alternate screen, restored when dropped — on an error too.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Self;
+        execute!(io::stderr(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stderr(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Lays out `files`, sorted by path, as a tree: a row for each directory the
+/// first time it is entered, then the files and directories inside it, indented.
+fn tree_rows(files: &[Candidate]) -> Vec<Row> {
+    let mut rows = Vec::new();
+    // The directories the current file is in, outermost first, with their rows.
+    let mut open: Vec<(String, usize)> = Vec::new();
+    let close = |rows: &mut Vec<Row>, row: usize, end: usize| {
+        if let Row::Dir { files, .. } = &mut rows[row] {
+            files.end = end;
+        }
+    };
+    files.iter().enumerate().for_each(|(index, file)| {
+        let dirs: Vec<String> = file
+            .path
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        let shared = open
+            .iter()
+            .zip(&dirs)
+            .take_while(|((name, _), dir)| name == *dir)
+            .count();
+        open.drain(shared..)
+            .for_each(|(_, row)| close(&mut rows, row, index));
+        dirs[shared..].iter().for_each(|name| {
+            open.push((name.clone(), rows.len()));
+            rows.push(Row::Dir {
+                name: name.clone(),
+                depth: open.len() - 1,
+                files: index..index,
+            });
+        });
+        rows.push(Row::File {
+            index,
+            depth: open.len(),
+        });
+    });
+    open.drain(..)
+        .for_each(|(_, row)| close(&mut rows, row, files.len()));
+    rows
+}
+
+/// Lists the files `config` would print and lets the user pick among them on
+/// the terminal (`--interactive`). Returns the picked paths, in path order, or
+/// `None` if the picker was cancelled.
+///
+/// # Errors
+///
+/// Returns an error if stdin or stderr is not a terminal, or as
+/// [`candidates`](crate::candidates) does.
+pub async fn pick_files(config: &Config) -> Result<Option<Vec<PathBuf>>, Error> {
+    use std::io::IsTerminal;
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return Err(anyhow::anyhow!("--interactive needs a terminal").into());
+    }
+    let files = crate::candidates(config).await?;
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || Picker::new(files).run(&config))
+        .await
+        .map_err(|e| anyhow::anyhow!("file picker panicked: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picker(paths: &[&str]) -> Picker {
+        Picker::new(
+            paths
+                .iter()
+                .map(|path| Candidate {
+                    path: PathBuf::from(path),
+                    lines: 10,
+                    bytes: 100,
+                })
+                .collect(),
+        )
+    }
+
+    fn texts(p: &Picker) -> Vec<String> {
+        p.rows.iter().map(|row| p.row_text(row)).collect()
+    }
+
+    #[test]
+    fn files_are_laid_out_under_their_directories() {
+        let p = picker(&["src/lib.rs", "README.md", "src/pdf/code.rs", "src/main.rs"]);
+        assert_eq!(
+            texts(&p),
+            [
+                "[x] README.md  10 lines",
+                "[x] src/",
+                "  [x] lib.rs  10 lines",
+                "  [x] main.rs  10 lines",
+                "  [x] pdf/",
+                "    [x] code.rs  10 lines",
+            ]
+        );
+        assert!(matches!(&p.rows[1], Row::Dir { files, .. } if *files == (1..4)));
+        assert!(matches!(&p.rows[4], Row::Dir { files, .. } if *files == (3..4)));
+    }
+
+    #[test]
+    fn toggling_a_directory_covers_its_files() {
+        let mut p = picker(&["a/x.rs", "a/b/y.rs", "c.rs"]);
+        p.cursor = 0;
+        p.toggle();
+        assert_eq!(p.selected(), [PathBuf::from("c.rs")]);
+        // Checking one file marks its directory as partly checked.
+        p.move_by(2);
+        p.toggle();
+        assert_eq!(texts(&p)[0], "[-] a/");
+        assert_eq!(
+            p.selected(),
+            [PathBuf::from("a/b/y.rs"), PathBuf::from("c.rs")]
+        );
+        // A partly checked directory checks fully first.
+        p.cursor = 0;
+        p.toggle();
+        assert_eq!(p.selected().len(), 3);
+    }
+
+    #[test]
+    fn keys_move_check_and_close() {
+        let mut p = picker(&["a.rs", "b.rs", "c.rs"]);
+        let none = KeyModifiers::NONE;
+        assert_eq!(p.key(KeyCode::Up, none, 10), State::Open);
+        assert_eq!(p.cursor, 0);
+        p.key(KeyCode::PageDown, none, 10);
+        assert_eq!(p.cursor, 2);
+        p.key(KeyCode::Char(' '), none, 10);
+        p.key(KeyCode::Char('a'), none, 10);
+        assert_eq!(p.selected().len(), 3);
+        p.key(KeyCode::Char('a'), none, 10);
+        assert!(p.selected().is_empty());
+        assert_eq!(p.key(KeyCode::Enter, none, 10), State::Confirmed);
+        assert_eq!(
+            p.key(KeyCode::Char('c'), KeyModifiers::CONTROL, 10),
+            State::Cancelled
+        );
+    }
+
+    #[test]
+    fn scrolling_keeps_the_cursor_on_screen() {
+        let mut p = picker(&["a", "b", "c", "d", "e", "f"]);
+        p.cursor = 5;
+        p.scroll(3);
+        assert_eq!(p.top, 3);
+        p.cursor = 1;
+        p.scroll(3);
+        assert_eq!(p.top, 1);
+    }
+
+    #[test]
+    fn totals_follow_the_checked_files() {
+        let config = Config::test_default();
+        let mut p = picker(&["a.rs", "b.rs"]);
+        assert!(p.totals(&config).starts_with("2 of 2 files · 20 lines · ~"));
+        p.toggle();
+        assert!(p.totals(&config).starts_with("1 of 2 files · 10 lines · ~"));
+    }
+
+    #[test]
+    fn an_empty_list_is_safe_to_use() {
+        let mut p = picker(&[]);
+        p.key(KeyCode::Down, KeyModifiers::NONE, 10);
+        p.key(KeyCode::Char(' '), KeyModifiers::NONE, 10);
+        assert_eq!(p.cursor, 0);
+        assert!(p.selected().is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+use std::path::Path;
+#[cfg(feature = "native")]
+use std::path::PathBuf;
+#[cfg(feature = "native")]
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "native")]
+use anyhow::{Context, bail};
+use serde::Deserialize;
+#[cfg(feature = "native")]
+use tokio::io::AsyncWriteExt;
+
+/// Seconds a preprocess hook may run on one file before it is killed.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Rewrites file content before it is highlighted: strip license headers, expand
+/// includes, decrypt secrets files, and so on.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::preprocess::{ContentTransform, Preprocessor};
+/// use std::path::Path;
+///
+/// struct StripHeader;
+///
+/// impl ContentTransform for StripHeader {
+///     fn transform(&self, _path: &Path, content: String) -> anyhow::Result<String> {
+///         Ok(content.trim_start_matches("// SPDX-License-Identifier: MIT\n").to_string())
+///     }
+/// }
+///
+/// let preprocess = Preprocessor::transform(StripHeader);
+/// ```
+pub trait ContentTransform: Send + Sync {
+    /// Returns the text to print for `path` (relative to the repository root).
+    ///
+    /// # Errors
+    ///
+    /// An error is handled according to the [`OnFailure`] policy.
+    fn transform(&self, path: &Path, content: String) -> anyhow::Result<String>;
+}
+
+/// What happens to a file whose hook fails or times out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnFailure {
+    /// Print the original content and report a warning.
+    #[default]
+    Keep,
+    /// Leave the file out and report a warning.
+    Skip,
+    /// Stop the run with [`Error::Preprocess`](crate::Error::Preprocess).
+    Abort,
+}
+
+// Only `apply` runs hooks; without the `native` feature they are inert.
+#[cfg_attr(not(feature = "native"), allow(dead_code))]
+#[derive(Clone)]
+enum Hook {
+    Command(String),
+    Transform(Arc<dyn ContentTransform>),
+}
+
+/// A content hook applied to every file between reading and highlighting.
+#[derive(Clone)]
+pub struct Preprocessor {
+    hook: Hook,
+    timeout: Option<Duration>,
+    on_failure: OnFailure,
+}
+
+impl std::fmt::Debug for Preprocessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hook = match &self.hook {
+            Hook::Command(template) => template.as_str(),
+            Hook::Transform(_) => "<ContentTransform>",
+        };
+        f.debug_struct("Preprocessor")
+            .field("hook", &hook)
+            .field("timeout", &self.timeout)
+            .field("on_failure", &self.on_failure)
+            .finish()
+    }
+}
+
+impl Preprocessor {
+    /// A shell command run once per file in the repository root. The file content
+    /// arrives on stdin and stdout replaces it; `{path}` in `template` expands to the
+    /// quoted repository-relative path.
+    pub fn command(template: impl Into<String>) -> Self {
+        Self::new(Hook::Command(template.into()))
+    }
+
+    /// A library transform, run on tokio's blocking thread pool.
+    pub fn transform(transform: impl ContentTransform + 'static) -> Self {
+        Self::new(Hook::Transform(Arc::new(transform)))
+    }
+
+    fn new(hook: Hook) -> Self {
+        Self {
+            hook,
+            timeout: Some(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            on_failure: OnFailure::default(),
+        }
+    }
+
+    /// Sets the per-file time limit; `None` lets the hook run indefinitely.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets what happens to a file whose hook fails.
+    pub fn with_on_failure(mut self, on_failure: OnFailure) -> Self {
+        self.on_failure = on_failure;
+        self
+    }
+
+    /// The failure policy.
+    pub fn on_failure(&self) -> OnFailure {
+        self.on_failure
+    }
+
+    /// Runs the hook on `content` of `path`, a file inside `repo_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hook fails, exits non-zero, prints invalid UTF-8, or
+    /// outlives the timeout.
+    #[cfg(feature = "native")]
+    pub async fn apply(
+        &self,
+        repo_path: &Path,
+        path: &Path,
+        content: String,
+    ) -> anyhow::Result<String> {
+        let run = async {
+            match &self.hook {
+                Hook::Command(template) => run_command(template, repo_path, path, content).await,
+                Hook::Transform(transform) => {
+                    let transform = Arc::clone(transform);
+                    let path: PathBuf = path.to_path_buf();
+                    tokio::task::spawn_blocking(move || transform.transform(&path, content))
+                        .await
+                        .context("preprocess transform panicked")?
+                }
+            }
+        };
+        match self.timeout {
+            Some(limit) => tokio::time::timeout(limit, run)
+                .await
+                .unwrap_or_else(|_| bail!("timed out after {}s", limit.as_secs())),
+            None => run.await,
+        }
+    }
+}
+
+/// Pipes `content` through `template` with `{path}` expanded. The child is killed
+/// if the future is dropped (on timeout).
+#[cfg(feature = "native")]
+async fn run_command(
+    template: &str,
+    repo_path: &Path,
+    path: &Path,
+    content: String,
+) -> anyhow::Result<String> {
+    let command = template.replace("{path}", &shell_quote(&path.to_string_lossy()));
+    let mut child = shell(&command)
+        .current_dir(repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("cannot run `{command}`"))?;
+
+    // Feed stdin concurrently so a hook that writes before reading everything
+    // cannot deadlock on a full pipe. A hook that ignores stdin closes it early,
+    // which is not an error.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let feed = async move {
+        let _ = stdin.write_all(content.as_bytes()).await;
+    };
+    let (_, output) = tokio::join!(feed, child.wait_with_output());
+    let output = output.with_context(|| format!("cannot run `{command}`"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("`{command}` failed ({}): {}", output.status, stderr.trim());
+    }
+    String::from_utf8(output.stdout).with_context(|| format!("`{command}` printed invalid UTF-8"))
+}
+
+#[cfg(all(feature = "native", not(windows)))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(all(feature = "native", windows))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(all(feature = "native", not(windows)))]
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[cfg(all(feature = "native", windows))]
+fn shell_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    async fn apply(preprocess: &Preprocessor, content: &str) -> anyhow::Result<String> {
+        let dir = tempfile::tempdir().unwrap();
+        preprocess
+            .apply(dir.path(), Path::new("src/it's.rs"), content.to_string())
+            .await
+    }
+
+    #[tokio::test]
+    async fn command_filters_stdin_and_sees_quoted_path() {
+        let hook = Preprocessor::command("printf '%s:' {path}; tr a-z A-Z");
+        assert_eq!(
+            apply(&hook, "fn main\n").await.unwrap(),
+            "src/it's.rs:FN MAIN\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_command_reports_stderr() {
+        let hook = Preprocessor::command("echo nope >&2; exit 3");
+        let err = apply(&hook, "x").await.unwrap_err();
+        assert!(err.to_string().contains("nope"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn slow_command_times_out() {
+        let hook = Preprocessor::command("sleep 5").with_timeout(Some(Duration::from_millis(100)));
+        let err = apply(&hook, "x").await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn transform_runs_on_content() {
+        struct Upper;
+        impl ContentTransform for Upper {
+            fn transform(&self, _: &Path, content: String) -> anyhow::Result<String> {
+                Ok(content.to_uppercase())
+            }
+        }
+        assert_eq!(
+            apply(&Preprocessor::transform(Upper), "abc").await.unwrap(),
+            "ABC"
+        );
+    }
+}
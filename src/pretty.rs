@@ -0,0 +1,348 @@
+use std::path::Path;
+
+/// Re-indents structured data files so they print as readable, multi-line text.
+///
+/// Supports JSON (`.json`, `.geojson`, `.webmanifest`), XML (`.xml`, `.svg`, `.xsd`,
+/// `.xsl`, `.plist`) and delimited tables (`.csv`, `.tsv`). Returns `None` when the
+/// extension is not recognised or the content does not parse, so callers can fall
+/// back to the original text unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use gitprint::pretty::pretty_print;
+///
+/// let out = pretty_print(r#"{"a":1,"b":[true,null]}"#, Path::new("x.json")).unwrap();
+/// assert_eq!(out, "{\n  \"a\": 1,\n  \"b\": [\n    true,\n    null\n  ]\n}\n");
+/// assert!(pretty_print("fn main() {}", Path::new("main.rs")).is_none());
+/// ```
+pub fn pretty_print(content: &str, path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "json" | "geojson" | "webmanifest" => pretty_json(content),
+        "xml" | "svg" | "xsd" | "xsl" | "xslt" | "plist" => pretty_xml(content),
+        "csv" => align_columns(content, ','),
+        "tsv" => align_columns(content, '\t'),
+        _ => None,
+    }
+}
+
+// ── JSON ───────────────────────────────────────────────────────────────────────
+
+/// Re-indents JSON with two spaces, preserving key order and number formatting.
+///
+/// The input is validated with serde_json first; the reformat itself is a single
+/// character pass so large documents never build an intermediate `Value` tree.
+fn pretty_json(content: &str) -> Option<String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(content).ok()?;
+
+    let mut out = String::with_capacity(content.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = content.chars().peekable();
+
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        (0..depth).for_each(|_| out.push_str("  "));
+    };
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                // Keep empty containers on one line: `{}` / `[]`.
+                while chars.peek().is_some_and(|n| n.is_whitespace()) {
+                    chars.next();
+                }
+                if matches!(chars.peek(), Some('}' | ']')) {
+                    out.extend(chars.next());
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out.push('\n');
+    Some(out)
+}
+
+// ── XML ────────────────────────────────────────────────────────────────────────
+
+/// Re-indents XML one element per line. Elements containing only text stay on a
+/// single line (`<name>value</name>`); comments, CDATA, and processing
+/// instructions are emitted verbatim.
+fn pretty_xml(content: &str) -> Option<String> {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with('<') {
+        return None;
+    }
+
+    enum Node<'a> {
+        Open(&'a str),
+        Close(&'a str),
+        Leaf(&'a str),
+        Text(&'a str),
+    }
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut rest = trimmed;
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = if rest.starts_with("<!--") {
+                rest.find("-->")? + 3
+            } else if rest.starts_with("<![CDATA[") {
+                rest.find("]]>")? + 3
+            } else {
+                tag_end(rest)? + 1
+            };
+            let tag = &rest[..end];
+            nodes.push(if tag.starts_with("</") {
+                Node::Close(tag)
+            } else if tag.ends_with("/>") || tag.starts_with("<?") || tag.starts_with("<!") {
+                Node::Leaf(tag)
+            } else {
+                Node::Open(tag)
+            });
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                nodes.push(Node::Text(text));
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    let mut out = String::with_capacity(content.len() * 2);
+    let mut depth = 0usize;
+    let mut i = 0;
+    let indent = |out: &mut String, depth: usize| (0..depth).for_each(|_| out.push_str("  "));
+    while i < nodes.len() {
+        match (&nodes[i], nodes.get(i + 1), nodes.get(i + 2)) {
+            (Node::Open(open), Some(Node::Text(text)), Some(Node::Close(close))) => {
+                indent(&mut out, depth);
+                out.push_str(open);
+                out.push_str(text);
+                out.push_str(close);
+                i += 3;
+            }
+            (Node::Open(open), Some(Node::Close(close)), _) => {
+                indent(&mut out, depth);
+                out.push_str(open);
+                out.push_str(close);
+                i += 2;
+            }
+            (Node::Open(tag), _, _) => {
+                indent(&mut out, depth);
+                out.push_str(tag);
+                depth += 1;
+                i += 1;
+            }
+            (Node::Close(tag), _, _) => {
+                depth = depth.saturating_sub(1);
+                indent(&mut out, depth);
+                out.push_str(tag);
+                i += 1;
+            }
+            (Node::Leaf(s) | Node::Text(s), _, _) => {
+                indent(&mut out, depth);
+                out.push_str(s);
+                i += 1;
+            }
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// Byte offset of the `>` closing the tag at the start of `s`, skipping `>` inside
+/// quoted attribute values.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    s.char_indices().find_map(|(i, c)| match (quote, c) {
+        (None, '"' | '\'') => {
+            quote = Some(c);
+            None
+        }
+        (Some(q), c) if c == q => {
+            quote = None;
+            None
+        }
+        (None, '>') => Some(i),
+        _ => None,
+    })
+}
+
+// ── CSV / TSV ──────────────────────────────────────────────────────────────────
+
+/// Widest a column is padded to; longer cells overflow rather than stretching
+/// every row of the file.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Pads delimited fields so columns line up. Quoted fields (including embedded
+/// delimiters) are kept intact; files with records spanning lines are not touched.
+fn align_columns(content: &str, delim: char) -> Option<String> {
+    let rows: Vec<Vec<&str>> = content
+        .lines()
+        .map(|line| split_fields(line, delim))
+        .collect::<Option<_>>()?;
+    if rows.iter().all(|r| r.len() < 2) {
+        return None;
+    }
+
+    let widths = rows.iter().fold(Vec::<usize>::new(), |mut widths, row| {
+        row.iter().enumerate().for_each(|(i, f)| {
+            let w = f.chars().count().min(MAX_COLUMN_WIDTH);
+            match widths.get_mut(i) {
+                Some(cur) => *cur = (*cur).max(w),
+                None => widths.push(w),
+            }
+        });
+        widths
+    });
+
+    let mut out = String::with_capacity(content.len() * 2);
+    rows.iter().for_each(|row| {
+        row.iter().enumerate().for_each(|(i, field)| {
+            out.push_str(field);
+            if i + 1 < row.len() {
+                out.push(delim);
+                let pad = widths[i].saturating_sub(field.chars().count());
+                // TSV already aligns via tabs in most viewers; only CSV needs a separator space.
+                if delim == ',' {
+                    out.push(' ');
+                }
+                (0..pad).for_each(|_| out.push(' '));
+            }
+        });
+        out.push('\n');
+    });
+    Some(out)
+}
+
+/// Splits one delimited line into fields, honouring double-quoted fields.
+/// Returns `None` when a quote is left open (a multi-line record).
+fn split_fields(line: &str, delim: char) -> Option<Vec<&str>> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    line.char_indices().for_each(|(i, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == delim && !in_quotes {
+            fields.push(line[start..i].trim());
+            start = i + c.len_utf8();
+        }
+    });
+    if in_quotes {
+        return None;
+    }
+    fields.push(line[start..].trim());
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_minified_is_reindented() {
+        let out = pretty_print(r#"{"b":{"c":"x,y"},"a":[]}"#, Path::new("f.json")).unwrap();
+        assert_eq!(
+            out,
+            "{\n  \"b\": {\n    \"c\": \"x,y\"\n  },\n  \"a\": []\n}\n"
+        );
+    }
+
+    #[test]
+    fn json_preserves_key_order_and_escapes() {
+        let out = pretty_print(r#"{"z":"a\"}b","a":1.50}"#, Path::new("f.json")).unwrap();
+        assert!(out.find("\"z\"").unwrap() < out.find("\"a\"").unwrap());
+        assert!(out.contains(r#""a\"}b""#));
+        assert!(out.contains("1.50"));
+    }
+
+    #[test]
+    fn json_invalid_returns_none() {
+        assert!(pretty_print("{not json", Path::new("f.json")).is_none());
+    }
+
+    #[test]
+    fn xml_is_reindented() {
+        let out = pretty_print(
+            r#"<?xml version="1.0"?><a x="1>2"><b>text</b><c/><d></d></a>"#,
+            Path::new("f.xml"),
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            "<?xml version=\"1.0\"?>\n<a x=\"1>2\">\n  <b>text</b>\n  <c/>\n  <d></d>\n</a>\n"
+        );
+    }
+
+    #[test]
+    fn xml_comment_kept_verbatim() {
+        let out = pretty_print("<a><!-- <b> --></a>", Path::new("f.svg")).unwrap();
+        assert!(out.contains("  <!-- <b> -->\n"));
+    }
+
+    #[test]
+    fn xml_non_markup_returns_none() {
+        assert!(pretty_print("plain text", Path::new("f.xml")).is_none());
+    }
+
+    #[test]
+    fn csv_columns_are_aligned() {
+        let out = pretty_print(
+            "id,name,role\n1,Alice,admin\n22,Bo,\"a, b\"\n",
+            Path::new("f.csv"),
+        )
+        .unwrap();
+        assert_eq!(
+            out,
+            "id, name,  role\n1,  Alice, admin\n22, Bo,    \"a, b\"\n"
+        );
+    }
+
+    #[test]
+    fn csv_multiline_record_returns_none() {
+        assert!(pretty_print("a,\"b\nc\"\n", Path::new("f.csv")).is_none());
+    }
+
+    #[test]
+    fn unknown_extension_returns_none() {
+        assert!(pretty_print("{}", Path::new("f.rs")).is_none());
+        assert!(pretty_print("{}", Path::new("Makefile")).is_none());
+    }
+}
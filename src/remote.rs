@@ -0,0 +1,284 @@
+/// Hosting service a remote lives on, which decides its web URL layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Host {
+    /// github.com.
+    GitHub,
+    /// gitlab.com or a self-hosted GitLab (`/-/blob/…`).
+    GitLab,
+    /// bitbucket.org (`/src/<rev>/<path>`).
+    Bitbucket,
+    /// Azure DevOps (`/_git/<repo>?path=…&version=GC<rev>`).
+    AzureDevOps,
+    /// Anything else; GitHub-style paths are assumed.
+    Other,
+}
+
+/// A remote repository's web location, able to build links into its UI.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::remote::{Host, RemoteRepo};
+///
+/// let repo = RemoteRepo::parse("git@bitbucket.org:team/app.git");
+/// assert_eq!(repo.host(), Host::Bitbucket);
+/// assert_eq!(
+///     repo.blob_url("abc123", "src/lib.rs"),
+///     "https://bitbucket.org/team/app/src/abc123/src/lib.rs"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    host: Host,
+    base: String,
+}
+
+impl RemoteRepo {
+    /// Parses a clone URL (`https://`, `ssh://` or SCP-style) into its web base URL
+    /// and host. Credentials embedded in the URL are dropped.
+    pub fn parse(url: &str) -> Self {
+        let https = normalize_to_https(url.trim());
+        let https = match https.split_once("://") {
+            Some((scheme, rest)) => {
+                let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+                let host = authority.rsplit('@').next().unwrap_or(authority);
+                format!("{scheme}://{host}/{path}")
+            }
+            None => https,
+        };
+        let base = https.trim_end_matches('/').trim_end_matches(".git");
+
+        // Azure SSH remotes (`git@ssh.dev.azure.com:v3/org/project/repo`) map to
+        // `https://dev.azure.com/org/project/_git/repo`.
+        let base = match base
+            .strip_prefix("https://ssh.dev.azure.com/v3/")
+            .map(|rest| rest.splitn(3, '/').collect::<Vec<_>>())
+        {
+            Some(parts) if parts.len() == 3 => format!(
+                "https://dev.azure.com/{}/{}/_git/{}",
+                parts[0], parts[1], parts[2]
+            ),
+            _ => base.to_string(),
+        };
+
+        let host_name = base.split('/').nth(2).unwrap_or("").to_ascii_lowercase();
+        let host = if host_name == "github.com" {
+            Host::GitHub
+        } else if host_name == "dev.azure.com" || host_name.ends_with(".visualstudio.com") {
+            Host::AzureDevOps
+        } else if host_name.contains("bitbucket") {
+            Host::Bitbucket
+        } else if host_name.contains("gitlab") {
+            Host::GitLab
+        } else {
+            Host::Other
+        };
+        Self { host, base }
+    }
+
+    /// The hosting service.
+    pub fn host(&self) -> Host {
+        self.host
+    }
+
+    /// The repository's web URL, without a trailing `.git`.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Link to a single commit.
+    pub fn commit_url(&self, rev: &str) -> String {
+        let base = &self.base;
+        match self.host {
+            Host::GitLab => format!("{base}/-/commit/{rev}"),
+            Host::Bitbucket => format!("{base}/commits/{rev}"),
+            Host::GitHub | Host::AzureDevOps | Host::Other => format!("{base}/commit/{rev}"),
+        }
+    }
+
+    /// Link to the repository tree at `rev`, or the repository root when `rev` is
+    /// empty.
+    pub fn tree_url(&self, rev: &str) -> String {
+        let base = &self.base;
+        if rev.is_empty() {
+            return base.clone();
+        }
+        match self.host {
+            Host::GitLab => format!("{base}/-/tree/{rev}"),
+            Host::Bitbucket => format!("{base}/src/{rev}"),
+            Host::AzureDevOps => format!("{base}?version=GC{rev}"),
+            Host::GitHub | Host::Other => format!("{base}/tree/{rev}"),
+        }
+    }
+
+    /// Link to `path` as of `rev` (`HEAD` for the default branch).
+    pub fn blob_url(&self, rev: &str, path: &str) -> String {
+        let base = &self.base;
+        match self.host {
+            Host::GitLab => format!("{base}/-/blob/{rev}/{path}"),
+            Host::Bitbucket => format!("{base}/src/{rev}/{path}"),
+            Host::AzureDevOps if rev == "HEAD" => format!("{base}?path=/{path}"),
+            Host::AzureDevOps => format!("{base}?path=/{path}&version=GC{rev}"),
+            Host::GitHub | Host::Other => format!("{base}/blob/{rev}/{path}"),
+        }
+    }
+
+    /// Link to the commit history filtered by author `email`.
+    pub fn author_url(&self, email: &str) -> String {
+        let base = &self.base;
+        match self.host {
+            Host::GitLab => format!("{base}/-/commits?author={email}"),
+            Host::Bitbucket => format!("{base}/commits?search={email}"),
+            Host::AzureDevOps => format!("{base}/commits?user={email}"),
+            Host::GitHub | Host::Other => format!("{base}/commits?author={email}"),
+        }
+    }
+}
+
+/// Extracts the repository name from a remote URL.
+///
+/// `https://github.com/user/repo.git` → `"repo"`
+/// `git@github.com:user/repo`         → `"repo"`
+pub fn repo_name_from_url(url: &str) -> String {
+    url.split(['/', ':'])
+        .next_back()
+        .unwrap_or("repo")
+        .trim_end_matches(".git")
+        .to_string()
+}
+
+/// Normalizes a git remote URL to an `https://` URL.
+///
+/// Handles SCP-style (`git@github.com:user/repo`) and `ssh://` URLs, converting
+/// them to their `https://` equivalents so they can be used in clickable links.
+/// Plain `https://` or `http://` URLs are returned unchanged.
+pub(crate) fn normalize_to_https(url: &str) -> String {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return url.to_string();
+    } else if let Some(rest) = url.strip_prefix("git@") {
+        // SCP-style: git@github.com:user/repo.git
+        if let Some(colon_pos) = rest.find(':') {
+            return format!("https://{}/{}", &rest[..colon_pos], &rest[colon_pos + 1..]);
+        }
+    } else if let Some(rest) = url.strip_prefix("ssh://git@") {
+        return format!("https://{rest}");
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        return format!("https://{rest}");
+    }
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_https_passthrough() {
+        assert_eq!(
+            normalize_to_https("https://github.com/user/repo.git"),
+            "https://github.com/user/repo.git"
+        );
+        assert_eq!(
+            normalize_to_https("http://example.com/repo"),
+            "http://example.com/repo"
+        );
+    }
+
+    #[test]
+    fn normalize_scp_style() {
+        assert_eq!(
+            normalize_to_https("git@github.com:user/repo.git"),
+            "https://github.com/user/repo.git"
+        );
+        assert_eq!(
+            normalize_to_https("git@gitlab.com:org/project"),
+            "https://gitlab.com/org/project"
+        );
+    }
+
+    #[test]
+    fn normalize_ssh_git_at_style() {
+        assert_eq!(
+            normalize_to_https("ssh://git@github.com/user/repo.git"),
+            "https://github.com/user/repo.git"
+        );
+    }
+
+    #[test]
+    fn normalize_ssh_style() {
+        assert_eq!(
+            normalize_to_https("ssh://github.com/user/repo.git"),
+            "https://github.com/user/repo.git"
+        );
+    }
+
+    #[test]
+    fn github_links() {
+        let repo = RemoteRepo::parse("https://github.com/user/repo.git");
+        assert_eq!(repo.host(), Host::GitHub);
+        assert_eq!(repo.base(), "https://github.com/user/repo");
+        assert_eq!(
+            repo.commit_url("abc"),
+            "https://github.com/user/repo/commit/abc"
+        );
+        assert_eq!(
+            repo.blob_url("HEAD", "src/main.rs"),
+            "https://github.com/user/repo/blob/HEAD/src/main.rs"
+        );
+    }
+
+    #[test]
+    fn gitlab_links_use_dash_prefix() {
+        let repo = RemoteRepo::parse("git@gitlab.com:group/sub/project.git");
+        assert_eq!(repo.host(), Host::GitLab);
+        assert_eq!(
+            repo.tree_url("abc"),
+            "https://gitlab.com/group/sub/project/-/tree/abc"
+        );
+        assert_eq!(
+            repo.blob_url("abc", "a.rs"),
+            "https://gitlab.com/group/sub/project/-/blob/abc/a.rs"
+        );
+    }
+
+    #[test]
+    fn bitbucket_links() {
+        let repo = RemoteRepo::parse("https://jane@bitbucket.org/team/app.git");
+        assert_eq!(repo.base(), "https://bitbucket.org/team/app");
+        assert_eq!(
+            repo.commit_url("abc"),
+            "https://bitbucket.org/team/app/commits/abc"
+        );
+        assert_eq!(
+            repo.tree_url("abc"),
+            "https://bitbucket.org/team/app/src/abc"
+        );
+    }
+
+    #[test]
+    fn azure_devops_https_and_ssh_agree() {
+        let https = RemoteRepo::parse("https://org@dev.azure.com/org/proj/_git/repo");
+        let ssh = RemoteRepo::parse("git@ssh.dev.azure.com:v3/org/proj/repo");
+        assert_eq!(https, ssh);
+        assert_eq!(https.host(), Host::AzureDevOps);
+        assert_eq!(
+            https.blob_url("abc", "src/a.rs"),
+            "https://dev.azure.com/org/proj/_git/repo?path=/src/a.rs&version=GCabc"
+        );
+        assert_eq!(
+            https.blob_url("HEAD", "src/a.rs"),
+            "https://dev.azure.com/org/proj/_git/repo?path=/src/a.rs"
+        );
+        assert_eq!(
+            https.commit_url("abc"),
+            "https://dev.azure.com/org/proj/_git/repo/commit/abc"
+        );
+    }
+
+    #[test]
+    fn empty_rev_tree_is_repo_root() {
+        let repo = RemoteRepo::parse("https://example.com/r");
+        assert_eq!(repo.host(), Host::Other);
+        assert_eq!(repo.tree_url(""), "https://example.com/r");
+    }
+}
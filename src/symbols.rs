@@ -0,0 +1,362 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Kind of definition listed in the `--symbols` index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SymbolKind {
+    /// Function, method, or arrow function bound to a name.
+    Function,
+    /// Struct, enum, trait, interface, or type alias.
+    Type,
+    /// Class or module.
+    Class,
+}
+
+impl SymbolKind {
+    /// Short label printed in the index.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::Type => "type",
+            Self::Class => "class",
+        }
+    }
+}
+
+/// A definition found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// What was defined.
+    pub kind: SymbolKind,
+    /// The defined name.
+    pub name: String,
+    /// 1-based line of the definition.
+    pub line_number: usize,
+    /// 1-based last line of the definition's body, judged by indentation.
+    pub end_line: usize,
+}
+
+struct Language {
+    extensions: &'static [&'static str],
+    rules: Vec<(SymbolKind, Regex)>,
+}
+
+/// Per-language definition patterns. Each is matched against a single line and
+/// captures the name in group 1; the first matching rule wins.
+fn languages() -> &'static [Language] {
+    static LANGUAGES: OnceLock<Vec<Language>> = OnceLock::new();
+    LANGUAGES.get_or_init(|| {
+        use SymbolKind::{Class, Function, Type};
+        let lang = |extensions: &'static [&'static str], rules: &[(SymbolKind, &str)]| Language {
+            extensions,
+            rules: rules
+                .iter()
+                .map(|(kind, pattern)| {
+                    let re = Regex::new(pattern).expect("built-in symbol pattern is valid");
+                    (*kind, re)
+                })
+                .collect(),
+        };
+        let java_modifiers = r"(?:(?:public|private|protected|internal|static|final|abstract|sealed|partial|data|open|override|virtual|async|synchronized|suspend|inline)\s+)*";
+        vec![
+            lang(
+                &["rs"],
+                &[
+                    (
+                        Function,
+                        r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+([A-Za-z_]\w*)"#,
+                    ),
+                    (
+                        Type,
+                        r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(?:struct|enum|trait|type|union)\s+([A-Za-z_]\w*)",
+                    ),
+                ],
+            ),
+            lang(
+                &["py", "pyi"],
+                &[
+                    (Function, r"^\s*(?:async\s+)?def\s+([A-Za-z_]\w*)"),
+                    (Class, r"^\s*class\s+([A-Za-z_]\w*)"),
+                ],
+            ),
+            lang(
+                &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"],
+                &[
+                    (
+                        Function,
+                        r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)",
+                    ),
+                    (
+                        Class,
+                        r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)",
+                    ),
+                    (
+                        Type,
+                        r"^\s*(?:export\s+)?(?:declare\s+)?(?:interface|type|enum)\s+([A-Za-z_$][\w$]*)",
+                    ),
+                    (
+                        Function,
+                        r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*=>|[A-Za-z_$][\w$]*\s*=>)",
+                    ),
+                ],
+            ),
+            lang(
+                &["go"],
+                &[
+                    (Function, r"^func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)"),
+                    (Type, r"^type\s+([A-Za-z_]\w*)"),
+                ],
+            ),
+            lang(
+                &["java", "kt", "kts", "cs", "scala"],
+                &[
+                    (
+                        Class,
+                        &format!(
+                            r"^\s*{java_modifiers}(?:class|interface|enum|record|object|trait|struct)\s+([A-Za-z_]\w*)"
+                        ),
+                    ),
+                    (
+                        Function,
+                        &format!(
+                            r"^\s*{java_modifiers}(?:fun|def)\s+(?:<[^>]*>\s*)?(?:[\w.]+\.)?([A-Za-z_]\w*)"
+                        ),
+                    ),
+                    (
+                        Function,
+                        r"^\s*(?:(?:public|private|protected|internal|static|final|abstract|override|virtual|async|synchronized)\s+)+[\w<>\[\],.?]+\s+([A-Za-z_]\w*)\s*\(",
+                    ),
+                ],
+            ),
+            lang(
+                &["c", "h", "cc", "cpp", "cxx", "hpp", "hh", "hxx"],
+                &[
+                    (Class, r"^\s*(?:template\s*<[^>]*>\s*)?class\s+([A-Za-z_]\w*)\s*(?:[:{]|$)"),
+                    (
+                        Type,
+                        r"^\s*(?:typedef\s+)?(?:struct|enum|union)\s+(?:class\s+)?([A-Za-z_]\w*)\s*(?:[:{]|$)",
+                    ),
+                    (
+                        Function,
+                        r"^[A-Za-z_][\w\s\*&:<>,]*?\b([A-Za-z_]\w*(?:::~?[A-Za-z_]\w*)*)\s*\([^;]*$",
+                    ),
+                ],
+            ),
+            lang(
+                &["rb"],
+                &[
+                    (Function, r"^\s*def\s+(?:self\.)?([A-Za-z_]\w*[?!=]?)"),
+                    (Class, r"^\s*(?:class|module)\s+([A-Z][\w:]*)"),
+                ],
+            ),
+            lang(
+                &["php"],
+                &[
+                    (
+                        Function,
+                        r"^\s*(?:(?:public|private|protected|static|abstract|final)\s+)*function\s+&?([A-Za-z_]\w*)",
+                    ),
+                    (
+                        Class,
+                        r"^\s*(?:(?:abstract|final|readonly)\s+)*(?:class|interface|trait|enum)\s+([A-Za-z_]\w*)",
+                    ),
+                ],
+            ),
+            lang(
+                &["sh", "bash", "zsh"],
+                &[(
+                    Function,
+                    r"^\s*(?:function\s+)?([A-Za-z_][\w-]*)\s*\(\)",
+                )],
+            ),
+        ]
+    })
+}
+
+/// Keywords that C-style function patterns would otherwise mistake for names.
+const NOT_NAMES: &[&str] = &["if", "for", "while", "switch", "return", "sizeof", "else"];
+
+/// Finds function, type, and class definitions in `content` with lightweight
+/// per-language patterns chosen by `path`'s extension. Files in other languages
+/// yield no symbols.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::symbols::{extract, SymbolKind};
+/// use std::path::Path;
+///
+/// let symbols = extract(Path::new("lib.rs"), "pub struct Config;\n\npub fn run() {}\n");
+/// let found: Vec<_> = symbols.iter().map(|s| (s.kind, s.name.as_str(), s.line_number)).collect();
+/// assert_eq!(found, [(SymbolKind::Type, "Config", 1), (SymbolKind::Function, "run", 3)]);
+/// ```
+pub fn extract(path: &Path, content: &str) -> Vec<Symbol> {
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return vec![];
+    };
+    let Some(language) = languages()
+        .iter()
+        .find(|l| l.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            language.rules.iter().find_map(|(kind, re)| {
+                let name = re.captures(line)?.get(1)?.as_str();
+                (!NOT_NAMES.contains(&name)).then(|| Symbol {
+                    kind: *kind,
+                    name: name.to_string(),
+                    line_number: i + 1,
+                    end_line: body_end(&lines, i),
+                })
+            })
+        })
+        .collect()
+}
+
+/// 1-based last line of the block opened on `lines[start]`: everything before the
+/// next non-blank line indented no deeper than it, except closing brackets, `end`,
+/// and `{` or `where` lines at the same depth, which still belong to the block.
+fn body_end(lines: &[&str], start: usize) -> usize {
+    let depth = indent(lines[start]);
+    let mut end = start;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let line_depth = indent(line);
+        if line_depth < depth || (line_depth == depth && !continues_block(trimmed)) {
+            break;
+        }
+        end = i;
+    }
+    end + 1
+}
+
+/// Leading whitespace width, counting a tab as four columns.
+fn indent(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn continues_block(trimmed: &str) -> bool {
+    trimmed.starts_with(['}', ')', ']', '{'])
+        || matches!(
+            trimmed.split(|c: char| !c.is_alphanumeric()).next(),
+            Some("end" | "where")
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(path: &str, content: &str) -> Vec<(SymbolKind, String)> {
+        extract(Path::new(path), content)
+            .into_iter()
+            .map(|s| (s.kind, s.name))
+            .collect()
+    }
+
+    fn f(name: &str) -> (SymbolKind, String) {
+        (SymbolKind::Function, name.to_string())
+    }
+
+    fn t(name: &str) -> (SymbolKind, String) {
+        (SymbolKind::Type, name.to_string())
+    }
+
+    fn c(name: &str) -> (SymbolKind, String) {
+        (SymbolKind::Class, name.to_string())
+    }
+
+    #[test]
+    fn rust_items() {
+        let src = "pub(crate) async fn load() {}\nimpl X {\n    const fn new() {}\n}\nenum E {}\npub trait T {}\n// fn commented\n";
+        assert_eq!(names("a.rs", src), [f("load"), f("new"), t("E"), t("T")]);
+    }
+
+    #[test]
+    fn python_and_ruby() {
+        assert_eq!(
+            names("a.py", "class A:\n    async def run(self):\n        pass\n"),
+            [c("A"), f("run")]
+        );
+        assert_eq!(
+            names("a.rb", "module M\n  def self.ready?\n  end\nend\n"),
+            [c("M"), f("ready?")]
+        );
+    }
+
+    #[test]
+    fn javascript_and_typescript() {
+        let src = "export default function main() {}\nexport const add = (a, b) => a + b;\nconst x = 1;\nexport interface Props {}\nclass View {}\n";
+        assert_eq!(
+            names("a.ts", src),
+            [f("main"), f("add"), t("Props"), c("View")]
+        );
+    }
+
+    #[test]
+    fn go_and_c() {
+        assert_eq!(
+            names(
+                "a.go",
+                "type Server struct{}\nfunc (s *Server) Serve() error {\n"
+            ),
+            [t("Server"), f("Serve")]
+        );
+        assert_eq!(
+            names(
+                "a.c",
+                "static int parse(const char *s)\n{\nint decl(void);\nstruct node {\n"
+            ),
+            [f("parse"), t("node")]
+        );
+    }
+
+    #[test]
+    fn java_methods_and_classes() {
+        let src = "public final class Main {\n    public static void main(String[] args) {\n        if (x) {}\n";
+        assert_eq!(names("Main.java", src), [c("Main"), f("main")]);
+    }
+
+    #[test]
+    fn body_ends_where_indentation_returns() {
+        let spans = |path: &str, src: &str| -> Vec<(String, usize, usize)> {
+            extract(Path::new(path), src)
+                .into_iter()
+                .map(|s| (s.name, s.line_number, s.end_line))
+                .collect()
+        };
+        let rust = "impl A {\n    fn one() {\n        x();\n    }\n\n    fn two() {}\n}\nfn three<T>()\nwhere\n    T: X,\n{\n}\n";
+        assert_eq!(
+            spans("a.rs", rust),
+            [
+                ("one".to_string(), 2, 4),
+                ("two".to_string(), 6, 6),
+                ("three".to_string(), 8, 12)
+            ]
+        );
+        let python = "class A:\n    def run(self):\n        pass\n\nx = 1\n";
+        assert_eq!(
+            spans("a.py", python),
+            [("A".to_string(), 1, 3), ("run".to_string(), 2, 3)]
+        );
+    }
+
+    #[test]
+    fn unknown_language_has_no_symbols() {
+        assert!(extract(Path::new("notes.txt"), "fn main() {}").is_empty());
+        assert!(extract(Path::new("Makefile"), "fn main() {}").is_empty());
+    }
+}
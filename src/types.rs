@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Activity filter for the user report event feed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
@@ -9,6 +12,17 @@ pub enum ActivityFilter {
     Commits,
 }
 
+/// Output format for the user report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// The printable PDF report
+    Pdf,
+    /// The aggregated report data as JSON
+    Json,
+    /// The PDF, plus the JSON next to it with a `.json` extension
+    Both,
+}
+
 /// Configuration for a `gitprint user` run.
 #[allow(missing_docs)]
 #[derive(Debug, Clone)]
@@ -23,10 +37,18 @@ pub struct UserReportConfig {
     pub last_commits: usize,
     /// Skip diff rendering entirely.
     pub no_diffs: bool,
+    /// Unchanged lines kept around each change; longer runs collapse to a marker.
+    pub diff_context: usize,
+    /// Append every commit from each push in the window, grouped by repository.
+    pub full_diffs: bool,
+    /// Most commits fetched for `full_diffs`.
+    pub full_diffs_limit: usize,
     /// Font size used for diff/code blocks.
     pub font_size: f64,
     /// GitHub personal access token (`GITHUB_TOKEN` env var).
     pub github_token: Option<String>,
+    /// Where GitHub API responses are cached (`None` = always fetch).
+    pub cache: Option<crate::cache::ApiCache>,
     /// Earliest date to include events from, in `YYYY-MM-DD` form (`None` = no lower bound).
     pub since: Option<String>,
     /// Latest date to include events from, in `YYYY-MM-DD` form (`None` = no upper bound).
@@ -35,6 +57,8 @@ pub struct UserReportConfig {
     pub activity: ActivityFilter,
     /// Maximum number of events to show in the activity feed.
     pub events: usize,
+    /// What to write: the PDF, the JSON data, or both.
+    pub format: ReportFormat,
 }
 
 /// Paper size for PDF output.
@@ -48,6 +72,321 @@ pub enum PaperSize {
     Legal,
 }
 
+/// Logical pages per physical sheet for `--nup`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum NUp {
+    /// Two pages side by side on a sheet of swapped orientation.
+    #[value(name = "2")]
+    Two,
+    /// Four pages in a 2×2 grid on a sheet of the same orientation.
+    #[value(name = "4")]
+    Four,
+}
+
+/// Vertical spacing preset (`--density`): line height and the gaps between
+/// headings, rows, and files, together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Density {
+    /// Tight lines and half gaps, to fit the most code on a page.
+    Compact,
+    /// Font size + 2 pt lines and the standard gaps.
+    #[default]
+    Normal,
+    /// Open lines and gaps half as large again, for reading and annotating.
+    Relaxed,
+}
+
+impl Density {
+    /// Points added to the font size for the line height when `--line-height`
+    /// isn't given.
+    pub fn leading(self) -> f64 {
+        match self {
+            Self::Compact => 1.0,
+            Self::Normal => 2.0,
+            Self::Relaxed => 4.0,
+        }
+    }
+
+    /// Factor on the gaps renderers leave between headings, rows, and files.
+    pub fn gap_scale(self) -> f32 {
+        match self {
+            Self::Compact => 0.5,
+            Self::Normal => 1.0,
+            Self::Relaxed => 1.5,
+        }
+    }
+}
+
+/// What happens to control characters in source text (`--control-chars`): form
+/// feeds, stray carriage returns, ANSI escape sequences, and the rest of C0, DEL,
+/// and C1. Tabs are left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ControlChars {
+    /// Shown in caret notation (`^L`, `^[`, `^?`), C1 as `<U+0085>`.
+    #[default]
+    Visualize,
+    /// Dropped, escape sequences whole.
+    Strip,
+}
+
+/// Room left for handwritten review comments (`--review-layout`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReviewLayout {
+    /// A ruled note column to the right of a narrowed code column.
+    Margin,
+    /// A lined note page after every page of code.
+    Pages,
+}
+
+/// Color treatment applied to everything drawn on a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Theme and UI colors unchanged.
+    Color,
+    /// Every color replaced by its luminance-equivalent gray (`--grayscale`).
+    Grayscale,
+    /// Saturated colors blended toward white to use less ink (`--ink-saver`).
+    InkSaver,
+}
+
+impl ColorMode {
+    /// Transforms one RGB color (components in `0.0..=1.0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::types::ColorMode;
+    ///
+    /// let (r, g, b) = ColorMode::Grayscale.apply(1.0, 0.0, 0.0);
+    /// assert_eq!((r, g), (g, b));
+    /// assert_eq!(ColorMode::InkSaver.apply(0.0, 0.0, 0.0), (0.0, 0.0, 0.0));
+    /// ```
+    pub fn apply(self, r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+        match self {
+            Self::Color => (r, g, b),
+            Self::Grayscale => {
+                // Rec. 709 relative luminance.
+                let l = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+                (l, l, l)
+            }
+            Self::InkSaver => {
+                // Grays (saturation 0) keep their value so text stays legible;
+                // fully saturated colors move halfway to white.
+                let saturation = r.max(g).max(b) - r.min(g).min(b);
+                let t = saturation * 0.5;
+                let lighten = |c: f32| c + (1.0 - c) * t;
+                (lighten(r), lighten(g), lighten(b))
+            }
+        }
+    }
+}
+
+/// Lines to tint in files matching a glob (`--mark <glob>:<ranges>`).
+#[derive(Debug, Clone)]
+pub struct LineMark {
+    /// Files the mark applies to, matched against repo-relative paths.
+    pub glob: globset::GlobMatcher,
+    /// 1-based, inclusive line ranges.
+    pub ranges: Vec<RangeInclusive<usize>>,
+}
+
+impl LineMark {
+    /// All ranges from `marks` whose glob matches `path`.
+    pub fn ranges_for(marks: &[LineMark], path: &Path) -> Vec<RangeInclusive<usize>> {
+        marks
+            .iter()
+            .filter(|m| m.glob.is_match(path))
+            .flat_map(|m| m.ranges.iter().cloned())
+            .collect()
+    }
+}
+
+impl FromStr for LineMark {
+    type Err = String;
+
+    /// Parses `<glob>:<ranges>`, where ranges are comma-separated `N` or `N-M`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::types::LineMark;
+    ///
+    /// let mark: LineMark = "src/auth.rs:40-75,90".parse().unwrap();
+    /// assert_eq!(mark.ranges, vec![40..=75, 90..=90]);
+    /// assert!("src/auth.rs".parse::<LineMark>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (glob, ranges) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("expected <glob>:<ranges>, got {s:?}"))?;
+        let glob = globset::Glob::new(glob)
+            .map_err(|e| format!("invalid glob {glob:?}: {e}"))?
+            .compile_matcher();
+        let ranges = ranges
+            .split(',')
+            .map(|r| {
+                let (start, end) = r.split_once('-').unwrap_or((r, r));
+                let parse = |n: &str| n.trim().parse::<usize>().ok().filter(|&n| n > 0);
+                match (parse(start), parse(end)) {
+                    (Some(start), Some(end)) if start <= end => Ok(start..=end),
+                    _ => Err(format!("invalid line range {r:?}")),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { glob, ranges })
+    }
+}
+
+/// A row of the cover page's metadata table (`--cover-rows`). Rows without a value
+/// are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoverRow {
+    /// Branch being printed.
+    Branch,
+    /// Short commit hash.
+    Commit,
+    /// Commit author and email.
+    Author,
+    /// Commit date.
+    Date,
+    /// Commit signature status.
+    Signature,
+    /// Commit message subject.
+    Message,
+    /// GitHub repository description.
+    Description,
+    /// GitHub topics.
+    Topics,
+    /// GitHub stars.
+    Stars,
+    /// GitHub forks.
+    Forks,
+    /// Open GitHub issues.
+    OpenIssues,
+    /// The remote's default branch.
+    DefaultBranch,
+    /// License detected by GitHub.
+    License,
+    /// Number of printed files.
+    Files,
+    /// Lines across the printed files.
+    Lines,
+    /// Size of the tracked files.
+    RepoSize,
+    /// Size of the checkout on disk.
+    FsSize,
+    /// Owner of the checkout directory.
+    FsOwner,
+    /// Group of the checkout directory.
+    FsGroup,
+    /// When the PDF was generated.
+    Generated,
+}
+
+impl CoverRow {
+    /// Every row, in the default order.
+    pub const ALL: [Self; 20] = [
+        Self::Branch,
+        Self::Commit,
+        Self::Author,
+        Self::Date,
+        Self::Signature,
+        Self::Message,
+        Self::Description,
+        Self::Topics,
+        Self::Stars,
+        Self::Forks,
+        Self::OpenIssues,
+        Self::DefaultBranch,
+        Self::License,
+        Self::Files,
+        Self::Lines,
+        Self::RepoSize,
+        Self::FsSize,
+        Self::FsOwner,
+        Self::FsGroup,
+        Self::Generated,
+    ];
+
+    /// Label printed in the table's first column.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Branch => "Branch",
+            Self::Commit => "Commit",
+            Self::Author => "Author",
+            Self::Date => "Date",
+            Self::Signature => "Signature",
+            Self::Message => "Message",
+            Self::Description => "Description",
+            Self::Topics => "Topics",
+            Self::Stars => "Stars",
+            Self::Forks => "Forks",
+            Self::OpenIssues => "Open Issues",
+            Self::DefaultBranch => "Default Branch",
+            Self::License => "License",
+            Self::Files => "Files",
+            Self::Lines => "Lines",
+            Self::RepoSize => "Repo Size",
+            Self::FsSize => "FS Size",
+            Self::FsOwner => "FS Owner",
+            Self::FsGroup => "FS Group",
+            Self::Generated => "Generated",
+        }
+    }
+}
+
+/// An extra row for the cover page's metadata table (`--meta <label>=<value>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetaRow {
+    /// Text in the label column.
+    pub label: String,
+    /// Text in the value column.
+    pub value: String,
+}
+
+impl FromStr for MetaRow {
+    type Err = String;
+
+    /// Parses `<label>=<value>`; the value may itself contain `=`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gitprint::types::MetaRow;
+    ///
+    /// let row: MetaRow = "Reviewed by=J. Smith".parse().unwrap();
+    /// assert_eq!((row.label.as_str(), row.value.as_str()), ("Reviewed by", "J. Smith"));
+    /// assert!("Ticket".parse::<MetaRow>().is_err());
+    /// assert!("=SEC-1421".parse::<MetaRow>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected <label>=<value>, got {s:?}"))?;
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(format!("empty label in {s:?}"));
+        }
+        Ok(Self {
+            label: label.to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+/// Typesetting overrides for one language, set under `"lang"` in `.gitprint.json`.
+/// Unset fields fall back to the run-wide flags.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LangStyle {
+    /// Code font size in points.
+    pub font_size: Option<f64>,
+    /// Soft-wrap lines wider than the page instead of letting them run off the edge.
+    pub wrap: Option<bool>,
+    /// Syntax highlighting theme.
+    pub theme: Option<String>,
+}
+
 /// Configuration for a gitprint run.
 #[allow(missing_docs)]
 #[derive(Debug, Clone)]
@@ -56,20 +395,169 @@ pub struct Config {
     pub output_path: PathBuf,
     pub include_patterns: Vec<String>,
     pub exclude_patterns: Vec<String>,
+    /// `--include-regex` expressions, matched against the whole relative path.
+    pub include_regexes: Vec<String>,
+    /// `--exclude-regex` expressions.
+    pub exclude_regexes: Vec<String>,
+    /// `--lang` names; only files in these languages are printed.
+    pub languages: Vec<String>,
+    /// `--exclude-lang` names; files in these languages are skipped.
+    pub exclude_languages: Vec<String>,
+    /// `--preset` names from [`PRESETS`](crate::filter::presets::PRESETS).
+    pub presets: Vec<String>,
+    /// Skip the built-in [`DEFAULT_EXCLUDES`](crate::defaults::DEFAULT_EXCLUDES)
+    /// (`--no-default-excludes`).
+    pub no_default_excludes: bool,
+    /// Globs added to the default excludes (`extra_default_excludes` in
+    /// `.gitprint.json`); they apply even with `no_default_excludes`.
+    pub extra_default_excludes: Vec<String>,
     pub theme: String,
+    /// Darken theme colors too pale to read on paper (`--print-optimize`).
+    pub print_optimize: bool,
+    /// Files longer than this many lines print without syntax colors; 0 highlights
+    /// every file (`--highlight-max-lines`).
+    pub highlight_max_lines: usize,
+    /// How control characters in source text print (`--control-chars`).
+    pub control_chars: ControlChars,
+    /// Font files for text that isn't code — regular, then optional bold and
+    /// italic (`--prose-font`); empty keeps JetBrains Mono throughout.
+    pub prose_fonts: Vec<PathBuf>,
+    /// Font file drawing emoji and other characters the text fonts lack
+    /// (`--emoji-font`); `None` prints them as boxes.
+    pub emoji_font: Option<PathBuf>,
     pub font_size: f64,
+    /// Line spacing as a multiple of the font size; `None` adds the density's
+    /// leading to the font size.
+    pub line_height: Option<f64>,
+    /// Spacing preset for lines and gaps.
+    pub density: Density,
+    /// Extra space between characters of code, in points.
+    pub letter_spacing: f64,
     pub no_line_numbers: bool,
+    /// Continue line numbers across files instead of restarting at 1.
+    pub global_line_numbers: bool,
+    /// Shade the line-number gutter by each line's age from `git blame`.
+    pub age_shading: bool,
+    /// Put the line-number gutter on optional content layers viewers can toggle
+    /// (`--layers`).
+    pub layers: bool,
     pub toc: bool,
     pub file_tree: bool,
+    /// Typeset the repository's README after the cover (`--preface`).
+    pub preface: bool,
+    /// Insert a repository statistics page after the cover.
+    pub summary: bool,
+    /// Print the short commit hash and generation date in every page's footer.
+    pub commit_footer: bool,
+    /// Show the current file's path next to the page number in page headers.
+    pub running_header: bool,
     pub branch: Option<String>,
     pub commit: Option<String>,
+    /// Time limit for each git command (`--git-timeout`); `None` for none.
+    pub git_timeout: Option<std::time::Duration>,
     pub paper_size: PaperSize,
     pub landscape: bool,
     /// Original remote URL when input was a remote repository, used for GitHub links.
     pub remote_url: Option<String>,
+    /// Never query the GitHub API for the cover's description, topics, and counters.
+    pub offline: bool,
+    /// GitHub personal access token (`GITHUB_TOKEN` env var) for those queries.
+    pub github_token: Option<String>,
+    /// Re-indent JSON/XML/CSV files before highlighting (see [`crate::pretty`]).
+    pub pretty_data: bool,
+    /// Add a "Page X of Y" footer to every page.
+    pub page_total: bool,
+    /// Impose pages as a saddle-stitched booklet (2-up, signature order).
+    pub booklet: bool,
+    /// Print several logical pages per sheet.
+    pub nup: Option<NUp>,
+    /// Room for handwritten review comments on code pages.
+    pub review_layout: Option<ReviewLayout>,
+    /// Grayscale / ink-saver color transform.
+    pub color_mode: ColorMode,
+    /// Emit a tagged PDF with a document structure tree.
+    pub tagged: bool,
+    /// Run files on continuously, separated by a rule, instead of one page break each.
+    pub compact: bool,
+    /// Line ranges to tint, per file glob.
+    pub marks: Vec<LineMark>,
+    /// Pattern whose matches are highlighted and listed on a results page.
+    pub grep: Option<regex::Regex>,
+    /// Print only files whose content matches this pattern.
+    pub only_matching: Option<regex::Regex>,
+    /// Append an index of functions, types, and classes with their page numbers.
+    pub symbols: bool,
+    /// Append a table of SHA-256 hashes of the printed files (`--checksums`).
+    pub checksums: bool,
+    /// Print the enclosing function or type name in the outer margin when it changes.
+    pub margin_notes: bool,
+    /// Rank files by commits since this date on a hotspot page (`--hotspots`).
+    pub hotspots: Option<String>,
+    /// Add a page listing every tag and branch (`--refs`).
+    pub refs: bool,
+    /// Print this commit's message and patch instead of the repository (`--show`).
+    pub show: Option<String>,
+    /// Unchanged lines kept around each change in printed diffs (`--diff-context`).
+    pub diff_context: usize,
+    /// Open each directory this many levels deep with a divider page (`--chapters`).
+    pub chapters: Option<usize>,
+    /// Draw QR codes linking to the remote on the cover and beside each file header.
+    pub qr: bool,
+    /// Rows of the cover's metadata table, in order (`--cover-rows`).
+    pub cover_rows: Vec<CoverRow>,
+    /// Extra rows appended to the cover's metadata table (`--meta`).
+    pub meta: Vec<MetaRow>,
+    /// Date order, thousands separator, and cover label language (`--locale`).
+    pub locale: crate::locale::Locale,
+    /// Secret scanner applied to file content before highlighting (`--redact-secrets`).
+    pub redact: Option<crate::redact::Redactor>,
+    /// Hook that rewrites file content after reading and before redaction.
+    pub preprocess: Option<crate::preprocess::Preprocessor>,
+    /// Per-language style overrides, keyed by language name (`rust`, `markdown`) or
+    /// file extension (`md`), matched case-insensitively.
+    pub lang_styles: HashMap<String, LangStyle>,
+    /// Also list files git doesn't track yet (honouring `.gitignore`).
+    pub untracked: bool,
+    /// Restrict the file list to files changed in the working tree: `Some(None)`
+    /// compares against the index, `Some(Some(rev))` against `rev`.
+    pub changed_only: Option<Option<String>>,
+    /// Keep only files last changed on or after this `YYYY-MM-DD` date.
+    pub since: Option<String>,
+    /// Keep only files last changed on or before this `YYYY-MM-DD` date.
+    pub until: Option<String>,
+    /// Skip last-modified dates: no history walk, blank dates in the TOC and
+    /// file headers (`--no-dates`).
+    pub no_dates: bool,
+    /// Exact files to print, in order (`--files-from`); bypasses all filtering.
+    pub files_from: Option<Vec<PathBuf>>,
+    /// Deepest directory level to list below the root (`0` = top-level files only).
+    pub max_depth: Option<usize>,
+    /// Whether dotfiles and files inside dot-directories are listed.
+    pub hidden: bool,
+    /// Stop adding files once the document reaches this many pages (`--max-pages`).
+    pub max_pages: Option<usize>,
+    /// Stop adding files once the PDF would pass roughly this many bytes
+    /// (`--max-output-size`).
+    pub max_output_size: Option<u64>,
+    /// Called with the path, bytes written, and total as a large output passes
+    /// each tenth of its write. `None` keeps the library silent; the CLI installs
+    /// [`pdf::report_progress`](crate::pdf::report_progress).
+    pub write_progress: Option<fn(&Path, u64, u64)>,
 }
 
 impl Config {
+    /// The [`LangStyle`] for `path`, highlighted as `language`: a key naming the
+    /// language wins over one naming the file extension.
+    pub fn lang_style(&self, language: &str, path: &Path) -> Option<&LangStyle> {
+        let find = |name: &str| {
+            self.lang_styles
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, style)| style)
+        };
+        find(language).or_else(|| find(path.extension()?.to_str()?))
+    }
+
     #[cfg(test)]
     pub(crate) fn test_default() -> Self {
         Self {
@@ -77,33 +565,354 @@ impl Config {
             output_path: PathBuf::from("/tmp/gitprint-test.pdf"),
             include_patterns: vec![],
             exclude_patterns: vec![],
+            include_regexes: vec![],
+            exclude_regexes: vec![],
+            languages: vec![],
+            exclude_languages: vec![],
+            presets: vec![],
+            no_default_excludes: false,
+            extra_default_excludes: vec![],
             theme: "InspiredGitHub".to_string(),
             font_size: 8.0,
             no_line_numbers: false,
+            global_line_numbers: false,
+            age_shading: false,
+            layers: false,
+            print_optimize: false,
+            highlight_max_lines: crate::highlight::DEFAULT_HIGHLIGHT_MAX_LINES,
+            control_chars: ControlChars::Visualize,
+            prose_fonts: vec![],
+            emoji_font: None,
             toc: true,
             file_tree: true,
             branch: None,
             commit: None,
+            git_timeout: None,
             paper_size: PaperSize::A4,
             landscape: false,
             remote_url: None,
+            pretty_data: false,
+            page_total: false,
+            booklet: false,
+            nup: None,
+            review_layout: None,
+            color_mode: ColorMode::Color,
+            tagged: false,
+            compact: false,
+            marks: Vec::new(),
+            grep: None,
+            only_matching: None,
+            redact: None,
+            untracked: false,
+            changed_only: None,
+            since: None,
+            until: None,
+            no_dates: false,
+            files_from: None,
+            max_depth: None,
+            hidden: true,
+            preprocess: None,
+            lang_styles: HashMap::new(),
+            line_height: None,
+            density: Density::Normal,
+            letter_spacing: 0.0,
+            symbols: false,
+            checksums: false,
+            margin_notes: false,
+            qr: false,
+            cover_rows: CoverRow::ALL.to_vec(),
+            meta: Vec::new(),
+            locale: crate::locale::Locale::default(),
+            commit_footer: true,
+            running_header: true,
+            chapters: None,
+            summary: true,
+            hotspots: None,
+            preface: false,
+            offline: true,
+            github_token: None,
+            refs: false,
+            show: None,
+            diff_context: 3,
+            max_pages: None,
+            max_output_size: None,
+            write_progress: None,
+        }
+    }
+}
+
+/// Outcome of a successful [`run`](crate::run).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    /// Files the repository listed, before any filter; 0 when nothing was
+    /// filtered (a single file, or `--show`).
+    pub files_listed: usize,
+    /// Files rendered into the PDF.
+    pub files_included: usize,
+    /// Selected files left out because they are binary, minified, or unreadable.
+    pub files_skipped: usize,
+    /// Files left out once `--max-pages` or `--max-output-size` was reached; they
+    /// are listed on a closing notice page.
+    pub files_truncated: usize,
+    /// Files left out by `--only-matching` because their content had no match.
+    pub files_unmatched: usize,
+    /// Pages in the written PDF (sheets, after `--nup` / `--booklet` imposition).
+    pub pages: usize,
+    /// Size of the written PDF in bytes.
+    pub pdf_bytes: u64,
+    /// Wall-clock time of the run.
+    pub elapsed: std::time::Duration,
+    /// Non-fatal problems, such as files that could not be read.
+    pub warnings: Vec<Warning>,
+    /// Every listed file that didn't make it into the PDF, and why, by path.
+    /// Files cut by `--max-pages` or `--max-output-size` are counted in
+    /// `files_truncated` instead.
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Why a listed file was left out of the PDF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SkipReason {
+    /// Matched a built-in or `extra_default_excludes` pattern.
+    DefaultExclude,
+    /// Dropped by an include or exclude glob or regex, `--lang`, or a preset.
+    Filter,
+    /// Last modified outside `--since` / `--until`.
+    DateRange,
+    /// Binary content.
+    Binary,
+    /// Minified content, such as bundled JavaScript.
+    Minified,
+    /// The preprocess hook failed on it under `on_failure = "skip"`.
+    Preprocess,
+    /// Could not be read.
+    Unreadable,
+    /// No `--grep` match under `--only-matching`.
+    Unmatched,
+}
+
+impl SkipReason {
+    /// Heading the CLI groups skipped files of this kind under.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DefaultExclude => "default excludes",
+            Self::Filter => "filters",
+            Self::DateRange => "outside the date range",
+            Self::Binary => "binary",
+            Self::Minified => "minified",
+            Self::Preprocess => "preprocess failures",
+            Self::Unreadable => "unreadable",
+            Self::Unmatched => "no match",
         }
     }
 }
 
+/// A listed file left out of the PDF, on [`RunReport::skipped`].
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::types::{SkipReason, SkippedFile};
+///
+/// let file = SkippedFile::new("tests/cli.rs", SkipReason::Filter).with_detail("exclude tests/**");
+/// assert_eq!(file.to_string(), "tests/cli.rs (exclude tests/**)");
+/// assert_eq!(SkippedFile::new("logo.png", SkipReason::Binary).to_string(), "logo.png");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Why it was left out.
+    pub reason: SkipReason,
+    /// The rule or error behind `reason`, when there is more to say.
+    pub detail: Option<String>,
+}
+
+impl SkippedFile {
+    /// `path`, left out for `reason`.
+    pub fn new(path: impl Into<PathBuf>, reason: SkipReason) -> Self {
+        Self {
+            path: path.into(),
+            reason,
+            detail: None,
+        }
+    }
+
+    /// This entry with the rule or error that decided it.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// `path`, then the detail in parentheses when there is one.
+impl std::fmt::Display for SkippedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&crate::display_path(&self.path))?;
+        match &self.detail {
+            Some(detail) => write!(f, " ({detail})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What a [`Warning`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningKind {
+    /// A selected file could not be read.
+    Unreadable,
+    /// A selected file was left out as binary or minified.
+    Skipped,
+    /// The preprocess hook failed on a file.
+    Preprocess,
+    /// A git history lookup — blame or a last-modified date — failed.
+    History,
+    /// The remote could not be reached, or there is none to link to.
+    Remote,
+    /// Characters the code font has no glyph for, printed as boxes.
+    Glyph,
+    /// An option that has no effect on this input.
+    Option,
+}
+
+impl WarningKind {
+    /// Heading the CLI groups warnings of this kind under.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unreadable => "unreadable files",
+            Self::Skipped => "skipped files",
+            Self::Preprocess => "preprocess failures",
+            Self::History => "history lookups",
+            Self::Remote => "remote",
+            Self::Glyph => "missing glyphs",
+            Self::Option => "options",
+        }
+    }
+}
+
+/// A non-fatal problem met during a [`run`](crate::run), collected on the
+/// [`RunReport`] instead of aborting it.
+///
+/// # Examples
+///
+/// ```
+/// use gitprint::types::{Warning, WarningKind};
+///
+/// let w = Warning::file(WarningKind::Skipped, "logo.png", "binary file");
+/// assert_eq!(w.to_string(), "logo.png: binary file");
+/// assert_eq!(Warning::new(WarningKind::Remote, "GitHub: timed out").to_string(), "GitHub: timed out");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What the warning is about.
+    pub kind: WarningKind,
+    /// File it concerns, relative to the repository root; `None` for the whole run.
+    pub path: Option<PathBuf>,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl Warning {
+    /// A warning about the run as a whole.
+    pub fn new(kind: WarningKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    /// A warning about the file at `path`.
+    pub fn file(kind: WarningKind, path: impl Into<PathBuf>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: Some(path.into()),
+            message: message.into(),
+        }
+    }
+}
+
+/// `path: message`, or just the message for run-wide warnings.
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {}", path.display(), self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// A file [`run`](crate::run) would print, as listed by
+/// [`candidates`](crate::candidates) with the counts its pages are estimated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Lines of text, after `--pretty-data`.
+    pub lines: usize,
+    /// Size of that text in bytes.
+    pub bytes: u64,
+}
+
+/// Size forecast from [`estimate`](crate::estimate), made without highlighting or
+/// rendering anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Estimate {
+    /// Files that would be printed.
+    pub files: usize,
+    /// Lines across those files.
+    pub lines: usize,
+    /// Approximate pages (sheets, after `--nup` / `--booklet` imposition).
+    pub pages: usize,
+    /// Rough size of the PDF in bytes.
+    pub approx_bytes: u64,
+}
+
+/// One-line summary, e.g. `12 files, 34 pages, 210.5 KB, 850ms`.
+impl std::fmt::Display for RunReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = if self.files_included == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} file{plural}, {} pages, {}, {}",
+            self.files_included,
+            self.pages,
+            crate::format_size(self.pdf_bytes),
+            crate::format_elapsed(self.elapsed),
+        )?;
+        if self.files_unmatched > 0 {
+            write!(f, ", {} without a match", self.files_unmatched)?;
+        }
+        if self.files_truncated > 0 {
+            write!(
+                f,
+                " (output truncated: {} of {} files rendered)",
+                self.files_included,
+                self.files_included + self.files_truncated
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// Metadata extracted from a git repository.
 #[allow(missing_docs)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RepoMetadata {
     pub name: String,
     pub branch: String,
     pub commit_hash: String,
     pub commit_hash_short: String,
+    /// Hash of the commit's root tree; empty outside git.
+    pub tree_hash: String,
     pub commit_date: String,
     pub commit_message: String,
     pub commit_author: String,
     /// Email address of the last committer.
     pub commit_author_email: String,
+    /// GPG/SSH signature status of the commit, e.g. `Good signature by Alice`;
+    /// empty outside git.
+    pub signature: String,
     pub file_count: usize,
     pub total_lines: usize,
     /// Filesystem owner of the input path (local paths only).
@@ -124,6 +933,9 @@ pub struct RepoMetadata {
     /// Absolute filesystem path to the repo root (local repos only, `None` for remote clones).
     /// Used to generate `file://` links on the cover page.
     pub repo_absolute_path: Option<PathBuf>,
+    /// Description, topics, and counters from the GitHub API, for github.com
+    /// remotes unless [`Config::offline`] is set.
+    pub github: Option<crate::github::GitHubRepo>,
 }
 
 /// An RGB color value.
@@ -145,6 +957,75 @@ pub struct HighlightedToken {
     pub italic: bool,
 }
 
+/// A tag or branch listed on the `--refs` page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRef {
+    /// Short name (`v1.2.0`, `main`, `origin/feature`).
+    pub name: String,
+    /// Tag or commit date (YYYY-MM-DD).
+    pub date: String,
+    /// Abbreviated hash of the commit the ref points to.
+    pub commit: String,
+    /// Subject line of that commit.
+    pub subject: String,
+    /// Commits (ahead, behind) the default branch; `None` for tags.
+    pub ahead_behind: Option<(usize, usize)>,
+}
+
+/// Every tag and branch in a repository, newest first, for the `--refs` page.
+#[derive(Debug, Clone, Default)]
+pub struct RefOverview {
+    /// Branch the others are compared with: `origin/HEAD`'s target, or `HEAD`.
+    pub default_branch: String,
+    /// Tags, each resolved to the commit it marks.
+    pub tags: Vec<GitRef>,
+    /// Local and remote-tracking branches.
+    pub branches: Vec<GitRef>,
+}
+
+/// One definition listed in the `--symbols` index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolEntry {
+    /// File the symbol is defined in.
+    pub path: PathBuf,
+    /// What was defined.
+    pub kind: crate::symbols::SymbolKind,
+    /// The defined name.
+    pub name: String,
+    /// 1-based line of the definition.
+    pub line_number: usize,
+    /// Physical PDF page the definition was printed on (link target).
+    pub page: usize,
+    /// Page number as printed in that page's header.
+    pub page_label: String,
+}
+
+/// One `--grep` match, listed on the search-results page.
+#[derive(Debug, Clone)]
+pub struct GrepHit {
+    /// File containing the match.
+    pub path: PathBuf,
+    /// 1-based line number of the match.
+    pub line_number: usize,
+    /// Physical PDF page the line was printed on (link target).
+    pub page: usize,
+    /// Page number as printed in that page's header.
+    pub page_label: String,
+    /// The full text of the matching line.
+    pub text: String,
+}
+
+/// One secret blanked out by `--redact-secrets`, listed on the redaction log page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redaction {
+    /// File the secret was found in.
+    pub path: PathBuf,
+    /// 1-based line on which the secret starts.
+    pub line_number: usize,
+    /// Name of the rule that matched, e.g. `"GitHub token"`.
+    pub kind: String,
+}
+
 /// A line of syntax-highlighted tokens.
 #[allow(missing_docs)]
 #[derive(Debug, Clone)]
@@ -157,6 +1038,53 @@ pub struct HighlightedLine {
 mod tests {
     use super::*;
 
+    #[test]
+    fn lang_style_prefers_language_name_over_extension() {
+        let style = |size| LangStyle {
+            font_size: Some(size),
+            ..LangStyle::default()
+        };
+        let mut config = Config::test_default();
+        config.lang_styles = HashMap::from([
+            ("Markdown".to_string(), style(9.5)),
+            ("md".to_string(), style(7.0)),
+            ("txt".to_string(), style(6.0)),
+        ]);
+        let size = |language, path| {
+            config
+                .lang_style(language, Path::new(path))
+                .and_then(|s| s.font_size)
+        };
+        assert_eq!(size("Markdown", "README.md"), Some(9.5));
+        assert_eq!(size("Plain Text", "notes.TXT"), Some(6.0));
+        assert_eq!(size("Rust", "main.rs"), None);
+    }
+
+    #[test]
+    fn line_mark_parses_ranges() {
+        let mark: LineMark = "src/*.rs:1,5-7".parse().unwrap();
+        assert_eq!(mark.ranges, vec![1..=1, 5..=7]);
+        assert!(mark.glob.is_match("src/auth.rs"));
+    }
+
+    #[test]
+    fn line_mark_rejects_bad_ranges() {
+        assert!("a.rs:0".parse::<LineMark>().is_err());
+        assert!("a.rs:9-3".parse::<LineMark>().is_err());
+        assert!("a.rs:x".parse::<LineMark>().is_err());
+        assert!("a.rs:".parse::<LineMark>().is_err());
+    }
+
+    #[test]
+    fn line_mark_ranges_for_matching_files_only() {
+        let marks: Vec<LineMark> = vec!["*.rs:1-2".parse().unwrap(), "lib.rs:9".parse().unwrap()];
+        assert_eq!(
+            LineMark::ranges_for(&marks, Path::new("lib.rs")),
+            vec![1..=2, 9..=9]
+        );
+        assert!(LineMark::ranges_for(&marks, Path::new("a.py")).is_empty());
+    }
+
     #[test]
     fn test_config_test_default() {
         let config = Config::test_default();
@@ -178,10 +1106,12 @@ mod tests {
             branch: "main".to_string(),
             commit_hash: "abc123".to_string(),
             commit_hash_short: "abc1234".to_string(),
+            tree_hash: String::new(),
             commit_date: "2024-01-01".to_string(),
             commit_message: "init".to_string(),
             commit_author: "Alice".to_string(),
             commit_author_email: "alice@example.com".to_string(),
+            signature: String::new(),
             file_count: 10,
             total_lines: 500,
             fs_owner: None,
@@ -191,12 +1121,34 @@ mod tests {
             fs_size: "1.5 MB".to_string(),
             detected_remote_url: None,
             repo_absolute_path: None,
+            github: None,
         };
         let cloned = meta.clone();
         assert_eq!(cloned.name, "test");
         assert_eq!(cloned.file_count, 10);
     }
 
+    #[test]
+    fn color_mode_color_is_identity() {
+        assert_eq!(ColorMode::Color.apply(0.2, 0.4, 0.6), (0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn color_mode_grayscale_uses_luminance() {
+        let (r, g, b) = ColorMode::Grayscale.apply(0.0, 1.0, 0.0);
+        assert!((r - 0.7152).abs() < 1e-6);
+        assert_eq!((r, r), (g, b));
+        assert_eq!(ColorMode::Grayscale.apply(1.0, 1.0, 1.0).0, 1.0);
+    }
+
+    #[test]
+    fn color_mode_ink_saver_lightens_saturated_only() {
+        // Gray unchanged.
+        assert_eq!(ColorMode::InkSaver.apply(0.5, 0.5, 0.5), (0.5, 0.5, 0.5));
+        // Pure red moves halfway to white on the unsaturated channels.
+        assert_eq!(ColorMode::InkSaver.apply(1.0, 0.0, 0.0), (1.0, 0.5, 0.5));
+    }
+
     #[test]
     fn test_rgb_color_copy() {
         let color = RgbColor {
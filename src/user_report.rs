@@ -1,10 +1,19 @@
 //! User report pipeline: fetch GitHub data in parallel, then render PDF.
 
+use anyhow::Context;
 use tokio::task::JoinSet;
 
-use crate::github::{self, CommitDetail, GitHubEvent, GitHubRepo, GitHubUser};
+use crate::cache::ApiCache;
+use crate::github::{
+    self, CommitDetail, ContributionDay, GitHubEvent, GitHubOrg, GitHubRepo, GitHubUser,
+};
 use crate::pdf;
-use crate::types::{ActivityFilter, UserReportConfig};
+use crate::pdf::user_cover::ContributionStats;
+use crate::pdf::user_issues::{IssueRepoStats, IssueStats, IssueThread};
+use crate::pdf::user_reviews::ReviewStats;
+use crate::pdf::user_team::TeamRow;
+use crate::remote::RemoteRepo;
+use crate::types::{ActivityFilter, ReportFormat, UserReportConfig};
 
 /// Pre-fetched GitHub data consumed by the PDF render phase.
 ///
@@ -19,6 +28,65 @@ pub(crate) struct UserReportData {
     pub events: Vec<GitHubEvent>,
     pub commit_msgs: std::collections::HashMap<String, String>,
     pub commit_details: Vec<(String, CommitDetail)>,
+    /// Every commit pushed in the window (`--full-diffs`), grouped by repository.
+    pub pushed_commits: Vec<PushedCommit>,
+    pub reviews: ReviewStats,
+    pub issues: IssueStats,
+    pub orgs: Vec<GitHubOrg>,
+    /// `None` without a token: the contributions calendar is GraphQL-only.
+    pub contributions: Option<ContributionStats>,
+}
+
+/// A commit from one of the user's pushes, for the `--full-diffs` appendix.
+#[derive(serde::Serialize)]
+pub(crate) struct PushedCommit {
+    /// `owner/repo`.
+    pub repo: String,
+    /// Branch the commit was pushed to.
+    pub branch: String,
+    #[serde(flatten)]
+    pub detail: CommitDetail,
+}
+
+/// Version of the `--format json` schema. Bumped whenever a field is renamed or
+/// removed; new fields may appear without a bump.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// `--format json` output for one user: the profile, repositories, and events as
+/// GitHub returns them, plus the statistics the PDF is built from.
+#[derive(serde::Serialize)]
+struct UserReportJson<'a> {
+    schema_version: u32,
+    since: Option<&'a str>,
+    until: Option<&'a str>,
+    user: &'a GitHubUser,
+    total_stars: u64,
+    contributions: Option<&'a ContributionStats>,
+    organizations: &'a [GitHubOrg],
+    starred_repos: &'a [GitHubRepo],
+    active_repos: &'a [GitHubRepo],
+    pushed_repos: &'a [GitHubRepo],
+    events: &'a [GitHubEvent],
+    reviews: &'a ReviewStats,
+    issues: &'a IssueStats,
+    recent_commits: Vec<RecentCommitJson<'a>>,
+    pushed_commits: &'a [PushedCommit],
+}
+
+/// A `--last-commits` commit in the JSON output.
+#[derive(serde::Serialize)]
+struct RecentCommitJson<'a> {
+    repo: &'a str,
+    #[serde(flatten)]
+    detail: &'a CommitDetail,
+}
+
+/// `--format json` output for a team: the comparison table, then each member.
+#[derive(serde::Serialize)]
+struct TeamReportJson<'a> {
+    schema_version: u32,
+    summary: Vec<TeamRow>,
+    users: Vec<UserReportJson<'a>>,
 }
 
 /// Fetches all GitHub data for the user report (Phases 1 & 2).
@@ -27,41 +95,68 @@ pub(crate) struct UserReportData {
 /// logic without triggering PDF rendering.
 pub(crate) async fn fetch_data(config: &UserReportConfig) -> anyhow::Result<UserReportData> {
     let token = config.github_token.as_deref();
+    let cache = config.cache.as_ref();
     let username = &config.username;
 
     // ── Phase 1: parallel API fetches ─────────────────────────────────────────
-    let (user_res, starred_res, active_res, pushed_res, events_res, search_commits_res) = tokio::join!(
-        github::get_user(username, token),
-        github::get_user_starred_repos(username, 5, token),
-        github::get_user_repos(username, "updated", 5, token),
-        github::get_user_repos(username, "pushed", config.last_repos, token),
-        github::get_user_events(username, 100, token),
+    let (
+        user_res,
+        starred_res,
+        active_res,
+        pushed_res,
+        events_res,
+        search_commits_res,
+        reviewed_res,
+        orgs_res,
+        calendar_res,
+    ) = tokio::join!(
+        github::get_user(username, token, cache),
+        github::get_user_starred_repos(username, 5, token, cache),
+        github::get_user_repos(username, "updated", 5, token, cache),
+        github::get_user_repos(username, "pushed", config.last_repos, token, cache),
+        fetch_events(config),
         async {
             if config.no_diffs || config.last_commits == 0 {
                 Ok(vec![])
             } else {
-                github::search_user_commits(username, config.last_commits, token).await
+                github::search_user_commits(username, config.last_commits, token, cache).await
             }
         },
+        async {
+            match config.activity {
+                ActivityFilter::All => {
+                    let (since, until) = (config.since.as_deref(), config.until.as_deref());
+                    github::count_reviewed_prs(username, since, until, token, cache)
+                        .await
+                        .ok()
+                }
+                ActivityFilter::Commits => None,
+            }
+        },
+        github::get_user_orgs(username, token, cache),
+        github::get_contribution_calendar(
+            username,
+            config.since.as_deref(),
+            config.until.as_deref(),
+            token,
+        ),
     );
 
     let user = user_res?;
     let starred_repos = starred_res.unwrap_or_default();
 
-    let events = {
-        let raw = coalesce_push_events(events_res.unwrap_or_default());
-        let date_filtered = raw.into_iter().filter(|e| {
-            let date = e.created_at.get(..10).unwrap_or(&e.created_at);
-            config.since.as_deref().is_none_or(|s| date >= s)
-                && config.until.as_deref().is_none_or(|u| date <= u)
-        });
-        match config.activity {
-            ActivityFilter::All => date_filtered.collect::<Vec<_>>(),
-            ActivityFilter::Commits => date_filtered
-                .filter(|e| e.kind == "PushEvent")
-                .collect::<Vec<_>>(),
-        }
+    let events: Vec<GitHubEvent> = events_res
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| in_report(e, config))
+        .collect();
+    // Every push counts towards the appendix, before same-day pushes are coalesced.
+    let full_diff_shas = if config.full_diffs && !config.no_diffs {
+        push_commits(&events, config.full_diffs_limit)
+    } else {
+        vec![]
     };
+    let events = coalesce_push_events(events);
 
     let push_event_repos: std::collections::HashSet<String> = events
         .iter()
@@ -95,6 +190,32 @@ pub(crate) async fn fetch_data(config: &UserReportConfig) -> anyhow::Result<User
         .collect();
 
     let total_stars: u64 = starred_repos.iter().map(|r| r.stargazers_count).sum();
+    let reviews = review_stats(&events, reviewed_res);
+    let issues = issue_stats(&events);
+    let contributions = calendar_res.ok().map(|days| contribution_stats(&days));
+
+    // Roles need a token that can see the membership; without one, list names only.
+    let mut orgs = orgs_res.unwrap_or_default();
+    if token.is_some() {
+        let mut roles = JoinSet::new();
+        orgs.iter().enumerate().for_each(|(i, org)| {
+            let (org, user, tok, cache) = (
+                org.login.clone(),
+                username.clone(),
+                token.map(str::to_string),
+                config.cache.clone(),
+            );
+            roles.spawn(async move {
+                let role = github::get_org_role(&org, &user, tok.as_deref(), cache.as_ref());
+                (i, role.await.ok())
+            });
+        });
+        roles
+            .join_all()
+            .await
+            .into_iter()
+            .for_each(|(i, role)| orgs[i].role = role);
+    }
 
     // ── Phase 2: fetch commit details in parallel ──────────────────────────────
     let search_commits = match search_commits_res {
@@ -107,37 +228,55 @@ pub(crate) async fn fetch_data(config: &UserReportConfig) -> anyhow::Result<User
         .map(|(_, sha, msg)| (sha.clone(), msg.clone()))
         .collect();
 
-    let commit_details: Vec<(String, CommitDetail)> = if !config.no_diffs && config.last_commits > 0
-    {
-        let shas: Vec<(String, String)> = search_commits
+    let shas: Vec<(String, String)> = if !config.no_diffs && config.last_commits > 0 {
+        search_commits
             .into_iter()
             .map(|(repo, sha, _)| (repo, sha))
-            .collect();
-        eprintln!("Fetching {} commit diff(s)...", shas.len());
-        let mut set: JoinSet<anyhow::Result<(String, CommitDetail)>> = JoinSet::new();
-        shas.into_iter().for_each(|(repo, sha)| {
-            let tok = token.map(str::to_string);
-            set.spawn(async move {
-                github::get_commit_detail(&repo, &sha, tok.as_deref())
-                    .await
-                    .map(|cd| (repo, cd))
-            });
-        });
-        let mut details: Vec<(String, CommitDetail)> = set
-            .join_all()
-            .await
-            .into_iter()
-            .filter_map(|r| match r {
-                Ok(pair) => Some(Ok(pair)),
-                Err(e) if e.to_string().contains("rate limit") => Some(Err(e)),
-                Err(_) => None,
-            })
-            .collect::<anyhow::Result<Vec<_>>>()?;
-        details.sort_unstable_by(|(_, a), (_, b)| b.commit.author.date.cmp(&a.commit.author.date));
-        details
+            .collect()
     } else {
         vec![]
     };
+    if !shas.is_empty() || !full_diff_shas.is_empty() {
+        eprintln!(
+            "Fetching {} commit diff(s)...",
+            shas.len() + full_diff_shas.len()
+        );
+    }
+    let branches: std::collections::HashMap<String, String> = full_diff_shas
+        .iter()
+        .map(|(_, branch, sha)| (sha.clone(), branch.clone()))
+        .collect();
+    let (details_res, pushed_res) = tokio::join!(
+        fetch_commit_details(shas, token, cache),
+        fetch_commit_details(
+            full_diff_shas
+                .into_iter()
+                .map(|(repo, _, sha)| (repo, sha))
+                .collect(),
+            token,
+            cache,
+        ),
+    );
+    let mut commit_details = details_res?;
+    commit_details
+        .sort_unstable_by(|(_, a), (_, b)| b.commit.author.date.cmp(&a.commit.author.date));
+    let mut pushed_commits: Vec<PushedCommit> = pushed_res?
+        .into_iter()
+        .map(|(repo, detail)| PushedCommit {
+            branch: branches.get(&detail.sha).cloned().unwrap_or_default(),
+            repo,
+            detail,
+        })
+        .collect();
+    pushed_commits.sort_by(|a, b| {
+        a.repo.cmp(&b.repo).then_with(|| {
+            a.detail
+                .commit
+                .author
+                .date
+                .cmp(&b.detail.commit.author.date)
+        })
+    });
 
     Ok(UserReportData {
         user,
@@ -148,6 +287,11 @@ pub(crate) async fn fetch_data(config: &UserReportConfig) -> anyhow::Result<User
         events,
         commit_msgs,
         commit_details,
+        pushed_commits,
+        reviews,
+        issues,
+        orgs,
+        contributions,
     })
 }
 
@@ -158,6 +302,13 @@ pub async fn run(config: &UserReportConfig) -> anyhow::Result<()> {
     eprintln!("Fetching GitHub data for @{}...", config.username);
     let data = fetch_data(config).await?;
 
+    if config.format != ReportFormat::Pdf {
+        write_json(&json_path(config), &report_json(config, &data), start).await?;
+    }
+    if config.format == ReportFormat::Json {
+        return Ok(());
+    }
+
     eprintln!("Rendering PDF...");
     let (doc, total_pages) = render_to_doc(config, &data)?;
     pdf::save_pdf(&doc, &config.output_path).await?;
@@ -177,6 +328,115 @@ pub async fn run(config: &UserReportConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Runs the team report pipeline: fetches every member in parallel, then writes one
+/// PDF opening with a comparative summary followed by a chapter per member.
+pub async fn run_team(config: &UserReportConfig, usernames: &[String]) -> anyhow::Result<()> {
+    let start = std::time::Instant::now();
+
+    eprintln!("Fetching GitHub data for {} users...", usernames.len());
+    let mut tasks = JoinSet::new();
+    usernames.iter().enumerate().for_each(|(i, username)| {
+        let config = UserReportConfig {
+            username: username.clone(),
+            ..config.clone()
+        };
+        tasks.spawn(async move { (i, fetch_data(&config).await) });
+    });
+    let mut members: Vec<Option<UserReportData>> = usernames.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        let (i, data) = joined?;
+        members[i] = Some(data.with_context(|| format!("fetching @{}", usernames[i]))?);
+    }
+    let members: Vec<UserReportData> = members.into_iter().flatten().collect();
+
+    if config.format != ReportFormat::Pdf {
+        let json = TeamReportJson {
+            schema_version: JSON_SCHEMA_VERSION,
+            summary: members.iter().map(team_row).collect(),
+            users: members
+                .iter()
+                .map(|data| report_json(config, data))
+                .collect(),
+        };
+        write_json(&json_path(config), &json, start).await?;
+    }
+    if config.format == ReportFormat::Json {
+        return Ok(());
+    }
+
+    eprintln!("Rendering PDF...");
+    let (doc, total_pages) = render_team_doc(config, &members)?;
+    pdf::save_pdf(&doc, &config.output_path).await?;
+
+    let elapsed = elapsed_str(start.elapsed());
+    let pdf_size = tokio::fs::metadata(&config.output_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    eprintln!(
+        "{} — {} users, {} pages, {}, {}",
+        config.output_path.display(),
+        members.len(),
+        total_pages,
+        format_size(pdf_size),
+        elapsed,
+    );
+    Ok(())
+}
+
+/// The `--format json` view of one user's report data.
+fn report_json<'a>(config: &'a UserReportConfig, data: &'a UserReportData) -> UserReportJson<'a> {
+    UserReportJson {
+        schema_version: JSON_SCHEMA_VERSION,
+        since: config.since.as_deref(),
+        until: config.until.as_deref(),
+        user: &data.user,
+        total_stars: data.total_stars,
+        contributions: data.contributions.as_ref(),
+        organizations: &data.orgs,
+        starred_repos: &data.starred_repos,
+        active_repos: &data.active_repos,
+        pushed_repos: &data.pushed_repos,
+        events: &data.events,
+        reviews: &data.reviews,
+        issues: &data.issues,
+        recent_commits: data
+            .commit_details
+            .iter()
+            .map(|(repo, detail)| RecentCommitJson { repo, detail })
+            .collect(),
+        pushed_commits: &data.pushed_commits,
+    }
+}
+
+/// Where the JSON goes: the output path itself with `--format json`, or beside
+/// the PDF with a `.json` extension with `--format both`.
+fn json_path(config: &UserReportConfig) -> std::path::PathBuf {
+    match config.format {
+        ReportFormat::Both => config.output_path.with_extension("json"),
+        ReportFormat::Pdf | ReportFormat::Json => config.output_path.clone(),
+    }
+}
+
+/// Writes `value` as pretty-printed JSON to `path` and reports the result.
+async fn write_json(
+    path: &std::path::Path,
+    value: &impl serde::Serialize,
+    start: std::time::Instant,
+) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(value)? + "\n";
+    tokio::fs::write(path, &json)
+        .await
+        .with_context(|| format!("writing {}", path.display()))?;
+    eprintln!(
+        "{} — JSON, {}, {}",
+        path.display(),
+        format_size(json.len() as u64),
+        elapsed_str(start.elapsed()),
+    );
+    Ok(())
+}
+
 /// Render the user report PDF from pre-fetched data.
 ///
 /// Returns the assembled `PdfDocument` (ready to save) and the page count.
@@ -189,16 +449,65 @@ pub(crate) fn render_to_doc(
     let fonts = pdf::fonts::load_fonts(&mut doc)?;
     let mut builder = pdf::create_user_builder(config, fonts);
 
+    render_user(&mut builder, config, data);
+
+    let pages = builder.finish();
+    let page_count = pages.len();
+    doc.with_pages(pages);
+    Ok((doc, page_count))
+}
+
+/// Render the team report PDF: the comparative summary page, then each member's
+/// report as its own chapter, in the order given.
+pub(crate) fn render_team_doc(
+    config: &UserReportConfig,
+    members: &[UserReportData],
+) -> anyhow::Result<(printpdf::PdfDocument, usize)> {
+    let mut doc = printpdf::PdfDocument::new("GitHub Team Report");
+    let fonts = pdf::fonts::load_fonts(&mut doc)?;
+    let mut builder = pdf::create_user_builder(config, fonts);
+
+    let rows: Vec<TeamRow> = members.iter().map(team_row).collect();
+    pdf::user_team::render(&mut builder, &period_label(config), &rows);
+    members.iter().for_each(|data| {
+        render_user(&mut builder, config, data);
+        builder.page_break();
+    });
+
+    let pages = builder.finish();
+    let page_count = pages.len();
+    doc.with_pages(pages);
+    Ok((doc, page_count))
+}
+
+/// Renders one user's report — cover, activity, reviews, issues, organizations,
+/// repositories, and commit diffs — onto `builder`. Shared by the single-user and
+/// team reports.
+fn render_user(
+    builder: &mut pdf::layout::PageBuilder,
+    config: &UserReportConfig,
+    data: &UserReportData,
+) {
     // Cover page
-    pdf::user_cover::render(&mut builder, &data.user, data.total_stars);
+    pdf::user_cover::render(
+        builder,
+        &data.user,
+        data.total_stars,
+        &data.orgs,
+        data.contributions.as_ref(),
+    );
 
     // Activity feed — capped to the requested display limit.
     let display_events = &data.events[..config.events.min(data.events.len())];
-    pdf::user_activity::render(&mut builder, display_events, &data.commit_msgs);
+    pdf::user_activity::render(builder, display_events, &data.commit_msgs);
+
+    pdf::user_reviews::render(builder, &data.reviews);
+    pdf::user_issues::render(builder, &data.issues);
+    pdf::user_orgs::render(builder, &data.orgs);
 
     // Repository sections — pass events + fetched commit msgs for rich context
     render_repos_section(
-        &mut builder,
+        builder,
         "Top Starred Repositories",
         &data.starred_repos,
         5,
@@ -206,7 +515,7 @@ pub(crate) fn render_to_doc(
         &data.commit_msgs,
     );
     render_repos_section(
-        &mut builder,
+        builder,
         "Repos You Were Active In",
         &data.active_repos,
         5,
@@ -214,7 +523,7 @@ pub(crate) fn render_to_doc(
         &data.commit_msgs,
     );
     render_repos_section(
-        &mut builder,
+        builder,
         "Repos User Pushed To",
         &data.pushed_repos,
         config.last_repos,
@@ -242,18 +551,197 @@ pub(crate) fn render_to_doc(
         builder.vertical_space(12.0);
         data.commit_details.iter().for_each(|(repo, detail)| {
             let branch = sha_to_branch.get(detail.sha.as_str()).copied();
-            pdf::diff::render_commit(&mut builder, detail, repo, branch, config.font_size as f32);
+            let remote = RemoteRepo::parse(&format!("https://github.com/{repo}"));
+            pdf::diff::render_commit(
+                builder,
+                detail,
+                repo,
+                Some(&remote),
+                branch,
+                config.font_size as f32,
+                config.diff_context,
+            );
         });
     }
 
-    let pages = builder.finish();
-    let page_count = pages.len();
-    doc.with_pages(pages);
-    Ok((doc, page_count))
+    render_pushed_commits(builder, config, &data.pushed_commits);
+}
+
+/// Renders the `--full-diffs` appendix on a fresh page: each repository's pushed
+/// commits in the order they were written, under a heading linking to the repository.
+fn render_pushed_commits(
+    builder: &mut pdf::layout::PageBuilder,
+    config: &UserReportConfig,
+    commits: &[PushedCommit],
+) {
+    if commits.is_empty() {
+        return;
+    }
+    let bold = builder.font(true, false).clone();
+    let regular = builder.font(false, false).clone();
+    let black = printpdf::Color::Rgb(printpdf::Rgb::new(0.0, 0.0, 0.0, None));
+    let gray = printpdf::Color::Rgb(printpdf::Rgb::new(0.47, 0.47, 0.47, None));
+
+    let groups: Vec<&[PushedCommit]> = commits.chunk_by(|a, b| a.repo == b.repo).collect();
+    builder.page_break();
+    builder.write_centered("Pushed Commits", &bold, printpdf::Pt(16.0), black.clone());
+    builder.vertical_space(4.0);
+    builder.write_line_centered(&[pdf::layout::Span {
+        text: format!(
+            "{} commit{} across {} repositor{}",
+            commits.len(),
+            if commits.len() == 1 { "" } else { "s" },
+            groups.len(),
+            if groups.len() == 1 { "y" } else { "ies" },
+        ),
+        font_id: regular.clone(),
+        size: printpdf::Pt(8.0),
+        color: gray.clone(),
+    }]);
+    builder.vertical_space(12.0);
+
+    groups.into_iter().for_each(|group| {
+        let repo = &group[0].repo;
+        builder.ensure_space(builder.line_height() * 6.0);
+        builder.write_line_justified(
+            &[pdf::layout::Span {
+                text: repo.clone(),
+                font_id: bold.clone(),
+                size: printpdf::Pt(12.0),
+                color: black.clone(),
+            }],
+            &[pdf::layout::Span {
+                text: format!(
+                    "{} commit{}",
+                    group.len(),
+                    if group.len() == 1 { "" } else { "s" }
+                ),
+                font_id: regular.clone(),
+                size: printpdf::Pt(8.0),
+                color: gray.clone(),
+            }],
+        );
+        builder.add_link(
+            builder.line_height(),
+            printpdf::Actions::Uri(format!("https://github.com/{repo}")),
+        );
+        builder.vertical_space(6.0);
+        let remote = RemoteRepo::parse(&format!("https://github.com/{repo}"));
+        group.iter().for_each(|pushed| {
+            pdf::diff::render_commit(
+                builder,
+                &pushed.detail,
+                repo,
+                Some(&remote),
+                Some(pushed.branch.as_str()).filter(|b| !b.is_empty()),
+                config.font_size as f32,
+                config.diff_context,
+            );
+        });
+        builder.vertical_space(8.0);
+    });
 }
 
 // ── Helpers ────────────────────────────────────────────────────────────────────
 
+/// Whether `event` falls in the `--since` / `--until` window and passes `--activity`.
+fn in_report(event: &GitHubEvent, config: &UserReportConfig) -> bool {
+    let date = event.created_at.get(..10).unwrap_or(&event.created_at);
+    config.since.as_deref().is_none_or(|s| date >= s)
+        && config.until.as_deref().is_none_or(|u| date <= u)
+        && match config.activity {
+            ActivityFilter::All => true,
+            ActivityFilter::Commits => event.kind == "PushEvent",
+        }
+}
+
+/// Fetches the event feed a page at a time until it covers the report: past
+/// `--since` when one is set, otherwise until `--events` events pass the filters.
+/// A failed later page ends the feed early rather than failing the report.
+async fn fetch_events(config: &UserReportConfig) -> anyhow::Result<Vec<GitHubEvent>> {
+    let (token, cache) = (config.github_token.as_deref(), config.cache.as_ref());
+    let mut events: Vec<GitHubEvent> = Vec::new();
+    for page in 1..=github::MAX_EVENT_PAGES {
+        let batch = match github::get_user_events(&config.username, page, token, cache).await {
+            Ok(batch) => batch,
+            Err(e) if page == 1 => return Err(e),
+            Err(_) => break,
+        };
+        let last_page = batch.len() < github::EVENTS_PER_PAGE;
+        events.extend(batch);
+        let covered = match config.since.as_deref() {
+            Some(since) => events
+                .last()
+                .is_some_and(|e| e.created_at.get(..10).unwrap_or(&e.created_at) < since),
+            None => events.iter().filter(|e| in_report(e, config)).count() >= config.events,
+        };
+        if last_page || covered {
+            break;
+        }
+    }
+    Ok(events)
+}
+
+/// Fetches `(repo, sha)` commit details in parallel. Commits that fail to load are
+/// dropped, except on a rate limit, which aborts the report.
+async fn fetch_commit_details(
+    shas: Vec<(String, String)>,
+    token: Option<&str>,
+    cache: Option<&ApiCache>,
+) -> anyhow::Result<Vec<(String, CommitDetail)>> {
+    let mut set: JoinSet<anyhow::Result<(String, CommitDetail)>> = JoinSet::new();
+    shas.into_iter().for_each(|(repo, sha)| {
+        let (tok, cache) = (token.map(str::to_string), cache.cloned());
+        set.spawn(async move {
+            github::get_commit_detail(&repo, &sha, tok.as_deref(), cache.as_ref())
+                .await
+                .map(|cd| (repo, cd))
+        });
+    });
+    set.join_all()
+        .await
+        .into_iter()
+        .filter_map(|r| match r {
+            Ok(pair) => Some(Ok(pair)),
+            Err(e) if e.to_string().contains("rate limit") => Some(Err(e)),
+            Err(_) => None,
+        })
+        .collect()
+}
+
+/// `(repo, branch, sha)` for every commit in the push events, newest push first,
+/// each commit once and at most `limit` in all. A push without a commit list
+/// contributes its head.
+fn push_commits(events: &[GitHubEvent], limit: usize) -> Vec<(String, String, String)> {
+    let mut seen = std::collections::HashSet::new();
+    events
+        .iter()
+        .filter(|e| e.kind == "PushEvent")
+        .flat_map(|e| {
+            let branch = e.payload["ref"]
+                .as_str()
+                .unwrap_or("")
+                .trim_start_matches("refs/heads/");
+            let listed: Vec<&str> = e.payload["commits"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|c| c["distinct"].as_bool() != Some(false))
+                .filter_map(|c| c["sha"].as_str())
+                .collect();
+            let shas = if listed.is_empty() {
+                e.payload["head"].as_str().into_iter().collect()
+            } else {
+                listed
+            };
+            shas.into_iter()
+                .map(move |sha| (e.repo.name.clone(), branch.to_string(), sha.to_string()))
+        })
+        .filter(|(_, _, sha)| seen.insert(sha.clone()))
+        .take(limit)
+        .collect()
+}
+
 /// Keep only the first PushEvent per (date, repo, branch) — GitHub emits one per push, so a busy
 /// day can produce many identical-looking entries. Keeping the first (newest) is sufficient.
 fn coalesce_push_events(events: Vec<GitHubEvent>) -> Vec<GitHubEvent> {
@@ -275,6 +763,200 @@ fn coalesce_push_events(events: Vec<GitHubEvent>) -> Vec<GitHubEvent> {
         .collect()
 }
 
+/// Repositories listed under "Most reviewed repositories".
+const TOP_REVIEWED_REPOS: usize = 5;
+
+/// Review activity from the event feed: verdicts from PullRequestReview events,
+/// inline comments from PullRequestReviewComment events. `searched` is the search
+/// API's count of reviewed PRs, which reaches past the feed's 90 days and wins
+/// when larger.
+fn review_stats(events: &[GitHubEvent], searched: Option<u64>) -> ReviewStats {
+    let review_events = || {
+        events
+            .iter()
+            .filter(|e| e.kind.starts_with("PullRequestReview"))
+    };
+    let verdicts: Vec<String> = review_events()
+        .filter(|e| e.kind == "PullRequestReviewEvent")
+        .map(|e| {
+            e.payload["review"]["state"]
+                .as_str()
+                .unwrap_or("")
+                .to_ascii_lowercase()
+        })
+        .collect();
+    let prs: std::collections::HashSet<(&str, u64)> = review_events()
+        .map(|e| {
+            let number = e.payload["pull_request"]["number"].as_u64().unwrap_or(0);
+            (e.repo.name.as_str(), number)
+        })
+        .collect();
+    let mut top_repos: Vec<(String, usize)> = prs
+        .iter()
+        .fold(
+            std::collections::HashMap::<&str, usize>::new(),
+            |mut map, (repo, _)| {
+                *map.entry(*repo).or_default() += 1;
+                map
+            },
+        )
+        .into_iter()
+        .map(|(repo, n)| (repo.to_string(), n))
+        .collect();
+    top_repos.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_repos.truncate(TOP_REVIEWED_REPOS);
+
+    ReviewStats {
+        prs_reviewed: (prs.len() as u64).max(searched.unwrap_or(0)),
+        reviews: verdicts.len(),
+        approvals: verdicts.iter().filter(|s| *s == "approved").count(),
+        changes_requested: verdicts
+            .iter()
+            .filter(|s| *s == "changes_requested")
+            .count(),
+        comments: review_events()
+            .filter(|e| e.kind == "PullRequestReviewCommentEvent")
+            .count(),
+        top_repos,
+    }
+}
+
+/// Totals and streaks from a contributions calendar, oldest day first. The current
+/// streak runs up to the last day, or the day before when the last day has no
+/// contributions yet — today's streak isn't broken until today is over.
+fn contribution_stats(days: &[ContributionDay]) -> ContributionStats {
+    let active = |d: &&ContributionDay| d.contribution_count > 0;
+    let longest_streak = days
+        .iter()
+        .fold((0, 0), |(run, best), d| {
+            if active(&d) {
+                (run + 1, best.max(run + 1))
+            } else {
+                (0, best)
+            }
+        })
+        .1;
+    let counted = match days.split_last() {
+        Some((last, earlier)) if last.contribution_count == 0 => earlier,
+        _ => days,
+    };
+    ContributionStats {
+        total: days.iter().map(|d| d.contribution_count).sum(),
+        current_streak: counted.iter().rev().take_while(active).count(),
+        longest_streak,
+    }
+}
+
+/// Repositories in the issue table, and threads under "Most active threads".
+const TOP_ISSUE_REPOS: usize = 8;
+const TOP_ISSUE_THREADS: usize = 5;
+
+/// Issue activity from IssuesEvent (opened, reopened, closed) and IssueCommentEvent
+/// events. Comments on pull requests arrive as IssueCommentEvent too; they are
+/// left out.
+fn issue_stats(events: &[GitHubEvent]) -> IssueStats {
+    let mut repos: std::collections::HashMap<&str, IssueRepoStats> =
+        std::collections::HashMap::new();
+    let mut threads: Vec<IssueThread> = Vec::new();
+    events
+        .iter()
+        .filter(|e| e.kind == "IssuesEvent" || e.kind == "IssueCommentEvent")
+        .filter(|e| e.payload["issue"]["pull_request"].is_null())
+        .for_each(|e| {
+            let stats = repos
+                .entry(e.repo.name.as_str())
+                .or_insert_with(|| IssueRepoStats {
+                    repo: e.repo.name.clone(),
+                    ..IssueRepoStats::default()
+                });
+            match (e.kind.as_str(), e.payload["action"].as_str()) {
+                ("IssueCommentEvent", _) => stats.commented += 1,
+                (_, Some("opened" | "reopened")) => stats.opened += 1,
+                (_, Some("closed")) => stats.closed += 1,
+                _ => return,
+            }
+            let issue = &e.payload["issue"];
+            let number = issue["number"].as_u64().unwrap_or(0);
+            match threads
+                .iter_mut()
+                .find(|t| t.repo == e.repo.name && t.number == number)
+            {
+                Some(thread) => thread.events += 1,
+                None => threads.push(IssueThread {
+                    repo: e.repo.name.clone(),
+                    number,
+                    title: issue["title"].as_str().unwrap_or("").to_string(),
+                    html_url: issue["html_url"].as_str().unwrap_or("").to_string(),
+                    events: 1,
+                }),
+            }
+        });
+
+    let mut repos: Vec<IssueRepoStats> = repos.into_values().filter(|r| r.total() > 0).collect();
+    repos.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.repo.cmp(&b.repo)));
+    repos.truncate(TOP_ISSUE_REPOS);
+    // Stable sort: equally busy threads keep feed order, most recent first.
+    threads.sort_by(|a, b| b.events.cmp(&a.events));
+    threads.truncate(TOP_ISSUE_THREADS);
+    IssueStats { repos, threads }
+}
+
+/// A member's line in the team summary: commits pushed, pull requests opened, and
+/// reviews submitted over the report window, plus stars on their top repositories.
+fn team_row(data: &UserReportData) -> TeamRow {
+    let commits = data
+        .events
+        .iter()
+        .filter(|e| e.kind == "PushEvent")
+        .map(|e| {
+            e.payload["size"]
+                .as_u64()
+                .filter(|&n| n > 0)
+                .unwrap_or_else(|| {
+                    e.payload["commits"]
+                        .as_array()
+                        .map_or(0, |c| c.len() as u64)
+                })
+        })
+        .sum();
+    let pull_requests = data
+        .events
+        .iter()
+        .filter(|e| e.kind == "PullRequestEvent" && e.payload["action"] == "opened")
+        .count();
+    TeamRow {
+        login: data.user.login.clone(),
+        name: data.user.name.clone(),
+        commits,
+        pull_requests,
+        reviews: data.reviews.reviews,
+        stars: data.total_stars,
+    }
+}
+
+/// The report window as shown under the team summary title.
+fn period_label(config: &UserReportConfig) -> String {
+    match (config.since.as_deref(), config.until.as_deref()) {
+        (Some(since), Some(until)) => format!("{since} to {until}"),
+        (Some(since), None) => format!("since {since}"),
+        (None, Some(until)) => format!("until {until}"),
+        (None, None) => "recent activity".to_string(),
+    }
+}
+
+/// Parses a `--team` file: one GitHub username per line, with an optional leading
+/// `@`. Blank lines and `#` comments are skipped, and repeated names kept once.
+pub fn parse_team(text: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .map(|name| name.trim_start_matches('@'))
+        .filter(|name| !name.is_empty())
+        .filter(|name| seen.insert(name.to_ascii_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
 fn render_repos_section(
     builder: &mut crate::pdf::layout::PageBuilder,
     title: &str,
@@ -411,6 +1093,54 @@ mod tests {
         assert_eq!(builder.current_page(), page_before);
     }
 
+    #[test]
+    fn contribution_stats_counts_streaks() {
+        let calendar = |counts: &[u64]| -> Vec<ContributionDay> {
+            counts
+                .iter()
+                .enumerate()
+                .map(|(i, &n)| ContributionDay {
+                    date: format!("2024-03-{:02}", i + 1),
+                    contribution_count: n,
+                })
+                .collect()
+        };
+        assert_eq!(
+            contribution_stats(&calendar(&[1, 2, 3, 0, 4, 1, 0])),
+            ContributionStats {
+                total: 11,
+                current_streak: 2,
+                longest_streak: 3,
+            }
+        );
+        assert_eq!(contribution_stats(&calendar(&[2, 0, 0])).current_streak, 0);
+        assert_eq!(contribution_stats(&calendar(&[0, 5, 5])).current_streak, 2);
+        assert_eq!(contribution_stats(&[]), ContributionStats::default());
+    }
+
+    #[test]
+    fn in_report_applies_window_and_activity() {
+        let mut config = mock_config(0);
+        let mut watch = make_push_event("alice/a");
+        watch.kind = "WatchEvent".to_string();
+        assert!(in_report(&make_push_event("alice/a"), &config));
+        assert!(in_report(&watch, &config));
+
+        config.since = Some("2024-03-01".to_string());
+        config.until = Some("2024-03-01".to_string());
+        assert!(in_report(&watch, &config));
+        config.since = Some("2024-03-02".to_string());
+        assert!(!in_report(&watch, &config));
+        config.since = None;
+        config.until = Some("2024-02-29".to_string());
+        assert!(!in_report(&watch, &config));
+
+        config.until = None;
+        config.activity = ActivityFilter::Commits;
+        assert!(in_report(&make_push_event("alice/a"), &config));
+        assert!(!in_report(&watch, &config));
+    }
+
     #[test]
     fn coalesce_keeps_first_push_per_day_branch() {
         let events = vec![
@@ -430,6 +1160,153 @@ mod tests {
         assert_eq!(coalesce_push_events(events).len(), 2);
     }
 
+    fn review_event(kind: &str, repo: &str, number: u64, state: &str) -> GitHubEvent {
+        GitHubEvent {
+            kind: kind.to_string(),
+            repo: EventRepo {
+                name: repo.to_string(),
+            },
+            payload: serde_json::json!({
+                "review": { "state": state },
+                "pull_request": { "number": number },
+            }),
+            created_at: "2024-03-01T12:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn review_stats_tallies_verdicts_and_repos() {
+        let events = vec![
+            review_event("PullRequestReviewEvent", "alice/a", 1, "approved"),
+            review_event("PullRequestReviewEvent", "alice/a", 1, "changes_requested"),
+            review_event("PullRequestReviewEvent", "alice/a", 2, "commented"),
+            review_event("PullRequestReviewCommentEvent", "alice/a", 2, ""),
+            review_event("PullRequestReviewEvent", "bob/b", 7, "APPROVED"),
+            make_push_event("alice/a"),
+        ];
+        let stats = review_stats(&events, None);
+        assert_eq!(stats.prs_reviewed, 3);
+        assert_eq!(stats.reviews, 4);
+        assert_eq!(stats.approvals, 2);
+        assert_eq!(stats.changes_requested, 1);
+        assert_eq!(stats.comments, 1);
+        assert_eq!(
+            stats.top_repos,
+            [("alice/a".to_string(), 2), ("bob/b".to_string(), 1)]
+        );
+        assert_eq!(review_stats(&events, Some(40)).prs_reviewed, 40);
+        assert!(review_stats(&[], None).is_empty());
+    }
+
+    fn issue_event(kind: &str, action: &str, number: u64, is_pr: bool) -> GitHubEvent {
+        let mut issue = serde_json::json!({
+            "number": number,
+            "title": format!("Issue {number}"),
+            "html_url": format!("https://github.com/alice/a/issues/{number}"),
+        });
+        if is_pr {
+            issue["pull_request"] = serde_json::json!({});
+        }
+        GitHubEvent {
+            kind: kind.to_string(),
+            repo: EventRepo {
+                name: "alice/a".to_string(),
+            },
+            payload: serde_json::json!({ "action": action, "issue": issue }),
+            created_at: "2024-03-01T12:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn issue_stats_counts_per_repo_and_thread() {
+        let events = vec![
+            issue_event("IssuesEvent", "opened", 1, false),
+            issue_event("IssueCommentEvent", "created", 2, false),
+            issue_event("IssueCommentEvent", "created", 2, false),
+            issue_event("IssuesEvent", "closed", 2, false),
+            issue_event("IssuesEvent", "labeled", 3, false),
+            issue_event("IssueCommentEvent", "created", 9, true),
+        ];
+        let stats = issue_stats(&events);
+        assert_eq!(
+            stats.repos,
+            [IssueRepoStats {
+                repo: "alice/a".to_string(),
+                opened: 1,
+                closed: 1,
+                commented: 2,
+            }]
+        );
+        let threads: Vec<_> = stats.threads.iter().map(|t| (t.number, t.events)).collect();
+        assert_eq!(threads, [(2, 3), (1, 1)]);
+        assert_eq!(stats.threads[0].title, "Issue 2");
+    }
+
+    #[test]
+    fn parse_team_skips_comments_and_repeats() {
+        let text = "# core team\nalice\n\n@bob  # reviewer\nAlice\n  carol  \n";
+        assert_eq!(parse_team(text), vec!["alice", "bob", "carol"]);
+    }
+
+    #[test]
+    fn period_label_describes_the_window() {
+        let config = |since: Option<&str>, until: Option<&str>| UserReportConfig {
+            since: since.map(str::to_string),
+            until: until.map(str::to_string),
+            ..mock_config(0)
+        };
+        assert_eq!(period_label(&config(None, None)), "recent activity");
+        assert_eq!(
+            period_label(&config(Some("2024-01-01"), None)),
+            "since 2024-01-01"
+        );
+        assert_eq!(
+            period_label(&config(Some("2024-01-01"), Some("2024-03-31"))),
+            "2024-01-01 to 2024-03-31"
+        );
+    }
+
+    #[test]
+    fn push_commits_lists_each_commit_once() {
+        let push = |repo: &str, payload| GitHubEvent {
+            payload,
+            ..make_push_event(repo)
+        };
+        let events = vec![
+            push(
+                "alice/a",
+                serde_json::json!({
+                    "ref": "refs/heads/main",
+                    "commits": [
+                        { "sha": "a2", "distinct": true },
+                        { "sha": "b1", "distinct": false },
+                        { "sha": "a3" },
+                    ],
+                }),
+            ),
+            push(
+                "alice/b",
+                serde_json::json!({ "ref": "refs/heads/topic", "head": "b1", "commits": [] }),
+            ),
+            push(
+                "alice/a",
+                serde_json::json!({ "ref": "refs/heads/main", "commits": [{ "sha": "a2" }] }),
+            ),
+        ];
+        let triple = |repo: &str, branch: &str, sha: &str| {
+            (repo.to_string(), branch.to_string(), sha.to_string())
+        };
+        assert_eq!(
+            push_commits(&events, 10),
+            vec![
+                triple("alice/a", "main", "a2"),
+                triple("alice/a", "main", "a3"),
+                triple("alice/b", "topic", "b1"),
+            ]
+        );
+        assert_eq!(push_commits(&events, 1).len(), 1);
+    }
+
     #[test]
     fn coalesce_preserves_non_push_events() {
         let events = vec![
@@ -477,12 +1354,17 @@ mod tests {
             last_repos: 0,
             last_commits: commits,
             no_diffs: false,
+            diff_context: 3,
+            full_diffs: false,
+            full_diffs_limit: 100,
             font_size: 8.0,
             github_token: None,
+            cache: None,
             since: None,
             until: None,
             activity: ActivityFilter::All,
             events: 0,
+            format: ReportFormat::Pdf,
         }
     }
 
@@ -523,6 +1405,11 @@ mod tests {
             events: vec![],
             commit_msgs: std::collections::HashMap::new(),
             commit_details: vec![],
+            pushed_commits: vec![],
+            reviews: ReviewStats::default(),
+            issues: IssueStats::default(),
+            orgs: vec![],
+            contributions: None,
         }
     }
 
@@ -549,4 +1436,104 @@ mod tests {
             "expected more pages with commits ({pages_with_commits}) than without ({pages_baseline})"
         );
     }
+
+    #[test]
+    fn team_row_counts_commits_prs_and_reviews() {
+        let pr = |action: &str| GitHubEvent {
+            kind: "PullRequestEvent".to_string(),
+            payload: serde_json::json!({ "action": action }),
+            ..make_push_event("alice/a")
+        };
+        let data = UserReportData {
+            events: vec![
+                GitHubEvent {
+                    payload: serde_json::json!({ "size": 3, "commits": [{}] }),
+                    ..make_push_event("alice/a")
+                },
+                GitHubEvent {
+                    payload: serde_json::json!({ "commits": [{}, {}] }),
+                    ..make_push_event("alice/b")
+                },
+                pr("opened"),
+                pr("closed"),
+            ],
+            reviews: ReviewStats {
+                reviews: 4,
+                ..ReviewStats::default()
+            },
+            total_stars: 17,
+            ..empty_report_data()
+        };
+        let row = team_row(&data);
+        assert_eq!(row.login, "alice");
+        assert_eq!(row.commits, 5);
+        assert_eq!(row.pull_requests, 1);
+        assert_eq!(row.reviews, 4);
+        assert_eq!(row.stars, 17);
+    }
+
+    /// Each member gets their own chapter after the summary page.
+    #[test]
+    fn render_team_doc_adds_a_chapter_per_member() {
+        let (_, single) = render_to_doc(&mock_config(0), &empty_report_data()).unwrap();
+        let (_, team) =
+            render_team_doc(&mock_config(0), &[empty_report_data(), empty_report_data()]).unwrap();
+        assert!(
+            team > 2 * single,
+            "expected a summary page plus two chapters ({team} pages, {single} per user)"
+        );
+    }
+
+    #[test]
+    fn pushed_commits_add_an_appendix() {
+        let (_, baseline) = render_to_doc(&mock_config(0), &empty_report_data()).unwrap();
+        let data = UserReportData {
+            pushed_commits: (0..3)
+                .map(mock_commit_detail)
+                .map(|(repo, detail)| PushedCommit {
+                    repo,
+                    branch: "main".to_string(),
+                    detail,
+                })
+                .collect(),
+            ..empty_report_data()
+        };
+        let (_, pages) = render_to_doc(&mock_config(0), &data).unwrap();
+        assert!(
+            pages > baseline,
+            "{pages} pages, {baseline} without the appendix"
+        );
+    }
+
+    #[test]
+    fn report_json_has_a_stable_shape() {
+        let data = UserReportData {
+            commit_details: vec![mock_commit_detail(1)],
+            ..empty_report_data()
+        };
+        let json = serde_json::to_value(report_json(&mock_config(1), &data)).unwrap();
+        assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(json["user"]["login"], "alice");
+        assert_eq!(json["recent_commits"][0]["repo"], "alice/repo1");
+        assert_eq!(json["recent_commits"][0]["files"][0]["additions"], 80);
+        assert!(json["contributions"].is_null());
+        assert!(json["reviews"]["top_repos"].is_array());
+    }
+
+    #[test]
+    fn json_path_sits_beside_the_pdf_with_both() {
+        let config = |format| UserReportConfig {
+            output_path: "out/alice.pdf".into(),
+            format,
+            ..mock_config(0)
+        };
+        assert_eq!(
+            json_path(&config(ReportFormat::Json)),
+            std::path::Path::new("out/alice.pdf")
+        );
+        assert_eq!(
+            json_path(&config(ReportFormat::Both)),
+            std::path::Path::new("out/alice.json")
+        );
+    }
 }
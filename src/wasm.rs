@@ -0,0 +1,52 @@
+//! JavaScript bindings for the WebAssembly build.
+//!
+//! Build with `--no-default-features --features wasm --target wasm32-unknown-unknown`
+//! (or `wasm-pack build -- --no-default-features --features wasm`). Without the
+//! `native` feature there is no git, filesystem, process, or network access: the
+//! page hands over file contents and gets PDF bytes back.
+//!
+//! ```js
+//! import init, { render_files } from "./pkg/gitprint.js";
+//!
+//! await init();
+//! const pdf = render_files(
+//!   ["src/main.rs", "README.md"],
+//!   [mainRs, readme],
+//!   JSON.stringify({ repo_path: "demo", theme: "InspiredGitHub", paper_size: "letter" }),
+//! );
+//! const url = URL.createObjectURL(new Blob([pdf], { type: "application/pdf" }));
+//! ```
+
+use std::path::PathBuf;
+
+use wasm_bindgen::prelude::*;
+
+use crate::config_json::ConfigJson;
+
+/// Renders `paths[i]` with content `contents[i]` to a PDF and returns its bytes.
+/// `options` is a [`ConfigJson`] object such as
+/// `{"theme": "base16-ocean.light", "toc": false}`; pass `"{}"` for the defaults.
+/// Its `repo_path` names the document.
+///
+/// # Errors
+///
+/// Throws if the arrays differ in length, the options are invalid, or rendering fails.
+#[wasm_bindgen]
+pub fn render_files(
+    paths: Vec<String>,
+    contents: Vec<String>,
+    options: &str,
+) -> Result<Vec<u8>, JsError> {
+    if paths.len() != contents.len() {
+        return Err(JsError::new(&format!(
+            "{} paths but {} contents",
+            paths.len(),
+            contents.len()
+        )));
+    }
+    let config = ConfigJson::parse(options)
+        .and_then(ConfigJson::into_config)
+        .map_err(|e| JsError::new(&format!("{e:#}")))?;
+    let files = paths.into_iter().map(PathBuf::from).zip(contents).collect();
+    crate::render_files(files, &config).map_err(|e| JsError::new(&e.to_string()))
+}
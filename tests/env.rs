@@ -0,0 +1,46 @@
+//! The `GITPRINT_*` variables are read by every `Args` parse, so setting them gets
+//! a test binary of its own: here they can't leak into the `cli` unit tests.
+
+use clap::Parser;
+use gitprint::cli::Args;
+
+#[test]
+fn gitprint_env_vars_fill_in_for_missing_flags() {
+    // SAFETY: this is the binary's only test, and nothing else reads the
+    // environment while it runs.
+    unsafe {
+        std::env::set_var("GITPRINT_INCLUDE", "src/**");
+        std::env::set_var("GITPRINT_EXCLUDE", "*.lock, docs/**,*.{png,svg}");
+        std::env::set_var("GITPRINT_NO_TOC", "yes");
+        std::env::set_var("GITPRINT_LANDSCAPE", "off");
+        std::env::set_var("GITPRINT_FONT_SIZE", "9.5");
+    }
+
+    let mut args = Args::parse_from(["gitprint", "."]);
+    args.apply_pattern_env();
+    assert_eq!(args.include, vec!["src/**"]);
+    assert_eq!(args.exclude, vec!["*.lock", "docs/**", "*.{png,svg}"]);
+    assert!(args.no_toc);
+    assert!(!args.landscape);
+    assert_eq!(args.font_size, 9.5);
+
+    // Flags on the command line win over the environment.
+    let mut args = Args::parse_from([
+        "gitprint",
+        ".",
+        "--exclude",
+        "*.md",
+        "--landscape",
+        "--font-size",
+        "11",
+    ]);
+    args.apply_pattern_env();
+    assert_eq!(args.exclude, vec!["*.md"]);
+    assert_eq!(args.include, vec!["src/**"]);
+    assert!(args.landscape);
+    assert_eq!(args.font_size, 11.0);
+
+    // A value that isn't boolish is rejected rather than read as false.
+    unsafe { std::env::set_var("GITPRINT_NO_TOC", "sometimes") };
+    assert!(Args::try_parse_from(["gitprint", "."]).is_err());
+}
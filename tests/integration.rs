@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use tempfile::TempDir;
 
-use gitprint::types::{Config, PaperSize};
+use gitprint::types::{ColorMode, Config, CoverRow, Density, PaperSize};
 
 async fn git_in(dir: &str, args: &[&str]) {
     let output = tokio::process::Command::new("git")
@@ -69,16 +69,78 @@ fn test_config(repo_path: PathBuf, output_path: PathBuf) -> Config {
         output_path,
         include_patterns: vec![],
         exclude_patterns: vec![],
+        include_regexes: vec![],
+        exclude_regexes: vec![],
+        languages: vec![],
+        exclude_languages: vec![],
+        presets: vec![],
+        no_default_excludes: false,
+        extra_default_excludes: vec![],
         theme: "InspiredGitHub".to_string(),
         font_size: 8.0,
         no_line_numbers: false,
+        global_line_numbers: false,
+        age_shading: false,
+        layers: false,
+        print_optimize: false,
+        highlight_max_lines: gitprint::highlight::DEFAULT_HIGHLIGHT_MAX_LINES,
+        control_chars: gitprint::types::ControlChars::default(),
+        prose_fonts: vec![],
+        emoji_font: None,
         toc: true,
         file_tree: true,
         branch: None,
         commit: None,
+        git_timeout: None,
         paper_size: PaperSize::A4,
         landscape: false,
         remote_url: None,
+        pretty_data: false,
+        page_total: false,
+        booklet: false,
+        nup: None,
+        review_layout: None,
+        color_mode: ColorMode::Color,
+        tagged: false,
+        compact: false,
+        marks: Vec::new(),
+        grep: None,
+        only_matching: None,
+        redact: None,
+        untracked: false,
+        changed_only: None,
+        since: None,
+        until: None,
+        no_dates: false,
+        files_from: None,
+        max_depth: None,
+        hidden: true,
+        preprocess: None,
+        lang_styles: std::collections::HashMap::new(),
+        line_height: None,
+        density: Density::Normal,
+        letter_spacing: 0.0,
+        symbols: false,
+        checksums: false,
+        margin_notes: false,
+        qr: false,
+        cover_rows: CoverRow::ALL.to_vec(),
+        meta: Vec::new(),
+        locale: gitprint::locale::Locale::default(),
+        commit_footer: true,
+        running_header: true,
+        chapters: None,
+        summary: true,
+        hotspots: None,
+        preface: false,
+        offline: true,
+        github_token: None,
+        refs: false,
+        show: None,
+        diff_context: 3,
+        max_pages: None,
+        max_output_size: None,
+        write_progress: None,
     }
 }
 
@@ -87,7 +149,7 @@ fn test_config(repo_path: PathBuf, output_path: PathBuf) -> Config {
 #[tokio::test]
 async fn git_verify_repo_valid() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
-    let info = gitprint::git::verify_repo(repo.path()).await?;
+    let info = gitprint::git::verify_repo(repo.path(), None).await?;
     assert!(info.is_git);
     assert!(info.scope.is_none());
     assert!(info.single_file.is_none());
@@ -97,7 +159,7 @@ async fn git_verify_repo_valid() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn git_verify_repo_subdir() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
-    let info = gitprint::git::verify_repo(&repo.path().join("src")).await?;
+    let info = gitprint::git::verify_repo(&repo.path().join("src"), None).await?;
     assert!(info.is_git);
     assert_eq!(info.scope, Some(PathBuf::from("src")));
     assert!(info.single_file.is_none());
@@ -107,7 +169,7 @@ async fn git_verify_repo_subdir() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn git_verify_repo_single_file_in_git() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
-    let info = gitprint::git::verify_repo(&repo.path().join("main.rs")).await?;
+    let info = gitprint::git::verify_repo(&repo.path().join("main.rs"), None).await?;
     assert!(info.is_git);
     assert_eq!(info.single_file, Some(PathBuf::from("main.rs")));
     assert!(info.scope.is_none());
@@ -117,7 +179,7 @@ async fn git_verify_repo_single_file_in_git() -> Result<(), Box<dyn std::error::
 #[tokio::test]
 async fn git_verify_repo_plain_directory() -> Result<(), Box<dyn std::error::Error>> {
     let dir = TempDir::new()?;
-    let info = gitprint::git::verify_repo(dir.path()).await?;
+    let info = gitprint::git::verify_repo(dir.path(), None).await?;
     assert!(!info.is_git);
     assert!(info.single_file.is_none());
     Ok(())
@@ -129,7 +191,7 @@ async fn git_verify_repo_plain_file() -> Result<(), Box<dyn std::error::Error>>
     tokio::fs::write(dir.path().join("hello.rs"), "fn main() {}")
         .await
         .unwrap();
-    let info = gitprint::git::verify_repo(&dir.path().join("hello.rs")).await?;
+    let info = gitprint::git::verify_repo(&dir.path().join("hello.rs"), None).await?;
     assert!(!info.is_git);
     assert_eq!(info.single_file, Some(PathBuf::from("hello.rs")));
     Ok(())
@@ -137,11 +199,10 @@ async fn git_verify_repo_plain_file() -> Result<(), Box<dyn std::error::Error>>
 
 #[tokio::test]
 async fn git_verify_repo_nonexistent_path() {
-    assert!(
-        gitprint::git::verify_repo(Path::new("/nonexistent/path"))
-            .await
-            .is_err()
-    );
+    let err = gitprint::git::verify_repo(Path::new("/nonexistent/path"), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, gitprint::Error::PathNotFound(_)));
 }
 
 #[tokio::test]
@@ -155,8 +216,11 @@ async fn git_get_metadata() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(metadata.commit_hash.len(), 40);
     assert!(metadata.commit_hash.chars().all(|c| c.is_ascii_hexdigit()));
     assert_eq!(metadata.commit_hash_short.len(), 7);
+    assert_eq!(metadata.tree_hash.len(), 40);
+    assert_ne!(metadata.tree_hash, metadata.commit_hash);
     assert_eq!(metadata.commit_message, "initial commit");
     assert!(!metadata.commit_date.is_empty());
+    assert_eq!(metadata.signature, "Unsigned");
     Ok(())
 }
 
@@ -183,6 +247,114 @@ async fn git_get_metadata_with_branch() -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+#[tokio::test]
+async fn blob_reader_reads_committed_content() -> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::git::BlobReader;
+
+    let repo = create_test_repo().await;
+    // An uncommitted edit must not show up when reading the branch.
+    tokio::fs::write(repo.path().join("main.rs"), "changed\n").await?;
+    let mut config = test_config(repo.path().to_path_buf(), PathBuf::from("/tmp/test.pdf"));
+    assert!(BlobReader::for_config(repo.path(), &config, 4)?.is_none());
+
+    config.branch = Some("main".to_string());
+    let blobs = BlobReader::for_config(repo.path(), &config, 2)?.expect("reader for a branch");
+    let (main, util, missing) = tokio::join!(
+        blobs.read(Path::new("main.rs")),
+        blobs.read(Path::new("src/util.rs")),
+        blobs.read(Path::new("nope.rs")),
+    );
+    assert_eq!(main?, "fn main() {\n    println!(\"hello\");\n}\n");
+    assert_eq!(util?, "// utility\npub fn noop() {}\n");
+    assert!(missing.unwrap_err().to_string().contains("nope.rs"));
+    // A missing file leaves the processes in step for the reads after it.
+    assert_eq!(blobs.read(Path::new("README.md")).await?, "# Test Repo\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn git_linked_worktree_has_its_own_branch_and_files() -> Result<(), Box<dyn std::error::Error>>
+{
+    let repo = create_test_repo().await;
+    let parent = TempDir::new()?;
+    let worktree = parent.path().join("feature");
+    let p = repo.path().to_str().unwrap();
+    git_in(
+        p,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "feature",
+            worktree.to_str().unwrap(),
+        ],
+    )
+    .await;
+    tokio::fs::write(worktree.join("feature.rs"), "fn feature() {}\n").await?;
+    let w = worktree.to_str().unwrap();
+    git_in(w, &["add", "feature.rs"]).await;
+    git_in(w, &["commit", "-m", "add feature"]).await;
+
+    let info = gitprint::git::verify_repo(&worktree.join("src"), None).await?;
+    assert!(info.is_git);
+    assert_eq!(info.root, tokio::fs::canonicalize(&worktree).await?);
+    assert_eq!(info.scope, Some(PathBuf::from("src")));
+
+    let config = test_config(info.root.clone(), PathBuf::from("/tmp/test.pdf"));
+    let metadata = gitprint::git::get_metadata(&info.root, &config, true, None).await?;
+    assert_eq!(metadata.branch, "feature");
+    assert_eq!(metadata.commit_message, "add feature");
+    let files = gitprint::git::list_tracked_files(&info.root, &config, true, None).await?;
+    assert!(files.contains(&PathBuf::from("feature.rs")));
+    let dates = gitprint::git::file_last_modified_dates(&info.root, &config, true, &files).await?;
+    assert!(dates.contains_key(Path::new("feature.rs")), "{dates:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn shallow_clone_fetches_an_older_commit() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let p = repo.path().to_str().unwrap();
+    let head = tokio::process::Command::new("git")
+        .args(["-C", p, "rev-parse", "HEAD"])
+        .output()
+        .await?;
+    let old = String::from_utf8(head.stdout)?.trim().to_string();
+    tokio::fs::write(repo.path().join("new.rs"), "fn new() {}\n").await?;
+    git_in(p, &["add", "."]).await;
+    git_in(p, &["commit", "-m", "second"]).await;
+
+    let url = format!("file://{p}");
+    let shallow = TempDir::new()?;
+    let dest = shallow.path().join("clone");
+    let mut warnings = Vec::new();
+    gitprint::git::clone_repo(&url, &dest, None, None, Some(1), None, None, &mut warnings).await?;
+    assert!(gitprint::git::is_shallow(&dest, None).await);
+
+    let pinned = TempDir::new()?;
+    let dest = pinned.path().join("clone");
+    gitprint::git::clone_repo(
+        &url,
+        &dest,
+        None,
+        Some(&old),
+        Some(1),
+        None,
+        None,
+        &mut warnings,
+    )
+    .await?;
+    let found = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(&dest)
+        .args(["cat-file", "-e", &format!("{old}^{{commit}}")])
+        .status()
+        .await?;
+    assert!(found.success(), "the pinned commit was fetched");
+    assert!(warnings.is_empty(), "no retries: {warnings:?}");
+    Ok(())
+}
+
 #[tokio::test]
 async fn git_list_tracked_files() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
@@ -197,6 +369,49 @@ async fn git_list_tracked_files() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn dates_cover_only_the_requested_files() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let mut config = test_config(repo.path().to_path_buf(), PathBuf::from("/tmp/test.pdf"));
+    let wanted = [PathBuf::from("main.rs"), PathBuf::from("src/util.rs")];
+    let dates =
+        gitprint::git::file_last_modified_dates(repo.path(), &config, true, &wanted).await?;
+    assert_eq!(dates.len(), 2, "{dates:?}");
+    assert!(wanted.iter().all(|p| dates[p].len() == 10), "{dates:?}");
+
+    config.no_dates = true;
+    let dates =
+        gitprint::git::file_last_modified_dates(repo.path(), &config, true, &wanted).await?;
+    assert!(dates.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn git_list_untracked_and_changed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    tokio::try_join!(
+        tokio::fs::write(repo.path().join("lib.rs"), "pub fn changed() {}\n"),
+        tokio::fs::write(repo.path().join("new.rs"), "fn new() {}\n"),
+        tokio::fs::remove_file(repo.path().join("README.md")),
+    )?;
+    let mut config = test_config(repo.path().to_path_buf(), PathBuf::from("/tmp/test.pdf"));
+
+    config.untracked = true;
+    let files = gitprint::git::list_tracked_files(repo.path(), &config, true, None).await?;
+    assert!(files.contains(&PathBuf::from("new.rs")));
+    assert!(files.contains(&PathBuf::from("main.rs")));
+
+    config.changed_only = Some(None);
+    let files = gitprint::git::list_tracked_files(repo.path(), &config, true, None).await?;
+    assert_eq!(files, [PathBuf::from("lib.rs"), PathBuf::from("new.rs")]);
+
+    config.untracked = false;
+    config.changed_only = Some(Some("HEAD".to_string()));
+    let files = gitprint::git::list_tracked_files(repo.path(), &config, true, None).await?;
+    assert_eq!(files, [PathBuf::from("lib.rs")]);
+    Ok(())
+}
+
 #[tokio::test]
 async fn git_list_files_plain_directory() -> Result<(), Box<dyn std::error::Error>> {
     let dir = TempDir::new()?;
@@ -254,6 +469,539 @@ async fn full_pipeline() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn estimate_matches_the_rendered_document() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let config = test_config(repo.path().to_path_buf(), out_dir.path().join("output.pdf"));
+
+    let estimate = gitprint::estimate(&config).await?;
+    assert!(!out_dir.path().join("output.pdf").exists());
+    let report = gitprint::run(&config).await?;
+
+    assert_eq!(estimate.files, report.files_included);
+    assert_eq!(estimate.lines, 9);
+    assert!(
+        estimate.pages.abs_diff(report.pages) <= 2,
+        "{estimate:?} vs {report}"
+    );
+    assert!(estimate.approx_bytes > 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn max_pages_truncates_and_reports_omitted_files() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("output.pdf"));
+    let full = gitprint::run(&config).await?;
+    assert_eq!(full.files_truncated, 0);
+
+    // The front matter alone fills the first page, so no file fits.
+    config.max_pages = Some(1);
+    let report = gitprint::run(&config).await?;
+
+    assert_eq!(report.files_included, 0);
+    assert_eq!(report.files_truncated, full.files_included);
+    assert!(report.pages <= full.pages);
+    assert!(report.to_string().contains("output truncated: 0 of"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_file_pages_carry_the_commit_footer() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let head = tokio::process::Command::new("git")
+        .args(["-C", repo.path().to_str().unwrap(), "rev-parse", "HEAD"])
+        .output()
+        .await?;
+    let short = String::from_utf8(head.stdout)?[..7].to_string();
+    let page_text = |path: &Path| -> Result<String, Box<dyn std::error::Error>> {
+        Ok(lopdf::Document::load(path)?.extract_text(&[1])?)
+    };
+
+    let stamped = out_dir.path().join("stamped.pdf");
+    let mut config = test_config(repo.path().join("main.rs"), stamped.clone());
+    gitprint::run(&config).await?;
+    assert!(page_text(&stamped)?.contains(&short));
+
+    let plain = out_dir.path().join("plain.pdf");
+    config.commit_footer = false;
+    config.output_path = plain.clone();
+    gitprint::run(&config).await?;
+    assert!(!page_text(&plain)?.contains(&short));
+    Ok(())
+}
+
+#[tokio::test]
+async fn budgets_are_refused_for_a_single_file() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().join("main.rs"), output_path.clone());
+
+    config.max_pages = Some(1);
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(matches!(
+        err,
+        gitprint::Error::RepositoryOnly("--max-pages")
+    ));
+
+    config.max_pages = None;
+    config.max_output_size = Some(1024);
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(matches!(
+        err,
+        gitprint::Error::RepositoryOnly("--max-output-size")
+    ));
+    assert!(!output_path.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_reports_included_and_skipped_files() -> Result<(), Box<dyn std::error::Error>>
+{
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output_path.clone());
+    config.files_from = Some(vec![PathBuf::from("main.rs"), PathBuf::from("missing.rs")]);
+
+    let report = gitprint::run(&config).await?;
+
+    assert_eq!(report.files_included, 1);
+    assert_eq!(report.files_skipped, 1);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].to_string().starts_with("missing.rs: "));
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(
+        report.skipped[0].reason,
+        gitprint::types::SkipReason::Unreadable
+    );
+    assert!(report.pages > 0);
+    assert_eq!(report.pdf_bytes, std::fs::metadata(&output_path)?.len());
+    assert!(report.to_string().starts_with("1 file, "));
+    Ok(())
+}
+
+#[tokio::test]
+async fn latin1_files_are_decoded_not_skipped() -> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::types::WarningKind;
+
+    let dir = TempDir::new()?;
+    // "Größe" and "Straße" in Latin-1, which isn't valid UTF-8.
+    tokio::fs::write(
+        dir.path().join("umlaut.c"),
+        b"/* Gr\xf6\xdfe der Stra\xdfe */\nint gr\xf6\xdfe = 1;\n",
+    )
+    .await?;
+    let out_dir = TempDir::new()?;
+    let config = test_config(dir.path().to_path_buf(), out_dir.path().join("out.pdf"));
+    let content =
+        gitprint::git::read_file_content(dir.path(), Path::new("umlaut.c"), &config).await?;
+    assert!(content.starts_with("/* Gr\u{f6}\u{df}e der Stra\u{df}e */"));
+
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_included, 1);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .all(|w| w.kind != WarningKind::Unreadable),
+        "{:?}",
+        report.warnings
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn skipped_files_are_reported_with_a_reason() -> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::types::SkipReason;
+
+    let repo = create_test_repo().await;
+    tokio::try_join!(
+        tokio::fs::write(repo.path().join("Cargo.lock"), "# lock\n"),
+        tokio::fs::write(repo.path().join("blob.dat"), b"\x00\x01\x02binary"),
+        tokio::fs::write(
+            repo.path().join("bundle.js"),
+            format!("var a=1;{}\n", "f(a);".repeat(2000))
+        ),
+    )?;
+    git_in(repo.path().to_str().unwrap(), &["add", "."]).await;
+    git_in(repo.path().to_str().unwrap(), &["commit", "-m", "more"]).await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("output.pdf"));
+    config.exclude_patterns = vec!["src/**".to_string()];
+
+    let report = gitprint::run(&config).await?;
+    let reasons: Vec<_> = report
+        .skipped
+        .iter()
+        .map(|s| (s.path.to_str().unwrap(), s.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            ("Cargo.lock", SkipReason::DefaultExclude),
+            ("blob.dat", SkipReason::Binary),
+            ("bundle.js", SkipReason::Minified),
+            ("src/util.rs", SkipReason::Filter),
+        ]
+    );
+    assert_eq!(report.files_skipped, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn output_inside_the_repository_is_not_printed() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    tokio::fs::write(dir.path().join("main.rs"), "fn main() {}\n").await?;
+    tokio::fs::write(dir.path().join("listing.pdf"), "an earlier run's output\n").await?;
+    let mut config = test_config(dir.path().to_path_buf(), dir.path().join("listing.pdf"));
+    config.exclude_patterns = vec!["!*.pdf".to_string()];
+
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_included, 1);
+
+    config.files_from = Some(vec![PathBuf::from("main.rs"), PathBuf::from("listing.pdf")]);
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_included, 1);
+    assert_eq!(report.files_skipped, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_lang_styles() -> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::types::LangStyle;
+
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output_path.clone());
+    config.lang_styles = std::collections::HashMap::from([
+        (
+            "markdown".to_string(),
+            LangStyle {
+                font_size: Some(9.5),
+                wrap: Some(true),
+                theme: None,
+            },
+        ),
+        (
+            "rust".to_string(),
+            LangStyle {
+                theme: Some("base16-ocean.light".to_string()),
+                ..LangStyle::default()
+            },
+        ),
+    ]);
+
+    gitprint::run(&config).await?;
+    assert!(output_path.exists());
+
+    std::fs::remove_file(&output_path)?;
+    config.lang_styles.get_mut("rust").unwrap().theme = Some("no-such-theme".to_string());
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(matches!(err, gitprint::Error::UnknownTheme(ref name) if name == "no-such-theme"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_symbols_and_margin_notes() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+
+    config.output_path = out_dir.path().join("symbols.pdf");
+    config.symbols = true;
+    let indexed = gitprint::run(&config).await?;
+    assert!(indexed.pages > plain.pages);
+
+    config.output_path = out_dir.path().join("margin.pdf");
+    config.symbols = false;
+    config.margin_notes = true;
+    let annotated = gitprint::run(&config).await?;
+    assert_eq!(annotated.pages, plain.pages);
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_chapters() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+
+    // Only src/ is a directory, so exactly one divider page is added.
+    config.output_path = out_dir.path().join("chapters.pdf");
+    config.chapters = Some(1);
+    let chaptered = gitprint::run(&config).await?;
+    assert_eq!(chaptered.pages, plain.pages + 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn skipped_files_and_missing_glyphs_become_warnings() -> Result<(), Box<dyn std::error::Error>>
+{
+    use gitprint::types::WarningKind;
+
+    let dir = TempDir::new()?;
+    tokio::fs::write(dir.path().join("a.rs"), "fn a() {}\n").await?;
+    tokio::fs::write(dir.path().join("data.dat"), b"\x00\x01data\x00").await?;
+    tokio::fs::write(dir.path().join("names.txt"), "\u{6F22}\u{5B57}\n").await?;
+    let out_dir = TempDir::new()?;
+    let config = test_config(dir.path().to_path_buf(), out_dir.path().join("out.pdf"));
+
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_skipped, 1);
+    let kinds: Vec<_> = report.warnings.iter().map(|w| w.kind).collect();
+    assert_eq!(kinds, [WarningKind::Skipped, WarningKind::Glyph]);
+    assert_eq!(
+        report.warnings[0].to_string(),
+        "data.dat: binary file, skipped"
+    );
+    assert!(
+        report.warnings[1]
+            .message
+            .starts_with("2 characters without a glyph")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_preface() -> Result<(), Box<dyn std::error::Error>> {
+    // The test repository's README.md is a single heading.
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+
+    config.output_path = out_dir.path().join("preface.pdf");
+    config.preface = true;
+    let prefaced = gitprint::run(&config).await?;
+    assert_eq!(prefaced.pages, plain.pages + 1);
+    assert!(prefaced.warnings.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_without_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("full.pdf"));
+    let full = gitprint::run(&config).await?;
+
+    config.output_path = out_dir.path().join("bare.pdf");
+    config.summary = false;
+    let bare = gitprint::run(&config).await?;
+    assert_eq!(bare.pages, full.pages - 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_hotspots() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+
+    config.output_path = out_dir.path().join("hotspots.pdf");
+    config.hotspots = Some("1 year ago".to_string());
+    let ranked = gitprint::run(&config).await?;
+    assert_eq!(ranked.pages, plain.pages + 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn age_shading_blames_each_file() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("aged.pdf"));
+    config.age_shading = true;
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_included, 4);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .all(|w| w.kind != WarningKind::Unreadable),
+        "{:?}",
+        report.warnings
+    );
+
+    let dir = TempDir::new()?;
+    tokio::fs::write(dir.path().join("a.rs"), "fn a() {}\n").await?;
+    config.repo_path = dir.path().to_path_buf();
+    config.output_path = out_dir.path().join("plain.pdf");
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(
+        report.warnings[0].to_string(),
+        "--age-shading: not a git repository"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn layers_register_optional_content() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output = out_dir.path().join("layers.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output.clone());
+    config.layers = true;
+    config.tagged = true;
+    gitprint::run(&config).await?;
+    let bytes = tokio::fs::read(&output).await?;
+    let has = |needle: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+    assert!(has(b"/OCProperties"));
+    assert!(has(b"/StructTreeRoot"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn checksums_append_a_page() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+    config.checksums = true;
+    config.output_path = out_dir.path().join("checksums.pdf");
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.pages, plain.pages + 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn theme_gallery_renders_every_theme() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = TempDir::new()?;
+    let output = out_dir.path().join("themes.pdf");
+    let config = test_config(out_dir.path().to_path_buf(), output.clone());
+    let themes = gitprint::theme_gallery(&config).await?;
+    assert_eq!(themes, gitprint::highlight::list_themes().len());
+    assert!(tokio::fs::read(&output).await?.starts_with(b"%PDF"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn prose_font_is_loaded_from_disk() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("prose.pdf"));
+    let fonts = Path::new(env!("CARGO_MANIFEST_DIR")).join("fonts");
+    config.prose_fonts = vec![fonts.join("JetBrainsMono-Italic.ttf")];
+    assert_eq!(gitprint::run(&config).await?.files_included, 4);
+
+    config.prose_fonts = vec![fonts.join("NoSuchFont.ttf")];
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(err.to_string().contains("NoSuchFont.ttf"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_refs() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let p = repo.path().to_str().unwrap();
+    git_in(p, &["tag", "v0.1.0"]).await;
+    git_in(p, &["branch", "topic"]).await;
+
+    let refs = gitprint::git::list_refs(repo.path(), None).await?;
+    assert_eq!(refs.default_branch, "HEAD");
+    assert_eq!(refs.tags.len(), 1);
+    assert_eq!(refs.tags[0].name, "v0.1.0");
+    let topic = refs.branches.iter().find(|b| b.name == "topic").unwrap();
+    assert_eq!(topic.ahead_behind, Some((0, 0)));
+
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("plain.pdf"));
+    let plain = gitprint::run(&config).await?;
+    config.output_path = out_dir.path().join("refs.pdf");
+    config.refs = true;
+    assert_eq!(gitprint::run(&config).await?.pages, plain.pages + 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_show() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let p = repo.path().to_str().unwrap();
+    tokio::fs::write(repo.path().join("main.rs"), "fn main() {}\n").await?;
+    git_in(p, &["commit", "-am", "Simplify main"]).await;
+
+    let detail = gitprint::git::show_commit(repo.path(), "HEAD", 3, None).await?;
+    assert_eq!(detail.commit.message, "Simplify main");
+    assert_eq!(detail.commit.author.name, "Test");
+    assert_eq!(detail.files.len(), 1);
+    assert_eq!(detail.files[0].filename, "main.rs");
+    assert_eq!(
+        (detail.files[0].additions, detail.files[0].deletions),
+        (1, 3)
+    );
+
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("show.pdf"));
+    config.show = Some("HEAD".to_string());
+    let report = gitprint::run(&config).await?;
+    assert_eq!(report.files_included, 1);
+    assert!(report.pages >= 1);
+
+    config.show = Some("no-such-rev".to_string());
+    assert!(gitprint::run(&config).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_with_qr_codes() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output_path.clone());
+    config.remote_url = Some("https://github.com/user/repo".to_string());
+    config.qr = true;
+
+    gitprint::run(&config).await?;
+    assert!(std::fs::metadata(&output_path)?.len() > 0);
+    Ok(())
+}
+
+struct FailOnMain;
+
+impl gitprint::preprocess::ContentTransform for FailOnMain {
+    fn transform(&self, path: &Path, content: String) -> anyhow::Result<String> {
+        anyhow::ensure!(path != Path::new("main.rs"), "refusing main.rs");
+        Ok(content)
+    }
+}
+
+#[tokio::test]
+async fn full_pipeline_preprocess_failure_policies() -> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::preprocess::{OnFailure, Preprocessor};
+
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output_path.clone());
+
+    config.preprocess = Some(Preprocessor::transform(FailOnMain).with_on_failure(OnFailure::Skip));
+    let skipped = gitprint::run(&config).await?;
+    assert_eq!(skipped.files_skipped, 1);
+    assert!(
+        skipped.warnings[0]
+            .to_string()
+            .starts_with("main.rs: preprocess hook failed, skipped")
+    );
+
+    std::fs::remove_file(&output_path)?;
+    config.preprocess = Some(Preprocessor::transform(FailOnMain).with_on_failure(OnFailure::Abort));
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(
+        matches!(err, gitprint::Error::Preprocess { ref path, .. } if path == Path::new("main.rs"))
+    );
+    assert!(!output_path.exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn full_pipeline_with_include_filter() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
@@ -282,6 +1030,49 @@ async fn full_pipeline_with_exclude_filter() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+#[tokio::test]
+async fn full_pipeline_with_language_and_preset_filters() -> Result<(), Box<dyn std::error::Error>>
+{
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("output.pdf"));
+
+    config.exclude_languages = vec!["markdown".to_string()];
+    assert_eq!(gitprint::run(&config).await?.files_included, 3);
+
+    config.exclude_languages = vec![];
+    config.languages = vec!["md".to_string()];
+    assert_eq!(gitprint::run(&config).await?.files_included, 1);
+
+    config.languages = vec![];
+    config.presets = vec!["src-only".to_string()];
+    assert_eq!(gitprint::run(&config).await?.files_included, 3);
+    config.presets = vec!["src-only".to_string(), "no-tests".to_string()];
+    config.include_patterns = vec!["src/**".to_string()];
+    assert_eq!(gitprint::run(&config).await?.files_included, 1);
+
+    config.languages = vec!["klingon".to_string()];
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(matches!(err, gitprint::Error::UnknownLanguage(ref name) if name == "klingon"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_matching_gates_files_by_content() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let mut config = test_config(repo.path().to_path_buf(), out_dir.path().join("output.pdf"));
+    config.only_matching = Some(regex::Regex::new(r"println!")?);
+
+    let report = gitprint::run(&config).await?;
+
+    assert_eq!(report.files_included, 1);
+    assert_eq!(report.files_unmatched, 3);
+    assert_eq!(report.files_skipped, 0);
+    assert!(report.to_string().contains("3 without a match"));
+    Ok(())
+}
+
 #[tokio::test]
 async fn full_pipeline_no_toc_no_tree() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
@@ -363,6 +1154,33 @@ async fn full_pipeline_single_file() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn full_pipeline_single_file_runs_the_preprocess_hook()
+-> Result<(), Box<dyn std::error::Error>> {
+    use gitprint::preprocess::{OnFailure, Preprocessor};
+
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().join("main.rs"), output_path.clone());
+
+    config.preprocess = Some(Preprocessor::transform(FailOnMain).with_on_failure(OnFailure::Keep));
+    let kept = gitprint::run(&config).await?;
+    assert!(kept.warnings.iter().any(|w| {
+        w.to_string()
+            .starts_with("main.rs: preprocess hook failed, printed unchanged")
+    }));
+
+    std::fs::remove_file(&output_path)?;
+    config.preprocess = Some(Preprocessor::transform(FailOnMain).with_on_failure(OnFailure::Abort));
+    let err = gitprint::run(&config).await.unwrap_err();
+    assert!(
+        matches!(err, gitprint::Error::Preprocess { ref path, .. } if path == Path::new("main.rs"))
+    );
+    assert!(!output_path.exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn full_pipeline_plain_directory() -> Result<(), Box<dyn std::error::Error>> {
     let dir = TempDir::new()?;
@@ -420,6 +1238,55 @@ async fn full_pipeline_include_excludes_everything() -> Result<(), Box<dyn std::
     Ok(())
 }
 
+#[tokio::test]
+async fn full_pipeline_date_window_excludes_everything() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let mut config = test_config(repo.path().to_path_buf(), output_path.clone());
+    config.since = Some("2999-01-01".to_string());
+
+    let report = gitprint::run(&config).await?;
+    assert!(output_path.exists());
+    assert_eq!(report.files_included, 0);
+    assert_eq!(report.skipped.len(), report.files_listed);
+    assert!(
+        report
+            .skipped
+            .iter()
+            .all(|s| s.reason == gitprint::types::SkipReason::DateRange),
+        "{:?}",
+        report.skipped
+    );
+    assert!(
+        report
+            .skipped
+            .iter()
+            .any(|s| s.path == std::path::Path::new("main.rs"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn full_pipeline_cancelled_writes_nothing() -> Result<(), Box<dyn std::error::Error>> {
+    let repo = create_test_repo().await;
+    let out_dir = TempDir::new()?;
+    let output_path = out_dir.path().join("output.pdf");
+    let config = test_config(repo.path().to_path_buf(), output_path.clone());
+
+    let cancel = gitprint::CancellationToken::new();
+    cancel.cancel();
+    let err = gitprint::run_with_cancel(&config, cancel)
+        .await
+        .expect_err("cancelled run must fail");
+    assert!(matches!(err, gitprint::Error::Cancelled));
+    assert_eq!(std::fs::read_dir(out_dir.path())?.count(), 0);
+
+    gitprint::run_with_cancel(&config, gitprint::CancellationToken::new()).await?;
+    assert!(output_path.exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn full_pipeline_custom_font_size() -> Result<(), Box<dyn std::error::Error>> {
     let repo = create_test_repo().await;
@@ -0,0 +1,59 @@
+//! `SOURCE_DATE_EPOCH` is read from the environment, so this test has a binary of
+//! its own: setting the variable here can't race the tests in `integration.rs`.
+
+use std::path::Path;
+
+use tempfile::TempDir;
+
+async fn git_in(dir: &Path, args: &[&str]) {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[tokio::test]
+async fn source_date_epoch_makes_runs_byte_identical() -> Result<(), Box<dyn std::error::Error>> {
+    // SAFETY: this is the binary's only test, and nothing else reads the
+    // environment while it is set.
+    unsafe { std::env::set_var("SOURCE_DATE_EPOCH", "1700000000") };
+
+    let repo = TempDir::new()?;
+    git_in(repo.path(), &["init", "-b", "main"]).await;
+    git_in(repo.path(), &["config", "user.email", "test@test.com"]).await;
+    git_in(repo.path(), &["config", "user.name", "Test"]).await;
+    std::fs::write(
+        repo.path().join("main.rs"),
+        "fn main() {\n    println!(\"hello\");\n}\n",
+    )?;
+    std::fs::write(repo.path().join("README.md"), "# Test Repo\n")?;
+    git_in(repo.path(), &["add", "."]).await;
+    git_in(repo.path(), &["commit", "-m", "initial commit"]).await;
+
+    let out_dir = TempDir::new()?;
+    let mut outputs = Vec::new();
+    for name in ["first.pdf", "second.pdf"] {
+        let output = out_dir.path().join(name);
+        let json = serde_json::json!({
+            "repo_path": repo.path(),
+            "output_path": output,
+            "offline": true,
+        });
+        let config = gitprint::config_json::ConfigJson::parse(&json.to_string())?.into_config()?;
+        gitprint::run(&config).await?;
+        outputs.push(std::fs::read(&output)?);
+    }
+
+    assert!(outputs[0].starts_with(b"%PDF"));
+    assert!(outputs[0] == outputs[1], "the two runs' PDFs differ");
+    Ok(())
+}